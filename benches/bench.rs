@@ -1,6 +1,6 @@
 use charts_rs::{
-    measure_text_width_family, svg_to_png, BarChart, Box, LegendCategory, SeriesCategory,
-    DEFAULT_FONT_FAMILY,
+    measure_text_width_family, svg_to_png, BarChart, Box, Canvas, Color, LegendCategory, Rect,
+    SeriesCategory, DEFAULT_FONT_FAMILY,
 };
 use criterion::{criterion_group, criterion_main, Criterion};
 
@@ -117,10 +117,33 @@ fn bar_chart_png_benchmark(c: &mut Criterion) {
     c.bench_function("bar chart png test", |b| b.iter(bar_chart_line_mixin_png));
 }
 
+fn canvas_many_components() {
+    let mut c = Canvas::new(1000.0, 1000.0);
+    for i in 0..10_000 {
+        let value = i as f32;
+        c.rect(Rect {
+            fill: Some(Color::from(((i % 255) as u8, 100, 200))),
+            left: value % 1000.0,
+            top: value / 10.0,
+            width: 1.5,
+            height: 0.5,
+            ..Default::default()
+        });
+    }
+    c.svg().unwrap();
+}
+
+fn canvas_many_components_benchmark(c: &mut Criterion) {
+    c.bench_function("canvas 10k components test", |b| {
+        b.iter(canvas_many_components)
+    });
+}
+
 criterion_group!(
     benches,
     measure_text_benchmark,
     bar_chart_benchmark,
     bar_chart_png_benchmark,
+    canvas_many_components_benchmark,
 );
 criterion_main!(benches);
@@ -1,21 +1,588 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::DeriveInput;
 
 #[proc_macro_derive(Chart)]
 pub fn my_default(input: TokenStream) -> TokenStream {
     let ast: DeriveInput = syn::parse(input).unwrap();
     let id = ast.ident;
+    // the legend category of series without category in auto mode
+    let default_legend_category = match id.to_string().as_str() {
+        "BarChart" | "HorizontalBarChart" | "PieChart" | "HeatmapChart" => {
+            quote! { LegendCategory::RoundRect }
+        }
+        "ScatterChart" => quote! { LegendCategory::Circle },
+        _ => quote! { LegendCategory::Normal },
+    };
+    let builder_id = format_ident!("{}Builder", id);
+    // the required fields of chart, they are validated by builder
+    let validate_required = match id.to_string().as_str() {
+        "HeatmapChart" => quote! {
+            if chart.series.data.is_empty() {
+                return required("series data");
+            }
+            if chart.x_axis_data.is_empty() || chart.y_axis_data.is_empty() {
+                return required("x axis data and y axis data");
+            }
+        },
+        "PieChart" | "ScatterChart" => quote! {
+            if chart.series_list.is_empty() {
+                return required("series list");
+            }
+        },
+        "RadarChart" => quote! {
+            if chart.series_list.is_empty() {
+                return required("series list");
+            }
+            if chart.indicators.is_empty() {
+                return required("indicators");
+            }
+        },
+        _ => quote! {
+            if chart.series_list.is_empty() {
+                return required("series list");
+            }
+            if chart.x_axis_data.is_empty() {
+                return required("x axis data");
+            }
+        },
+    };
 
+    // the series data length of chart is limited by x axis data or indicators
+    let expected_length = match id.to_string().as_str() {
+        "BarChart" | "LineChart" | "HorizontalBarChart" => quote! { Some(self.x_axis_data.len()) },
+        // open, close, low and high values of each category
+        "CandlestickChart" => quote! { Some(self.x_axis_data.len() * 4) },
+        "RadarChart" => quote! { Some(self.indicators.len()) },
+        _ => quote! { None },
+    };
+    let validate_series = if id == "HeatmapChart" {
+        quote! {
+            if self.series.data.is_empty() {
+                return Err(ChartError::EmptySeries);
+            }
+            if let Some(index) = self.series.data.iter().position(|item| item.value.is_infinite()) {
+                return Err(ChartError::InfiniteValue {
+                    name: "heatmap".to_string(),
+                    index,
+                });
+            }
+        }
+    } else {
+        quote! {
+            if self.series_list.iter().all(|series| series.data.is_empty()) {
+                return Err(ChartError::EmptySeries);
+            }
+            let expected: Option<usize> = #expected_length;
+            for series in self.series_list.iter() {
+                if let Some(expected) = expected {
+                    if series.data.len() > expected {
+                        return Err(ChartError::LengthMismatch {
+                            name: series.name.clone(),
+                            length: series.data.len(),
+                            expected,
+                        });
+                    }
+                }
+                // NaN is nil value, so only the infinite value is invalid
+                if let Some(index) = series.data.iter().position(|value| value.is_infinite()) {
+                    return Err(ChartError::InfiniteValue {
+                        name: series.name.clone(),
+                        index,
+                    });
+                }
+            }
+        }
+    };
+    let warn_series = if id == "HeatmapChart" {
+        quote! {}
+    } else {
+        quote! {
+            let expected: Option<usize> = #expected_length;
+            for (index, series) in self.series_list.iter().enumerate() {
+                let length = series.start_index + series.data.len();
+                if let Some(expected) = expected {
+                    // the longer data is the error of validation
+                    if series.data.len() <= expected && length > expected {
+                        warnings.push(ChartWarning::SeriesExceedsAxis {
+                            name: series.name.clone(),
+                            length,
+                            expected,
+                        });
+                    }
+                }
+                let color = get_color(&self.series_colors, series.index.unwrap_or(index));
+                let ratio = color.contrast_ratio(&self.background_color);
+                if series.visible && self.background_color.is_nontransparent() && ratio < 1.5 {
+                    warnings.push(ChartWarning::LowContrast {
+                        name: series.name.clone(),
+                        ratio,
+                    });
+                }
+            }
+        }
+    };
     let gen = quote! {
+        /// Fluent builder of chart, the options are applied after the theme,
+        /// so the order of method calls doesn't matter.
+        #[derive(Default)]
+        pub struct #builder_id {
+            theme: Option<String>,
+            aspect_ratio: Option<f32>,
+            options: Vec<std::boxed::Box<dyn FnOnce(&mut #id)>>,
+        }
+
+        impl #builder_id {
+            /// Sets the theme of chart.
+            pub fn theme(mut self, theme: &str) -> Self {
+                self.theme = Some(theme.to_string());
+                self
+            }
+            /// Sets the option of chart by custom function.
+            pub fn with(mut self, option: impl FnOnce(&mut #id) + 'static) -> Self {
+                self.options.push(std::boxed::Box::new(option));
+                self
+            }
+            /// Sets the width of chart.
+            pub fn width(self, width: f32) -> Self {
+                self.with(move |chart| chart.width = width)
+            }
+            /// Sets the height of chart.
+            pub fn height(self, height: f32) -> Self {
+                self.with(move |chart| chart.height = height)
+            }
+            /// Sets the aspect ratio(width / height) of chart, the height is computed by the width.
+            pub fn aspect_ratio(mut self, aspect_ratio: f32) -> Self {
+                self.aspect_ratio = Some(aspect_ratio);
+                self
+            }
+            /// Emits the svg of 100% width, the height is scaled by aspect ratio.
+            pub fn responsive(self, responsive: bool) -> Self {
+                self.with(move |chart| chart.responsive = responsive)
+            }
+            /// Sets the margin of chart.
+            pub fn margin(self, margin: Box) -> Self {
+                self.with(move |chart| chart.margin = margin)
+            }
+            /// Sets the font family of chart.
+            pub fn font_family(self, font_family: &str) -> Self {
+                let font_family = font_family.to_string();
+                self.with(move |chart| chart.font_family = font_family)
+            }
+            /// Sets the title text of chart.
+            pub fn title(self, title: &str) -> Self {
+                let title = title.to_string();
+                self.with(move |chart| chart.title_text = title)
+            }
+            /// Sets the sub title text of chart.
+            pub fn sub_title(self, sub_title: &str) -> Self {
+                let sub_title = sub_title.to_string();
+                self.with(move |chart| chart.sub_title_text = sub_title)
+            }
+            /// Sets the footer text of chart.
+            pub fn footer(self, footer: &str) -> Self {
+                let footer = footer.to_string();
+                self.with(move |chart| chart.footer_text = footer)
+            }
+            /// Sets the series list of chart.
+            pub fn series(self, series_list: Vec<Series>) -> Self {
+                self.with(move |chart| chart.series_list = series_list)
+            }
+            /// Sets the data of x axis.
+            pub fn x_axis_data(self, x_axis_data: Vec<String>) -> Self {
+                self.with(move |chart| chart.x_axis_data = x_axis_data)
+            }
+            /// Sets the formatter of the first y axis, e.g. {c} ml.
+            pub fn y_axis_formatter(self, formatter: &str) -> Self {
+                let formatter = formatter.to_string();
+                self.with(move |chart| {
+                    if let Some(config) = chart.y_axis_configs.first_mut() {
+                        config.axis_formatter = Some(formatter);
+                    }
+                })
+            }
+            /// Sets the formatter of series label, e.g. {c} ml.
+            pub fn series_label_formatter(self, formatter: &str) -> Self {
+                let formatter = formatter.to_string();
+                self.with(move |chart| chart.series_label_formatter = formatter)
+            }
+            /// Sets the colors of series.
+            pub fn series_colors(self, series_colors: Vec<Color>) -> Self {
+                self.with(move |chart| chart.series_colors = series_colors)
+            }
+            /// Shows or hides the legend.
+            pub fn legend_show(self, legend_show: bool) -> Self {
+                self.with(move |chart| chart.legend_show = Some(legend_show))
+            }
+            /// Builds the chart, it returns error if the required fields are not set.
+            pub fn build(self) -> canvas::Result<#id> {
+                let option = serde_json::json!({
+                    "theme": self.theme.unwrap_or_else(get_default_theme_name),
+                });
+                let mut chart = #id::from_json(&option.to_string())?;
+                for option in self.options {
+                    option(&mut chart);
+                }
+                if let Some(aspect_ratio) = self.aspect_ratio.filter(|value| *value > 0.0) {
+                    chart.height = chart.width / aspect_ratio;
+                }
+                let required = |name: &str| {
+                    Err(canvas::Error::Params {
+                        message: format!("{name} is required"),
+                    })
+                };
+                #validate_required
+                Ok(chart)
+            }
+        }
+
         impl #id {
+            /// Validates the configuration of chart, e.g. the size, series list and values.
+            pub fn validate(&self) -> std::result::Result<(), ChartError> {
+                if !(self.width > 0.0 && self.height > 0.0) {
+                    return Err(ChartError::InvalidSize {
+                        width: self.width,
+                        height: self.height,
+                    });
+                }
+                #validate_series
+                Ok(())
+            }
+            /// Gets the non-fatal issues of chart, e.g. the overlapping labels or
+            /// the series color which is hard to distinguish from background.
+            pub fn get_warnings(&self) -> canvas::Result<Vec<ChartWarning>> {
+                let mut warnings = vec![];
+                #warn_series
+                let ratio = self.title_font_color.contrast_ratio(&self.background_color);
+                if !self.title_text.is_empty() && self.background_color.is_nontransparent() && ratio < 3.0 {
+                    warnings.push(ChartWarning::LowContrast {
+                        name: "title".to_string(),
+                        ratio,
+                    });
+                }
+                let svg = self.render_plain_svg()?;
+                for (text, other) in get_text_overlaps(&svg, &self.font_family) {
+                    warnings.push(ChartWarning::OverlappingLabels { text, other });
+                }
+                Ok(warnings)
+            }
+            /// Gets the key of static layer, it is the hash of options rendered in the layer
+            /// (background, title, legend, grid and axes), the values of y axes and the state of chart type.
+            fn get_layer_key(&self, y_axis_values_list: &[&AxisValues], state: impl std::hash::Hash) -> u64 {
+                use std::hash::{Hash, Hasher};
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                for value in [
+                    self.width,
+                    self.height,
+                    self.x,
+                    self.y,
+                    self.title_font_size,
+                    self.title_height,
+                    self.sub_title_font_size,
+                    self.sub_title_height,
+                    self.footer_font_size,
+                    self.legend_font_size,
+                    self.x_axis_height,
+                    self.x_axis_font_size,
+                    self.x_axis_name_gap,
+                    self.x_axis_name_rotate,
+                    self.grid_stroke_width,
+                ] {
+                    value.to_bits().hash(&mut hasher);
+                }
+                (&self.margin, &self.font_family, &self.background_color, self.is_light).hash(&mut hasher);
+                (
+                    &self.title_text,
+                    &self.title_font_color,
+                    &self.title_font_weight,
+                    &self.title_font_style,
+                    self.title_letter_spacing.map(f32::to_bits),
+                    &self.title_text_decoration,
+                    &self.title_margin,
+                    &self.title_align,
+                    &self.title_shadow,
+                    &self.title_link,
+                )
+                    .hash(&mut hasher);
+                (
+                    &self.sub_title_text,
+                    &self.sub_title_font_color,
+                    &self.sub_title_font_weight,
+                    &self.sub_title_font_style,
+                    self.sub_title_letter_spacing.map(f32::to_bits),
+                    &self.sub_title_text_decoration,
+                    &self.sub_title_margin,
+                    &self.sub_title_align,
+                )
+                    .hash(&mut hasher);
+                (
+                    &self.footer_text,
+                    &self.footer_font_color,
+                    &self.footer_font_weight,
+                    &self.footer_margin,
+                    &self.footer_align,
+                )
+                    .hash(&mut hasher);
+                (
+                    &self.legend_font_color,
+                    &self.legend_font_weight,
+                    &self.legend_align,
+                    &self.legend_margin,
+                    &self.legend_category,
+                    &self.legend_show,
+                    &self.legend_position,
+                    &self.legend_order,
+                    &self.series_colors,
+                )
+                    .hash(&mut hasher);
+                // only the legend of series is in the static layer
+                for series in self.series_list.iter() {
+                    (
+                        &series.name,
+                        &series.legend_text,
+                        &series.index,
+                        series.visible,
+                        &series.category,
+                        &series.stroke_dash_array,
+                        series.trend_line.is_some(),
+                    )
+                        .hash(&mut hasher);
+                }
+                (
+                    &self.x_axis_data,
+                    &self.x_axis_stroke_color,
+                    &self.x_axis_font_color,
+                    &self.x_axis_font_weight,
+                    &self.x_axis_margin,
+                    &self.x_boundary_gap,
+                    &self.y_axis_configs,
+                )
+                    .hash(&mut hasher);
+                (
+                    &self.grid_stroke_color,
+                    &self.zero_line_color,
+                    &self.axis_pointer,
+                    self.element_ids,
+                    self.rtl,
+                    &self.locale,
+                    &self.coordinate_precision,
+                )
+                    .hash(&mut hasher);
+                state.hash(&mut hasher);
+                for values in y_axis_values_list.iter() {
+                    values.data.hash(&mut hasher);
+                    values.min.to_bits().hash(&mut hasher);
+                    values.max.to_bits().hash(&mut hasher);
+                }
+                hasher.finish()
+            }
+            /// Gets the texts which extend beyond the canvas, e.g. the rotated x axis labels
+            /// or the labels outside the bars.
+            pub fn get_overflows(&self) -> canvas::Result<Vec<TextOverflow>> {
+                let svg = self.render_plain_svg()?;
+                Ok(get_text_overflows(&svg, &self.font_family))
+            }
+            /// Renders the svg of one element per line, the texts are detected by their attributes.
+            fn render_plain_svg(&self) -> canvas::Result<String> {
+                let mut chart = self.clone();
+                chart.svg_format = SVGFormat::default();
+                chart.style_hoisting = false;
+                chart.font_embedding = FontEmbedding::default();
+                chart.responsive = false;
+                chart.svg()
+            }
+            /// Expands the margin by the overflows of texts, it returns true if the margin is changed.
+            pub fn expand_margin_for_overflows(&mut self) -> canvas::Result<bool> {
+                let overflows = self.get_overflows()?;
+                if overflows.is_empty() {
+                    return Ok(false);
+                }
+                let mut expanded = Box::default();
+                for item in overflows.iter() {
+                    expanded.left = expanded.left.max(item.overflow.left.ceil());
+                    expanded.top = expanded.top.max(item.overflow.top.ceil());
+                    expanded.right = expanded.right.max(item.overflow.right.ceil());
+                    expanded.bottom = expanded.bottom.max(item.overflow.bottom.ceil());
+                }
+                self.margin.left += expanded.left;
+                self.margin.top += expanded.top;
+                self.margin.right += expanded.right;
+                self.margin.bottom += expanded.bottom;
+                Ok(true)
+            }
+            /// Gets the chart of categories in x range, it returns none if the x range is not set.
+            /// The axis pointer is moved with the range, and it is removed if out of range.
+            fn get_x_range_chart(&self) -> Option<Self> {
+                let (start, end) = self.x_range?;
+                // the range is ignored if it is out of the categories, e.g. empty x axis data
+                if start >= self.x_axis_data.len() {
+                    return None;
+                }
+                let end = end.min(self.x_axis_data.len() - 1);
+                if start > end {
+                    return None;
+                }
+                let mut chart = self.clone();
+                chart.x_range = None;
+                chart.x_axis_data = self.x_axis_data[start..=end].to_vec();
+                chart.series_list = self
+                    .series_list
+                    .iter()
+                    .map(|series| series.slice_categories(start, end))
+                    .collect();
+                chart.axis_pointer = self.axis_pointer.clone().and_then(|mut axis_pointer| {
+                    if axis_pointer.index < start || axis_pointer.index > end {
+                        return None;
+                    }
+                    axis_pointer.index -= start;
+                    Some(axis_pointer)
+                });
+                if self.x_range_overview {
+                    // the overview is rendered as inset below the chart
+                    let overview_height = 40.0;
+                    let bottom = self.height - self.margin.bottom;
+                    let area = Box {
+                        left: self.margin.left,
+                        top: bottom - overview_height,
+                        right: self.width - self.margin.right,
+                        bottom,
+                    };
+                    if let Ok(svg) = self.render_x_range_overview(area.width(), overview_height, start, end) {
+                        chart.insets.insert(0, Inset::new(area, svg));
+                        chart.margin.bottom += overview_height + 10.0;
+                    }
+                }
+                Some(chart)
+            }
+            /// Renders the overview strip of all categories, the series are drawn as thin lines
+            /// and the categories from start to end are highlighted.
+            fn render_x_range_overview(&self, width: f32, height: f32, start: usize, end: usize) -> canvas::Result<String> {
+                let mut c = Canvas::new(width, height);
+                let count = self.x_axis_data.len().max(1);
+                let unit_width = width / count as f32;
+                let data_list: Vec<f32> = self
+                    .series_list
+                    .iter()
+                    .filter(|series| series.visible)
+                    .flat_map(|series| series.get_data())
+                    .filter(|value| !is_nil_value(*value))
+                    .collect();
+                let min = data_list.iter().cloned().fold(f32::MAX, f32::min);
+                let max = data_list.iter().cloned().fold(f32::MIN, f32::max);
+                let padding = 4.0;
+                let get_y = |value: f32| -> f32 {
+                    if max <= min {
+                        return height / 2.0;
+                    }
+                    height - padding - (value - min) / (max - min) * (height - padding * 2.0)
+                };
+                for (index, series) in self.series_list.iter().enumerate() {
+                    if !series.visible {
+                        continue;
+                    }
+                    let points: Vec<Point> = series
+                        .get_data()
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, value)| !is_nil_value(**value) && i + series.start_index < count)
+                        .map(|(i, value)| (unit_width * ((i + series.start_index) as f32 + 0.5), get_y(*value)).into())
+                        .collect();
+                    c.straight_line(StraightLine {
+                        color: Some(get_color(&self.series_colors, series.index.unwrap_or(index))),
+                        points,
+                        symbol: None,
+                        ..Default::default()
+                    });
+                }
+                c.rect(Rect {
+                    fill: Some(self.x_axis_stroke_color.with_alpha(40)),
+                    left: unit_width * start as f32,
+                    width: unit_width * (end - start + 1) as f32,
+                    height,
+                    ..Default::default()
+                });
+                c.rect(Rect {
+                    color: Some(self.x_axis_stroke_color),
+                    fill: Some(Color::transparent()),
+                    width,
+                    height,
+                    ..Default::default()
+                });
+                c.svg()
+            }
+            /// Validates the chart, it renders the empty state if all series are empty.
+            fn render_empty_state(&self) -> canvas::Result<Option<String>> {
+                match self.validate() {
+                    Ok(()) => return Ok(None),
+                    Err(ChartError::EmptySeries) => {}
+                    Err(err) => return Err(err.into()),
+                }
+                let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+                c.aria_label = self.aria_label.clone();
+                c.description = self.description.clone();
+                c.svg_format = self.svg_format.clone();
+                c.coordinate_precision = self.coordinate_precision;
+                c.style_hoisting = self.style_hoisting;
+                c.font_embedding = self.font_embedding.clone();
+                c.responsive = self.responsive;
+
+                self.render_background(c.child(Box::default()));
+                c.margin = self.margin.clone();
+                let title_height = self.render_title(c.child(Box::default()));
+                let footer_height = self.render_footer(c.child(Box::default()));
+                let mut c1 = c.child(Box {
+                    top: title_height,
+                    bottom: footer_height,
+                    ..Default::default()
+                });
+                let cx = c1.width() / 2.0;
+                let mut cy = c1.height() / 2.0;
+                if self.empty_icon {
+                    // the icon of empty box above the text
+                    let (width, height) = (48.0, 32.0);
+                    let top = cy - height - 4.0;
+                    c1.rect(Rect {
+                        color: Some(self.x_axis_stroke_color),
+                        fill: Some(Color::transparent()),
+                        left: cx - width / 2.0,
+                        top,
+                        width,
+                        height,
+                        rx: Some(4.0),
+                        ry: Some(4.0),
+                        ..Default::default()
+                    });
+                    c1.line(Line {
+                        color: Some(self.x_axis_stroke_color),
+                        left: cx - width / 2.0,
+                        top: top + height / 2.0,
+                        right: cx + width / 2.0,
+                        bottom: top + height / 2.0,
+                        ..Default::default()
+                    });
+                    cy += self.x_axis_font_size;
+                }
+                c1.text(Text {
+                    text: self.empty_text.clone().unwrap_or_else(|| "No data".to_string()),
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(self.x_axis_font_color),
+                    font_size: Some(self.x_axis_font_size),
+                    align: Some(Align::Center),
+                    vertical_align: Some(VerticalAlign::Middle),
+                    x: Some(cx),
+                    y: Some(cy),
+                    ..Default::default()
+                });
+                c.svg().map(Some)
+            }
+            /// Creates a fluent builder of chart.
+            pub fn builder() -> #builder_id {
+                #builder_id::default()
+            }
             /// Fills the default options of current theme.
             fn fill_theme(&mut self, t: Arc<Theme>) {
-                self.font_family = t.font_family.clone();
                 self.margin = t.margin.clone();
-                self.width = t.width;
-                self.height = t.height;
                 self.background_color = t.background_color;
                 self.is_light = t.is_light;
 
@@ -32,6 +599,10 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                 self.sub_title_align = t.sub_title_align.clone();
                 self.sub_title_height = t.sub_title_height;
 
+                self.footer_font_color = t.sub_title_font_color;
+                self.footer_font_size = DEFAULT_FOOTER_FONT_SIZE;
+                self.footer_align = Align::Left;
+
                 self.legend_font_color = t.legend_font_color;
                 self.legend_font_size = t.legend_font_size;
                 self.legend_align = t.legend_align.clone();
@@ -66,21 +637,30 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                     self.series_stroke_width,
                     Some(self.background_color),
                 ));
+
+                let style = EffectiveStyle::new(Some(t));
+                self.font_family = style.font_family(None);
+                self.width = style.width(None);
+                self.height = style.height(None);
             }
             /// Fills the options from json config.
             fn fill_option(&mut self, data: &str) -> canvas::Result<serde_json::Value> {
                 let data: serde_json::Value = serde_json::from_str(data)?;
                 let series_list = get_series_list_from_value(&data).unwrap_or_default();
                 let theme = get_string_from_value(&data, "theme").unwrap_or_default();
-                let theme = get_theme(&theme);
+                let style = EffectiveStyle::new(find_theme(&theme));
+                let theme = style.theme();
                 self.fill_theme(theme.clone());
                 self.series_list = series_list;
+                self.resolve_style(&style, &data);
 
-                if let Some(width) = get_f32_from_value(&data, "width") {
-                    self.width = width;
-                }
-                if let Some(height) = get_f32_from_value(&data, "height") {
-                    self.height = height;
+                // the size is computed by aspect ratio(width / height) if only width or height is set
+                if let Some(aspect_ratio) = get_f32_from_value(&data, "aspect_ratio").filter(|value| *value > 0.0) {
+                    if data.get("width").is_none() && data.get("height").is_some() {
+                        self.width = self.height * aspect_ratio;
+                    } else if data.get("height").is_none() {
+                        self.height = self.width / aspect_ratio;
+                    }
                 }
                 if let Some(x) = get_f32_from_value(&data, "x") {
                     self.x = x;
@@ -91,21 +671,21 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                 if let Some(margin) = get_margin_from_value(&data, "margin") {
                     self.margin = margin;
                 }
-                if let Some(font_family) = get_string_from_value(&data, "font_family") {
-                    self.font_family = font_family;
-                }
                 if let Some(title_text) = get_string_from_value(&data, "title_text") {
                     self.title_text = title_text;
                 }
-                if let Some(title_font_size) = get_f32_from_value(&data, "title_font_size") {
-                    self.title_font_size = title_font_size;
-                }
-                if let Some(title_font_color) = get_color_from_value(&data, "title_font_color") {
-                    self.title_font_color = title_font_color;
-                }
                 if let Some(title_font_weight) = get_string_from_value(&data, "title_font_weight") {
                     self.title_font_weight = Some(title_font_weight);
                 }
+                if let Some(title_font_style) = get_string_from_value(&data, "title_font_style") {
+                    self.title_font_style = Some(title_font_style);
+                }
+                if let Some(title_letter_spacing) = get_f32_from_value(&data, "title_letter_spacing") {
+                    self.title_letter_spacing = Some(title_letter_spacing);
+                }
+                if let Some(title_text_decoration) = get_string_from_value(&data, "title_text_decoration") {
+                    self.title_text_decoration = Some(title_text_decoration);
+                }
                 if let Some(title_margin) = get_margin_from_value(&data, "title_margin") {
                     self.title_margin = Some(title_margin);
                 }
@@ -115,19 +695,25 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                 if let Some(title_height) = get_f32_from_value(&data, "title_height") {
                     self.title_height = title_height;
                 }
+                if let Some(title_shadow) = get_shadow_from_value(&data, "title_shadow") {
+                    self.title_shadow = Some(title_shadow);
+                }
 
                 if let Some(sub_title_text) = get_string_from_value(&data, "sub_title_text") {
                     self.sub_title_text = sub_title_text;
                 }
-                if let Some(sub_title_font_size) = get_f32_from_value(&data, "sub_title_font_size") {
-                    self.sub_title_font_size = sub_title_font_size;
-                }
-                if let Some(sub_title_font_color) = get_color_from_value(&data, "sub_title_font_color") {
-                    self.sub_title_font_color = sub_title_font_color;
-                }
                 if let Some(sub_title_font_weight) = get_string_from_value(&data, "sub_title_font_weight") {
                     self.sub_title_font_weight = Some(sub_title_font_weight);
                 }
+                if let Some(sub_title_font_style) = get_string_from_value(&data, "sub_title_font_style") {
+                    self.sub_title_font_style = Some(sub_title_font_style);
+                }
+                if let Some(sub_title_letter_spacing) = get_f32_from_value(&data, "sub_title_letter_spacing") {
+                    self.sub_title_letter_spacing = Some(sub_title_letter_spacing);
+                }
+                if let Some(sub_title_text_decoration) = get_string_from_value(&data, "sub_title_text_decoration") {
+                    self.sub_title_text_decoration = Some(sub_title_text_decoration);
+                }
                 if let Some(sub_title_margin) = get_margin_from_value(&data, "sub_title_margin") {
                     self.sub_title_margin = Some(sub_title_margin);
                 }
@@ -138,12 +724,6 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                     self.sub_title_height = sub_title_height;
                 }
 
-                if let Some(legend_font_size) = get_f32_from_value(&data, "legend_font_size") {
-                    self.legend_font_size = legend_font_size;
-                }
-                if let Some(legend_font_color) = get_color_from_value(&data, "legend_font_color") {
-                    self.legend_font_color = legend_font_color;
-                }
                 if let Some(legend_font_weight) = get_string_from_value(&data, "legend_font_weight") {
                     self.legend_font_weight = Some(legend_font_weight);
                 }
@@ -159,6 +739,27 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                 if let Some(legend_show) = get_bool_from_value(&data, "legend_show") {
                     self.legend_show = Some(legend_show);
                 }
+                if let Some(footer_text) = get_string_from_value(&data, "footer_text") {
+                    self.footer_text = footer_text;
+                }
+                if let Some(footer_font_weight) = get_string_from_value(&data, "footer_font_weight") {
+                    self.footer_font_weight = Some(footer_font_weight);
+                }
+                if let Some(footer_margin) = get_margin_from_value(&data, "footer_margin") {
+                    self.footer_margin = Some(footer_margin);
+                }
+                if let Some(footer_align) = get_align_from_value(&data, "footer_align") {
+                    self.footer_align = footer_align;
+                }
+                if let Some(title_link) = get_string_from_value(&data, "title_link") {
+                    self.title_link = Some(title_link);
+                }
+                if let Some(legend_position) = get_position_from_value(&data, "legend_position") {
+                    self.legend_position = Some(legend_position);
+                }
+                if let Some(legend_order) = get_string_slice_from_value(&data, "legend_order") {
+                    self.legend_order = Some(legend_order);
+                }
 
                 if let Some(x_axis_data) = get_string_slice_from_value(&data, "x_axis_data") {
                     self.x_axis_data = x_axis_data;
@@ -166,15 +767,6 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                 if let Some(x_axis_height) = get_f32_from_value(&data, "x_axis_height") {
                     self.x_axis_height = x_axis_height;
                 }
-                if let Some(x_axis_stroke_color) = get_color_from_value(&data, "x_axis_stroke_color") {
-                    self.x_axis_stroke_color = x_axis_stroke_color;
-                }
-                if let Some(x_axis_font_size) = get_f32_from_value(&data, "x_axis_font_size") {
-                    self.x_axis_font_size = x_axis_font_size;
-                }
-                if let Some(x_axis_font_color) = get_color_from_value(&data, "x_axis_font_color") {
-                    self.x_axis_font_color = x_axis_font_color;
-                }
                 if let Some(x_axis_font_weight) = get_string_from_value(&data, "x_axis_font_weight") {
                     self.x_axis_font_weight = Some(x_axis_font_weight);
                 }
@@ -190,6 +782,14 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                 if let Some(x_boundary_gap) = get_bool_from_value(&data, "x_boundary_gap") {
                     self.x_boundary_gap = Some(x_boundary_gap);
                 }
+                if let Some(x_range) = get_usize_slice_from_value(&data, "x_range") {
+                    if x_range.len() == 2 {
+                        self.x_range = Some((x_range[0], x_range[1]));
+                    }
+                }
+                if let Some(x_range_overview) = get_bool_from_value(&data, "x_range_overview") {
+                    self.x_range_overview = x_range_overview;
+                }
 
                 if let Some(y_axis_configs) = get_y_axis_configs_from_value(theme.clone(), &data, "y_axis_configs") {
                     self.y_axis_configs = y_axis_configs;
@@ -201,28 +801,29 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                 if let Some(grid_stroke_width) = get_f32_from_value(&data, "grid_stroke_width") {
                     self.grid_stroke_width = grid_stroke_width;
                 }
+                if let Some(zero_line_color) = get_color_from_value(&data, "zero_line_color") {
+                    self.zero_line_color = Some(zero_line_color);
+                }
+                if let Some(guide_line) = get_guide_line_from_value(&data, "guide_line") {
+                    self.guide_line = Some(guide_line);
+                }
 
                 if let Some(series_stroke_width) = get_f32_from_value(&data, "series_stroke_width") {
                     self.series_stroke_width = series_stroke_width;
                 }
-                if let Some(series_label_font_color) =
-                    get_color_from_value(&data, "series_label_font_color")
-                {
-                    self.series_label_font_color = series_label_font_color;
-                }
-                if let Some(series_label_font_size) = get_f32_from_value(&data, "series_label_font_size") {
-                    self.series_label_font_size = series_label_font_size;
-                }
                 if let Some(series_label_font_weight) = get_string_from_value(&data, "series_label_font_weight") {
                     self.series_label_font_weight = Some(series_label_font_weight);
                 }
                 if let Some(series_label_formatter) = get_string_from_value(&data, "series_label_formatter") {
                     self.series_label_formatter = series_label_formatter;
                 }
-
-                if let Some(series_colors) = get_color_slice_from_value(&data, "series_colors") {
-                    self.series_colors = series_colors;
+                if let Some(series_label_overlap) = get_label_overlap_from_value(&data, "series_label_overlap") {
+                    self.series_label_overlap = series_label_overlap;
+                }
+                if let Some(series_shadow) = get_shadow_from_value(&data, "series_shadow") {
+                    self.series_shadow = Some(series_shadow);
                 }
+
                 if let Some(series_symbol) = get_series_symbol_from_value(&data, "series_symbol") {
                     self.series_symbol = Some(series_symbol);
                 }
@@ -232,9 +833,115 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                 if let Some(series_fill) = get_bool_from_value(&data, "series_fill") {
                     self.series_fill = series_fill;
                 }
+                if let Some(aria_label) = get_string_from_value(&data, "aria_label") {
+                    self.aria_label = Some(aria_label);
+                }
+                if let Some(description) = get_string_from_value(&data, "description") {
+                    self.description = Some(description);
+                }
+                if let Some(coordinate_precision) = get_usize_from_value(&data, "coordinate_precision") {
+                    self.coordinate_precision = Some(coordinate_precision);
+                }
+                if let Some(svg_format) = get_svg_format_from_value(&data, "svg_format") {
+                    self.svg_format = svg_format;
+                }
+                if let Some(rtl) = get_bool_from_value(&data, "rtl") {
+                    self.rtl = rtl;
+                }
+                if let Some(locale) = get_string_from_value(&data, "locale") {
+                    self.locale = Some(locale);
+                }
+                if let Some(empty_text) = get_string_from_value(&data, "empty_text") {
+                    self.empty_text = Some(empty_text);
+                }
+                if let Some(empty_icon) = get_bool_from_value(&data, "empty_icon") {
+                    self.empty_icon = empty_icon;
+                }
+                if let Some(insets) = get_insets_from_value(&data, "insets") {
+                    self.insets = insets;
+                }
+                if let Some(emphasis) = get_emphasis_from_value(&data, "emphasis") {
+                    self.emphasis = Some(emphasis);
+                }
+                if let Some(axis_pointer) = get_axis_pointer_from_value(&data, "axis_pointer") {
+                    self.axis_pointer = Some(axis_pointer);
+                }
+                if let Some(style_hoisting) = get_bool_from_value(&data, "style_hoisting") {
+                    self.style_hoisting = style_hoisting;
+                }
+                if let Some(responsive) = get_bool_from_value(&data, "responsive") {
+                    self.responsive = responsive;
+                }
+                if let Some(font_embedding) = get_font_embedding_from_value(&data, "font_embedding") {
+                    self.font_embedding = font_embedding;
+                }
+                if let Some(tooltip_show) = get_bool_from_value(&data, "tooltip_show") {
+                    self.tooltip_show = tooltip_show;
+                }
+                if let Some(element_ids) = get_bool_from_value(&data, "element_ids") {
+                    self.element_ids = element_ids;
+                }
+                if let Some(series_fill_opacity) = get_f32_from_value(&data, "series_fill_opacity") {
+                    self.series_fill_opacity = Some(series_fill_opacity);
+                }
+                if let Some(max_points_per_series) = get_usize_from_value(&data, "max_points_per_series") {
+                    self.max_points_per_series = Some(max_points_per_series);
+                }
 
                 Ok(data)
             }
+            /// Resolves the style of chart from json config by the cascade:
+            /// explicit value → default option → theme → built-in.
+            fn resolve_style(&mut self, style: &EffectiveStyle, data: &serde_json::Value) {
+                self.font_family = style.font_family(get_string_from_value(data, "font_family"));
+                self.width = style.width(get_f32_from_value(data, "width"));
+                self.height = style.height(get_f32_from_value(data, "height"));
+
+                self.title_font_size =
+                    style.size(get_f32_from_value(data, "title_font_size"), |t| t.title_font_size);
+                self.title_font_color =
+                    style.color(get_color_from_value(data, "title_font_color"), |t| t.title_font_color);
+                self.sub_title_font_size = style.size(
+                    get_f32_from_value(data, "sub_title_font_size"),
+                    |t| t.sub_title_font_size,
+                );
+                self.sub_title_font_color = style.color(
+                    get_color_from_value(data, "sub_title_font_color"),
+                    |t| t.sub_title_font_color,
+                );
+                self.footer_font_size = style.size(
+                    get_f32_from_value(data, "footer_font_size"),
+                    |_| DEFAULT_FOOTER_FONT_SIZE,
+                );
+                self.footer_font_color = style.color(
+                    get_color_from_value(data, "footer_font_color"),
+                    |t| t.sub_title_font_color,
+                );
+                self.legend_font_size =
+                    style.size(get_f32_from_value(data, "legend_font_size"), |t| t.legend_font_size);
+                self.legend_font_color =
+                    style.color(get_color_from_value(data, "legend_font_color"), |t| t.legend_font_color);
+
+                self.x_axis_font_size =
+                    style.size(get_f32_from_value(data, "x_axis_font_size"), |t| t.x_axis_font_size);
+                self.x_axis_font_color =
+                    style.color(get_color_from_value(data, "x_axis_font_color"), |t| t.x_axis_font_color);
+                self.x_axis_stroke_color = style.color(
+                    get_color_from_value(data, "x_axis_stroke_color"),
+                    |t| t.x_axis_stroke_color,
+                );
+
+                self.series_colors =
+                    style.series_colors(get_color_slice_from_value(data, "series_colors"));
+                self.series_label_font_size = style.size(
+                    get_f32_from_value(data, "series_label_font_size"),
+                    |t| t.series_label_font_size,
+                );
+                self.series_label_font_color = style.color(
+                    get_color_from_value(data, "series_label_font_color"),
+                    |t| t.series_label_font_color,
+                );
+            }
             /// Gets y axis config by index.
             fn get_y_axis_config(&self, index: usize) -> YAxisConfig {
                 let size = self.y_axis_configs.len();
@@ -250,11 +957,19 @@ pub fn my_default(input: TokenStream) -> TokenStream {
             fn get_y_axis_values(&self, y_axis_index: usize) -> (AxisValues, f32) {
                 let y_axis_config = self.get_y_axis_config(y_axis_index);
                 let mut data_list = vec![];
+                let mut stacked_series_list = vec![];
                 for series in self.series_list.iter() {
-                    if series.y_axis_index == y_axis_index {
-                        data_list.append(series.data.clone().as_mut());
+                    if series.visible && series.y_axis_index == y_axis_index {
+                        data_list.append(series.get_data().as_mut());
+                        data_list.append(series.lower_data.clone().as_mut());
+                        data_list.append(series.upper_data.clone().as_mut());
+                        if !matches!(series.category, Some(SeriesCategory::Line | SeriesCategory::Band)) {
+                            stacked_series_list.push(series);
+                        }
                     }
                 }
+                // the stacked bars take the range of their sums
+                data_list.append(get_stack_sum_values(&stacked_series_list).as_mut());
                 if data_list.is_empty() {
                    return (AxisValues::default(), 0.0);
                 }
@@ -269,22 +984,24 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                     min: y_axis_config.axis_min,
                     max: y_axis_config.axis_max,
                     thousands_format,
+                    include_zero: y_axis_config.axis_include_zero,
+                    symmetric: y_axis_config.axis_symmetric,
+                    nice: y_axis_config.axis_nice,
+                    max_significant_digits: y_axis_config.axis_max_significant_digits,
                 });
                 let y_axis_width = if let Some(value) = y_axis_config.axis_width {
                     value
                 } else {
+                    // the widest formatted label is measured, the longest text may be narrower
                     let y_axis_formatter = &y_axis_config.axis_formatter.clone().unwrap_or_default();
-                    let mut longest_item: &str = "";
+                    let mut max_width = None;
                     for item in &y_axis_values.data {
-                        if item.chars().count() > longest_item.chars().count() { longest_item = item }
-                    }
-                    let value = format_string(longest_item, y_axis_formatter);
-                    if let Ok(b) = measure_text_width_family(&self.font_family, y_axis_config.axis_font_size, &value)
-                    {
-                        b.width() + 5.0
-                    } else {
-                        DEFAULT_Y_AXIS_WIDTH
+                        let value = format_string(item, y_axis_formatter);
+                        if let Ok(b) = measure_text_width_family(&self.font_family, y_axis_config.axis_font_size, &value) {
+                            max_width = Some(b.width().max(max_width.unwrap_or_default()));
+                        }
                     }
+                    max_width.map(|width| width + 5.0).unwrap_or(DEFAULT_Y_AXIS_WIDTH)
                 };
                 (y_axis_values, y_axis_width)
             }
@@ -307,120 +1024,454 @@ pub fn my_default(input: TokenStream) -> TokenStream {
             fn render_title(&self, c: Canvas) -> f32 {
                 let mut title_height = 0.0;
 
+                let mut c = c;
+                let title_filter_id = self.title_shadow.as_ref().map(|shadow| {
+                    let id = "title-shadow";
+                    c.drop_shadow(shadow.to_drop_shadow(id));
+                    id.to_string()
+                });
+                // each line of title is rendered separately
+                let mut title_line_count = 0;
                 if !self.title_text.is_empty() {
                     let title_margin = self.title_margin.clone().unwrap_or_default();
-                    let mut x = 0.0;
-                    if let Ok(title_box) =
-                        measure_text_width_family(&self.font_family, self.title_font_size, &self.title_text)
-                    {
-                        x = match self.title_align {
-                            Align::Center => (c.width() - title_box.width()) / 2.0,
-                            Align::Right => c.width() - title_box.width(),
-                            _ => 0.0,
+                    let title_margin_bottom = title_margin.bottom;
+                    let mut title_canvas = c.child(title_margin);
+                    for (index, text) in self.title_text.split('\n').enumerate() {
+                        let mut x = 0.0;
+                        if let Ok(title_box) =
+                            measure_text_width_family(&self.font_family, self.title_font_size, text)
+                        {
+                            // the letter spacing is added after each character
+                            let width = title_box.width()
+                                + self.title_letter_spacing.unwrap_or_default() * text.chars().count() as f32;
+                            x = match self.title_align {
+                                Align::Center => (c.width() - width) / 2.0,
+                                Align::Right => c.width() - width,
+                                _ => 0.0,
+                            }
                         }
+                        let b = title_canvas.text(Text {
+                            text: text.to_string(),
+                            font_family: Some(self.font_family.clone()),
+                            font_size: Some(self.title_font_size),
+                            font_weight: self.title_font_weight.clone(),
+                            font_style: self.title_font_style.clone(),
+                            letter_spacing: self.title_letter_spacing,
+                            text_decoration: self.title_text_decoration.clone(),
+                            font_color: Some(self.title_font_color),
+                            line_height: Some(self.title_height),
+                            x: Some(x),
+                            y: Some(self.title_height * index as f32),
+                            filter_id: title_filter_id.clone(),
+                            link: self.title_link.clone(),
+                            ..Default::default()
+                        });
+                        title_height = b.outer_height() + title_margin_bottom;
+                        title_line_count += 1;
                     }
-                    let title_margin_bottom = title_margin.bottom;
-                    let b = c.child(title_margin).text(Text {
-                        text: self.title_text.clone(),
-                        font_family: Some(self.font_family.clone()),
-                        font_size: Some(self.title_font_size),
-                        font_weight: self.title_font_weight.clone(),
-                        font_color: Some(self.title_font_color),
-                        line_height: Some(self.title_height),
-                        x: Some(x),
-                        ..Default::default()
-                    });
-                    title_height = b.outer_height() + title_margin_bottom;
                 }
                 if !self.sub_title_text.is_empty() {
                     let mut sub_title_margin = self.sub_title_margin.clone().unwrap_or_default();
-                    let mut x = 0.0;
-                    if let Ok(sub_title_box) = measure_text_width_family(
-                        &self.font_family,
-                        self.sub_title_font_size,
-                        &self.sub_title_text,
-                    ) {
-                        x = match self.sub_title_align {
-                            Align::Center => (c.width() - sub_title_box.width()) / 2.0,
-                            Align::Right => c.width() - sub_title_box.width(),
-                            _ => 0.0,
+                    let sub_title_margin_bottom = sub_title_margin.bottom;
+                    sub_title_margin.top += self.title_height * title_line_count.max(1) as f32;
+                    let mut sub_title_canvas = c.child(sub_title_margin);
+                    for (index, text) in self.sub_title_text.split('\n').enumerate() {
+                        let mut x = 0.0;
+                        if let Ok(sub_title_box) = measure_text_width_family(
+                            &self.font_family,
+                            self.sub_title_font_size,
+                            text,
+                        ) {
+                            let width = sub_title_box.width()
+                                + self.sub_title_letter_spacing.unwrap_or_default() * text.chars().count() as f32;
+                            x = match self.sub_title_align {
+                                Align::Center => (c.width() - width) / 2.0,
+                                Align::Right => c.width() - width,
+                                _ => 0.0,
+                            }
                         }
+                        let b = sub_title_canvas.text(Text {
+                            text: text.to_string(),
+                            font_family: Some(self.font_family.clone()),
+                            font_size: Some(self.sub_title_font_size),
+                            font_color: Some(self.sub_title_font_color),
+                            line_height: Some(self.sub_title_height),
+                            font_weight: self.sub_title_font_weight.clone(),
+                            font_style: self.sub_title_font_style.clone(),
+                            letter_spacing: self.sub_title_letter_spacing,
+                            text_decoration: self.sub_title_text_decoration.clone(),
+                            x: Some(x),
+                            y: Some(self.sub_title_height * index as f32),
+                            filter_id: title_filter_id.clone(),
+                            ..Default::default()
+                        });
+                        title_height = b.outer_height() + sub_title_margin_bottom;
                     }
-                    let sub_title_margin_bottom = sub_title_margin.bottom;
-                    sub_title_margin.top += self.title_height;
-                    let b = c.child(sub_title_margin).text(Text {
-                        text: self.sub_title_text.clone(),
-                        font_family: Some(self.font_family.clone()),
-                        font_size: Some(self.sub_title_font_size),
-                        font_color: Some(self.sub_title_font_color),
-                        line_height: Some(self.sub_title_height),
-                        font_weight: self.sub_title_font_weight.clone(),
-                        x: Some(x),
-                        ..Default::default()
-                    });
-                    title_height = b.outer_height() + sub_title_margin_bottom;
                 }
                 title_height
             }
-            /// Renders legend widget for canvas.
-            fn render_legend(&self, c: Canvas) -> f32 {
+            /// Renders the inset charts, the area of inset is relative to the chart.
+            fn render_insets(&self, c: Canvas) {
+                let mut c1 = c;
+                c1.margin = Box::default();
+                for inset in self.insets.iter() {
+                    c1.inset(inset.clone());
+                }
+            }
+            /// Gets the canvas of series elements, they are grouped with the name of series for accessibility,
+            /// with the z index if any series of chart has z index, and with the opacity if it is faded.
+            fn get_series_canvas(&self, c: &mut Canvas, series: &Series, index: usize) -> Canvas {
+                let z_ordered = self.series_list.iter().any(|item| item.z_index != 0);
+                let opacity = self
+                    .emphasis
+                    .as_ref()
+                    .filter(|emphasis| emphasis.is_series_faded(index))
+                    .map(|emphasis| emphasis.get_fade_opacity());
+                if self.aria_label.is_none() && !z_ordered && opacity.is_none() {
+                    return c.clone();
+                }
+                c.group(Group {
+                    aria_label: self.aria_label.as_ref().map(|_| series.name.clone()),
+                    z_index: z_ordered.then_some(series.z_index),
+                    opacity,
+                    ..Default::default()
+                })
+            }
+            /// Renders the annotations of chart, the data coordinates are converted
+            /// by the y axis values, they are ignored if the chart has no y axis.
+            fn render_annotations(
+                &self,
+                c: Canvas,
+                y_axis_values_list: &[&AxisValues],
+                max_height: f32,
+                series_data_count: usize,
+            ) {
+                if self.annotations.is_empty() {
+                    return;
+                }
+                let mut c1 = c;
+                let x_boundary_gap = self.x_boundary_gap.unwrap_or(true);
+                let split_unit_count = if x_boundary_gap {
+                    series_data_count as f32
+                } else {
+                    series_data_count as f32 - 1.0
+                };
+                let unit_width = c1.width() / split_unit_count;
+                let margin = c1.margin.clone();
+                let get_point = |point: &AnnotationPoint, y_axis_index: usize| -> Option<Point> {
+                    match point {
+                        AnnotationPoint::Pixel(x, y) => Some((x - margin.left, y - margin.top).into()),
+                        AnnotationPoint::Data(index, value) => {
+                            let y_axis_values = y_axis_values_list
+                                .get(y_axis_index)
+                                .or(y_axis_values_list.first())?;
+                            let mut x = unit_width * *index as f32;
+                            if x_boundary_gap {
+                                x += unit_width / 2.0;
+                            }
+                            Some((x, y_axis_values.get_offset_height(*value, max_height)).into())
+                        }
+                    }
+                };
+                for annotation in self.annotations.iter() {
+                    let Some(point) = get_point(&annotation.point, annotation.y_axis_index) else {
+                        continue;
+                    };
+                    let color = annotation.color.unwrap_or(self.title_font_color);
+                    // the annotation of z index is reordered with the series
+                    let mut c2 = if annotation.z_index.is_some() {
+                        c1.group(Group {
+                            z_index: annotation.z_index,
+                            ..Default::default()
+                        })
+                    } else {
+                        c1.clone()
+                    };
+                    match annotation.shape {
+                        AnnotationShape::Text(ref text) => {
+                            let font_size = annotation.font_size.unwrap_or(self.series_label_font_size);
+                            let padding = 5.0;
+                            let text_box = measure_text_width_family(&self.font_family, font_size, text)
+                                .unwrap_or_default();
+                            let text_width = text_box.width()
+                                + annotation.letter_spacing.unwrap_or_default() * text.chars().count() as f32;
+                            c2.rect(Rect {
+                                color: Some(color),
+                                fill: Some(self.background_color),
+                                left: point.x,
+                                top: point.y,
+                                width: text_width + padding * 2.0,
+                                height: text_box.height() + padding * 2.0,
+                                ..Default::default()
+                            });
+                            c2.text(Text {
+                                text: text.clone(),
+                                font_family: Some(self.font_family.clone()),
+                                font_size: Some(font_size),
+                                font_color: Some(color),
+                                font_style: annotation.font_style.clone(),
+                                letter_spacing: annotation.letter_spacing,
+                                text_decoration: annotation.text_decoration.clone(),
+                                x: Some(point.x + padding),
+                                y: Some(point.y + padding + font_size),
+                                ..Default::default()
+                            });
+                        }
+                        AnnotationShape::Line(ref end) | AnnotationShape::Arrow(ref end) => {
+                            let Some(end) = get_point(end, annotation.y_axis_index) else {
+                                continue;
+                            };
+                            c2.line(Line {
+                                color: Some(color),
+                                stroke_width: 1.0,
+                                left: point.x,
+                                top: point.y,
+                                right: end.x,
+                                bottom: end.y,
+                                ..Default::default()
+                            });
+                            if let AnnotationShape::Arrow(_) = annotation.shape {
+                                // the head of arrow at the end point
+                                let angle = (end.y - point.y).atan2(end.x - point.x);
+                                let size = 8.0;
+                                let spread = std::f32::consts::PI / 7.0;
+                                c2.polygon(Polygon {
+                                    color: Some(color),
+                                    fill: Some(color),
+                                    points: vec![
+                                        end,
+                                        (
+                                            end.x - size * (angle - spread).cos(),
+                                            end.y - size * (angle - spread).sin(),
+                                        )
+                                            .into(),
+                                        (
+                                            end.x - size * (angle + spread).cos(),
+                                            end.y - size * (angle + spread).sin(),
+                                        )
+                                            .into(),
+                                    ],
+                                    ..Default::default()
+                                });
+                            }
+                        }
+                        AnnotationShape::Circle(r) => {
+                            c2.circle(Circle {
+                                stroke_color: Some(color),
+                                cx: point.x,
+                                cy: point.y,
+                                r,
+                                ..Default::default()
+                            });
+                        }
+                        AnnotationShape::Dot(r) => {
+                            c2.circle(Circle {
+                                stroke_color: Some(color),
+                                fill: Some(color),
+                                cx: point.x,
+                                cy: point.y,
+                                r,
+                                ..Default::default()
+                            });
+                        }
+                    }
+                }
+            }
+            /// Renders footer widget for canvas, returns the height of footer.
+            fn render_footer(&self, c: Canvas) -> f32 {
+                if self.footer_text.is_empty() {
+                    return 0.0;
+                }
+                let footer_margin = self.footer_margin.clone().unwrap_or_default();
+                let footer_box = measure_text_width_family(&self.font_family, self.footer_font_size, &self.footer_text)
+                    .unwrap_or_default();
+                let footer_height = footer_box.height() + footer_margin.top + footer_margin.bottom;
+                let mut footer_canvas = c.child(footer_margin);
+                let x = match self.footer_align {
+                    Align::Center => (footer_canvas.width() - footer_box.width()) / 2.0,
+                    Align::Right => footer_canvas.width() - footer_box.width(),
+                    _ => 0.0,
+                };
+                // the footer is at the bottom of canvas
+                let y = footer_canvas.height() - footer_box.height() + self.footer_font_size;
+                footer_canvas.text(Text {
+                    text: self.footer_text.clone(),
+                    font_family: Some(self.font_family.clone()),
+                    font_size: Some(self.footer_font_size),
+                    font_weight: self.footer_font_weight.clone(),
+                    font_color: Some(self.footer_font_color),
+                    x: Some(x),
+                    y: Some(y),
+                    ..Default::default()
+                });
+                footer_height
+            }
+            /// Renders legend widget for canvas,
+            /// returns the space of the legend on each side of canvas.
+            fn render_legend(&self, c: Canvas) -> Box {
                 if !self.legend_show.unwrap_or(true) || self.series_list.is_empty() {
-                    return 0.0
+                    return Box::default();
                 }
-                let mut legend_left = 0.0;
-                let legends: Vec<&str> = self
-                    .series_list
+                let is_auto = self.legend_category == LegendCategory::Auto;
+                // the trend lines have their own legends after series
+                let mut legend_items = vec![];
+                for (index, series) in self.series_list.iter().enumerate() {
+                    let mut category = self.legend_category.clone();
+                    let mut stroke_dash_array = None;
+                    if is_auto {
+                        category = match series.category {
+                            Some(SeriesCategory::Bar) => LegendCategory::RoundRect,
+                            Some(_) => LegendCategory::Normal,
+                            None => #default_legend_category,
+                        };
+                        if category == LegendCategory::Normal {
+                            stroke_dash_array = series.stroke_dash_array.clone();
+                        }
+                    }
+                    let text = series.legend_text.clone().unwrap_or_else(|| series.name.clone());
+                    // the id is of series, it is stable whatever the legend order is
+                    let id = format!("legend-item-{}", series.index.unwrap_or(index));
+                    legend_items.push((text, series.index.unwrap_or(index), series.visible, category, stroke_dash_array, &series.name, id));
+                }
+                for (index, series) in self.series_list.iter().enumerate() {
+                    if series.visible && series.trend_line.is_some() && !series.name.is_empty() {
+                        let (category, stroke_dash_array) = if is_auto {
+                            (LegendCategory::Normal, Some("4,2".to_string()))
+                        } else {
+                            (self.legend_category.clone(), None)
+                        };
+                        let text = series.legend_text.clone().unwrap_or_else(|| series.name.clone());
+                        let id = format!("legend-item-{}-trend", series.index.unwrap_or(index));
+                        legend_items.push((format!("{text} Trend"), series.index.unwrap_or(index), true, category, stroke_dash_array, &series.name, id));
+                    }
+                }
+                // only the series in legend order are shown
+                if let Some(ref legend_order) = self.legend_order {
+                    legend_items.retain(|item| legend_order.contains(item.5));
+                    legend_items.sort_by_key(|item| legend_order.iter().position(|name| name == item.5));
+                }
+                let legends: Vec<&str> = legend_items
                     .iter()
-                    .map(|item| item.name.as_str())
+                    .map(|item| item.0.as_str())
                     .collect();
                 let legend_margin = self.legend_margin.clone().unwrap_or_default();
                 let legend_margin_value = legend_margin.top + legend_margin.bottom;
-                let mut legend_canvas = c.child(legend_margin);
+                let mut legend_canvas = c.child(legend_margin.clone());
                 let (legend_width, legend_width_list) =
                     measure_legends(&self.font_family, self.legend_font_size, &legends);
                 let legend_canvas_width = legend_canvas.width();
-                if legend_width < legend_canvas_width {
-                    legend_left = match self.legend_align {
-                        Align::Right => legend_canvas_width - legend_width,
-                        Align::Left => 0.0,
-                        Align::Center => (legend_canvas_width - legend_width) / 2.0,
-                    };
-                    if legend_left < 0.0 {
-                        legend_left = 0.0;
-                    }
-                }
                 let legend_unit_height = self.legend_font_size + LEGEND_MARGIN;
+                let position = self.legend_position.clone().unwrap_or(Position::Top);
+                let is_vertical = matches!(position, Position::Left | Position::Right);
+
+                // the position of each legend item
+                let mut legend_positions = vec![];
                 let mut legend_top = 0.0;
-                for (index, series) in self.series_list.iter().enumerate() {
-                    if series.name.is_empty() {
+                let mut legend_left = 0.0;
+                let mut max_legend_width = 0.0_f32;
+                for (index, (name, ..)) in legend_items.iter().enumerate() {
+                    if name.is_empty() {
                         continue;
                     }
-                    let color = get_color(&self.series_colors, series.index.unwrap_or(index));
+                    max_legend_width = max_legend_width.max(legend_width_list[index]);
+                    if is_vertical {
+                        legend_positions.push((index, 0.0, legend_top));
+                        legend_top += legend_unit_height;
+                        continue;
+                    }
+                    if legend_left + legend_width_list[index] > legend_canvas_width {
+                        legend_left = 0.0;
+                        legend_top += legend_unit_height;
+                    }
+                    legend_positions.push((index, legend_left, legend_top));
+                    legend_left += legend_width_list[index] - LEGEND_TEXT_MARGIN + LEGEND_MARGIN;
+                }
+                let legend_height = if is_vertical {
+                    legend_top
+                } else {
+                    legend_unit_height + legend_top
+                };
+
+                let mut offset_left = 0.0;
+                let mut offset_top = 0.0;
+                match position {
+                    Position::Left | Position::Right => {
+                        // the vertical legend is in the middle
+                        offset_top = ((legend_canvas.height() - legend_height) / 2.0).max(0.0);
+                        if position == Position::Right {
+                            offset_left = (legend_canvas_width - max_legend_width).max(0.0);
+                        }
+                    }
+                    _ => {
+                        if legend_width < legend_canvas_width {
+                            offset_left = match self.legend_align {
+                                Align::Right => legend_canvas_width - legend_width,
+                                Align::Left => 0.0,
+                                Align::Center => (legend_canvas_width - legend_width) / 2.0,
+                            };
+                        }
+                        if position == Position::Bottom {
+                            offset_top = (legend_canvas.height() - legend_height).max(0.0);
+                        }
+                    }
+                }
+
+                for (index, mut left, top) in legend_positions {
+                    // the legend items are from right to left in rtl layout
+                    if self.rtl && !is_vertical {
+                        left = legend_canvas_width - 2.0 * offset_left - left - legend_width_list[index] + LEGEND_TEXT_MARGIN;
+                    }
+                    let (name, color_index, visible, category, stroke_dash_array, _, id) = &legend_items[index];
+                    let mut color = get_color(&self.series_colors, *color_index);
+                    let mut font_color = self.legend_font_color;
+                    let mut class = "legend-item".to_string();
+                    // the legend of hidden series is greyed out
+                    if !visible {
+                        color = (204, 204, 204).into();
+                        font_color = color;
+                        class += " legend-item-hidden";
+                    }
                     let fill = if self.is_light {
                         Some(self.background_color)
                     } else {
                         Some(color)
                     };
-                    if legend_left + legend_width_list[index] > legend_canvas_width {
-                        legend_left = 0.0;
-                        legend_top += legend_unit_height;
-                    }
-                    let b = legend_canvas.legend(Legend {
-                        text: series.name.to_string(),
+                    legend_canvas.legend(Legend {
+                        text: name.to_string(),
                         font_size: self.legend_font_size,
                         font_family: self.font_family.clone(),
-                        font_color: Some(self.legend_font_color),
+                        font_color: Some(font_color),
                         font_weight: self.legend_font_weight.clone(),
                         stroke_color: Some(color),
                         fill,
-                        left: legend_left,
-                        top: legend_top,
-                        category: self.legend_category.clone(),
+                        left: offset_left + left,
+                        top: offset_top + top,
+                        category: category.clone(),
+                        stroke_dash_array: stroke_dash_array.clone(),
+                        // the id and class are emitted only with element ids
+                        id: self.element_ids.then(|| id.clone()),
+                        class: self.element_ids.then_some(class),
                     });
-                    legend_left += b.width() + LEGEND_MARGIN;
                 }
 
-                legend_unit_height + legend_top + legend_margin_value
+                match position {
+                    Position::Left => Box {
+                        left: max_legend_width + legend_margin.left + legend_margin.right + LEGEND_MARGIN,
+                        ..Default::default()
+                    },
+                    Position::Right => Box {
+                        right: max_legend_width + legend_margin.left + legend_margin.right + LEGEND_MARGIN,
+                        ..Default::default()
+                    },
+                    Position::Bottom => Box {
+                        bottom: legend_height + legend_margin_value,
+                        ..Default::default()
+                    },
+                    _ => Box {
+                        top: legend_height + legend_margin_value,
+                        ..Default::default()
+                    },
+                }
             }
             /// Renders grid for canvas, the axis width is the right padding of grid canvas,
             /// and the axis height is the bottom padding of grid canvas.
@@ -438,12 +1489,107 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                     ..Default::default()
                 });
             }
+            /// Renders the highlighted grid line at zero value,
+            /// it is ignored if zero is not between the min and max of y axis.
+            fn render_zero_line(&self, c: Canvas, y_axis_values: &AxisValues, axis_width: f32, axis_height: f32) {
+                let Some(color) = self.zero_line_color else {
+                    return;
+                };
+                if y_axis_values.min >= 0.0 || y_axis_values.max <= 0.0 {
+                    return;
+                }
+                let mut c1 = c;
+                let y = y_axis_values.get_offset_height(0.0, axis_height);
+                c1.line(Line {
+                    color: Some(color),
+                    stroke_width: self.grid_stroke_width.max(1.0),
+                    top: y,
+                    right: axis_width,
+                    bottom: y,
+                    ..Default::default()
+                });
+            }
+            /// Renders the band and vertical crosshair line of the selected category.
+            fn render_axis_pointer(&self, c: Canvas, axis_width: f32, axis_height: f32, series_data_count: usize) {
+                let Some(ref axis_pointer) = self.axis_pointer else {
+                    return;
+                };
+                if axis_pointer.index >= series_data_count {
+                    return;
+                }
+                let mut c1 = c;
+                // the point is on the split line if no boundary gap
+                let (unit_width, x) = if self.x_boundary_gap.unwrap_or(true) {
+                    let unit_width = axis_width / series_data_count as f32;
+                    (unit_width, unit_width * (axis_pointer.index as f32 + 0.5))
+                } else {
+                    let unit_width = axis_width / series_data_count.saturating_sub(1).max(1) as f32;
+                    (unit_width, unit_width * axis_pointer.index as f32)
+                };
+                let left = (x - unit_width / 2.0).max(0.0);
+                c1.rect(Rect {
+                    fill: Some(axis_pointer.band_color.unwrap_or(self.x_axis_stroke_color.with_alpha(30))),
+                    left,
+                    width: (x + unit_width / 2.0).min(axis_width) - left,
+                    height: axis_height,
+                    ..Default::default()
+                });
+                c1.line(Line {
+                    color: Some(axis_pointer.line_color.unwrap_or(self.x_axis_stroke_color)),
+                    stroke_width: 1.0,
+                    left: x,
+                    top: 0.0,
+                    right: x,
+                    bottom: axis_height,
+                    stroke_dash_array: Some("4,2".to_string()),
+                });
+            }
+            /// Renders the guide line of statistic computed across the values of all visible series,
+            /// only the series of the first y axis are computed.
+            fn render_guide_line(&self, c: Canvas, y_axis_values: &AxisValues, axis_width: f32, axis_height: f32) {
+                let Some(ref guide_line) = self.guide_line else {
+                    return;
+                };
+                let values: Vec<f32> = self
+                    .series_list
+                    .iter()
+                    .filter(|series| series.visible && series.y_axis_index == 0)
+                    .flat_map(|series| series.get_data())
+                    .filter(|value| !is_nil_value(*value))
+                    .collect();
+                let Some(value) = guide_line.get_value(&values) else {
+                    return;
+                };
+                let mut c1 = c;
+                let color = guide_line.color.unwrap_or(self.series_label_font_color);
+                let y = y_axis_values.get_offset_height(value, axis_height);
+                c1.line(Line {
+                    color: Some(color),
+                    stroke_width: 1.0,
+                    top: y,
+                    right: axis_width,
+                    bottom: y,
+                    stroke_dash_array: Some("6,3".to_string()),
+                    ..Default::default()
+                });
+                c1.text(Text {
+                    text: guide_line.get_label(value),
+                    font_family: Some(self.font_family.clone()),
+                    font_size: Some(self.series_label_font_size),
+                    font_color: Some(color),
+                    x: Some(axis_width - 2.0),
+                    y: Some(y - 4.0),
+                    align: Some(Align::Right),
+                    ..Default::default()
+                });
+            }
             /// Renders y axis for canvas, if the axis index greater than zero means the right y axis.
             fn render_y_axis(&self, c: Canvas, data: Vec<String>, axis_height: f32, axis_width: f32, axis_index: usize) {
                 let mut c1 = c;
                 let y_axis_config = &self.get_y_axis_config(axis_index);
                 let mut position = Position::Left;
-                if axis_index > 0 {
+                // the first y axis is on the right side of rtl layout
+                if (axis_index > 0) != self.rtl {
                     position = Position::Right;
                 }
                 let mut name_align = Align::Left;
@@ -465,6 +1611,7 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                     font_weight: y_axis_config.axis_font_weight.clone(),
                     data,
                     formatter: y_axis_config.axis_formatter.clone(),
+                    label_id_prefix: self.element_ids.then(|| format!("y-axis-{axis_index}")),
                     ..Default::default()
                 });
             }
@@ -494,39 +1641,183 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                     name_gap: self.x_axis_name_gap,
                     name_rotate: self.x_axis_name_rotate,
                     name_align,
+                    label_id_prefix: self.element_ids.then(|| "x-axis".to_string()),
                     ..Default::default()
                 });
             }
+            /// Gets the points of series trend line, it is split by nil value.
+            fn get_trend_points_list(
+                &self,
+                series: &Series,
+                y_axis_values: &AxisValues,
+                max_height: f32,
+                unit_width: f32,
+                x_offset: f32,
+            ) -> Vec<Vec<Point>> {
+                let Some(ref trend) = series.trend_line else {
+                    return vec![];
+                };
+                let mut points_list = vec![];
+                let mut points: Vec<Point> = vec![];
+                for (i, value) in get_trend_values(&series.get_data(), trend).into_iter().enumerate() {
+                    if is_nil_value(value) {
+                        if !points.is_empty() {
+                            points_list.push(points);
+                            points = vec![];
+                        }
+                        continue;
+                    }
+                    let x = unit_width * (i + series.start_index) as f32 + x_offset;
+                    points.push((x, y_axis_values.get_offset_height(value, max_height)).into());
+                }
+                if !points.is_empty() {
+                    points_list.push(points);
+                }
+                points_list
+            }
+            /// Gets the tooltip of series value, e.g. "Mon: Email = 120",
+            /// it returns none if the tooltip is not shown.
+            fn get_tooltip(&self, series: &Series, index: usize, value: f32) -> Option<String> {
+                if !self.tooltip_show {
+                    return None;
+                }
+                let category = self
+                    .x_axis_data
+                    .get(index + series.start_index)
+                    .cloned()
+                    .unwrap_or_default();
+                Some(format!("{category}: {} = {}", series.name, format_label_float(value)))
+            }
+            /// Formats the label of series value, the label formatter of series is preferred.
+            fn format_series_label(&self, series: &Series, index: usize, value: f32) -> String {
+                if series.label_formatter.is_none() && series.label_precision.is_none() {
+                    return format_series_value(value, &self.series_label_formatter);
+                }
+                let formatter = series
+                    .label_formatter
+                    .clone()
+                    .unwrap_or_else(|| self.series_label_formatter.clone());
+                let total: f32 = series
+                    .data
+                    .iter()
+                    .filter(|item| !is_nil_value(**item))
+                    .sum();
+                let percentage = if total == 0.0 { 0.0 } else { value / total };
+                LabelOption {
+                    series_name: series.name.clone(),
+                    category_name: self
+                        .x_axis_data
+                        .get(index + series.start_index)
+                        .cloned()
+                        .unwrap_or_default(),
+                    value,
+                    percentage,
+                    formatter,
+                    precision: series.label_precision,
+                }
+                .format()
+            }
             /// Renders series label widget for canvas.
             fn render_series_label(&self, c:Canvas, series_labels_list: Vec<Vec<SeriesLabel>>) {
                 if series_labels_list.is_empty() {
                     return;
                 }
                 let mut c1 = c;
+                let mut texts = vec![];
+                let mut boxes = vec![];
                 for series_labels in series_labels_list.iter() {
                     for series_label in series_labels.iter() {
+                        let position = series_label.position.clone().unwrap_or(Position::Top);
+                        let mut x = series_label.point.x;
+                        let mut y = series_label.point.y;
                         let mut dx = None;
+                        let mut dy = Some(-8.0);
+                        let mut label_width = 0.0;
+                        let mut label_height = 0.0;
                         if let Ok(value) = measure_text_width_family(
                             &self.font_family,
                             self.series_label_font_size,
                             &series_label.text,
                         ) {
-                            dx = Some(-value.width() / 2.0);
+                            let width = value.width();
+                            let height = value.height();
+                            label_width = width;
+                            label_height = height;
+                            dx = Some(-width / 2.0);
+                            match position {
+                                Position::Inside => {
+                                    y += series_label.height / 2.0;
+                                    dy = Some(height / 2.0 - 2.0);
+                                }
+                                Position::InsideTop => {
+                                    dy = Some(height + 2.0);
+                                }
+                                Position::Bottom => {
+                                    y += series_label.height;
+                                    dy = Some(height + 4.0);
+                                }
+                                Position::Left => {
+                                    y += series_label.height / 2.0;
+                                    x -= width + 5.0;
+                                    dx = None;
+                                    dy = Some(height / 2.0 - 2.0);
+                                }
+                                Position::Right => {
+                                    y += series_label.height / 2.0;
+                                    x += 5.0;
+                                    dx = None;
+                                    dy = Some(height / 2.0 - 2.0);
+                                }
+                                _ => {}
+                            }
                         }
-                        c1.text(Text {
+                        let mut font_color = series_label.font_color.unwrap_or(self.series_label_font_color);
+                        // 标签在内部时，根据填充色选择对比色
+                        if let Some(fill) = series_label.fill {
+                            if position == Position::Inside || position == Position::InsideTop {
+                                font_color = fill.contrast_color();
+                            }
+                        }
+                        let left = x + dx.unwrap_or_default();
+                        let bottom = y + dy.unwrap_or_default();
+                        boxes.push((
+                            Box {
+                                left,
+                                top: bottom - label_height,
+                                right: left + label_width,
+                                bottom,
+                            },
+                            series_label.priority,
+                        ));
+                        texts.push(Text {
                             text: series_label.text.clone(),
-                            dy: Some(-8.0),
+                            dy,
                             dx,
                             font_family: Some(self.font_family.clone()),
-                            font_color: Some(self.series_label_font_color),
+                            font_color: Some(font_color),
                             font_size: Some(self.series_label_font_size),
                             font_weight: self.series_label_font_weight.clone(),
-                            x: Some(series_label.point.x),
-                            y: Some(series_label.point.y),
+                            x: Some(x),
+                            y: Some(y),
+                            id: series_label.id.clone(),
+                            class: series_label.id.as_ref().map(|_| "series-label".to_string()),
                             ..Default::default()
                         });
                     }
                 }
+                let offsets = match self.series_label_overlap {
+                    LabelOverlap::None => vec![Some(0.0); texts.len()],
+                    LabelOverlap::Hide => resolve_label_overlap(&boxes, false),
+                    LabelOverlap::Shift => resolve_label_overlap(&boxes, true),
+                };
+                for (mut text, offset) in texts.into_iter().zip(offsets) {
+                    // the overlapping label is hidden
+                    let Some(offset) = offset else {
+                        continue;
+                    };
+                    text.y = text.y.map(|y| y + offset);
+                    c1.text(text);
+                }
             }
             /// Renders the bar widget for canvas.
             fn render_bar(
@@ -536,78 +1827,204 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                 y_axis_values_list: &[&AxisValues],
                 max_height: f32,
                 series_data_count: usize,
-                radius: Option<f32>,
-            ) -> Vec<Vec<SeriesLabel>> {
+                bar_params: BarParams,
+            ) -> (Vec<Vec<SeriesLabel>>, Vec<LayoutItem>) {
                 if series_list.is_empty() {
-                    return vec![];
+                    return (vec![], vec![]);
                 }
+                let mut layout_items = vec![];
                 let mut c1 = c;
 
                 let unit_width = c1.width() / series_data_count as f32;
-                let bar_chart_margin = 5.0_f32;
-                let bar_chart_gap = 3.0_f32;
-
-                let bar_chart_margin_width = bar_chart_margin * 2.0;
-                let bar_chart_gap_width = bar_chart_gap * (series_list.len() - 1) as f32;
-                let bar_width = (unit_width - bar_chart_margin_width - bar_chart_gap_width) / series_list.len() as f32;
+                // the hidden series doesn't take the place of bar
+                let visible_series_list: Vec<(usize, &Series)> = series_list
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, series)| series.visible)
+                    .map(|(index, series)| (index, *series))
+                    .collect();
+                // the series of the same stack share one bar, the others take their own bar
+                let mut stacks: Vec<Option<&str>> = vec![];
+                let mut bar_indexes = vec![];
+                for (_, series) in visible_series_list.iter() {
+                    let stack = series.stack.as_deref();
+                    let found = stack.and_then(|name| stacks.iter().position(|item| *item == Some(name)));
+                    bar_indexes.push(found.unwrap_or_else(|| {
+                        stacks.push(stack);
+                        stacks.len() - 1
+                    }));
+                }
+                // the positive and negative sums of stacked bars at every category
+                let mut stack_sums: Vec<Vec<Option<(f32, f32)>>> = vec![vec![None; series_data_count]; stacks.len()];
+                let mut stack_y_axis_values = vec![None; stacks.len()];
+                let bar_size = get_bar_size(&bar_params, unit_width, stacks.len());
+                let bar_chart_margin = bar_size.margin;
+                let bar_chart_gap = bar_size.gap;
+                let bar_width = bar_size.width;
                 let half_bar_width = bar_width / 2.0;
 
+                let filter_id = self.series_shadow.as_ref().map(|shadow| {
+                    let id = "series-bar-shadow";
+                    c1.drop_shadow(shadow.to_drop_shadow(id));
+                    id.to_string()
+                });
                 let mut series_labels_list = vec![];
-                let get_bar_color = |colors: &Option<Vec<Option<Color>>>, index: usize| -> Option<Color> {
-                    if let Some(colors) = &colors  {
-                        if colors.len() <= index {
-                            return None;
-                        }
-                        if let Some(color) = colors[index] {
-                            return Some(color);
-                        }
-                    }
-                    None
-                };
-                for (index, series) in series_list.iter().enumerate() {
+                for ((index, series), bar_index) in visible_series_list.into_iter().zip(bar_indexes) {
+                    // the elements of series are grouped with its name for accessibility
+                    let mut c2 = self.get_series_canvas(&mut c1, series, series.index.unwrap_or(index));
                     let y_axis_values = if index >= y_axis_values_list.len() {
                         y_axis_values_list[0]
                     } else {
                         y_axis_values_list[series.y_axis_index]
                     };
+                    if series.stack.is_some() {
+                        stack_y_axis_values[bar_index].get_or_insert(y_axis_values);
+                    }
                     let color = get_color(&self.series_colors, series.index.unwrap_or(index));
+                    let fill_id = series.append_fill_defs(&mut c2, series.index.unwrap_or(index), color);
+                    let zero_y = y_axis_values.get_zero_offset_height(max_height);
                     let mut series_labels = vec![];
-                    for (i, p) in series.data.iter().enumerate() {
+                    for (i, p) in series.get_data().iter().enumerate() {
                         let value = p.to_owned();
+                        let mut left = unit_width * (i + series.start_index) as f32 + bar_chart_margin;
+                        left += (bar_width + bar_chart_gap) * bar_index as f32;
+                        if series.show_background {
+                            c2.rect(Rect {
+                                fill: Some(series.get_background_color()),
+                                left,
+                                width: bar_width,
+                                height: max_height,
+                                rx: bar_params.radius,
+                                ry: bar_params.radius,
+                                ..Default::default()
+                            });
+                        }
                         // nil value忽略
-                        if value == NIL_VALUE {
+                        if is_nil_value(value) {
                             continue;
                         }
-                        let mut left = unit_width * (i + series.start_index) as f32 + bar_chart_margin;
-                        left += (bar_width + bar_chart_gap) * index as f32;
 
-                        let y = y_axis_values.get_offset_height(value, max_height);
+                        // the stacked bar grows from the sum of previous series
+                        let start = series
+                            .stack
+                            .as_ref()
+                            .and_then(|_| stack_sums[bar_index].get_mut(i + series.start_index))
+                            .map(|sums| {
+                                let sums = sums.get_or_insert((0.0, 0.0));
+                                let sum = if value < 0.0 { &mut sums.1 } else { &mut sums.0 };
+                                let start = *sum;
+                                *sum += value;
+                                start
+                            })
+                            .unwrap_or_default();
+                        let start_y = if start == 0.0 {
+                            zero_y
+                        } else {
+                            y_axis_values.get_offset_height(start, max_height)
+                        };
+                        let y = y_axis_values.get_offset_height(start + value, max_height);
+                        // the bar grows from zero, so the bar of negative value grows downward
+                        let (top, height) = if y > start_y {
+                            (start_y, y - start_y)
+                        } else {
+                            (y, start_y - y)
+                        };
 
-                        let mut fill = get_bar_color(&series.colors, i);
+                        let mut fill = series.get_item_color(i);
                         if fill.is_none() {
-                            fill = Some(color);
+                            fill = if value < 0.0 { series.negative_color.or(Some(color)) } else { Some(color) };
+                        }
+                        if let Some(ref emphasis) = self.emphasis {
+                            if emphasis.is_point_faded(series.index.unwrap_or(index), i) {
+                                fill = fill.map(|fill| fill.with_alpha((emphasis.get_fade_opacity() * 255.0) as u8));
+                            }
                         }
 
-                        c1.rect(Rect {
+                        c2.rect(Rect {
                             fill,
+                            fill_id: fill_id.clone(),
                             left,
-                            top: y,
+                            top,
                             width: bar_width,
-                            height: max_height - y,
-                            rx: radius,
-                            ry: radius,
+                            height,
+                            rx: bar_params.radius,
+                            ry: bar_params.radius,
+                            filter_id: filter_id.clone(),
+                            id: self.element_ids.then(|| format!("series-{}-bar-{i}", series.index.unwrap_or(index))),
+                            class: self.element_ids.then(|| format!("series-bar series-{}", series.index.unwrap_or(index))),
+                            title: self.get_tooltip(series, i, value),
                             ..Default::default()
                         });
+                        layout_items.push(LayoutItem {
+                            series_index: series.index.unwrap_or(index),
+                            data_index: i,
+                            value,
+                            point: (c1.margin.left + left + half_bar_width, c1.margin.top + y).into(),
+                            area: Box {
+                                left: c1.margin.left + left,
+                                top: c1.margin.top + top,
+                                right: c1.margin.left + left + bar_width,
+                                bottom: c1.margin.top + top + height,
+                            },
+                            shape: LayoutShape::Rect,
+                        });
                         series_labels.push(SeriesLabel{
-                            point: (left + half_bar_width, y).into(),
-                            text: format_series_value(p.to_owned(), &self.series_label_formatter),
+                            point: (left + half_bar_width, top).into(),
+                            text: self.format_series_label(series, i, value),
+                            height,
+                            // the label of negative value is below the bar by default
+                            position: series.label_position.clone().or_else(|| (value < 0.0).then_some(Position::Bottom)),
+                            fill,
+                            priority: series.label_priority,
+                            id: self.element_ids.then(|| format!("series-{}-label-{i}", series.index.unwrap_or(index))),
+                            ..Default::default()
                         })
                     }
+                    for points in self.get_trend_points_list(series, y_axis_values, max_height, unit_width, unit_width / 2.0) {
+                        c2.straight_line(StraightLine {
+                            points,
+                            color: Some(color),
+                            stroke_width: self.series_stroke_width,
+                            stroke_dash_array: Some("4,2".to_string()),
+                            ..Default::default()
+                        });
+                    }
                     if series.label_show {
                         series_labels_list.push(series_labels);
                     }
                 }
-                series_labels_list
+                if bar_params.stack_total {
+                    let mut total_labels = vec![];
+                    for (bar_index, sums) in stack_sums.into_iter().enumerate() {
+                        let Some(y_axis_values) = stack_y_axis_values[bar_index] else {
+                            continue;
+                        };
+                        let zero_y = y_axis_values.get_zero_offset_height(max_height);
+                        for (i, (positive, negative)) in sums.into_iter().enumerate().filter_map(|(i, sums)| Some((i, sums?))) {
+                            let mut left = unit_width * i as f32 + bar_chart_margin;
+                            left += (bar_width + bar_chart_gap) * bar_index as f32;
+                            // the total is above the top segment, it is below the bars if they are all negative
+                            let (top, height, position) = if positive > 0.0 || negative == 0.0 {
+                                (y_axis_values.get_offset_height(positive, max_height), 0.0, None)
+                            } else {
+                                let y = y_axis_values.get_offset_height(negative, max_height);
+                                (zero_y, y - zero_y, Some(Position::Bottom))
+                            };
+                            total_labels.push(SeriesLabel {
+                                point: (left + half_bar_width, top).into(),
+                                text: format_series_value(positive + negative, &self.series_label_formatter),
+                                height,
+                                position,
+                                id: self.element_ids.then(|| format!("stack-{bar_index}-total-{i}")),
+                                ..Default::default()
+                            });
+                        }
+                    }
+                    if !total_labels.is_empty() {
+                        series_labels_list.push(total_labels);
+                    }
+                }
+                (series_labels_list, layout_items)
             }
             /// Renders the line widget for canvas.
             fn render_line(
@@ -618,50 +2035,79 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                 max_height: f32,
                 axis_height: f32,
                 series_data_count: usize,
-            ) -> Vec<Vec<SeriesLabel>> {
+            ) -> (Vec<Vec<SeriesLabel>>, Vec<LayoutItem>) {
                 if series_list.is_empty() {
-                    return vec![];
+                    return (vec![], vec![]);
                 }
+                let mut layout_items = vec![];
                 let mut c1 = c;
                 let x_boundary_gap = self.x_boundary_gap.unwrap_or(true);
                 let mut split_unit_offset = 0.0;
                 if !x_boundary_gap {
                     split_unit_offset = 1.0;
                 }
+                let filter_id = self.series_shadow.as_ref().map(|shadow| {
+                    let id = "series-line-shadow";
+                    c1.drop_shadow(shadow.to_drop_shadow(id));
+                    id.to_string()
+                });
                 let mut series_labels_list = vec![];
 
                 for (index, series) in series_list.iter().enumerate() {
+                    if !series.visible {
+                        continue;
+                    }
+                    // the elements of series are grouped with its name for accessibility
+                    let mut c2 = self.get_series_canvas(&mut c1, series, series.index.unwrap_or(index));
                     let y_axis_values = if series.y_axis_index >= y_axis_values_list.len() {
                         y_axis_values_list[0]
                     } else {
                         y_axis_values_list[series.y_axis_index]
                     };
                     let split_unit_count = series_data_count as f32 - split_unit_offset;
-                    let unit_width = c1.width() / split_unit_count;
+                    let unit_width = c2.width() / split_unit_count;
                     let mut points: Vec<Point> = vec![];
                     let mut points_list: Vec<Vec<Point>> = vec![];
                     let mut series_labels = vec![];
+                    let mut symbol_points = vec![];
+                    // the values which are not kept by downsampling are skipped
+                    let data = series.get_data();
+                    let mut kept_list = vec![true; data.len()];
+                    if let Some(max_points) = self.max_points_per_series {
+                        if data.len() > max_points {
+                            kept_list = vec![false; data.len()];
+                            for i in downsample_lttb(&data, max_points) {
+                                kept_list[i] = true;
+                            }
+                        }
+                    }
 
                     let mut max_value = f32::MIN;
                     let mut min_value = f32::MAX;
+                    // the indexes of series labels, they are not the same as data indexes
+                    // if some values are nil or skipped by downsampling
                     let mut max_index = 0;
                     let mut min_index = 0;
-                    for (i, p) in series.data.iter().enumerate() {
+                    for (i, p) in data.iter().enumerate() {
                         let value = p.to_owned();
-                        if value == NIL_VALUE {
-                            if !points.is_empty() {
+                        if is_nil_value(value) {
+                            // bridge across the gap if connect nulls
+                            if !series.connect_nulls && !points.is_empty() {
                                 points_list.push(points);
                                 points = vec![];
                             }
                             continue;
                         }
+                        if !kept_list[i] {
+                            continue;
+                        }
                         if value > max_value {
                             max_value = value;
-                            max_index = i;
+                            max_index = series_labels.len();
                         }
                         if value < min_value {
                             min_value = value;
-                            min_index = i;
+                            min_index = series_labels.len();
                         }
                         // 居中
                         let mut x = unit_width * (i + series.start_index) as f32;
@@ -670,57 +2116,222 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                         }
                         let y = y_axis_values.get_offset_height(value, max_height);
                         points.push((x, y).into());
+                        symbol_points.push((i, Point::from((x, y))));
                         series_labels.push(SeriesLabel{
                             point: (x, y).into(),
-                            text: format_series_value(value, &self.series_label_formatter),
+                            text: self.format_series_label(series, i, value),
+                            position: series.label_position.clone(),
+                            priority: series.label_priority,
+                            id: self.element_ids.then(|| format!("series-{}-label-{i}", series.index.unwrap_or(index))),
+                            ..Default::default()
                         })
                     }
+                    let color = get_color(&self.series_colors, series.index.unwrap_or(index));
                     if series.label_show {
                         series_labels_list.push(series_labels.clone());
+                    } else if let Some(ref end_label) = series.end_label {
+                        // only the first or last value is labeled beside the line end
+                        let mut end_labels = vec![];
+                        if *end_label != EndLabel::Last {
+                            if let Some(label) = series_labels.first() {
+                                end_labels.push(SeriesLabel {
+                                    position: Some(Position::Left),
+                                    font_color: Some(color),
+                                    ..label.clone()
+                                });
+                            }
+                        }
+                        if *end_label == EndLabel::Last
+                            || (*end_label == EndLabel::Both && series_labels.len() > 1)
+                        {
+                            if let Some(label) = series_labels.last() {
+                                end_labels.push(SeriesLabel {
+                                    position: Some(Position::Right),
+                                    font_color: Some(color),
+                                    ..label.clone()
+                                });
+                            }
+                        }
+                        series_labels_list.push(end_labels);
                     }
                     if !points.is_empty() {
                         points_list.push(points);
                     }
 
-                    let color = get_color(&self.series_colors, series.index.unwrap_or(index));
-
-                    let fill = color.with_alpha(100);
-                    let series_fill = self.series_fill;
+                    let fill = if let Some(opacity) = self.series_fill_opacity {
+                        color.with_alpha((opacity.clamp(0.0, 1.0) * 255.0) as u8)
+                    } else {
+                        color.with_alpha(100)
+                    };
+                    // the area is filled down to the baseline value if it is set
+                    // the area is filled toward zero if the axis contains negative value
+                    let fill_bottom = if let Some(baseline) = series.area_baseline {
+                        y_axis_values.get_offset_height(baseline, max_height).clamp(0.0, axis_height)
+                    } else if y_axis_values.min < 0.0 {
+                        y_axis_values.get_offset_height(0.0, max_height).clamp(0.0, axis_height)
+                    } else {
+                        axis_height
+                    };
+                    let stroke_width = series.stroke_width.unwrap_or(self.series_stroke_width);
+                    let line_color = if let Some(opacity) = series.opacity {
+                        color.with_alpha((opacity.clamp(0.0, 1.0) * 255.0) as u8)
+                    } else {
+                        color
+                    };
+                    let mut symbol = series.symbol.clone().or_else(|| self.series_symbol.clone());
+                    if let Some(size) = series.symbol_size {
+                        symbol = symbol.map(|item| item.with_size(size));
+                    }
+                    let r = match symbol {
+                        Some(Symbol::Circle(r, _)) => r,
+                        _ => series.symbol_size.unwrap_or(stroke_width * 2.0) / 2.0,
+                    };
+                    for (i, point) in symbol_points.iter() {
+                        let x = c2.margin.left + point.x;
+                        let y = c2.margin.top + point.y;
+                        layout_items.push(LayoutItem {
+                            series_index: series.index.unwrap_or(index),
+                            data_index: *i,
+                            value: data[*i],
+                            point: (x, y).into(),
+                            area: Box {
+                                left: x - r,
+                                top: y - r,
+                                right: x + r,
+                                bottom: y + r,
+                            },
+                            shape: LayoutShape::Circle(r),
+                        });
+                    }
+                    // the symbols of some points are rendered separately,
+                    // they are also separated if the elements have id or tooltip
+                    let show_all_symbol = series.symbol_visibility == SymbolVisibility::All
+                        && !matches!(symbol, Some(Symbol::Path(_)))
+                        && series.colors.is_none()
+                        && !self.element_ids
+                        && !self.tooltip_show;
+                    let line_symbol = if show_all_symbol { symbol.clone() } else { None };
+                    let fill_id = series.append_fill_defs(&mut c2, series.index.unwrap_or(index), color);
+                    // the area is filled if the series has gradient or pattern
+                    let series_fill = self.series_fill || fill_id.is_some();
+                    if series.category == Some(SeriesCategory::Band) {
+                        let mut band_list = vec![];
+                        let mut upper_points: Vec<Point> = vec![];
+                        let mut lower_points: Vec<Point> = vec![];
+                        for (i, (upper, lower)) in series.upper_data.iter().zip(series.lower_data.iter()).enumerate() {
+                            // the band is split by nil value
+                            if is_nil_value(*upper) || is_nil_value(*lower) {
+                                if !upper_points.is_empty() {
+                                    band_list.push((upper_points, lower_points));
+                                    upper_points = vec![];
+                                    lower_points = vec![];
+                                }
+                                continue;
+                            }
+                            let mut x = unit_width * (i + series.start_index) as f32;
+                            if x_boundary_gap {
+                                x += unit_width / 2.0;
+                            }
+                            upper_points.push((x, y_axis_values.get_offset_height(*upper, max_height)).into());
+                            lower_points.push((x, y_axis_values.get_offset_height(*lower, max_height)).into());
+                        }
+                        if !upper_points.is_empty() {
+                            band_list.push((upper_points, lower_points));
+                        }
+                        for (upper_points, lower_points) in band_list {
+                            let mut points = upper_points;
+                            points.extend(lower_points.into_iter().rev());
+                            c2.polygon(Polygon {
+                                fill: Some(fill),
+                                fill_id: fill_id.clone(),
+                                points,
+                                ..Default::default()
+                            });
+                        }
+                    }
                     for points in points_list.iter() {
                         if self.series_smooth {
                             if series_fill {
-                                c1.smooth_line_fill(SmoothLineFill {
+                                c2.smooth_line_fill(SmoothLineFill {
                                     fill,
+                                    fill_id: fill_id.clone(),
                                     points: points.clone(),
-                                    bottom: axis_height,
+                                    bottom: fill_bottom,
                                 });
                             }
-                            c1.smooth_line(SmoothLine {
+                            c2.smooth_line(SmoothLine {
                                 points: points.clone(),
-                                color: Some(color),
-                                stroke_width: self.series_stroke_width,
-                                symbol: self.series_symbol.clone(),
+                                color: Some(line_color),
+                                stroke_width,
+                                symbol: line_symbol.clone(),
                                 stroke_dash_array: series.stroke_dash_array.clone(),
+                                filter_id: filter_id.clone(),
                             });
                         } else {
                             if series_fill {
-                                c1.straight_line_fill(StraightLineFill {
+                                c2.straight_line_fill(StraightLineFill {
                                     fill,
+                                    fill_id: fill_id.clone(),
                                     points: points.clone(),
-                                    bottom: axis_height,
+                                    bottom: fill_bottom,
                                     ..Default::default()
                                 });
                             }
-                            c1.straight_line(StraightLine {
+                            c2.straight_line(StraightLine {
                                 points: points.clone(),
-                                color: Some(color),
-                                stroke_width: self.series_stroke_width,
-                                symbol: self.series_symbol.clone(),
+                                color: Some(line_color),
+                                stroke_width,
+                                symbol: line_symbol.clone(),
                                 stroke_dash_array: series.stroke_dash_array.clone(),
+                                filter_id: filter_id.clone(),
                                 ..Default::default()
                             });
                         }
                     }
+                    let x_offset = if x_boundary_gap { unit_width / 2.0 } else { 0.0 };
+                    for points in self.get_trend_points_list(series, y_axis_values, max_height, unit_width, x_offset) {
+                        c2.straight_line(StraightLine {
+                            points,
+                            color: Some(line_color),
+                            stroke_width,
+                            stroke_dash_array: Some("4,2".to_string()),
+                            ..Default::default()
+                        });
+                    }
+                    if !show_all_symbol {
+                        let last_index = symbol_points.last().map(|item| item.0).unwrap_or_default();
+                        for (i, point) in symbol_points.iter() {
+                            if !series.symbol_visibility.is_visible(*i, last_index) {
+                                continue;
+                            }
+                            match symbol {
+                                Some(Symbol::Circle(r, fill)) => {
+                                    c2.circle(Circle {
+                                        stroke_color: Some(series.get_item_color(*i).unwrap_or(line_color)),
+                                        fill,
+                                        stroke_width,
+                                        cx: point.x,
+                                        cy: point.y,
+                                        r,
+                                        id: self.element_ids.then(|| format!("series-{}-point-{i}", series.index.unwrap_or(index))),
+                                        class: self.element_ids.then(|| format!("series-point series-{}", series.index.unwrap_or(index))),
+                                        title: self.get_tooltip(series, *i, data[*i]),
+                                        ..Default::default()
+                                    });
+                                }
+                                Some(Symbol::Path(ref path)) => {
+                                    c2.path_symbol(PathSymbol {
+                                        path: path.clone(),
+                                        fill: Some(series.get_item_color(*i).unwrap_or(line_color)),
+                                        cx: point.x,
+                                        cy: point.y,
+                                        size: series.symbol_size.unwrap_or(stroke_width * 2.0),
+                                    });
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
                     for mark_point in series.mark_points.iter() {
                         let index = match mark_point.category {
                             MarkPointCategory::Max => max_index,
@@ -729,7 +2340,7 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                         if let Some(ref label) = series_labels.get(index) {
                             let r = 15.0;
                             let y = label.point.y - r * 2.0;
-                            c1.bubble(Bubble{
+                            c2.bubble(Bubble{
                                 x: label.point.x,
                                 y,
                                 r,
@@ -748,7 +2359,7 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                             } else {
                                 "#D8D9DA".into()
                             };
-                            c1.text(Text {
+                            c2.text(Text {
                                 text: label.text.clone(),
                                 line_height: Some(r) ,
                                 dx,
@@ -763,7 +2374,7 @@ pub fn my_default(input: TokenStream) -> TokenStream {
                     }
 
                 }
-                series_labels_list
+                (series_labels_list, layout_items)
             }
         }
     };
@@ -5,57 +5,79 @@
 mod bar_chart;
 mod candlestick_chart;
 mod canvas;
+mod chart_type;
 mod color;
 mod common;
 mod component;
 #[cfg(feature = "image-encoder")]
 mod encoder;
+mod facet_chart;
 mod font;
+mod gauge_chart;
 mod heatmap_chart;
 mod horizontal_bar_chart;
 mod line_chart;
+mod locale;
 mod multi_chart;
 mod params;
 mod path;
 mod pie_chart;
+mod progress_chart;
 mod radar_chart;
 mod scatter_chart;
 mod table_chart;
 mod theme;
 mod util;
 
-pub use bar_chart::BarChart;
+pub use bar_chart::{BarChart, BarChartBuilder};
 pub use canvas::Canvas;
+pub use canvas::ChartError;
 pub use canvas::Error as CanvasError;
 pub use canvas::Result as CanvasResult;
-pub use color::*;
+pub use chart_type::ChartType;
+pub use color::Error as ColorError;
+pub use color::{generate_palette, Color, ColorScale};
 pub use common::*;
 pub use component::{
-    Axis, Circle, Grid, Legend, LegendCategory, Line, Pie, Polygon, Polyline, Rect, SmoothLine,
-    SmoothLineFill, StraightLine, StraightLineFill, Text,
+    Arc, Axis, Circle, ClipPath, DropShadow, Ellipse, Gradient, Grid, Group, Inset, Legend,
+    LegendCategory, Line, Path, PathSymbol, Pattern, Pie, Polygon, Polyline, RadialGradient, Rect,
+    Sector, SmoothLine, SmoothLineFill, StraightLine, StraightLineFill, Text, TextSpan,
 };
 #[cfg(feature = "image-encoder")]
-pub(crate) use encoder::get_or_init_fontdb;
-#[cfg(feature = "image-encoder")]
 pub use encoder::Error as EncoderError;
 #[cfg(feature = "image-encoder")]
 pub use encoder::*;
+#[cfg(feature = "image-encoder")]
+pub(crate) use encoder::{add_font_to_fontdb, get_or_init_fontdb};
 
-pub use candlestick_chart::CandlestickChart;
+pub use candlestick_chart::{CandlestickChart, CandlestickChartBuilder};
+pub use facet_chart::{FacetChart, FacetChartType};
 pub use font::Error as FontError;
 pub use font::{
-    get_font, get_font_families, get_or_try_init_fonts, measure_text_width_family,
-    DEFAULT_FONT_DATA, DEFAULT_FONT_FAMILY,
+    get_font, get_font_families, get_fonts, get_or_try_init_fonts, measure_text_width_family,
+    register_font, register_font_file, DEFAULT_FONT_DATA, DEFAULT_FONT_FAMILY,
+};
+pub use gauge_chart::{GaugeBand, GaugeChart, GaugePointer};
+pub use heatmap_chart::{
+    HeatmapChart, HeatmapChartBuilder, HeatmapData, HeatmapSeries, HeatmapSort,
+};
+pub use horizontal_bar_chart::{HorizontalBarChart, HorizontalBarChartBuilder};
+pub use line_chart::{LineChart, LineChartBuilder};
+pub use locale::{
+    add_locale, get_locale, list_locale_name, Locale, LOCALE_DE_DE, LOCALE_EN_US, LOCALE_FR_FR,
+    LOCALE_ZH_CN,
 };
-pub use heatmap_chart::{HeatmapChart, HeatmapData, HeatmapSeries};
-pub use horizontal_bar_chart::HorizontalBarChart;
-pub use line_chart::LineChart;
 pub use multi_chart::{ChildChart, MultiChart};
 pub use path::*;
-pub use pie_chart::PieChart;
-pub use radar_chart::{RadarChart, RadarIndicator};
-pub use scatter_chart::ScatterChart;
-pub use table_chart::{TableCellStyle, TableChart};
+pub use pie_chart::{PieChart, PieChartBuilder};
+pub use progress_chart::ProgressChart;
+pub use radar_chart::{RadarChart, RadarChartBuilder, RadarIndicator, RadarScale};
+pub use scatter_chart::{ScatterChart, ScatterChartBuilder};
+pub use table_chart::{TableCellStyle, TableChart, TableSparkline};
 pub use theme::Theme;
-pub use theme::{add_theme, get_theme, list_theme_name, THEME_ANT, THEME_DARK, THEME_GRAFANA};
+pub use theme::{
+    add_theme, find_theme, get_default_option, get_theme, list_theme_name, load_theme_dir,
+    register_theme, set_default_option, DefaultOption, EffectiveStyle, THEME_ANT, THEME_DARK,
+    THEME_GRAFANA,
+};
 pub use util::*;
@@ -2,12 +2,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::canvas;
+use super::canvas::{self, ChartError};
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::locale::LocaleGuard;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme, Theme, DEFAULT_Y_AXIS_WIDTH};
+use super::theme::{
+    find_theme, get_default_theme_name, get_theme, EffectiveStyle, Theme, DEFAULT_FOOTER_FONT_SIZE,
+    DEFAULT_Y_AXIS_WIDTH,
+};
 use super::util::*;
 use super::Canvas;
 use crate::charts::measure_text_width_family;
@@ -32,8 +36,15 @@ pub struct BarChart {
     pub title_font_size: f32,
     pub title_font_color: Color,
     pub title_font_weight: Option<String>,
+    // font style of title, e.g. italic
+    pub title_font_style: Option<String>,
+    pub title_letter_spacing: Option<f32>,
+    // text decoration of title, e.g. underline
+    pub title_text_decoration: Option<String>,
     pub title_margin: Option<Box>,
     pub title_align: Align,
+    pub title_shadow: Option<Shadow>,
+    pub title_link: Option<String>,
     pub title_height: f32,
 
     // sub title
@@ -41,10 +52,23 @@ pub struct BarChart {
     pub sub_title_font_size: f32,
     pub sub_title_font_color: Color,
     pub sub_title_font_weight: Option<String>,
+    // font style of sub title, e.g. italic
+    pub sub_title_font_style: Option<String>,
+    pub sub_title_letter_spacing: Option<f32>,
+    // text decoration of sub title, e.g. underline
+    pub sub_title_text_decoration: Option<String>,
     pub sub_title_margin: Option<Box>,
     pub sub_title_align: Align,
     pub sub_title_height: f32,
 
+    // footer
+    pub footer_text: String,
+    pub footer_font_size: f32,
+    pub footer_font_color: Color,
+    pub footer_font_weight: Option<String>,
+    pub footer_margin: Option<Box>,
+    pub footer_align: Align,
+
     // legend
     pub legend_font_size: f32,
     pub legend_font_color: Color,
@@ -53,6 +77,8 @@ pub struct BarChart {
     pub legend_margin: Option<Box>,
     pub legend_category: LegendCategory,
     pub legend_show: Option<bool>,
+    pub legend_position: Option<Position>,
+    pub legend_order: Option<Vec<String>>,
 
     // x axis
     pub x_axis_data: Vec<String>,
@@ -66,6 +92,10 @@ pub struct BarChart {
     pub x_axis_margin: Option<Box>,
     pub x_axis_hidden: bool,
     pub x_boundary_gap: Option<bool>,
+    // the range(start and end index, inclusive) of categories to render, the axes are recalculated
+    pub x_range: Option<(usize, usize)>,
+    // the overview strip of all categories below the chart, the x range is highlighted
+    pub x_range_overview: bool,
 
     // y axis
     pub y_axis_hidden: bool,
@@ -74,6 +104,10 @@ pub struct BarChart {
     // grid
     pub grid_stroke_color: Color,
     pub grid_stroke_width: f32,
+    // the color of grid line at zero value, it is not highlighted if not set
+    pub zero_line_color: Option<Color>,
+    // the reference line of statistic computed across all visible series
+    pub guide_line: Option<GuideLine>,
 
     // series
     pub series_stroke_width: f32,
@@ -81,12 +115,54 @@ pub struct BarChart {
     pub series_label_font_size: f32,
     pub series_label_font_weight: Option<String>,
     pub series_label_formatter: String,
+    pub series_label_overlap: LabelOverlap,
+    pub series_shadow: Option<Shadow>,
     pub series_colors: Vec<Color>,
     pub series_symbol: Option<Symbol>,
     pub series_smooth: bool,
     pub series_fill: bool,
+    pub series_fill_opacity: Option<f32>,
+    pub max_points_per_series: Option<usize>,
 
     pub radius: Option<f32>,
+    pub bar_width: Option<f32>,
+    pub bar_max_width: Option<f32>,
+    // gap between the bars of series
+    pub bar_gap: Option<f32>,
+    // gap between the bars and the edge of category
+    pub bar_category_gap: Option<f32>,
+    // the category axis is rendered at the zero position of value axis,
+    // it is useful for diverging bars, e.g. profit and loss
+    pub x_axis_on_zero: bool,
+    // the total of stacked bars is labeled above the top segment
+    pub stack_total_label: bool,
+    pub annotations: Vec<Annotation>,
+    // emit the stable id and class of elements, e.g. series-0-bar-3
+    pub element_ids: bool,
+    // native tooltip of elements, e.g. Mon: Email = 120
+    pub tooltip_show: bool,
+    // accessible name and description of chart
+    pub aria_label: Option<String>,
+    pub description: Option<String>,
+    pub svg_format: SVGFormat,
+    pub coordinate_precision: Option<usize>,
+    pub style_hoisting: bool,
+    pub font_embedding: FontEmbedding,
+    // emit the svg of 100% width, the height is scaled by aspect ratio
+    pub responsive: bool,
+    // mirrored layout of right-to-left locale, the y axis is on the right side
+    pub rtl: bool,
+    // locale of number and date names, e.g. de-DE
+    pub locale: Option<String>,
+    // the placeholder of empty series, the text is "No data" if not set
+    pub empty_text: Option<String>,
+    pub empty_icon: bool,
+    // the inset charts, they are rendered after the series
+    pub insets: Vec<Inset>,
+    // the emphasized series or points, the others are faded
+    pub emphasis: Option<Emphasis>,
+    // the selected category which is indicated by crosshair line and band
+    pub axis_pointer: Option<AxisPointer>,
 }
 
 impl BarChart {
@@ -105,6 +181,24 @@ impl BarChart {
         if let Some(radius) = get_f32_from_value(&value, "radius") {
             b.radius = Some(radius);
         }
+        if let Some(bar_width) = get_f32_from_value(&value, "bar_width") {
+            b.bar_width = Some(bar_width);
+        }
+        if let Some(bar_max_width) = get_f32_from_value(&value, "bar_max_width") {
+            b.bar_max_width = Some(bar_max_width);
+        }
+        if let Some(bar_gap) = get_f32_from_value(&value, "bar_gap") {
+            b.bar_gap = Some(bar_gap);
+        }
+        if let Some(bar_category_gap) = get_f32_from_value(&value, "bar_category_gap") {
+            b.bar_category_gap = Some(bar_category_gap);
+        }
+        if let Some(x_axis_on_zero) = get_bool_from_value(&value, "x_axis_on_zero") {
+            b.x_axis_on_zero = x_axis_on_zero;
+        }
+        if let Some(stack_total_label) = get_bool_from_value(&value, "stack_total_label") {
+            b.stack_total_label = stack_total_label;
+        }
         Ok(b)
     }
     /// Creates a bar chart with custom theme.
@@ -134,10 +228,58 @@ impl BarChart {
     pub fn new(series_list: Vec<Series>, x_axis_data: Vec<String>) -> BarChart {
         BarChart::new_with_theme(series_list, x_axis_data, &get_default_theme_name())
     }
+    /// Converts the bar chart to sparkline, the axes, grid, legend and title are hidden.
+    /// The end point and the min/max points of first series are marked if they are enabled.
+    pub fn sparkline(&mut self, end_point: bool, min_max: bool) {
+        self.width = 120.0;
+        self.height = 30.0;
+        // the room of markers
+        self.margin = (3.0).into();
+        self.title_text = "".to_string();
+        self.sub_title_text = "".to_string();
+        self.legend_show = Some(false);
+        self.x_axis_hidden = true;
+        self.y_axis_hidden = true;
+        self.grid_stroke_width = 0.0;
+        self.bar_category_gap = Some(1.0);
+        // the x axis is hidden, so the data of x axis is only used for the count of points
+        if self.x_axis_data.is_empty() {
+            let count = self
+                .series_list
+                .iter()
+                .map(|series| series.data.len())
+                .max()
+                .unwrap_or_default();
+            self.x_axis_data = (0..count).map(|index| index.to_string()).collect();
+        }
+        if let Some(series) = self.series_list.first() {
+            let color = get_color(&self.series_colors, series.index.unwrap_or_default());
+            self.annotations
+                .extend(get_sparkline_annotations(series, color, end_point, min_max));
+        }
+    }
     /// Converts bar chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
-        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
-
+        self.render_with_layout().map(|(svg, _)| svg)
+    }
+    /// Converts bar chart to svg, the static layer(background, title, legend, grid and axes)
+    /// is reused from the cache if the options except series data are not changed.
+    pub fn svg_with_cache(&self, cache: &LayerCache) -> canvas::Result<String> {
+        self.render(Some(cache)).map(|(svg, _)| svg)
+    }
+    /// Converts bar chart to svg, and returns the layout of plot area and series elements.
+    pub fn render_with_layout(&self) -> canvas::Result<(String, ChartLayout)> {
+        self.render(None)
+    }
+    /// Renders the static layer of chart, e.g. background, title, legend, grid and axes.
+    fn render_static_layer(
+        &self,
+        c: &mut Canvas,
+        left_y_axis_values: &AxisValues,
+        right_y_axis_values: &AxisValues,
+        left_y_axis_width: f32,
+        right_y_axis_width: f32,
+    ) -> StaticLayer {
         self.render_background(c.child(Box::default()));
         let mut x_axis_height = self.x_axis_height;
         if self.x_axis_hidden {
@@ -147,36 +289,37 @@ impl BarChart {
 
         let title_height = self.render_title(c.child(Box::default()));
 
-        let legend_height = self.render_legend(c.child(Box::default()));
+        let footer_height = self.render_footer(c.child(Box::default()));
+
+        let legend_box = self.render_legend(c.child(Box {
+            bottom: footer_height,
+            ..Default::default()
+        }));
         // get the max height of title and legend
-        let axis_top = if legend_height > title_height {
-            legend_height
+        let axis_top = if legend_box.top > title_height {
+            legend_box.top
         } else {
             title_height
         };
-
-        let (left_y_axis_values, mut left_y_axis_width) = self.get_y_axis_values(0);
-        if self.y_axis_hidden {
-            left_y_axis_width = 0.0;
-        }
-        let mut exist_right_y_axis = false;
-        // check the right y axis
-        for series in self.series_list.iter() {
-            if series.y_axis_index != 0 {
-                exist_right_y_axis = true;
-            }
-        }
-        let mut right_y_axis_values = AxisValues::default();
-        let mut right_y_axis_width = 0.0_f32;
-        if !self.y_axis_hidden && exist_right_y_axis {
-            (right_y_axis_values, right_y_axis_width) = self.get_y_axis_values(1);
-        }
+        // the legend on the other sides and footer take the space of chart
+        *c = c.child(Box {
+            left: legend_box.left,
+            right: legend_box.right,
+            bottom: legend_box.bottom + footer_height,
+            ..Default::default()
+        });
 
         let axis_height = c.height() - x_axis_height - axis_top;
         let axis_width = c.width() - left_y_axis_width - right_y_axis_width;
+        // the first y axis is on the right side of rtl layout
+        let (left_width, right_width) = if self.rtl {
+            (right_y_axis_width, left_y_axis_width)
+        } else {
+            (left_y_axis_width, right_y_axis_width)
+        };
         // minus the height of top text area
         if axis_top > 0.0 {
-            c = c.child(Box {
+            *c = c.child(Box {
                 top: axis_top,
                 ..Default::default()
             });
@@ -184,17 +327,42 @@ impl BarChart {
 
         self.render_grid(
             c.child(Box {
-                left: left_y_axis_width,
+                left: left_width,
+                ..Default::default()
+            }),
+            axis_width,
+            axis_height,
+        );
+        self.render_zero_line(
+            c.child(Box {
+                left: left_width,
+                ..Default::default()
+            }),
+            left_y_axis_values,
+            axis_width,
+            axis_height,
+        );
+        self.render_axis_pointer(
+            c.child(Box {
+                left: left_width,
                 ..Default::default()
             }),
             axis_width,
             axis_height,
+            self.x_axis_data.len(),
         );
 
         // y axis
         if left_y_axis_width > 0.0 {
             self.render_y_axis(
-                c.child(Box::default()),
+                c.child(Box {
+                    left: if self.rtl {
+                        c.width() - left_y_axis_width
+                    } else {
+                        0.0
+                    },
+                    ..Default::default()
+                }),
                 left_y_axis_values.data.clone(),
                 axis_height,
                 left_y_axis_width,
@@ -205,7 +373,11 @@ impl BarChart {
         if right_y_axis_width > 0.0 {
             self.render_y_axis(
                 c.child(Box {
-                    left: c.width() - right_y_axis_width,
+                    left: if self.rtl {
+                        0.0
+                    } else {
+                        c.width() - right_y_axis_width
+                    },
                     ..Default::default()
                 }),
                 right_y_axis_values.data.clone(),
@@ -217,17 +389,101 @@ impl BarChart {
 
         // x axis
         if !self.x_axis_hidden {
+            let mut x_axis_top = c.height() - x_axis_height;
+            if self.x_axis_on_zero && left_y_axis_values.min < 0.0 && left_y_axis_values.max > 0.0 {
+                x_axis_top = left_y_axis_values.get_offset_height(0.0, axis_height);
+            }
             self.render_x_axis(
                 c.child(Box {
-                    top: c.height() - x_axis_height,
-                    left: left_y_axis_width,
-                    right: right_y_axis_width,
+                    top: x_axis_top,
+                    left: left_width,
+                    right: right_width,
                     ..Default::default()
                 }),
                 self.x_axis_data.clone(),
                 axis_width,
             );
         }
+        StaticLayer {
+            margin: c.margin.clone(),
+            x_axis_height,
+            axis_width,
+            axis_height,
+            left_width,
+            right_width,
+            ..Default::default()
+        }
+    }
+    /// Renders the chart, the static layer is taken from the cache if it is set.
+    fn render(&self, cache: Option<&LayerCache>) -> canvas::Result<(String, ChartLayout)> {
+        if let Some(chart) = self.get_x_range_chart() {
+            return chart.render(cache);
+        }
+        if let Some(svg) = self.render_empty_state()? {
+            return Ok((svg, ChartLayout::default()));
+        }
+        let _locale = LocaleGuard::new(self.locale.as_deref());
+        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        c.aria_label = self.aria_label.clone();
+        c.description = self.description.clone();
+        c.svg_format = self.svg_format.clone();
+        c.coordinate_precision = self.coordinate_precision;
+        c.style_hoisting = self.style_hoisting;
+        c.font_embedding = self.font_embedding.clone();
+        c.responsive = self.responsive;
+
+        let (left_y_axis_values, mut left_y_axis_width) = self.get_y_axis_values(0);
+        if self.y_axis_hidden {
+            left_y_axis_width = 0.0;
+        }
+        let mut exist_right_y_axis = false;
+        // check the right y axis
+        for series in self.series_list.iter() {
+            if series.y_axis_index != 0 {
+                exist_right_y_axis = true;
+            }
+        }
+        let mut right_y_axis_values = AxisValues::default();
+        let mut right_y_axis_width = 0.0_f32;
+        if !self.y_axis_hidden && exist_right_y_axis {
+            (right_y_axis_values, right_y_axis_width) = self.get_y_axis_values(1);
+        }
+
+        // the static layer is reused if the options except series data are not changed
+        let key = cache.map(|_| {
+            self.get_layer_key(
+                &[&left_y_axis_values, &right_y_axis_values],
+                (self.x_axis_hidden, self.y_axis_hidden, self.x_axis_on_zero),
+            )
+        });
+        let cached_layer = cache.zip(key).and_then(|(cache, key)| cache.get(key));
+        let layer = if let Some(layer) = cached_layer {
+            c.raw(layer.svg.clone());
+            layer
+        } else {
+            let mut layer = self.render_static_layer(
+                &mut c,
+                &left_y_axis_values,
+                &right_y_axis_values,
+                left_y_axis_width,
+                right_y_axis_width,
+            );
+            if let Some((cache, key)) = cache.zip(key) {
+                layer.key = key;
+                layer.svg = c.freeze()?;
+                cache.set(layer.clone());
+            }
+            layer
+        };
+        c.margin = layer.margin.clone();
+        let StaticLayer {
+            x_axis_height,
+            axis_width,
+            axis_height,
+            left_width,
+            right_width,
+            ..
+        } = layer;
 
         // bar point
         let max_height = c.height() - x_axis_height;
@@ -236,7 +492,7 @@ impl BarChart {
         // filter line and bar series points
         self.series_list.iter().for_each(|item| {
             if let Some(ref cat) = item.category {
-                if *cat == SeriesCategory::Line {
+                if matches!(cat, SeriesCategory::Line | SeriesCategory::Band) {
                     line_series_list.push(item);
                     return;
                 }
@@ -245,23 +501,30 @@ impl BarChart {
         });
 
         let y_axis_values_list = vec![&left_y_axis_values, &right_y_axis_values];
-        let mut bar_series_labels_list = self.render_bar(
+        let (mut bar_series_labels_list, mut layout_items) = self.render_bar(
             c.child(Box {
-                left: left_y_axis_width,
-                right: right_y_axis_width,
+                left: left_width,
+                right: right_width,
                 ..Default::default()
             }),
             &bar_series_list,
             &y_axis_values_list,
             max_height,
             self.x_axis_data.len(),
-            self.radius,
+            BarParams {
+                radius: self.radius,
+                width: self.bar_width,
+                max_width: self.bar_max_width,
+                gap: self.bar_gap,
+                category_gap: self.bar_category_gap,
+                stack_total: self.stack_total_label,
+            },
         );
 
-        let mut line_series_labels_list = self.render_line(
+        let (mut line_series_labels_list, mut line_layout_items) = self.render_line(
             c.child(Box {
-                left: left_y_axis_width,
-                right: right_y_axis_width,
+                left: left_width,
+                right: right_width,
                 ..Default::default()
             }),
             &line_series_list,
@@ -272,25 +535,60 @@ impl BarChart {
         );
 
         bar_series_labels_list.append(&mut line_series_labels_list);
+        layout_items.append(&mut line_layout_items);
 
         self.render_series_label(
             c.child(Box {
-                left: left_y_axis_width,
-                right: right_y_axis_width,
+                left: left_width,
+                right: right_width,
                 ..Default::default()
             }),
             bar_series_labels_list,
         );
+        self.render_guide_line(
+            c.child(Box {
+                left: left_width,
+                ..Default::default()
+            }),
+            &left_y_axis_values,
+            axis_width,
+            axis_height,
+        );
 
-        c.svg()
+        self.render_annotations(
+            c.child(Box {
+                left: left_width,
+                right: right_width,
+                ..Default::default()
+            }),
+            &y_axis_values_list,
+            max_height,
+            self.x_axis_data.len(),
+        );
+        self.render_insets(c.child(Box::default()));
+        let layout = ChartLayout {
+            plot_area: c
+                .child(Box {
+                    left: left_width,
+                    right: right_width,
+                    bottom: x_axis_height,
+                    ..Default::default()
+                })
+                .area(),
+            items: layout_items,
+        };
+        Ok((c.svg()?, layout))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::BarChart;
+    use crate::charts::util::AxisValues;
     use crate::{
-        Box, LegendCategory, SeriesCategory, NIL_VALUE, THEME_ANT, THEME_DARK, THEME_GRAFANA,
+        Align, Box, ChartWarning, Direction, Emphasis, GuideLine, GuideLineCategory, LayerCache,
+        LegendCategory, PatternCategory, Position, Series, SeriesCategory, TrendType, NIL_VALUE,
+        THEME_ANT, THEME_DARK, THEME_GRAFANA,
     };
     use pretty_assertions::assert_eq;
     #[test]
@@ -753,6 +1051,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bar_chart_all_nil_value() {
+        let mut bar_chart = BarChart::new(
+            vec![("Email", vec![NIL_VALUE, NIL_VALUE]).into()],
+            vec!["Mon".to_string(), "Tue".to_string()],
+        );
+        bar_chart.title_text = "Bar Chart".to_string();
+        // the zero baseline is not clamped to the empty range of values
+        assert_eq!(true, bar_chart.svg().is_ok());
+    }
+
     #[test]
     fn bar_chart_no_axis() {
         let mut bar_chart = BarChart::new(
@@ -802,4 +1111,794 @@ mod tests {
             bar_chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn bar_chart_label_formatter() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Bar Chart".to_string();
+        bar_chart.legend_margin = Some(Box {
+            top: 35.0,
+            bottom: 10.0,
+            ..Default::default()
+        });
+        bar_chart.series_list[0].label_show = true;
+        bar_chart.series_list[0].label_formatter = Some("{b}: {c}".to_string());
+        bar_chart.series_list[1].label_show = true;
+        bar_chart.series_list[1].label_formatter = Some("{d}%".to_string());
+        bar_chart.series_list[1].label_precision = Some(2);
+
+        assert_eq!(
+            include_str!("../../asset/bar_chart/label_formatter.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_label_position() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Bar Chart".to_string();
+        bar_chart.legend_margin = Some(Box {
+            top: 35.0,
+            bottom: 10.0,
+            ..Default::default()
+        });
+        bar_chart.series_list[0].label_show = true;
+        bar_chart.series_list[0].label_position = Some(Position::Inside);
+        bar_chart.series_list[1].label_show = true;
+        bar_chart.series_list[1].label_position = Some(Position::InsideTop);
+
+        assert_eq!(
+            include_str!("../../asset/bar_chart/label_position.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_bar_width() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Bar Chart".to_string();
+        bar_chart.legend_margin = Some(Box {
+            top: 35.0,
+            bottom: 10.0,
+            ..Default::default()
+        });
+        bar_chart.bar_max_width = Some(30.0);
+        bar_chart.bar_gap = Some(8.0);
+
+        assert_eq!(
+            include_str!("../../asset/bar_chart/bar_width.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_show_background() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Bar Chart".to_string();
+        bar_chart.legend_margin = Some(Box {
+            top: 35.0,
+            bottom: 10.0,
+            ..Default::default()
+        });
+        bar_chart.series_list[0].show_background = true;
+        bar_chart.series_list[1].show_background = true;
+        bar_chart.series_list[1].background_color = Some((145, 204, 117, 40).into());
+
+        assert_eq!(
+            include_str!("../../asset/bar_chart/show_background.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_hidden_series() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+                (
+                    "Direct",
+                    vec![320.0, 332.0, 301.0, 334.0, 390.0, 330.0, 320.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Hidden Series".to_string();
+        bar_chart.series_list[1].visible = false;
+        assert_eq!(
+            include_str!("../../asset/bar_chart/hidden_series.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_legend_auto() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Evaporation", vec![2.0, 4.9, 7.0, 23.2, 25.6, 76.7, 135.6]).into(),
+                (
+                    "Precipitation",
+                    vec![2.6, 5.9, 9.0, 26.4, 28.7, 70.7, 175.6],
+                )
+                    .into(),
+                ("Temperature", vec![2.0, 2.2, 3.3, 4.5, 6.3, 10.2, 20.3]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Legend Auto".to_string();
+        bar_chart.legend_category = LegendCategory::Auto;
+        bar_chart.series_list[2].category = Some(SeriesCategory::Line);
+        bar_chart.series_list[2].stroke_dash_array = Some("4,2".to_string());
+        assert_eq!(
+            include_str!("../../asset/bar_chart/legend_auto.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_footer() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Footer".to_string();
+        bar_chart.footer_text = "Source: internal metrics, 2024".to_string();
+        bar_chart.footer_margin = Some((0.0, 5.0, 0.0, 0.0).into());
+        bar_chart.legend_position = Some(Position::Bottom);
+        assert_eq!(
+            include_str!("../../asset/bar_chart/footer.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_fill_gradient() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Bar Chart".to_string();
+        bar_chart.legend_margin = Some(Box {
+            top: 35.0,
+            bottom: 10.0,
+            ..Default::default()
+        });
+        bar_chart.series_list[0].fill_gradient = Some((
+            (84, 112, 198).into(),
+            (84, 112, 198, 30).into(),
+            Direction::TopToBottom,
+        ));
+        bar_chart.series_list[1].category = Some(SeriesCategory::Line);
+        bar_chart.series_list[1].fill_gradient = Some((
+            (145, 204, 117, 180).into(),
+            (145, 204, 117, 0).into(),
+            Direction::TopToBottom,
+        ));
+
+        assert_eq!(
+            include_str!("../../asset/bar_chart/fill_gradient.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_fill_pattern() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+                ("Direct", vec![320.0, 332.0, 301.0, 334.0, 390.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Bar Chart".to_string();
+        bar_chart.legend_margin = Some(Box {
+            top: 35.0,
+            bottom: 10.0,
+            ..Default::default()
+        });
+        bar_chart.series_list[0].fill_pattern = Some(PatternCategory::DiagonalHatch);
+        bar_chart.series_list[1].fill_pattern = Some(PatternCategory::CrossHatch);
+        bar_chart.series_list[2].fill_pattern = Some(PatternCategory::Dots);
+
+        assert_eq!(
+            include_str!("../../asset/bar_chart/fill_pattern.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_element_ids() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Element Ids".to_string();
+        bar_chart.element_ids = true;
+        bar_chart.series_list[0].label_show = true;
+        bar_chart.series_list[1].category = Some(SeriesCategory::Line);
+        assert_eq!(
+            include_str!("../../asset/bar_chart/element_ids.svg"),
+            bar_chart.svg().unwrap()
+        );
+
+        // the ids of legend items are of series whatever the legend order is
+        bar_chart.series_list[0].trend_line = Some(TrendType::Linear);
+        bar_chart.legend_order = Some(vec!["Union Ads".to_string(), "Email".to_string()]);
+        let svg = bar_chart.svg().unwrap();
+        let union_ads_index = svg.find(r#"id="legend-item-1""#).unwrap();
+        let email_index = svg.find(r#"id="legend-item-0""#).unwrap();
+        assert_eq!(true, union_ads_index < email_index);
+        assert_eq!(true, svg.contains(r#"id="legend-item-0-trend""#));
+    }
+
+    #[test]
+    fn bar_chart_aria() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Weekly Visits".to_string();
+        bar_chart.aria_label = Some("Weekly visits of email and union ads".to_string());
+        bar_chart.description = Some("The visits of union ads are more than email".to_string());
+        bar_chart.series_list[1].category = Some(SeriesCategory::Line);
+        assert_eq!(
+            include_str!("../../asset/bar_chart/aria.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+    #[test]
+    fn bar_chart_rtl() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("البريد", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("الإعلانات", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "الاثنين".to_string(),
+                "الثلاثاء".to_string(),
+                "الأربعاء".to_string(),
+                "الخميس".to_string(),
+                "الجمعة".to_string(),
+            ],
+        );
+        bar_chart.rtl = true;
+        bar_chart.legend_align = Align::Left;
+        bar_chart.series_list[1].y_axis_index = 1;
+        assert_eq!(
+            include_str!("../../asset/bar_chart/rtl.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+    #[test]
+    fn bar_chart_empty() {
+        let mut bar_chart = BarChart::new(
+            vec![Series::new("Email".to_string(), vec![])],
+            vec!["Mon".to_string(), "Tue".to_string()],
+        );
+        bar_chart.title_text = "Bar Chart".to_string();
+        bar_chart.empty_icon = true;
+        assert_eq!(
+            include_str!("../../asset/bar_chart/empty.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_sparkline() {
+        let mut bar_chart = BarChart::new(
+            vec![(
+                "Email",
+                vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+            )
+                .into()],
+            vec![],
+        );
+        bar_chart.sparkline(false, true);
+        assert_eq!(
+            include_str!("../../asset/bar_chart/sparkline.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_negative() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Profit", vec![120.0, -132.0, 101.0, -34.0, 90.0]).into(),
+                ("Cost", vec![-80.0, 52.0, -91.0, 134.0, -60.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Negative Value".to_string();
+        bar_chart.zero_line_color = Some((70, 70, 70).into());
+        assert_eq!(
+            include_str!("../../asset/bar_chart/negative.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_diverging() {
+        let mut bar_chart = BarChart::new(
+            vec![("Profit", vec![320.0, -132.0, 101.0, -234.0, 190.0]).into()],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Profit and Loss".to_string();
+        bar_chart.x_axis_on_zero = true;
+        bar_chart.series_list[0].label_show = true;
+        bar_chart.series_list[0].negative_color = Some((238, 102, 102).into());
+        assert_eq!(
+            include_str!("../../asset/bar_chart/diverging.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_stack_total() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, -101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, -191.0, 234.0, 290.0]).into(),
+                ("Direct", vec![320.0, 332.0, 301.0, 334.0, 390.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Stack Total".to_string();
+        bar_chart.series_list[0].stack = Some("ads".to_string());
+        bar_chart.series_list[1].stack = Some("ads".to_string());
+        bar_chart.stack_total_label = true;
+        assert_eq!(
+            include_str!("../../asset/bar_chart/stack_total.svg"),
+            bar_chart.svg().unwrap()
+        );
+
+        let json_chart = BarChart::from_json(
+            r###"{
+                "title_text": "Stack Total",
+                "stack_total_label": true,
+                "series_list": [
+                    {"name": "Email", "data": [120.0, 132.0, -101.0, 134.0, 90.0], "stack": "ads"},
+                    {"name": "Union Ads", "data": [220.0, 182.0, -191.0, 234.0, 290.0], "stack": "ads"},
+                    {"name": "Direct", "data": [320.0, 332.0, 301.0, 334.0, 390.0]}
+                ],
+                "x_axis_data": ["Mon", "Tue", "Wed", "Thu", "Fri"]
+            }"###,
+        )
+        .unwrap();
+        assert_eq!(bar_chart.svg().unwrap(), json_chart.svg().unwrap());
+
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![NIL_VALUE, NIL_VALUE]).into(),
+                ("Union Ads", vec![NIL_VALUE, NIL_VALUE]).into(),
+            ],
+            vec!["Mon".to_string(), "Tue".to_string()],
+        );
+        bar_chart.series_list[0].stack = Some("ads".to_string());
+        bar_chart.series_list[1].stack = Some("ads".to_string());
+        bar_chart.stack_total_label = true;
+        // the zero baseline of stacks is not clamped to the empty range of values
+        assert_eq!(true, bar_chart.svg().is_ok());
+    }
+
+    #[test]
+    fn bar_chart_z_index() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Evaporation", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Temperature", vec![100.0, 160.0, 120.0, 110.0, 100.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Z Index".to_string();
+        bar_chart.series_list[1].category = Some(SeriesCategory::Line);
+        // the bars are drawn on top of the line
+        bar_chart.series_list[0].z_index = 1;
+        assert_eq!(
+            include_str!("../../asset/bar_chart/z_index.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_emphasis() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Evaporation", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Precipitation", vec![100.0, 160.0, 120.0, 110.0, 100.0]).into(),
+                ("Temperature", vec![80.0, 90.0, 110.0, 120.0, 130.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Emphasis".to_string();
+        bar_chart.series_list[2].category = Some(SeriesCategory::Line);
+        bar_chart.emphasis = Some(Emphasis {
+            series_index: Some(1),
+            data_indexes: vec![1],
+            ..Default::default()
+        });
+        assert_eq!(
+            include_str!("../../asset/bar_chart/emphasis.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_guide_line() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Evaporation", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Precipitation", vec![100.0, 160.0, 120.0, 110.0, 100.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Guide Line".to_string();
+        bar_chart.guide_line = Some(GuideLine {
+            category: GuideLineCategory::Percentile(95.0),
+            color: Some("#a90000".into()),
+            ..Default::default()
+        });
+        assert_eq!(
+            include_str!("../../asset/bar_chart/guide_line.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_layout() {
+        let bar_chart = BarChart::new(
+            vec![
+                ("Evaporation", vec![120.0, 132.0, 101.0]).into(),
+                ("Precipitation", vec![100.0, 160.0, 120.0]).into(),
+            ],
+            vec!["Mon".to_string(), "Tue".to_string(), "Wed".to_string()],
+        );
+        let (svg, layout) = bar_chart.render_with_layout().unwrap();
+        assert_eq!(bar_chart.svg().unwrap(), svg);
+        assert_eq!("(34,27,595,365)", layout.plot_area.to_string());
+        assert_eq!(6, layout.items.len());
+        let item = &layout.items[4];
+        assert_eq!(1, item.series_index);
+        assert_eq!(1, item.data_index);
+        assert_eq!(160.0, item.value);
+        assert_eq!("(316,64.6,403,365)", item.area.to_string());
+
+        let map = layout.image_map("bar", |item| {
+            if item.series_index != 1 {
+                return vec![];
+            }
+            vec![(
+                "href".to_string(),
+                format!("/detail?day={}", item.data_index),
+            )]
+        });
+        assert_eq!(
+            r###"<map name="bar">
+<area shape="rect" coords="129,177.2,216,365" href="/detail?day=0"/>
+<area shape="rect" coords="316,64.6,403,365" href="/detail?day=1"/>
+<area shape="rect" coords="503,139.7,590,365" href="/detail?day=2"/>
+</map>"###,
+            map
+        );
+    }
+
+    #[test]
+    fn bar_chart_overflow() {
+        let mut bar_chart = BarChart::new(
+            vec![("Evaporation", vec![120.0, 132.0, 101.0]).into()],
+            vec![
+                "Monday".to_string(),
+                "Tuesday".to_string(),
+                "Wednesday Afternoon".to_string(),
+            ],
+        );
+        bar_chart.width = 300.0;
+        bar_chart.height = 200.0;
+        bar_chart.x_axis_name_rotate = -0.5;
+        let overflows = bar_chart.get_overflows().unwrap();
+        assert_eq!(3, overflows.len());
+        assert_eq!("Wednesday Afternoon", overflows[2].text);
+        assert_eq!("(0,0,14.6,51.8)", overflows[2].overflow.to_string());
+
+        assert_eq!(true, bar_chart.expand_margin_for_overflows().unwrap());
+        assert_eq!("(5,5,20,57)", bar_chart.margin.to_string());
+        assert_eq!(0, bar_chart.get_overflows().unwrap().len());
+        assert_eq!(false, bar_chart.expand_margin_for_overflows().unwrap());
+    }
+
+    #[test]
+    fn bar_chart_warnings() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Evaporation", vec![120.0, 132.0, 101.0]).into(),
+                ("Precipitation", vec![100.0, 130.0]).into(),
+            ],
+            vec![
+                "Monday".to_string(),
+                "Tuesday".to_string(),
+                "Wednesday".to_string(),
+            ],
+        );
+        bar_chart.width = 160.0;
+        bar_chart.height = 200.0;
+        bar_chart.series_colors[0] = "#F5F5F5".into();
+        bar_chart.series_list[0].label_show = true;
+        bar_chart.series_list[1].label_show = true;
+        bar_chart.series_list[1].start_index = 2;
+        let warnings: Vec<String> = bar_chart
+            .get_warnings()
+            .unwrap()
+            .iter()
+            .map(|item| item.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "contrast ratio of Evaporation is 1.1, it is hard to read",
+                "series Precipitation ends at 4, it exceeds the axis length 3",
+                "label 101 overlaps 100",
+            ],
+            warnings
+        );
+        assert_eq!(
+            ChartWarning::SeriesExceedsAxis {
+                name: "Precipitation".to_string(),
+                length: 4,
+                expected: 3,
+            },
+            bar_chart.get_warnings().unwrap()[1]
+        );
+
+        let bar_chart = BarChart::new(
+            vec![("Evaporation", vec![120.0, 132.0, 101.0]).into()],
+            vec!["Mon".to_string(), "Tue".to_string(), "Wed".to_string()],
+        );
+        assert_eq!(0, bar_chart.get_warnings().unwrap().len());
+    }
+
+    #[test]
+    fn bar_chart_layer_cache() {
+        let cache = LayerCache::new();
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Evaporation", vec![120.0, 132.0, 101.0]).into(),
+                ("Precipitation", vec![100.0, 160.0, 120.0]).into(),
+            ],
+            vec!["Mon".to_string(), "Tue".to_string(), "Wed".to_string()],
+        );
+        bar_chart.title_text = "Bar Chart".to_string();
+        bar_chart.series_list[0].label_show = true;
+        let get_key = |bar_chart: &BarChart| {
+            let (values, _) = bar_chart.get_y_axis_values(0);
+            bar_chart.get_layer_key(
+                &[&values, &AxisValues::default()],
+                (
+                    bar_chart.x_axis_hidden,
+                    bar_chart.y_axis_hidden,
+                    bar_chart.x_axis_on_zero,
+                ),
+            )
+        };
+        assert_eq!(
+            bar_chart.svg().unwrap(),
+            bar_chart.svg_with_cache(&cache).unwrap()
+        );
+        let key = get_key(&bar_chart);
+        assert!(cache.get(key).is_some());
+
+        // the values of y axis are not changed, so the static layer is reused
+        bar_chart.series_list[0].data = vec![110.0, 150.0, 90.0];
+        assert_eq!(key, get_key(&bar_chart));
+        // the options of series are not in the static layer
+        bar_chart.series_list[1].label_show = true;
+        bar_chart.series_label_font_size = 16.0;
+        bar_chart.radius = Some(5.0);
+        assert_eq!(key, get_key(&bar_chart));
+        bar_chart.series_list[1].label_show = false;
+        bar_chart.series_label_font_size = 14.0;
+        bar_chart.radius = None;
+        assert_eq!(
+            bar_chart.svg().unwrap(),
+            bar_chart.svg_with_cache(&cache).unwrap()
+        );
+
+        bar_chart.series_list[1].data = vec![100.0, 360.0, 120.0];
+        assert_ne!(key, get_key(&bar_chart));
+        assert_eq!(
+            bar_chart.svg().unwrap(),
+            bar_chart.svg_with_cache(&cache).unwrap()
+        );
+        assert!(cache.get(key).is_none());
+
+        bar_chart.title_text = "Bar Chart Updated".to_string();
+        assert_eq!(
+            bar_chart.svg().unwrap(),
+            bar_chart.svg_with_cache(&cache).unwrap()
+        );
+    }
+
+    #[test]
+    fn bar_chart_style_hoisting() {
+        let mut bar_chart = BarChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        bar_chart.title_text = "Style Hoisting".to_string();
+        bar_chart.style_hoisting = true;
+        // the lines of grid are in the same line of svg
+        assert_eq!(
+            include_str!("../../asset/bar_chart/style_hoisting.svg"),
+            bar_chart.svg().unwrap()
+        );
+    }
 }
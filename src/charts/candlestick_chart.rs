@@ -2,12 +2,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::canvas;
+use super::canvas::{self, ChartError};
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::locale::LocaleGuard;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme, Theme, DEFAULT_Y_AXIS_WIDTH};
+use super::theme::{
+    find_theme, get_default_theme_name, get_theme, EffectiveStyle, Theme, DEFAULT_FOOTER_FONT_SIZE,
+    DEFAULT_Y_AXIS_WIDTH,
+};
 use super::util::*;
 use super::Canvas;
 use crate::charts::measure_text_width_family;
@@ -33,8 +37,15 @@ pub struct CandlestickChart {
     pub title_font_size: f32,
     pub title_font_color: Color,
     pub title_font_weight: Option<String>,
+    // font style of title, e.g. italic
+    pub title_font_style: Option<String>,
+    pub title_letter_spacing: Option<f32>,
+    // text decoration of title, e.g. underline
+    pub title_text_decoration: Option<String>,
     pub title_margin: Option<Box>,
     pub title_align: Align,
+    pub title_shadow: Option<Shadow>,
+    pub title_link: Option<String>,
     pub title_height: f32,
 
     // sub title
@@ -42,10 +53,23 @@ pub struct CandlestickChart {
     pub sub_title_font_size: f32,
     pub sub_title_font_color: Color,
     pub sub_title_font_weight: Option<String>,
+    // font style of sub title, e.g. italic
+    pub sub_title_font_style: Option<String>,
+    pub sub_title_letter_spacing: Option<f32>,
+    // text decoration of sub title, e.g. underline
+    pub sub_title_text_decoration: Option<String>,
     pub sub_title_margin: Option<Box>,
     pub sub_title_align: Align,
     pub sub_title_height: f32,
 
+    // footer
+    pub footer_text: String,
+    pub footer_font_size: f32,
+    pub footer_font_color: Color,
+    pub footer_font_weight: Option<String>,
+    pub footer_margin: Option<Box>,
+    pub footer_align: Align,
+
     // legend
     pub legend_font_size: f32,
     pub legend_font_color: Color,
@@ -54,6 +78,8 @@ pub struct CandlestickChart {
     pub legend_margin: Option<Box>,
     pub legend_category: LegendCategory,
     pub legend_show: Option<bool>,
+    pub legend_position: Option<Position>,
+    pub legend_order: Option<Vec<String>>,
 
     // x axis
     pub x_axis_data: Vec<String>,
@@ -67,6 +93,10 @@ pub struct CandlestickChart {
     pub x_axis_margin: Option<Box>,
     pub x_axis_hidden: bool,
     pub x_boundary_gap: Option<bool>,
+    // the range(start and end index, inclusive) of categories to render, the axes are recalculated
+    pub x_range: Option<(usize, usize)>,
+    // the overview strip of all categories below the chart, the x range is highlighted
+    pub x_range_overview: bool,
 
     // y axis
     pub y_axis_hidden: bool,
@@ -75,6 +105,10 @@ pub struct CandlestickChart {
     // grid
     pub grid_stroke_color: Color,
     pub grid_stroke_width: f32,
+    // the color of grid line at zero value, it is not highlighted if not set
+    pub zero_line_color: Option<Color>,
+    // the reference line of statistic computed across all visible series
+    pub guide_line: Option<GuideLine>,
 
     // series
     pub series_stroke_width: f32,
@@ -82,15 +116,50 @@ pub struct CandlestickChart {
     pub series_label_font_size: f32,
     pub series_label_font_weight: Option<String>,
     pub series_label_formatter: String,
+    pub series_label_overlap: LabelOverlap,
+    pub series_shadow: Option<Shadow>,
     pub series_colors: Vec<Color>,
     pub series_symbol: Option<Symbol>,
     pub series_smooth: bool,
     pub series_fill: bool,
+    pub series_fill_opacity: Option<f32>,
+    pub max_points_per_series: Option<usize>,
 
     pub candlestick_up_color: Color,
     pub candlestick_up_border_color: Color,
     pub candlestick_down_color: Color,
     pub candlestick_down_border_color: Color,
+    // volume of each candlestick, the volume panel is rendered below the price panel if it is not empty
+    pub volume_data: Vec<f32>,
+    // the ratio(0-1) of volume panel height to the axis height
+    pub volume_height_ratio: f32,
+    pub annotations: Vec<Annotation>,
+    // emit the stable id and class of elements, e.g. series-0-bar-3
+    pub element_ids: bool,
+    // native tooltip of elements, e.g. Mon: Email = 120
+    pub tooltip_show: bool,
+    // accessible name and description of chart
+    pub aria_label: Option<String>,
+    pub description: Option<String>,
+    pub svg_format: SVGFormat,
+    pub coordinate_precision: Option<usize>,
+    pub style_hoisting: bool,
+    pub font_embedding: FontEmbedding,
+    // emit the svg of 100% width, the height is scaled by aspect ratio
+    pub responsive: bool,
+    // mirrored layout of right-to-left locale, the y axis is on the right side
+    pub rtl: bool,
+    // locale of number and date names, e.g. de-DE
+    pub locale: Option<String>,
+    // the placeholder of empty series, the text is "No data" if not set
+    pub empty_text: Option<String>,
+    pub empty_icon: bool,
+    // the inset charts, they are rendered after the series
+    pub insets: Vec<Inset>,
+    // the emphasized series or points, the others are faded
+    pub emphasis: Option<Emphasis>,
+    // the selected category which is indicated by crosshair line and band
+    pub axis_pointer: Option<AxisPointer>,
 }
 
 impl CandlestickChart {
@@ -107,6 +176,9 @@ impl CandlestickChart {
         if self.candlestick_down_border_color.is_zero() {
             self.candlestick_down_border_color = (0, 143, 40).into();
         }
+        if self.volume_height_ratio <= 0.0 {
+            self.volume_height_ratio = 0.25;
+        }
     }
     /// Creates a candlestick chart from json.
     pub fn from_json(data: &str) -> canvas::Result<CandlestickChart> {
@@ -132,6 +204,12 @@ impl CandlestickChart {
         if let Some(y_axis_hidden) = get_bool_from_value(&value, "y_axis_hidden") {
             c.y_axis_hidden = y_axis_hidden;
         }
+        if let Some(volume_data) = get_f32_slice_from_value(&value, "volume_data") {
+            c.volume_data = volume_data;
+        }
+        if let Some(volume_height_ratio) = get_f32_from_value(&value, "volume_height_ratio") {
+            c.volume_height_ratio = volume_height_ratio;
+        }
         c.fill_default();
         Ok(c)
     }
@@ -162,9 +240,90 @@ impl CandlestickChart {
     pub fn new(series_list: Vec<Series>, x_axis_data: Vec<String>) -> CandlestickChart {
         CandlestickChart::new_with_theme(series_list, x_axis_data, &get_default_theme_name())
     }
+    /// Renders the volume panel, the bar color is the same as the candlestick.
+    fn render_volume(
+        &self,
+        c: Canvas,
+        volume_values: &AxisValues,
+        left_width: f32,
+        y_axis_width: f32,
+        axis_width: f32,
+        axis_height: f32,
+    ) {
+        let split_number = volume_values.data.len().saturating_sub(1);
+        c.child(Box {
+            left: left_width,
+            ..Default::default()
+        })
+        .grid(Grid {
+            right: axis_width,
+            bottom: axis_height,
+            color: Some(self.grid_stroke_color),
+            stroke_width: self.grid_stroke_width,
+            horizontals: split_number,
+            hidden_horizontals: vec![split_number],
+            ..Default::default()
+        });
+        if !self.y_axis_hidden {
+            self.render_y_axis(
+                c.child(Box {
+                    left: if self.rtl { axis_width } else { 0.0 },
+                    ..Default::default()
+                }),
+                volume_values.data.clone(),
+                axis_height,
+                y_axis_width,
+                0,
+            );
+        }
+        let Some(series) = self
+            .series_list
+            .iter()
+            .find(|item| item.visible && item.category.is_none())
+        else {
+            return;
+        };
+        let chunk_width = axis_width / self.x_axis_data.len() as f32;
+        let half_chunk_width = chunk_width / 2.0;
+        let mut c1 = c.child(Box {
+            left: left_width,
+            ..Default::default()
+        });
+        for (index, value) in self.volume_data.iter().enumerate() {
+            if is_nil_value(*value) {
+                continue;
+            }
+            let mut fill = self.candlestick_up_color;
+            if let Some(chunk) = series.data.chunks(4).nth(index) {
+                if chunk.len() == 4 && chunk[0] > chunk[1] {
+                    fill = self.candlestick_down_color;
+                }
+            }
+            let top = volume_values.get_offset_height(*value, axis_height);
+            c1.rect(Rect {
+                fill: Some(fill),
+                left: half_chunk_width / 2.0 + chunk_width * index as f32 - 1.0,
+                top,
+                width: half_chunk_width,
+                height: axis_height - top,
+                ..Default::default()
+            });
+        }
+    }
     /// Converts candlestick chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = self.render_empty_state()? {
+            return Ok(svg);
+        }
+        let _locale = LocaleGuard::new(self.locale.as_deref());
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        c.aria_label = self.aria_label.clone();
+        c.description = self.description.clone();
+        c.svg_format = self.svg_format.clone();
+        c.coordinate_precision = self.coordinate_precision;
+        c.style_hoisting = self.style_hoisting;
+        c.font_embedding = self.font_embedding.clone();
+        c.responsive = self.responsive;
 
         self.render_background(c.child(Box::default()));
         let mut x_axis_height = self.x_axis_height;
@@ -175,21 +334,57 @@ impl CandlestickChart {
 
         let title_height = self.render_title(c.child(Box::default()));
 
-        let legend_height = self.render_legend(c.child(Box::default()));
+        let footer_height = self.render_footer(c.child(Box::default()));
+
+        let legend_box = self.render_legend(c.child(Box {
+            bottom: footer_height,
+            ..Default::default()
+        }));
         // get the max height of title and legend
-        let axis_top = if legend_height > title_height {
-            legend_height
+        let axis_top = if legend_box.top > title_height {
+            legend_box.top
         } else {
             title_height
         };
+        // the legend on the other sides and footer take the space of chart
+        c = c.child(Box {
+            left: legend_box.left,
+            right: legend_box.right,
+            bottom: legend_box.bottom + footer_height,
+            ..Default::default()
+        });
 
         let (left_y_axis_values, mut left_y_axis_width) = self.get_y_axis_values(0);
+        let volume_values = get_axis_values(AxisValueParams {
+            data_list: self.volume_data.clone(),
+            split_number: 2,
+            reverse: Some(true),
+            ..Default::default()
+        });
+        // the y axis of volume panel has the same width
+        if !self.volume_data.is_empty() {
+            let font_size = self.get_y_axis_config(0).axis_font_size;
+            for item in volume_values.data.iter() {
+                if let Ok(b) = measure_text_width_family(&self.font_family, font_size, item) {
+                    left_y_axis_width = left_y_axis_width.max(b.width() + 5.0);
+                }
+            }
+        }
         if self.y_axis_hidden {
             left_y_axis_width = 0.0;
         }
 
-        let axis_height = c.height() - x_axis_height - axis_top;
+        let mut axis_height = c.height() - x_axis_height - axis_top;
+        // the volume panel shares the x axis with the price panel
+        let volume_gap = 10.0;
+        let mut volume_height = 0.0;
+        if !self.volume_data.is_empty() {
+            volume_height = axis_height * self.volume_height_ratio.clamp(0.0, 0.9);
+            axis_height -= volume_height + volume_gap;
+        }
         let axis_width = c.width() - left_y_axis_width;
+        // the y axis is on the right side of rtl layout
+        let left_width = if self.rtl { 0.0 } else { left_y_axis_width };
         // minus the height of top text area
         if axis_top > 0.0 {
             c = c.child(Box {
@@ -200,7 +395,7 @@ impl CandlestickChart {
 
         self.render_grid(
             c.child(Box {
-                left: left_y_axis_width,
+                left: left_width,
                 ..Default::default()
             }),
             axis_width,
@@ -210,7 +405,10 @@ impl CandlestickChart {
         // y axis
         if !self.y_axis_hidden {
             self.render_y_axis(
-                c.child(Box::default()),
+                c.child(Box {
+                    left: if self.rtl { axis_width } else { 0.0 },
+                    ..Default::default()
+                }),
                 left_y_axis_values.data.clone(),
                 axis_height,
                 left_y_axis_width,
@@ -218,12 +416,26 @@ impl CandlestickChart {
             );
         }
 
+        if volume_height > 0.0 {
+            self.render_volume(
+                c.child(Box {
+                    top: axis_height + volume_gap,
+                    ..Default::default()
+                }),
+                &volume_values,
+                left_width,
+                left_y_axis_width,
+                axis_width,
+                volume_height,
+            );
+        }
+
         // x axis
         if !self.x_axis_hidden {
             self.render_x_axis(
                 c.child(Box {
                     top: c.height() - x_axis_height,
-                    left: left_y_axis_width,
+                    left: left_width,
                     ..Default::default()
                 }),
                 self.x_axis_data.clone(),
@@ -233,7 +445,7 @@ impl CandlestickChart {
         let chunk_width = axis_width / self.x_axis_data.len() as f32;
         let half_chunk_width = chunk_width / 2.0;
         for series in self.series_list.iter() {
-            if series.category.is_some() {
+            if !series.visible || series.category.is_some() {
                 continue;
             }
             // split the series point to chunk
@@ -261,7 +473,7 @@ impl CandlestickChart {
 
                 let line_left = half_chunk_width + chunk_width * index as f32 - 1.0;
                 c.child(Box {
-                    left: left_y_axis_width,
+                    left: left_width,
                     ..Default::default()
                 })
                 .line(Line {
@@ -275,7 +487,7 @@ impl CandlestickChart {
                 });
 
                 c.child(Box {
-                    left: left_y_axis_width,
+                    left: left_width,
                     ..Default::default()
                 })
                 .rect(Rect {
@@ -292,17 +504,17 @@ impl CandlestickChart {
         let mut line_series_list = vec![];
         self.series_list.iter().for_each(|item| {
             if let Some(ref cat) = item.category {
-                if *cat == SeriesCategory::Line {
+                if matches!(cat, SeriesCategory::Line | SeriesCategory::Band) {
                     line_series_list.push(item);
                 }
             }
         });
 
         let y_axis_values_list = vec![&left_y_axis_values];
-        let max_height = c.height() - x_axis_height;
-        let line_series_labels_list = self.render_line(
+        let max_height = axis_height;
+        let (line_series_labels_list, _) = self.render_line(
             c.child(Box {
-                left: left_y_axis_width,
+                left: left_width,
                 ..Default::default()
             }),
             &line_series_list,
@@ -314,12 +526,22 @@ impl CandlestickChart {
 
         self.render_series_label(
             c.child(Box {
-                left: left_y_axis_width,
+                left: left_width,
                 ..Default::default()
             }),
             line_series_labels_list,
         );
 
+        self.render_annotations(
+            c.child(Box {
+                left: left_width,
+                ..Default::default()
+            }),
+            &y_axis_values_list,
+            max_height,
+            self.x_axis_data.len(),
+        );
+        self.render_insets(c.child(Box::default()));
         c.svg()
     }
 }
@@ -507,4 +729,30 @@ mod tests {
             candlestick_chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn candlestick_chart_volume() {
+        let mut candlestick_chart = CandlestickChart::new(
+            vec![(
+                "",
+                vec![
+                    20.0, 34.0, 10.0, 38.0, 40.0, 35.0, 30.0, 50.0, 31.0, 38.0, 33.0, 44.0, 38.0,
+                    15.0, 5.0, 42.0,
+                ],
+            )
+                .into()],
+            vec![
+                "2017-10-24".to_string(),
+                "2017-10-25".to_string(),
+                "2017-10-26".to_string(),
+                "2017-10-27".to_string(),
+            ],
+        );
+        candlestick_chart.volume_data = vec![1200.0, 3400.0, 2100.0, 4800.0];
+        candlestick_chart.volume_height_ratio = 0.3;
+        assert_eq!(
+            include_str!("../../asset/candlestick_chart/volume.svg"),
+            candlestick_chart.svg().unwrap()
+        );
+    }
 }
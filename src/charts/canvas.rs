@@ -2,15 +2,18 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use super::common::{FontEmbedding, SVGFormat};
 use super::component::{
-    generate_svg, Arrow, Axis, Bubble, Circle, Component, Grid, Legend, Line, Pie, Polygon,
-    Polyline, Rect, SmoothLine, SmoothLineFill, StraightLine, StraightLineFill, Text, LEGEND_WIDTH,
+    format_svg, generate_svg, hoist_style, make_responsive, Arc, Arrow, Axis, Bubble, Circle,
+    ClipPath, Component, DropShadow, Ellipse, Gradient, Grid, Group, Inset, Legend, Line, Path,
+    PathSymbol, Pattern, Pie, Polygon, Polyline, RadialGradient, Rect, Sector, SmoothLine,
+    SmoothLineFill, StraightLine, StraightLineFill, Text, LEGEND_WIDTH,
 };
+use super::component::{with_shared_components, SharedComponents};
 
+use super::font::{convert_text_to_outline, embed_fonts};
 use super::{measure_text_width_family, util::*};
 use snafu::{ResultExt, Snafu};
-use std::cell::RefCell;
-use std::rc::Rc;
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -22,6 +25,40 @@ pub enum Error {
     Json { source: serde_json::Error },
     #[snafu(display("Font is invalid: {source}"))]
     Font { source: super::FontError },
+    #[snafu(display("Toml is invalid: {source}"))]
+    Toml { source: toml::de::Error },
+    #[snafu(display("Io {file}: {source}"))]
+    Io {
+        file: String,
+        source: std::io::Error,
+    },
+    #[snafu(display("Chart is invalid: {source}"))]
+    Chart { source: ChartError },
+}
+
+/// The error of chart configuration, it is returned by the validation of chart.
+#[derive(Debug, Snafu, PartialEq)]
+pub enum ChartError {
+    #[snafu(display("width and height should be greater than 0, got {width}x{height}"))]
+    InvalidSize { width: f32, height: f32 },
+    #[snafu(display("series list is empty"))]
+    EmptySeries,
+    #[snafu(display(
+        "length of series {name} is {length}, it exceeds the expected length {expected}"
+    ))]
+    LengthMismatch {
+        name: String,
+        length: usize,
+        expected: usize,
+    },
+    #[snafu(display("value of series {name} at index {index} is infinite"))]
+    InfiniteValue { name: String, index: usize },
+}
+
+impl From<ChartError> for Error {
+    fn from(value: ChartError) -> Self {
+        Error::Chart { source: value }
+    }
 }
 
 impl From<serde_json::Error> for Error {
@@ -30,6 +67,12 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<toml::de::Error> for Error {
+    fn from(value: toml::de::Error) -> Self {
+        Error::Toml { source: value }
+    }
+}
+
 impl From<super::FontError> for Error {
     fn from(value: super::FontError) -> Self {
         Error::Font { source: value }
@@ -44,8 +87,20 @@ pub struct Canvas {
     pub height: f32,
     pub x: f32,
     pub y: f32,
-    pub components: Rc<RefCell<Vec<Component>>>,
+    components: SharedComponents,
     pub margin: Box,
+    // accessible name of svg, it is emitted as title
+    pub aria_label: Option<String>,
+    // accessible description of svg, it is emitted as desc
+    pub description: Option<String>,
+    pub svg_format: SVGFormat,
+    // decimal precision of coordinates, it is one by default
+    pub coordinate_precision: Option<usize>,
+    // hoist the repeated style attributes into class
+    pub style_hoisting: bool,
+    pub font_embedding: FontEmbedding,
+    // the width of svg is 100% of container, the height is scaled by aspect ratio
+    pub responsive: bool,
 }
 
 impl Canvas {
@@ -60,8 +115,15 @@ impl Canvas {
             height,
             x,
             y,
-            components: Rc::new(RefCell::new(vec![])),
+            components: SharedComponents::default(),
             margin: Box::default(),
+            aria_label: None,
+            description: None,
+            svg_format: SVGFormat::default(),
+            coordinate_precision: None,
+            style_hoisting: false,
+            font_embedding: FontEmbedding::default(),
+            responsive: false,
         }
     }
     /// Gets the width of canvas.
@@ -80,10 +142,38 @@ impl Canvas {
         Canvas {
             width: self.width,
             height: self.height,
-            components: Rc::clone(&self.components),
+            components: SharedComponents::clone(&self.components),
             margin: m,
             x: self.x,
             y: self.y,
+            aria_label: self.aria_label.clone(),
+            description: self.description.clone(),
+            svg_format: self.svg_format.clone(),
+            coordinate_precision: self.coordinate_precision,
+            style_hoisting: self.style_hoisting,
+            font_embedding: self.font_embedding.clone(),
+            responsive: self.responsive,
+        }
+    }
+    /// Creates a child canvas at the position which is relative to the canvas,
+    /// the size of child is fixed and not affected by the margin.
+    pub fn child_at(&self, x: f32, y: f32, width: f32, height: f32) -> Self {
+        let mut c = self.child_left_top(Box {
+            left: x,
+            top: y,
+            ..Default::default()
+        });
+        c.margin.right = self.width - c.margin.left - width;
+        c.margin.bottom = self.height - c.margin.top - height;
+        c
+    }
+    /// Gets the area of canvas, it is the absolute coordinates of svg.
+    pub fn area(&self) -> Box {
+        Box {
+            left: self.margin.left,
+            top: self.margin.top,
+            right: self.width - self.margin.right,
+            bottom: self.height - self.margin.bottom,
         }
     }
     /// Creates a child canvas.
@@ -96,10 +186,63 @@ impl Canvas {
         Canvas {
             width: self.width,
             height: self.height,
-            components: Rc::clone(&self.components),
+            components: SharedComponents::clone(&self.components),
             margin: m,
             x: self.x,
             y: self.y,
+            aria_label: self.aria_label.clone(),
+            description: self.description.clone(),
+            svg_format: self.svg_format.clone(),
+            coordinate_precision: self.coordinate_precision,
+            style_hoisting: self.style_hoisting,
+            font_embedding: self.font_embedding.clone(),
+            responsive: self.responsive,
+        }
+    }
+    /// Creates a child canvas, the components of it are clipped by the region of child.
+    pub fn clip(&mut self, margin: Box) -> Self {
+        let mut c = self.child(margin);
+        let clip_path = ClipPath {
+            id: format!(
+                "clip-{}-{}-{}-{}",
+                format_coordinate(c.margin.left),
+                format_coordinate(c.margin.top),
+                format_coordinate(c.width()),
+                format_coordinate(c.height())
+            ),
+            left: c.margin.left,
+            top: c.margin.top,
+            width: c.width(),
+            height: c.height(),
+        };
+        let clip_path_id = clip_path.id.clone();
+        self.append(Component::ClipPath(clip_path));
+        c.group(Group {
+            clip_path_id: Some(clip_path_id),
+            ..Default::default()
+        })
+    }
+    /// Creates a child canvas, the components of it are wrapped in the group.
+    /// The transform and opacity of group are applied to all of them.
+    pub fn group(&mut self, group: Group) -> Self {
+        let components = SharedComponents::default();
+        let mut g = group;
+        g.components = SharedComponents::clone(&components);
+        self.append(Component::Group(g));
+        Canvas {
+            width: self.width,
+            height: self.height,
+            components,
+            margin: self.margin.clone(),
+            x: self.x,
+            y: self.y,
+            aria_label: self.aria_label.clone(),
+            description: self.description.clone(),
+            svg_format: self.svg_format.clone(),
+            coordinate_precision: self.coordinate_precision,
+            style_hoisting: self.style_hoisting,
+            font_embedding: self.font_embedding.clone(),
+            responsive: self.responsive,
         }
     }
     /// Appends arrow widget to canvas.
@@ -169,6 +312,58 @@ impl Canvas {
         self.append(Component::Circle(c));
         b
     }
+    /// Appends ellipse widget to canvas.
+    pub fn ellipse(&mut self, ellipse: Ellipse) -> Box {
+        let mut c = ellipse;
+        c.cx += self.margin.left;
+        c.cy += self.margin.top;
+        let b = Box {
+            left: c.cx - c.rx,
+            top: c.cy - c.ry,
+            right: c.cx + c.rx,
+            bottom: c.cy + c.ry,
+        };
+        self.append(Component::Ellipse(c));
+        b
+    }
+    /// Appends path symbol widget to canvas.
+    pub fn path_symbol(&mut self, symbol: PathSymbol) -> Box {
+        let mut c = symbol;
+        c.cx += self.margin.left;
+        c.cy += self.margin.top;
+        let b = Box {
+            left: c.cx - c.size,
+            top: c.cy - c.size,
+            right: c.cx + c.size,
+            bottom: c.cy + c.size,
+        };
+        self.append(Component::PathSymbol(c));
+        b
+    }
+    /// Appends path widget to canvas, the path is translated by the margin of canvas.
+    pub fn path(&mut self, path: Path) -> Box {
+        let mut c = path;
+        if self.margin.left != 0.0 || self.margin.top != 0.0 {
+            let translate = format!(
+                "translate({} {})",
+                format_coordinate(self.margin.left),
+                format_coordinate(self.margin.top)
+            );
+            c.transform = Some(if let Some(ref transform) = c.transform {
+                format!("{translate} {transform}")
+            } else {
+                translate
+            });
+        }
+        let b = Box {
+            left: self.margin.left,
+            top: self.margin.top,
+            right: self.width - self.margin.right,
+            bottom: self.height - self.margin.bottom,
+        };
+        self.append(Component::Path(c));
+        b
+    }
     /// Appends polygon widget to canvas.
     pub fn polygon(&mut self, polygon: Polygon) -> Box {
         let mut c = polygon;
@@ -215,7 +410,7 @@ impl Canvas {
             }
         }
 
-        self.append(Component::Text(c));
+        self.append(Component::Text(std::boxed::Box::new(c)));
         b
     }
     /// Appends pie widget to canvas.
@@ -233,6 +428,34 @@ impl Canvas {
         self.append(Component::Pie(c));
         b
     }
+    /// Appends arc widget to canvas.
+    pub fn arc(&mut self, arc: Arc) -> Box {
+        let mut c = arc;
+        c.cx += self.margin.left;
+        c.cy += self.margin.top;
+        let b = Box {
+            left: c.cx - c.r,
+            top: c.cy - c.r,
+            right: c.cx + c.r,
+            bottom: c.cy + c.r,
+        };
+        self.append(Component::Arc(c));
+        b
+    }
+    /// Appends sector widget to canvas.
+    pub fn sector(&mut self, sector: Sector) -> Box {
+        let mut c = sector;
+        c.cx += self.margin.left;
+        c.cy += self.margin.top;
+        let b = Box {
+            left: c.cx - c.r,
+            top: c.cy - c.r,
+            right: c.cx + c.r,
+            bottom: c.cy + c.r,
+        };
+        self.append(Component::Sector(c));
+        b
+    }
     /// Appends smooth line points widget to canvas.
     pub fn smooth_line(&mut self, line: SmoothLine) -> Box {
         let mut c = line;
@@ -341,51 +564,232 @@ impl Canvas {
         self.append(Component::Bubble(c));
         b
     }
+    /// Appends gradient defs to canvas, it can be referenced by id.
+    pub fn gradient(&mut self, gradient: Gradient) -> Box {
+        self.append(Component::Gradient(gradient));
+        Box::default()
+    }
+    /// Appends radial gradient defs to canvas, it can be referenced by id.
+    pub fn radial_gradient(&mut self, gradient: RadialGradient) -> Box {
+        let mut c = gradient;
+        // the center is the coordinate of canvas
+        if c.user_space {
+            c.cx += self.margin.left;
+            c.cy += self.margin.top;
+        }
+        self.append(Component::RadialGradient(c));
+        Box::default()
+    }
+    /// Appends drop shadow filter to canvas, it can be referenced by id.
+    pub fn drop_shadow(&mut self, shadow: DropShadow) -> Box {
+        self.append(Component::DropShadow(shadow));
+        Box::default()
+    }
+    /// Appends inset chart to canvas, it is scaled to fit the area.
+    pub fn inset(&mut self, inset: Inset) -> Box {
+        let mut c = inset;
+        c.area.left += self.margin.left;
+        c.area.right += self.margin.left;
+        c.area.top += self.margin.top;
+        c.area.bottom += self.margin.top;
+        let b = c.area.clone();
+        self.append(Component::Inset(c));
+        b
+    }
+    /// Appends pattern defs to canvas, it can be referenced by id.
+    pub fn pattern(&mut self, pattern: Pattern) -> Box {
+        self.append(Component::Pattern(pattern));
+        Box::default()
+    }
+    /// Appends the rendered svg to canvas, it is written as it is.
+    pub(crate) fn raw(&mut self, svg: String) {
+        self.append(Component::Raw(svg));
+    }
+    /// Renders the components of canvas to svg, they are replaced by the svg,
+    /// so the following components are rendered after them.
+    pub(crate) fn freeze(&mut self) -> Result<String> {
+        let precision = self.coordinate_precision.unwrap_or(1);
+        with_shared_components(&self.components, |components| {
+            sort_by_z_index(components);
+            let mut data = String::with_capacity(components.len() * 128);
+            with_coordinate_precision(precision, || write_components_svg(components, &mut data))?;
+            *components = vec![Component::Raw(data.clone())];
+            Ok(data)
+        })
+    }
     pub fn append(&mut self, component: Component) {
-        let mut components = self.components.borrow_mut();
-        components.push(component);
+        with_shared_components(&self.components, |components| components.push(component));
+    }
+    /// Calls the function with the components of canvas, e.g. counts or removes them.
+    pub fn with_components<T>(&self, f: impl FnOnce(&mut Vec<Component>) -> T) -> T {
+        with_shared_components(&self.components, f)
     }
     /// Generates the svg of canvas.
     pub fn svg(&self) -> Result<String> {
-        let mut data = vec![];
-        for c in self.components.borrow().iter() {
-            let value = match c {
-                Component::Line(c) => c.svg(),
-                Component::Rect(c) => c.svg(),
-                Component::Arrow(c) => c.svg(),
-                Component::Bubble(c) => c.svg(),
-                Component::Polyline(c) => c.svg(),
-                Component::Circle(c) => c.svg(),
-                Component::Polygon(c) => c.svg(),
-                Component::Text(c) => c.svg(),
-                Component::SmoothLine(c) => c.svg(),
-                Component::StraightLine(c) => c.svg(),
-                Component::SmoothLineFill(c) => c.svg(),
-                Component::StraightLineFill(c) => c.svg(),
-                Component::Grid(c) => c.svg(),
-                Component::Axis(c) => c.svg().context(ToSVGSnafu)?,
-                Component::Legend(c) => c.svg(),
-                Component::Pie(c) => c.svg(),
-            };
-            data.push(value);
-        }
-        Ok(generate_svg(
+        let data = with_coordinate_precision(self.coordinate_precision.unwrap_or(1), || {
+            with_shared_components(&self.components, |components| {
+                sort_by_z_index(components);
+                // estimate the size of svg to avoid the reallocation
+                let mut data = String::with_capacity(components.len() * 128);
+                write_components_svg(components, &mut data)?;
+                if self.font_embedding == FontEmbedding::Outline {
+                    data = convert_text_to_outline(data);
+                }
+                Ok::<String, Error>(data)
+            })
+        })?;
+        let svg = generate_svg(
             self.width,
             self.height,
             self.x,
             self.y,
-            data.join("\n"),
-        ))
+            data,
+            self.aria_label.as_deref(),
+            self.description.as_deref(),
+        );
+        let svg = if self.font_embedding == FontEmbedding::Base64 {
+            embed_fonts(svg)
+        } else {
+            svg
+        };
+        let svg = if self.style_hoisting {
+            hoist_style(svg)
+        } else {
+            svg
+        };
+        let svg = if self.responsive {
+            make_responsive(svg)
+        } else {
+            svg
+        };
+        Ok(format_svg(svg, &self.svg_format))
     }
 }
 
+// Reorders the groups of z index by it, they take the places of each other
+// and the other components keep their positions.
+fn sort_by_z_index(components: &mut Vec<Component>) {
+    let get_z_index = |c: &Component| match c {
+        Component::Group(g) => g.z_index,
+        _ => None,
+    };
+    let positions: Vec<usize> = components
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| get_z_index(c).is_some())
+        .map(|(index, _)| index)
+        .collect();
+    if positions.len() < 2 {
+        return;
+    }
+    let mut sorted_positions = positions.clone();
+    sorted_positions.sort_by_key(|index| get_z_index(&components[*index]));
+    let mut items: Vec<Option<Component>> =
+        std::mem::take(components).into_iter().map(Some).collect();
+    let mut sorted_positions = sorted_positions.into_iter();
+    for index in 0..items.len() {
+        let mut source = index;
+        if positions.binary_search(&index).is_ok() {
+            source = sorted_positions.next().unwrap_or(index);
+        }
+        if let Some(c) = items[source].take() {
+            components.push(c);
+        }
+    }
+}
+
+// Writes the svg of component to the output, the group is rendered with its components.
+fn write_component_svg(c: &Component, out: &mut String) -> Result<()> {
+    match c {
+        Component::Line(c) => c.write_svg(out),
+        Component::Rect(c) => c.write_svg(out),
+        Component::Arrow(c) => c.write_svg(out),
+        Component::Bubble(c) => c.write_svg(out),
+        Component::Polyline(c) => c.write_svg(out),
+        Component::Circle(c) => c.write_svg(out),
+        Component::Ellipse(c) => c.write_svg(out),
+        Component::PathSymbol(c) => c.write_svg(out),
+        Component::Path(c) => c.write_svg(out),
+        Component::Polygon(c) => c.write_svg(out),
+        Component::Text(c) => c.write_svg(out),
+        Component::SmoothLine(c) => c.write_svg(out),
+        Component::StraightLine(c) => c.write_svg(out),
+        Component::SmoothLineFill(c) => c.write_svg(out),
+        Component::StraightLineFill(c) => c.write_svg(out),
+        Component::Grid(c) => c.write_svg(out),
+        Component::Axis(c) => c.write_svg(out).context(ToSVGSnafu)?,
+        Component::Legend(c) => c.write_svg(out),
+        Component::Pie(c) => c.write_svg(out),
+        Component::Arc(c) => c.write_svg(out),
+        Component::Sector(c) => c.write_svg(out),
+        Component::Gradient(c) => c.write_svg(out),
+        Component::RadialGradient(c) => c.write_svg(out),
+        Component::DropShadow(c) => c.write_svg(out),
+        Component::Pattern(c) => c.write_svg(out),
+        Component::ClipPath(c) => c.write_svg(out),
+        Component::Inset(c) => c.write_svg(out),
+        Component::Raw(c) => out.push_str(c),
+        Component::Group(c) => c.write_svg(out, |out| {
+            with_shared_components(&c.components, |components| {
+                write_components_svg(components, out)
+            })
+        })?,
+    };
+    Ok(())
+}
+
+// The min count of components to render in parallel,
+// the cost of threads is more than the rendering for few components.
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_COMPONENTS: usize = 64;
+
+// Writes the svg of components to the output, they are separated by line break.
+fn write_components_svg(components: &[Component], out: &mut String) -> Result<()> {
+    #[cfg(feature = "parallel")]
+    if components.len() >= PARALLEL_MIN_COMPONENTS {
+        return write_components_svg_parallel(components, out);
+    }
+    for (index, c) in components.iter().enumerate() {
+        if index != 0 {
+            out.push('\n');
+        }
+        write_component_svg(c, out)?;
+    }
+    Ok(())
+}
+
+// Renders the components in parallel(e.g. the groups of series),
+// the results are joined in order.
+#[cfg(feature = "parallel")]
+fn write_components_svg_parallel(components: &[Component], out: &mut String) -> Result<()> {
+    use super::locale::{get_current_locale, LocaleGuard};
+    use rayon::prelude::*;
+    // the precision and locale are thread local, they should be passed to the worker thread
+    let precision = get_coordinate_precision();
+    let locale = get_current_locale();
+    let svg_list = components
+        .par_iter()
+        .map(|c| {
+            let _locale = LocaleGuard::with_locale(locale.clone());
+            // each worker writes to its own buffer, they are joined in order
+            with_coordinate_precision(precision, || {
+                let mut value = String::new();
+                write_component_svg(c, &mut value).map(|_| value)
+            })
+        })
+        .collect::<Result<Vec<String>>>()?;
+    out.push_str(&svg_list.join("\n"));
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::Canvas;
+    use crate::charts::locale::LocaleGuard;
     use crate::{
-        convert_to_points, Align, Axis, Grid, Legend, LegendCategory, Line, Polyline, Rect,
-        SmoothLine, SmoothLineFill, StraightLine, StraightLineFill, Symbol, Text,
-        DEFAULT_FONT_FAMILY,
+        convert_to_points, Align, Axis, Box, Circle, FontEmbedding, Grid, Group, Legend,
+        LegendCategory, Line, Path, Polyline, Rect, SVGFormat, SmoothLine, SmoothLineFill,
+        StraightLine, StraightLineFill, Symbol, Text, DEFAULT_FONT_FAMILY, LOCALE_DE_DE,
     };
     use pretty_assertions::assert_eq;
     #[test]
@@ -399,6 +803,12 @@ mod tests {
         assert_eq!("(5,10,15,20)", c.margin.to_string());
         assert_eq!(380.0, c.width());
         assert_eq!(270.0, c.height());
+        assert_eq!("(5,10,385,280)", c.area().to_string());
+
+        let c = c.child_at(20.0, 30.0, 100.0, 50.0);
+        assert_eq!(100.0, c.width());
+        assert_eq!(50.0, c.height());
+        assert_eq!("(25,40,125,90)", c.area().to_string());
     }
     #[test]
     fn canvas_line() {
@@ -431,6 +841,7 @@ mod tests {
             height: 30.0,
             rx: Some(3.0),
             ry: Some(5.0),
+            ..Default::default()
         });
         assert_eq!("(10,10,110,40)", b.to_string());
         assert_eq!(
@@ -452,6 +863,265 @@ mod tests {
         assert_eq!(
             r###"<svg width="400" height="300" viewBox="0 0 400 300" xmlns="http://www.w3.org/2000/svg">
 <polyline fill="none" stroke-width="1" points="1,5 30,60 50,10 70,40" stroke="#000000"/>
+</svg>"###,
+            c.svg().unwrap()
+        );
+    }
+    #[test]
+    fn canvas_path() {
+        let c = Canvas::new(400.0, 300.0);
+        let b = c
+            .child(Box {
+                left: 10.0,
+                top: 20.0,
+                ..Default::default()
+            })
+            .path(Path {
+                d: "M0 0 L50 50".to_string(),
+                stroke_color: Some((0, 0, 0).into()),
+                ..Default::default()
+            });
+        assert_eq!("(10,20,400,300)", b.to_string());
+        assert_eq!(
+            r###"<svg width="400" height="300" viewBox="0 0 400 300" xmlns="http://www.w3.org/2000/svg">
+<path d="M0 0 L50 50" transform="translate(10 20)" stroke="#000000" stroke-width="1" fill="none"/>
+</svg>"###,
+            c.svg().unwrap()
+        );
+    }
+    #[test]
+    fn canvas_clip() {
+        let mut c = Canvas::new(400.0, 300.0);
+        let mut clip = c.clip(Box {
+            left: 10.0,
+            top: 10.0,
+            right: 10.0,
+            bottom: 10.0,
+        });
+        clip.circle(Circle {
+            cx: 0.0,
+            cy: 0.0,
+            r: 5.0,
+            ..Default::default()
+        });
+        assert_eq!(
+            r###"<svg width="400" height="300" viewBox="0 0 400 300" xmlns="http://www.w3.org/2000/svg">
+<defs>
+<clipPath id="clip-10-10-380-280">
+<rect x="10" y="10" width="380" height="280"/>
+</clipPath>
+</defs>
+<g clip-path="url(#clip-10-10-380-280)">
+<circle cx="10" cy="10" r="5" stroke-width="1" fill="none"/>
+</g>
+</svg>"###,
+            c.svg().unwrap()
+        );
+    }
+    #[test]
+    fn canvas_group() {
+        let mut c = Canvas::new(400.0, 300.0);
+        let mut g = c.group(Group {
+            id: Some("widget".to_string()),
+            class: Some("badge".to_string()),
+            transform: Some("rotate(45 50 50)".to_string()),
+            opacity: Some(0.5),
+            ..Default::default()
+        });
+        g.rect(Rect {
+            left: 40.0,
+            top: 40.0,
+            width: 20.0,
+            height: 20.0,
+            ..Default::default()
+        });
+        g.circle(Circle {
+            cx: 50.0,
+            cy: 50.0,
+            ..Default::default()
+        });
+        assert_eq!(
+            r###"<svg width="400" height="300" viewBox="0 0 400 300" xmlns="http://www.w3.org/2000/svg">
+<g id="widget" class="badge" transform="rotate(45 50 50)" opacity="0.5">
+<rect x="40" y="40" width="20" height="20"/>
+<circle cx="50" cy="50" r="3" stroke-width="1" fill="none"/>
+</g>
+</svg>"###,
+            c.svg().unwrap()
+        );
+    }
+    #[test]
+    fn canvas_svg_format() {
+        let mut c = Canvas::new(400.0, 300.0);
+        c.svg_format = SVGFormat::Minified;
+        let mut g = c.group(Group::default());
+        g.text(Text {
+            text: "Hello World!".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(
+            r###"<svg width="400" height="300" viewBox="0 0 400 300" xmlns="http://www.w3.org/2000/svg"><g><text>Hello World!</text></g></svg>"###,
+            c.svg().unwrap()
+        );
+
+        let mut minified = Canvas::new(400.0, 300.0);
+        minified.svg_format = SVGFormat::Minified;
+        minified.coordinate_precision = Some(2);
+        minified.rect(Rect {
+            fill: Some((0, 0, 0, 128).into()),
+            left: 0.0,
+            top: 10.26,
+            width: 20.5,
+            height: 0.44,
+            ..Default::default()
+        });
+        assert_eq!(
+            r###"<svg width="400" height="300" viewBox="0 0 400 300" xmlns="http://www.w3.org/2000/svg"><rect y="10.26" width="20.5" height=".44" fill="#000000" fill-opacity=".5"/></svg>"###,
+            minified.svg().unwrap()
+        );
+
+        c.svg_format = SVGFormat::Pretty;
+        assert_eq!(
+            r###"<svg width="400" height="300" viewBox="0 0 400 300" xmlns="http://www.w3.org/2000/svg">
+  <g>
+    <text x="0" y="0">
+      Hello World!
+    </text>
+  </g>
+</svg>"###,
+            c.svg().unwrap()
+        );
+        c.svg_format = SVGFormat::Normalized;
+        let mut g = c.group(Group::default());
+        g.rect(Rect {
+            fill: Some((0, 0, 0).into()),
+            left: -0.01,
+            width: 10.0,
+            height: 10.0,
+            ..Default::default()
+        });
+        g.rect(Rect {
+            fill: Some((0, 0, 0).into()),
+            width: 10.0,
+            height: 10.0,
+            ..Default::default()
+        });
+        assert_eq!(
+            r###"<svg height="300" viewBox="0 0 400 300" width="400" xmlns="http://www.w3.org/2000/svg">
+<g>
+<text x="0" y="0">
+Hello World!
+</text>
+</g>
+<g>
+<rect fill="#000000" height="10" width="10" x="0" y="0"/>
+<rect fill="#000000" height="10" width="10" x="0" y="0"/>
+</g>
+</svg>"###,
+            c.svg().unwrap()
+        );
+    }
+    #[test]
+    fn canvas_many_components() {
+        let mut c = Canvas::new(400.0, 300.0);
+        let mut g = c.group(Group::default());
+        for i in 0..100 {
+            g.circle(Circle {
+                cx: i as f32 + 0.25,
+                cy: 10.0,
+                r: 3.0,
+                ..Default::default()
+            });
+        }
+        // the circles are appended to the group
+        assert_eq!(1, c.with_components(|components| components.len()));
+        assert_eq!(100, g.with_components(|components| components.len()));
+        c.coordinate_precision = Some(2);
+        let svg = c.svg().unwrap();
+        let lines: Vec<&str> = svg.lines().collect();
+        assert_eq!(104, lines.len());
+        // the order of components is kept, and the precision is applied
+        for i in 0..100 {
+            assert_eq!(
+                format!(
+                    r#"<circle cx="{}.25" cy="10" r="3" stroke-width="1" fill="none"/>"#,
+                    i
+                ),
+                lines[i + 2]
+            );
+        }
+    }
+    #[test]
+    fn canvas_many_components_locale() {
+        let _locale = LocaleGuard::new(Some(LOCALE_DE_DE));
+        let mut c = Canvas::new(400.0, 300.0);
+        for i in 0..70 {
+            c.axis(Axis {
+                data: vec!["3".to_string()],
+                formatter: Some("{month}".to_string()),
+                top: i as f32,
+                width: 100.0,
+                height: 30.0,
+                ..Default::default()
+            });
+        }
+        // the locale is passed to the workers of parallel rendering
+        let svg = c.svg().unwrap();
+        assert_eq!(70, svg.matches("März").count());
+    }
+    #[test]
+    fn canvas_font_embedding() {
+        let mut c = Canvas::new(400.0, 300.0);
+        c.text(Text {
+            text: "Hi".to_string(),
+            font_family: Some(DEFAULT_FONT_FAMILY.to_string()),
+            font_size: Some(14.0),
+            font_color: Some((0, 0, 0).into()),
+            ..Default::default()
+        });
+        c.font_embedding = FontEmbedding::Base64;
+        let svg = c.svg().unwrap();
+        let lines: Vec<&str> = svg.lines().collect();
+        assert_eq!("<style>", lines[1]);
+        assert!(lines[2]
+            .starts_with(r#"@font-face{font-family:"Roboto";src:url(data:font/ttf;base64,AAEAAA"#));
+        assert!(svg.contains(r#"font-family="Roboto""#));
+
+        c.font_embedding = FontEmbedding::Outline;
+        let svg = c.svg().unwrap();
+        assert!(!svg.contains("<text"));
+        assert!(!svg.contains("@font-face"));
+        assert!(svg.contains(r##"<path fill="#000000" d="M"##));
+    }
+    #[test]
+    fn canvas_style_hoisting() {
+        let mut c = Canvas::new(400.0, 300.0);
+        c.style_hoisting = true;
+        for x in [10.0, 50.0] {
+            c.circle(Circle {
+                stroke_color: Some((0, 0, 0).into()),
+                fill: Some((255, 255, 255).into()),
+                cx: x,
+                cy: 10.0,
+                r: 3.0,
+                class: Some("point".to_string()),
+                ..Default::default()
+            });
+        }
+        c.circle(Circle {
+            cx: 90.0,
+            cy: 10.0,
+            r: 3.0,
+            ..Default::default()
+        });
+        assert_eq!(
+            r###"<svg width="400" height="300" viewBox="0 0 400 300" xmlns="http://www.w3.org/2000/svg">
+<style>
+.s0{stroke-width:1px;stroke:#000000;fill:#FFFFFF}
+</style>
+<circle class="point s0" cx="10" cy="10" r="3"/>
+<circle class="point s0" cx="50" cy="10" r="3"/>
+<circle cx="90" cy="10" r="3" stroke-width="1" fill="none"/>
 </svg>"###,
             c.svg().unwrap()
         );
@@ -554,6 +1224,7 @@ Hello World!
                 (90.0, 40.0),
             ]),
             bottom: 150.0,
+            ..Default::default()
         });
         assert_eq!("(10,10,90,150)", b.to_string());
         assert_eq!(
@@ -0,0 +1,88 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::canvas;
+use super::params::get_string_from_value;
+use super::{
+    BarChart, CandlestickChart, GaugeChart, HeatmapChart, HorizontalBarChart, LineChart, PieChart,
+    ProgressChart, RadarChart, ScatterChart, TableChart,
+};
+
+/// The chart of any kind, it is used to store the different charts
+/// in one collection and render them in the same way.
+#[derive(Clone, Debug)]
+pub enum ChartType {
+    Bar(BarChart),
+    Candlestick(CandlestickChart),
+    Gauge(GaugeChart),
+    Heatmap(HeatmapChart),
+    HorizontalBar(HorizontalBarChart),
+    Line(LineChart),
+    Pie(PieChart),
+    Progress(ProgressChart),
+    Radar(RadarChart),
+    Scatter(ScatterChart),
+    Table(TableChart),
+}
+
+impl ChartType {
+    /// Creates a chart from json, the kind of chart is the `type` field
+    /// and it is bar chart if not set.
+    pub fn from_json(data: &str) -> canvas::Result<ChartType> {
+        let value: serde_json::Value = serde_json::from_str(data)?;
+        let chart_type = get_string_from_value(&value, "type").unwrap_or_default();
+        let chart = match chart_type.as_str() {
+            "" | "bar" => ChartType::Bar(BarChart::from_json(data)?),
+            "candlestick" => ChartType::Candlestick(CandlestickChart::from_json(data)?),
+            "gauge" => ChartType::Gauge(GaugeChart::from_json(data)?),
+            "heatmap" => ChartType::Heatmap(HeatmapChart::from_json(data)?),
+            "horizontal_bar" => ChartType::HorizontalBar(HorizontalBarChart::from_json(data)?),
+            "line" => ChartType::Line(LineChart::from_json(data)?),
+            "pie" => ChartType::Pie(PieChart::from_json(data)?),
+            "progress" => ChartType::Progress(ProgressChart::from_json(data)?),
+            "radar" => ChartType::Radar(RadarChart::from_json(data)?),
+            "scatter" => ChartType::Scatter(ScatterChart::from_json(data)?),
+            "table" => ChartType::Table(TableChart::from_json(data)?),
+            _ => {
+                return Err(canvas::Error::Params {
+                    message: format!("chart type {chart_type} is not supported"),
+                })
+            }
+        };
+        Ok(chart)
+    }
+    /// Gets the name of chart type, it is the same as the `type` field of json.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChartType::Bar(_) => "bar",
+            ChartType::Candlestick(_) => "candlestick",
+            ChartType::Gauge(_) => "gauge",
+            ChartType::Heatmap(_) => "heatmap",
+            ChartType::HorizontalBar(_) => "horizontal_bar",
+            ChartType::Line(_) => "line",
+            ChartType::Pie(_) => "pie",
+            ChartType::Progress(_) => "progress",
+            ChartType::Radar(_) => "radar",
+            ChartType::Scatter(_) => "scatter",
+            ChartType::Table(_) => "table",
+        }
+    }
+    /// Converts the chart to svg.
+    pub fn svg(&self) -> canvas::Result<String> {
+        match self {
+            ChartType::Bar(c) => c.svg(),
+            ChartType::Candlestick(c) => c.svg(),
+            ChartType::Gauge(c) => c.svg(),
+            ChartType::Heatmap(c) => c.svg(),
+            ChartType::HorizontalBar(c) => c.svg(),
+            ChartType::Line(c) => c.svg(),
+            ChartType::Pie(c) => c.svg(),
+            ChartType::Progress(c) => c.svg(),
+            ChartType::Radar(c) => c.svg(),
+            ChartType::Scatter(c) => c.svg(),
+            // the height of table is recounted, so it is rendered by the clone
+            ChartType::Table(c) => c.clone().svg(),
+        }
+    }
+}
@@ -3,9 +3,11 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::str::FromStr;
 use substring::Substring;
 
-#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Hash, Debug, Default)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -57,6 +59,94 @@ impl Color {
         c.a = a;
         c
     }
+    /// Converts color to hsl, the hue is in degrees, saturation and lightness are in [0, 1].
+    pub fn hsl(&self) -> (f32, f32, f32) {
+        let r = self.r as f32 / 255.0;
+        let g = self.g as f32 / 255.0;
+        let b = self.b as f32 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return (0.0, 0.0, l);
+        }
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            ((g - b) / d).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+    /// Creates color from hsl and alpha.
+    pub fn from_hsl(h: f32, s: f32, l: f32, a: u8) -> Color {
+        let (r, g, b) = hsl_to_rgb(h, s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        Color { r, g, b, a }
+    }
+    /// Increases the lightness of color, the amount is in [0, 1].
+    pub fn lighten(&self, amount: f32) -> Color {
+        let (h, s, l) = self.hsl();
+        Color::from_hsl(h, s, l + amount, self.a)
+    }
+    /// Decreases the lightness of color, the amount is in [0, 1].
+    pub fn darken(&self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+    /// Increases the saturation of color, negative amount desaturates it.
+    pub fn saturate(&self, amount: f32) -> Color {
+        let (h, s, l) = self.hsl();
+        Color::from_hsl(h, s + amount, l, self.a)
+    }
+    /// Rotates the hue of color by degrees.
+    pub fn rotate_hue(&self, degrees: f32) -> Color {
+        let (h, s, l) = self.hsl();
+        Color::from_hsl(h + degrees, s, l, self.a)
+    }
+    /// Mixes two colors, t is the weight of other color in [0, 1].
+    pub fn mix(&self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Color {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+            a: mix(self.a, other.a),
+        }
+    }
+    /// Gets the relative luminance of color, it is in [0, 1].
+    pub fn luminance(&self) -> f32 {
+        let linear = |value: u8| {
+            let value = value as f32 / 255.0;
+            if value <= 0.03928 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+    /// Gets the contrast ratio of two colors, it is in [1, 21].
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        let a = self.luminance();
+        let b = other.luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+    /// Returns the color of candidates which has the most contrast with the color.
+    pub fn contrast_color_of(&self, candidates: &[Color]) -> Option<Color> {
+        candidates
+            .iter()
+            .max_by(|a, b| self.contrast_ratio(a).total_cmp(&self.contrast_ratio(b)))
+            .copied()
+    }
+    /// Returns black or white color which has more contrast with the color,
+    /// it is used for the text on the fill.
+    pub fn contrast_color(&self) -> Color {
+        self.contrast_color_of(&[Color::black(), Color::white()])
+            .unwrap_or_else(Color::black)
+    }
     /// Returns ture if the color is light.
     pub fn is_light(&self) -> bool {
         let mut r = self.r as f64;
@@ -90,39 +180,418 @@ impl From<(u8, u8, u8, u8)> for Color {
     }
 }
 
-fn parse_hex(hex: &str) -> u8 {
-    u8::from_str_radix(hex, 16).unwrap_or_default()
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Error parse color: {value}"))]
+    ParseColor { value: String },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+// css named colors, sorted by name
+static NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
+
+fn parse_hex(hex: &str) -> Option<u8> {
+    u8::from_str_radix(hex, 16).ok()
+}
+
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    if !hex.is_ascii() {
+        return None;
+    }
+    // #RGB and #RGBA are the short form of #RRGGBB and #RRGGBBAA
+    let hex = if hex.len() <= 4 {
+        hex.chars().map(|c| c.to_string().repeat(2)).collect()
+    } else {
+        hex.to_string()
+    };
+    if hex.len() != 6 && hex.len() != 8 {
+        return None;
+    }
+    let a = if hex.len() == 8 {
+        parse_hex(hex.substring(6, 8))?
+    } else {
+        255
+    };
+    Some(Color {
+        r: parse_hex(hex.substring(0, 2))?,
+        g: parse_hex(hex.substring(2, 4))?,
+        b: parse_hex(hex.substring(4, 6))?,
+        a,
+    })
+}
+
+// parses the value of color function, e.g. 255, 50% or 0.5
+fn parse_number(value: &str, max: f32) -> Option<f32> {
+    let value = if let Some(percent) = value.strip_suffix('%') {
+        percent.parse::<f32>().ok()? / 100.0 * max
+    } else {
+        value.parse::<f32>().ok()?
+    };
+    value.is_finite().then_some(value.clamp(0.0, max))
+}
+
+// splits the arguments of color function, e.g. "255, 0, 0" or "255 0 0 / 50%"
+fn parse_function_args(value: &str) -> Option<(Vec<&str>, u8)> {
+    let (values, alpha) = if let Some((values, alpha)) = value.split_once('/') {
+        (values, Some(alpha.trim()))
+    } else {
+        (value, None)
+    };
+    let mut args: Vec<&str> = values
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|item| !item.is_empty())
+        .collect();
+    let alpha = match alpha {
+        Some(alpha) if args.len() == 3 => alpha,
+        None if args.len() == 4 => args.pop()?,
+        None if args.len() == 3 => "1",
+        _ => return None,
+    };
+    let alpha = (parse_number(alpha, 1.0)? * 255.0).round() as u8;
+    Some((args, alpha))
+}
+
+fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    let to_u8 = |value: f32| ((value + m) * 255.0).round() as u8;
+    (to_u8(r), to_u8(g), to_u8(b))
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim().to_lowercase();
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex_color(hex);
+    }
+    if let Some((name, args)) = value.split_once('(') {
+        let (args, a) = parse_function_args(args.strip_suffix(')')?)?;
+        return match name.trim() {
+            "rgb" | "rgba" => Some(Color {
+                r: parse_number(args[0], 255.0)?.round() as u8,
+                g: parse_number(args[1], 255.0)?.round() as u8,
+                b: parse_number(args[2], 255.0)?.round() as u8,
+                a,
+            }),
+            "hsl" | "hsla" => {
+                let h = args[0].strip_suffix("deg").unwrap_or(args[0]);
+                let h = h.parse::<f32>().ok().filter(|h| h.is_finite())?;
+                let s = parse_number(args[1].strip_suffix('%')?, 100.0)? / 100.0;
+                let l = parse_number(args[2].strip_suffix('%')?, 100.0)? / 100.0;
+                let (r, g, b) = hsl_to_rgb(h, s, l);
+                Some(Color { r, g, b, a })
+            }
+            _ => None,
+        };
+    }
+    if value == "transparent" || value == "none" {
+        return Some(Color::transparent());
+    }
+    let index = NAMED_COLORS
+        .binary_search_by(|(name, _)| name.cmp(&value.as_str()))
+        .ok()?;
+    let rgb = NAMED_COLORS[index].1;
+    Some(((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8).into())
+}
+
+impl FromStr for Color {
+    type Err = Error;
+    /// Parses color from #RGB, #RGBA, #RRGGBB, #RRGGBBAA,
+    /// rgb(), rgba(), hsl(), hsla() and the css named colors.
+    fn from_str(value: &str) -> Result<Self> {
+        parse_color(value).ok_or_else(|| Error::ParseColor {
+            value: value.to_string(),
+        })
+    }
 }
 
 impl From<&str> for Color {
+    /// Converts str to color, the invalid color is converted to transparent.
     fn from(value: &str) -> Self {
-        let mut c = Color::default();
-        if !value.starts_with('#') {
-            return c;
+        value.parse().unwrap_or_default()
+    }
+}
+
+/// Continuous color scale, it interpolates between the color stops.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct ColorScale {
+    pub stops: Vec<Color>,
+    // the value domain of scale, it is [0, 1] if not set
+    pub domain: Option<(f32, f32)>,
+}
+
+fn new_color_scale(stops: &[&str]) -> ColorScale {
+    ColorScale::new(stops.iter().map(|item| Color::from(*item)).collect())
+}
+
+impl ColorScale {
+    /// Creates a color scale of the stops.
+    pub fn new(stops: Vec<Color>) -> Self {
+        ColorScale {
+            stops,
+            domain: None,
+        }
+    }
+    /// Sets the value domain of color scale.
+    pub fn with_domain(mut self, min: f32, max: f32) -> Self {
+        self.domain = Some((min, max));
+        self
+    }
+    /// Returns the viridis color scale.
+    pub fn viridis() -> Self {
+        new_color_scale(&[
+            "#440154", "#472D7B", "#3B528B", "#2C728E", "#21918C", "#28AE80", "#5EC962", "#ADDC30",
+            "#FDE725",
+        ])
+    }
+    /// Returns the plasma color scale.
+    pub fn plasma() -> Self {
+        new_color_scale(&[
+            "#0D0887", "#4C02A1", "#7E03A8", "#A92395", "#CC4778", "#E56B5D", "#F89441", "#FDC328",
+            "#F0F921",
+        ])
+    }
+    /// Returns the turbo color scale.
+    pub fn turbo() -> Self {
+        new_color_scale(&[
+            "#30123B", "#4662D7", "#36AAF9", "#1AE4B6", "#72FE5E", "#C8EF34", "#FABA39", "#F66B19",
+            "#CB2A04", "#7A0403",
+        ])
+    }
+    /// Returns the red-yellow-green diverging color scale.
+    pub fn rd_yl_gn() -> Self {
+        new_color_scale(&[
+            "#A50026", "#D73027", "#F46D43", "#FDAE61", "#FEE08B", "#FFFFBF", "#D9EF8B", "#A6D96A",
+            "#66BD63", "#1A9850", "#006837",
+        ])
+    }
+    /// Gets the color at position t of scale, t is clamped to [0, 1].
+    pub fn color_at(&self, t: f32) -> Color {
+        if self.stops.is_empty() {
+            return Color::default();
+        }
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let count = self.stops.len() - 1;
+        let position = t * count as f32;
+        let index = (position.floor() as usize).min(count);
+        if index == count {
+            return self.stops[count];
         }
-        let hex = value.substring(1, value.len());
-        if hex.len() == 3 {
-            c.r = parse_hex(&hex.substring(0, 1).repeat(2));
-            c.g = parse_hex(&hex.substring(1, 2).repeat(2));
-            c.b = parse_hex(&hex.substring(2, 3).repeat(2));
+        self.stops[index].mix(self.stops[index + 1], position - index as f32)
+    }
+    /// Gets the color of value, the value is mapped by the domain of scale.
+    pub fn get_color(&self, value: f32) -> Color {
+        let t = if let Some((min, max)) = self.domain {
+            if max == min {
+                0.0
+            } else {
+                (value - min) / (max - min)
+            }
         } else {
-            c.r = parse_hex(hex.substring(0, 2));
-            c.g = parse_hex(hex.substring(2, 4));
-            c.b = parse_hex(hex.substring(4, 6));
+            value
+        };
+        self.color_at(t)
+    }
+    /// Samples count colors of scale evenly.
+    pub fn colors(&self, count: usize) -> Vec<Color> {
+        if count <= 1 {
+            return (0..count).map(|_| self.color_at(0.0)).collect();
         }
-        c.a = 255;
-        c
+        (0..count)
+            .map(|index| self.color_at(index as f32 / (count - 1) as f32))
+            .collect()
     }
 }
 
+static GOLDEN_ANGLE: f32 = 137.50777;
+static PALETTE_SATURATION: f32 = 0.6;
+static PALETTE_LIGHTNESS: f32 = 0.55;
+
+fn get_palette_color(start_hue: f32, index: usize) -> Color {
+    let hue = start_hue + (index + 1) as f32 * GOLDEN_ANGLE;
+    Color::from_hsl(hue, PALETTE_SATURATION, PALETTE_LIGHTNESS, 255)
+}
+
+/// Generates count visually distinct colors,
+/// the hue is stepped by golden angle with fixed saturation and lightness.
+pub fn generate_palette(count: usize) -> Vec<Color> {
+    (0..count)
+        .map(|index| get_palette_color(0.0, index))
+        .collect()
+}
+
+/// Gets the color of series, the color is generated
+/// if the index exceeds the count of colors.
 pub(crate) fn get_color(colors: &[Color], index: usize) -> Color {
-    let i = index % colors.len();
-    *colors.get(i).unwrap_or_else(|| &colors[0])
+    if let Some(color) = colors.get(index) {
+        return *color;
+    }
+    let start_hue = colors.last().map(|item| item.hsl().0).unwrap_or_default();
+    get_palette_color(start_hue, index - colors.len())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Color;
+    use super::{generate_palette, get_color, Color, ColorScale};
     use pretty_assertions::assert_eq;
     #[test]
     fn color_hex() {
@@ -176,4 +645,101 @@ mod tests {
         c = c.with_alpha(51);
         assert_eq!("rgba(255,255,255,0.2)", c.rgba());
     }
+
+    #[test]
+    fn color_parse() {
+        let parse = |value: &str| value.parse::<Color>().unwrap().rgba();
+        assert_eq!("rgba(255,0,0,1.0)", parse("#F00"));
+        assert_eq!("rgba(255,0,0,0.2)", parse("#F003"));
+        assert_eq!("rgba(84,112,198,1.0)", parse("#5470c6"));
+        assert_eq!("rgba(84,112,198,0.5)", parse("#5470C680"));
+        assert_eq!("rgba(84,112,198,1.0)", parse("rgb(84, 112, 198)"));
+        assert_eq!("rgba(84,112,198,0.5)", parse("rgba(84,112,198,0.5)"));
+        assert_eq!("rgba(255,128,0,0.5)", parse("rgb(100% 50% 0 / 50%)"));
+        assert_eq!("rgba(255,0,0,1.0)", parse("hsl(0, 100%, 50%)"));
+        assert_eq!("rgba(0,128,0,0.8)", parse("hsla(120deg, 100%, 25%, 0.8)"));
+        assert_eq!("rgba(102,51,153,1.0)", parse("RebeccaPurple"));
+        assert_eq!("rgba(0,0,0,0.0)", parse("transparent"));
+
+        for value in ["", "#12", "#GGGGGG", "rgb(1,2)", "hsl(0,1,2)", "unknown"] {
+            assert_eq!(
+                format!("Error parse color: {value}"),
+                value.parse::<Color>().unwrap_err().to_string()
+            );
+        }
+        assert_eq!(Color::default(), Color::from("unknown"));
+    }
+
+    #[test]
+    fn color_manipulation() {
+        let c = Color::from("#5470C6");
+        let (h, s, l) = c.hsl();
+        assert_eq!("225,0.50,0.55", format!("{h:.0},{s:.2},{l:.2}"));
+        assert_eq!("#5470C6", Color::from_hsl(h, s, l, 255).hex());
+
+        assert_eq!("#A1B0E0", c.lighten(0.2).hex());
+        assert_eq!("#2D4387", c.darken(0.2).hex());
+        assert_eq!("#FFFFFF", c.lighten(1.0).hex());
+        assert_eq!("#3D64DD", c.saturate(0.2).hex());
+        assert_eq!("#8D8D8D", c.saturate(-1.0).hex());
+        assert_eq!("#C6AA54", c.rotate_hue(180.0).hex());
+        assert_eq!("#5470C6", c.rotate_hue(360.0).hex());
+        assert_eq!(Color::white(), c.contrast_color());
+        assert_eq!(Color::black(), Color::from("#FAC858").contrast_color());
+        assert_eq!(
+            "21.0",
+            format!("{:.1}", Color::white().contrast_ratio(&Color::black()))
+        );
+        assert_eq!(
+            Some(Color::from("#EEEEEE")),
+            Color::from("#C45354").contrast_color_of(&["#464646".into(), "#EEEEEE".into()])
+        );
+        assert_eq!(c.a, c.with_alpha(100).lighten(0.1).a + 155);
+
+        assert_eq!("#5470C6", c.mix(Color::white(), 0.0).hex());
+        assert_eq!("#AAB8E3", c.mix(Color::white(), 0.5).hex());
+        assert_eq!(
+            "rgba(128,128,128,0.5)",
+            Color::white().mix(Color::black().with_alpha(0), 0.5).rgba()
+        );
+    }
+
+    #[test]
+    fn color_scale() {
+        let scale = ColorScale::new(vec![Color::black(), Color::white()]);
+        assert_eq!("#000000", scale.color_at(-1.0).hex());
+        assert_eq!("#808080", scale.color_at(0.5).hex());
+        assert_eq!("#FFFFFF", scale.color_at(2.0).hex());
+
+        let scale = ColorScale::viridis().with_domain(0.0, 80.0);
+        assert_eq!("#440154", scale.get_color(0.0).hex());
+        assert_eq!("#21918C", scale.get_color(40.0).hex());
+        assert_eq!("#FDE725", scale.get_color(100.0).hex());
+        assert_eq!(
+            vec!["#A50026", "#FFFFBF", "#006837"],
+            ColorScale::rd_yl_gn()
+                .colors(3)
+                .iter()
+                .map(|item| item.hex())
+                .collect::<Vec<String>>()
+        );
+        assert_eq!(10, ColorScale::turbo().stops.len());
+        assert_eq!(Color::default(), ColorScale::default().color_at(0.5));
+    }
+
+    #[test]
+    fn color_palette() {
+        assert_eq!(
+            vec!["#47D170", "#9847D1", "#D1C047"],
+            generate_palette(3)
+                .iter()
+                .map(|item| item.hex())
+                .collect::<Vec<String>>()
+        );
+        let colors = vec![Color::from("#5470C6"), Color::from("#91CC75")];
+        assert_eq!("#91CC75", get_color(&colors, 1).hex());
+        assert_eq!("#474CD1", get_color(&colors, 2).hex());
+        assert_eq!("#D16B47", get_color(&colors, 3).hex());
+        assert_eq!("#47D170", get_color(&[], 0).hex());
+    }
 }
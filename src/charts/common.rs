@@ -2,11 +2,14 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::{Box, Color};
-use crate::Point;
+use super::{Box, Canvas, Color, DropShadow, Gradient, Pattern};
+use crate::{escape_xml, format_float, format_label_float, is_nil_value, Point, NIL_VALUE};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
 
-#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Hash, Debug, Default, Serialize, Deserialize)]
 pub enum Position {
     #[default]
     Left,
@@ -14,9 +17,10 @@ pub enum Position {
     Right,
     Bottom,
     Inside,
+    InsideTop,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Hash, Debug, Default)]
 pub enum Align {
     Left,
     #[default]
@@ -24,16 +28,188 @@ pub enum Align {
     Right,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum VerticalAlign {
+    Top,
+    #[default]
+    Middle,
+    Bottom,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum SVGFormat {
+    // one element per line
+    #[default]
+    Normal,
+    // no newline between elements, the duplicate attributes and the attributes
+    // of default value are removed, the leading zero of decimals is removed
+    Minified,
+    // the nested elements are indented
+    Pretty,
+    // one element per line and the attributes are sorted by name,
+    // it is stable for snapshot testing
+    Normalized,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum FontEmbedding {
+    // the text uses the font installed
+    #[default]
+    None,
+    // the fonts are embedded as base64 font face
+    Base64,
+    // the text is converted to path of outline
+    Outline,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum LabelOverlap {
+    // keep all labels
+    #[default]
+    None,
+    // hide the overlapping labels of lower priority
+    Hide,
+    // shift the overlapping labels up, hide them if it is still overlapping
+    Shift,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub enum Symbol {
     None,
     Circle(f32, Option<Color>),
+    // svg path drawn in the box from (-1, -1) to (1, 1)
+    Path(String),
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum Direction {
+    #[default]
+    TopToBottom,
+    BottomToTop,
+    LeftToRight,
+    RightToLeft,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum PatternCategory {
+    #[default]
+    DiagonalHatch,
+    CrossHatch,
+    Dots,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Shadow {
+    pub dx: f32,
+    pub dy: f32,
+    pub blur: f32,
+    pub color: Color,
+}
+
+impl Default for Shadow {
+    fn default() -> Self {
+        Shadow {
+            dx: 0.0,
+            dy: 2.0,
+            blur: 3.0,
+            color: (0, 0, 0, 77).into(),
+        }
+    }
+}
+
+// The float is hashed by its bits.
+impl Hash for Shadow {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for value in [self.dx, self.dy, self.blur] {
+            value.to_bits().hash(state);
+        }
+        self.color.hash(state);
+    }
+}
+
+impl Shadow {
+    /// Converts the shadow to drop shadow filter with id.
+    pub(crate) fn to_drop_shadow(&self, id: &str) -> DropShadow {
+        DropShadow {
+            id: id.to_string(),
+            dx: self.dx,
+            dy: self.dy,
+            std_deviation: self.blur,
+            color: self.color,
+        }
+    }
+}
+
+impl Symbol {
+    /// Returns the symbol with new size.
+    pub(crate) fn with_size(&self, size: f32) -> Symbol {
+        match self {
+            Symbol::Circle(_, fill) => Symbol::Circle(size, *fill),
+            _ => self.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum SymbolVisibility {
+    // show symbol on all points
+    #[default]
+    All,
+    // only show symbol on the last point
+    Last,
+    // show symbol on every nth point
+    Every(usize),
+    // show symbol on the points of indexes
+    Indexes(Vec<usize>),
+}
+
+impl SymbolVisibility {
+    /// Returns true if the symbol of the point is visible.
+    pub(crate) fn is_visible(&self, index: usize, last_index: usize) -> bool {
+        match self {
+            SymbolVisibility::All => true,
+            SymbolVisibility::Last => index == last_index,
+            SymbolVisibility::Every(n) => *n != 0 && index % n == 0,
+            SymbolVisibility::Indexes(indexes) => indexes.contains(&index),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum EndLabel {
+    // label the first value, it is placed at the left of line start
+    First,
+    // label the last value, it is placed at the right of line end
+    Last,
+    // label the first and last values
+    Both,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum TrendType {
+    Linear,
+    // polynomial fit of degree
+    Polynomial(usize),
+    // moving average of window
+    MovingAverage(usize),
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum SeriesTransform {
+    // cumulative sum of values
+    Cumsum,
+    // difference from the previous value
+    Diff,
+    // percent change from the previous value
+    PercentChange,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Hash, Debug)]
 pub enum SeriesCategory {
     Line,
     Bar,
+    // filled region between the lower and upper data, rendered with line
+    Band,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
@@ -44,6 +220,58 @@ pub enum MarkLineCategory {
     Max,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum GuideLineCategory {
+    #[default]
+    Mean,
+    Median,
+    // percentile(0-100) of values, e.g. 95 is p95
+    Percentile(f32),
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct GuideLine {
+    pub category: GuideLineCategory,
+    // color of line and label, default is the series label font color
+    pub color: Option<Color>,
+    // label prefix of value, e.g. Mean, it is the name of category if not set
+    pub label: Option<String>,
+}
+
+impl GuideLine {
+    /// Computes the statistic of values, it returns none if values are empty.
+    pub(crate) fn get_value(&self, values: &[f32]) -> Option<f32> {
+        if values.is_empty() {
+            return None;
+        }
+        let mut values = values.to_vec();
+        values.sort_by(|a, b| a.total_cmp(b));
+        let percentile = match self.category {
+            GuideLineCategory::Mean => {
+                return Some(values.iter().sum::<f32>() / values.len() as f32);
+            }
+            GuideLineCategory::Median => 50.0,
+            GuideLineCategory::Percentile(percentile) => percentile.clamp(0.0, 100.0),
+        };
+        // linear interpolation between the closest ranks
+        let rank = percentile / 100.0 * (values.len() - 1) as f32;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        Some(values[lower] + (values[upper] - values[lower]) * (rank - lower as f32))
+    }
+    /// Gets the label text of value.
+    pub(crate) fn get_label(&self, value: f32) -> String {
+        let label = self.label.clone().unwrap_or_else(|| match self.category {
+            GuideLineCategory::Mean => "Mean".to_string(),
+            GuideLineCategory::Median => "Median".to_string(),
+            GuideLineCategory::Percentile(percentile) => {
+                format!("P{}", format_float(percentile))
+            }
+        });
+        format!("{label}: {}", format_label_float(value))
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
 pub enum MarkPointCategory {
     #[default]
@@ -61,10 +289,142 @@ pub struct MarkPoint {
     pub category: MarkPointCategory,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum AnnotationPoint {
+    // pixel coordinates of chart
+    Pixel(f32, f32),
+    // data coordinates, the index of x axis and the value of y axis
+    Data(usize, f32),
+}
+
+impl Default for AnnotationPoint {
+    fn default() -> Self {
+        AnnotationPoint::Pixel(0.0, 0.0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum AnnotationShape {
+    // text box, the point is the left top of box
+    Text(String),
+    // arrow from the point to the end point
+    Arrow(AnnotationPoint),
+    // line from the point to the end point
+    Line(AnnotationPoint),
+    // circle of radius, the point is the center
+    Circle(f32),
+    // filled circle of radius, the point is the center
+    Dot(f32),
+}
+
+impl Default for AnnotationShape {
+    fn default() -> Self {
+        AnnotationShape::Text("".to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct Annotation {
+    pub shape: AnnotationShape,
+    pub point: AnnotationPoint,
+    pub color: Option<Color>,
+    pub font_size: Option<f32>,
+    // font style of text annotation, e.g. italic
+    pub font_style: Option<String>,
+    pub letter_spacing: Option<f32>,
+    // text decoration of text annotation, e.g. underline
+    pub text_decoration: Option<String>,
+    // y axis index of data coordinates
+    pub y_axis_index: usize,
+    // draw order with the series, it is drawn after the series if not set
+    pub z_index: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Hash, Debug, Default)]
+pub struct AxisPointer {
+    // index of the selected category
+    pub index: usize,
+    // color of the vertical crosshair line, default is the x axis stroke color
+    pub line_color: Option<Color>,
+    // color of the category band, default is the x axis stroke color with low alpha
+    pub band_color: Option<Color>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct Emphasis {
+    // index of the emphasized series, all series are emphasized if not set
+    pub series_index: Option<usize>,
+    // indexes of the emphasized points(e.g. bars) of series, all points are emphasized if it is empty
+    pub data_indexes: Vec<usize>,
+    // opacity of the faded elements, default is 0.2
+    pub fade_opacity: Option<f32>,
+}
+
+impl Emphasis {
+    pub(crate) fn get_fade_opacity(&self) -> f32 {
+        self.fade_opacity.unwrap_or(0.2)
+    }
+    /// Returns true if the series is not emphasized.
+    pub(crate) fn is_series_faded(&self, series_index: usize) -> bool {
+        self.series_index
+            .map(|index| index != series_index)
+            .unwrap_or_default()
+    }
+    /// Returns true if the point of emphasized series is not emphasized.
+    pub(crate) fn is_point_faded(&self, series_index: usize, data_index: usize) -> bool {
+        !self.is_series_faded(series_index)
+            && !self.data_indexes.is_empty()
+            && !self.data_indexes.contains(&data_index)
+    }
+}
+
+/// Gets the marker annotations of sparkline, the end point is marked with the color of series,
+/// the min and max points are marked with red and green.
+pub(crate) fn get_sparkline_annotations(
+    series: &Series,
+    color: Color,
+    end_point: bool,
+    min_max: bool,
+) -> Vec<Annotation> {
+    let values: Vec<(usize, f32)> = series
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| !is_nil_value(**value))
+        .map(|(index, value)| (index + series.start_index, *value))
+        .collect();
+    let new_dot = |(index, value): (usize, f32), color: Color| Annotation {
+        shape: AnnotationShape::Dot(2.5),
+        point: AnnotationPoint::Data(index, value),
+        color: Some(color),
+        y_axis_index: series.y_axis_index,
+        ..Default::default()
+    };
+    let mut annotations = vec![];
+    if min_max {
+        let min = values.iter().min_by(|a, b| a.1.total_cmp(&b.1));
+        let max = values.iter().max_by(|a, b| a.1.total_cmp(&b.1));
+        if let (Some(min), Some(max)) = (min, max) {
+            annotations.push(new_dot(*min, (238, 102, 102).into()));
+            annotations.push(new_dot(*max, (145, 204, 117).into()));
+        }
+    }
+    if end_point {
+        if let Some(last) = values.last() {
+            annotations.push(new_dot(*last, color));
+        }
+    }
+    annotations
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
 pub struct Series {
     // name of series
     pub name: String,
+    // whether the series is rendered, the hidden series is still in legend
+    pub visible: bool,
+    // text of legend, default is the name of series
+    pub legend_text: Option<String>,
     // data list of series
     pub data: Vec<f32>,
     // start index of series
@@ -79,18 +439,250 @@ pub struct Series {
     pub mark_lines: Vec<MarkLine>,
     // mark points
     pub mark_points: Vec<MarkPoint>,
-    // colors of series bar
+    // colors of series items(bar or point), the item uses the series color if its color is none
     pub colors: Option<Vec<Option<Color>>>,
     // category of series
     pub category: Option<SeriesCategory>,
     // stroke dash array for series
     pub stroke_dash_array: Option<String>,
+    // stroke width for series, it will override the series stroke width of chart
+    pub stroke_width: Option<f32>,
+    // opacity(0-1) of series line
+    pub opacity: Option<f32>,
+    // symbol of series, it will override the series symbol of chart
+    pub symbol: Option<Symbol>,
+    // size of series symbol
+    pub symbol_size: Option<f32>,
+    // which points of series show symbol
+    pub symbol_visibility: SymbolVisibility,
+    // the value which area fill is anchored at, default is the bottom of axis
+    pub area_baseline: Option<f32>,
+    // lower data list of band series
+    pub lower_data: Vec<f32>,
+    // upper data list of band series
+    pub upper_data: Vec<f32>,
+    // trend line of series, it is drawn as dashed line with its own legend
+    pub trend_line: Option<TrendType>,
+    // transform of series data, it is applied before rendering
+    pub transform: Option<SeriesTransform>,
+    // whether to connect the line across nil values
+    pub connect_nulls: bool,
+    // label formatter of series, it will override the series label formatter of chart
+    pub label_formatter: Option<String>,
+    // decimal precision of label value
+    pub label_precision: Option<usize>,
+    // position of label, default is top
+    pub label_position: Option<Position>,
+    // priority of label, the higher one is kept when labels overlap
+    pub label_priority: usize,
+    // whether to display the background bar
+    pub show_background: bool,
+    // color of the background bar
+    pub background_color: Option<Color>,
+    // linear gradient(start color, end color, direction) to fill bar or area
+    pub fill_gradient: Option<(Color, Color, Direction)>,
+    // pattern to fill bar or area, it is distinguishable in grayscale
+    pub fill_pattern: Option<PatternCategory>,
+    // color of the bar of negative value, default is the color of series
+    pub negative_color: Option<Color>,
+    // draw order of series and its mark lines, the higher one is drawn on top
+    pub z_index: i32,
+    // label only the first or last value of line series with the color of series
+    pub end_label: Option<EndLabel>,
+    // the bar series of the same stack are stacked in one bar, it is supported by bar chart
+    pub stack: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum LayoutShape {
+    // the rect of area, e.g. bar
+    #[default]
+    Rect,
+    // the circle of radius at the point, e.g. symbol of line
+    Circle(f32),
+    // the polygon of points, e.g. slice of pie
+    Polygon(Vec<Point>),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LayoutItem {
+    pub series_index: usize,
+    pub data_index: usize,
+    pub value: f32,
+    // the point of value, e.g. the top center of bar
+    pub point: Point,
+    // the area of element, e.g. the rect of bar or the box of symbol
+    pub area: Box,
+    pub shape: LayoutShape,
+}
+
+/// The layout of rendered chart, the coordinates are absolute pixels of svg.
+#[derive(Clone, Debug, Default)]
+pub struct ChartLayout {
+    // the area of series, it doesn't include the axes
+    pub plot_area: Box,
+    pub items: Vec<LayoutItem>,
+}
+
+impl ChartLayout {
+    /// Generates the html image map of items, the attributes(e.g. href) of area
+    /// are returned by the function, the item without attributes is skipped.
+    pub fn image_map<F>(&self, name: &str, attrs: F) -> String
+    where
+        F: Fn(&LayoutItem) -> Vec<(String, String)>,
+    {
+        let mut map = format!(r#"<map name="{}">"#, escape_xml(name));
+        for item in self.items.iter() {
+            let attrs = attrs(item);
+            if attrs.is_empty() {
+                continue;
+            }
+            let (shape, values) = match &item.shape {
+                LayoutShape::Rect => (
+                    "rect",
+                    vec![
+                        item.area.left,
+                        item.area.top,
+                        item.area.right,
+                        item.area.bottom,
+                    ],
+                ),
+                LayoutShape::Circle(r) => ("circle", vec![item.point.x, item.point.y, *r]),
+                LayoutShape::Polygon(points) => (
+                    "poly",
+                    points.iter().flat_map(|point| [point.x, point.y]).collect(),
+                ),
+            };
+            let coords: Vec<String> = values.into_iter().map(format_float).collect();
+            map.push_str(&format!(
+                "\n<area shape=\"{shape}\" coords=\"{}\"",
+                coords.join(",")
+            ));
+            for (key, value) in attrs {
+                map.push_str(&format!(r#" {key}="{}""#, escape_xml(&value)));
+            }
+            map.push_str("/>");
+        }
+        map.push_str("\n</map>");
+        map
+    }
+}
+
+/// The non-fatal issue of chart, the chart can be rendered but it may be unreadable.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ChartWarning {
+    // the texts overlap each other, e.g. the labels of axis
+    OverlappingLabels {
+        text: String,
+        other: String,
+    },
+    // the data of series extends beyond the categories from the start index
+    SeriesExceedsAxis {
+        name: String,
+        length: usize,
+        expected: usize,
+    },
+    // the contrast ratio between the color of element and background is too low
+    LowContrast {
+        name: String,
+        ratio: f32,
+    },
+}
+
+impl fmt::Display for ChartWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChartWarning::OverlappingLabels { text, other } => {
+                write!(f, "label {text} overlaps {other}")
+            }
+            ChartWarning::SeriesExceedsAxis {
+                name,
+                length,
+                expected,
+            } => write!(
+                f,
+                "series {name} ends at {length}, it exceeds the axis length {expected}"
+            ),
+            ChartWarning::LowContrast { name, ratio } => write!(
+                f,
+                "contrast ratio of {name} is {}, it is hard to read",
+                format_float(*ratio)
+            ),
+        }
+    }
+}
+
+/// The static layer of chart, it is rendered before the series.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct StaticLayer {
+    // hash of the options except the series data
+    pub key: u64,
+    pub svg: String,
+    // margin of the canvas of series
+    pub margin: Box,
+    pub x_axis_height: f32,
+    pub axis_width: f32,
+    pub axis_height: f32,
+    pub left_width: f32,
+    pub right_width: f32,
+}
+
+/// The cache of static layer of chart, e.g. background, title, legend, grid and axes.
+/// It is reused by the next rendering if the options except series data are not changed,
+/// so only the series are rendered.
+#[derive(Debug, Default)]
+pub struct LayerCache {
+    layer: Mutex<Option<StaticLayer>>,
+}
+
+impl LayerCache {
+    /// Creates an empty layer cache.
+    pub fn new() -> Self {
+        LayerCache::default()
+    }
+    /// Gets the cached layer of key.
+    pub(crate) fn get(&self, key: u64) -> Option<StaticLayer> {
+        self.layer
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|layer| layer.key == key)
+            .cloned()
+    }
+    /// Replaces the cached layer.
+    pub(crate) fn set(&self, layer: StaticLayer) {
+        *self.layer.lock().unwrap() = Some(layer);
+    }
+    /// Clears the cached layer, the next rendering renders all layers.
+    pub fn clear(&self) {
+        *self.layer.lock().unwrap() = None;
+    }
+}
+
+/// The text which extends beyond the canvas of chart.
+#[derive(Clone, Debug, Default)]
+pub struct TextOverflow {
+    pub text: String,
+    // the box of text, the coordinates are absolute pixels of svg
+    pub area: Box,
+    // the size beyond each side of canvas, it is 0 if not overflow
+    pub overflow: Box,
 }
 
 #[derive(Clone, PartialEq, Debug, Default)]
 pub struct SeriesLabel {
     pub point: Point,
     pub text: String,
+    // height of the element below the point, e.g. bar
+    pub height: f32,
+    pub position: Option<Position>,
+    // fill color of the element, it is used for the label inside
+    pub fill: Option<Color>,
+    pub priority: usize,
+    // id of the label element
+    pub id: Option<String>,
+    // font color of label, default is the series label font color of chart
+    pub font_color: Option<Color>,
 }
 
 impl Series {
@@ -99,9 +691,118 @@ impl Series {
             name,
             data,
             index: None,
+            visible: true,
             ..Default::default()
         }
     }
+    /// Creates a series of optional values, the none value is converted to nil value.
+    pub fn from_options(name: String, data: Vec<Option<f32>>) -> Self {
+        let data = data.iter().map(|item| item.unwrap_or(NIL_VALUE)).collect();
+        Series::new(name, data)
+    }
+    /// Gets the color of item, it is none if the item doesn't override the series color.
+    pub(crate) fn get_item_color(&self, index: usize) -> Option<Color> {
+        self.colors
+            .as_ref()
+            .and_then(|colors| colors.get(index).cloned().flatten())
+    }
+    /// Returns the data of series after transform, the nil value is kept.
+    pub fn get_data(&self) -> Vec<f32> {
+        let Some(ref transform) = self.transform else {
+            return self.data.clone();
+        };
+        let mut sum = 0.0;
+        let mut prev = None;
+        self.data
+            .iter()
+            .map(|value| {
+                let value = *value;
+                if is_nil_value(value) {
+                    return NIL_VALUE;
+                }
+                let result = match transform {
+                    SeriesTransform::Cumsum => {
+                        sum += value;
+                        sum
+                    }
+                    SeriesTransform::Diff => prev.map(|prev| value - prev).unwrap_or(NIL_VALUE),
+                    SeriesTransform::PercentChange => match prev {
+                        Some(prev) if prev != 0.0 => (value - prev) / prev * 100.0,
+                        _ => NIL_VALUE,
+                    },
+                };
+                prev = Some(value);
+                result
+            })
+            .collect()
+    }
+    /// Gets the series of categories from start to end(inclusive),
+    /// the transform is applied before slicing.
+    pub(crate) fn slice_categories(&self, start: usize, end: usize) -> Series {
+        let slice = |data: &[f32]| -> Vec<f32> {
+            data.iter()
+                .enumerate()
+                .filter(|(i, _)| {
+                    let index = i + self.start_index;
+                    index >= start && index <= end
+                })
+                .map(|(_, value)| *value)
+                .collect()
+        };
+        let colors = self.colors.as_ref().map(|colors| {
+            colors
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| {
+                    let index = i + self.start_index;
+                    index >= start && index <= end
+                })
+                .map(|(_, color)| *color)
+                .collect()
+        });
+        Series {
+            data: slice(&self.get_data()),
+            lower_data: slice(&self.lower_data),
+            upper_data: slice(&self.upper_data),
+            start_index: self.start_index.saturating_sub(start),
+            transform: None,
+            colors,
+            ..self.clone()
+        }
+    }
+    /// Appends the fill defs(pattern or gradient) of series to canvas,
+    /// returns the id of defs. The pattern is preferred to the gradient.
+    pub(crate) fn append_fill_defs(
+        &self,
+        c: &mut Canvas,
+        index: usize,
+        color: Color,
+    ) -> Option<String> {
+        if let Some(ref category) = self.fill_pattern {
+            let id = format!("series-pattern-{index}");
+            c.pattern(Pattern {
+                id: id.clone(),
+                category: category.clone(),
+                color,
+                background: Some(color.with_alpha(60)),
+                ..Default::default()
+            });
+            return Some(id);
+        }
+        let (start, end, direction) = self.fill_gradient.clone()?;
+        let id = format!("series-gradient-{index}");
+        c.gradient(Gradient {
+            id: id.clone(),
+            direction,
+            stops: vec![(0.0, start), (1.0, end)],
+        });
+        Some(id)
+    }
+    /// Gets the color of background bar, the default is light gray.
+    pub(crate) fn get_background_color(&self) -> Color {
+        self.background_color
+            .unwrap_or_else(|| (180, 180, 180, 50).into())
+    }
 }
 impl From<(&str, Vec<f32>)> for Series {
     fn from(value: (&str, Vec<f32>)) -> Self {
@@ -123,4 +824,37 @@ pub struct YAxisConfig {
     pub axis_formatter: Option<String>,
     pub axis_min: Option<f32>,
     pub axis_max: Option<f32>,
+    // the zero is always in the range of axis
+    pub axis_include_zero: bool,
+    // the range is symmetric around zero, e.g. diverging data
+    pub axis_symmetric: bool,
+    // the step of ticks is rounded to 1, 2 or 5 times power of 10
+    pub axis_nice: bool,
+    pub axis_max_significant_digits: Option<usize>,
+}
+
+// The float is hashed by its bits.
+impl Hash for YAxisConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for value in [self.axis_font_size, self.axis_name_gap] {
+            value.to_bits().hash(state);
+        }
+        for value in [self.axis_width, self.axis_min, self.axis_max] {
+            value.map(f32::to_bits).hash(state);
+        }
+        (
+            &self.axis_font_color,
+            &self.axis_font_weight,
+            &self.axis_stroke_color,
+            self.axis_split_number,
+            &self.axis_name_align,
+            &self.axis_margin,
+            &self.axis_formatter,
+            self.axis_include_zero,
+            self.axis_symmetric,
+            self.axis_nice,
+            self.axis_max_significant_digits,
+        )
+            .hash(state);
+    }
 }
@@ -4,8 +4,14 @@
 
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt;
+#[cfg(feature = "parallel")]
+use std::sync::Mutex;
 use std::vec;
+#[cfg(not(feature = "parallel"))]
+use std::{cell::RefCell, rc::Rc};
 
 use super::color::*;
 use super::common::*;
@@ -14,17 +20,47 @@ use super::measure_text_width_family;
 use super::path::*;
 use super::util::*;
 
+// Implements the svg function of components, it writes the svg to a new string.
+macro_rules! impl_svg {
+    ($($name:ident),*) => {
+        $(
+            impl $name {
+                pub fn svg(&self) -> String {
+                    let mut out = String::new();
+                    self.write_svg(&mut out);
+                    out
+                }
+            }
+        )*
+    };
+}
+
 static TAG_SVG: &str = "svg";
 static TAG_LINE: &str = "line";
 static TAG_RECT: &str = "rect";
 static TAG_POLYLINE: &str = "polyline";
 static TAG_CIRCLE: &str = "circle";
+static TAG_ELLIPSE: &str = "ellipse";
 static TAG_POLYGON: &str = "polygon";
 static TAG_TEXT: &str = "text";
 static TAG_PATH: &str = "path";
 static TAG_GROUP: &str = "g";
+static TAG_DEFS: &str = "defs";
+static TAG_LINEAR_GRADIENT: &str = "linearGradient";
+static TAG_RADIAL_GRADIENT: &str = "radialGradient";
+static TAG_STOP: &str = "stop";
+static TAG_FILTER: &str = "filter";
+static TAG_FE_DROP_SHADOW: &str = "feDropShadow";
+static TAG_PATTERN: &str = "pattern";
+static TAG_LINK: &str = "a";
+static TAG_CLIP_PATH: &str = "clipPath";
+static TAG_TITLE: &str = "title";
+static TAG_DESC: &str = "desc";
+static TAG_STYLE: &str = "style";
+static TAG_TSPAN: &str = "tspan";
 
 static ATTR_VIEW_BOX: &str = "viewBox";
+static ATTR_PRESERVE_ASPECT_RATIO: &str = "preserveAspectRatio";
 static ATTR_XMLNS: &str = "xmlns";
 static ATTR_HEIGHT: &str = "height";
 static ATTR_WIDTH: &str = "width";
@@ -35,11 +71,17 @@ static ATTR_TRANSFORM: &str = "transform";
 static ATTR_DOMINANT_BASELINE: &str = "dominant-baseline";
 static ATTR_TEXT_ANCHOR: &str = "text-anchor";
 static ATTR_ALIGNMENT_BASELINE: &str = "alignment-baseline";
+static ATTR_FONT_STYLE: &str = "font-style";
+static ATTR_LETTER_SPACING: &str = "letter-spacing";
+static ATTR_TEXT_DECORATION: &str = "text-decoration";
+static ATTR_DIRECTION: &str = "direction";
 static ATTR_STROKE_OPACITY: &str = "stroke-opacity";
 static ATTR_FILL_OPACITY: &str = "fill-opacity";
 static ATTR_STROKE_WIDTH: &str = "stroke-width";
 static ATTR_STROKE: &str = "stroke";
 static ATTR_STROKE_DASH_ARRAY: &str = "stroke-dasharray";
+static ATTR_STROKE_LINECAP: &str = "stroke-linecap";
+static ATTR_CLIP_PATH: &str = "clip-path";
 static ATTR_X: &str = "x";
 static ATTR_Y: &str = "y";
 static ATTR_FILL: &str = "fill";
@@ -56,49 +98,676 @@ static ATTR_DX: &str = "dx";
 static ATTR_DY: &str = "dy";
 static ATTR_R: &str = "r";
 static ATTR_D: &str = "d";
+static ATTR_ID: &str = "id";
+static ATTR_CLASS: &str = "class";
+static ATTR_OPACITY: &str = "opacity";
+static ATTR_ROLE: &str = "role";
+static ATTR_ARIA_LABEL: &str = "aria-label";
+static ATTR_HREF: &str = "href";
+static ATTR_OFFSET: &str = "offset";
+static ATTR_STOP_COLOR: &str = "stop-color";
+static ATTR_STOP_OPACITY: &str = "stop-opacity";
+static ATTR_GRADIENT_UNITS: &str = "gradientUnits";
+static ATTR_FILTER: &str = "filter";
+static ATTR_PATTERN_UNITS: &str = "patternUnits";
+static ATTR_STD_DEVIATION: &str = "stdDeviation";
+static ATTR_FLOOD_COLOR: &str = "flood-color";
+static ATTR_FLOOD_OPACITY: &str = "flood-opacity";
+
+/// The value of attribute, the constant value(e.g. none) and the text of component
+/// are borrowed, only the formatted value(e.g. coordinate or color) is owned.
+type AttrValue<'a> = Cow<'a, str>;
+
+/// Formats the arguments to attribute value, the arguments without placeholder are not allocated.
+fn format_attr(args: fmt::Arguments) -> AttrValue<'static> {
+    match args.as_str() {
+        Some(value) => Cow::Borrowed(value),
+        None => Cow::Owned(fmt::format(args)),
+    }
+}
+
+/// Converts color to hex attribute value.
+fn hex_attr(color: &Color) -> AttrValue<'static> {
+    format_attr(format_args!(
+        "#{:02X}{:02X}{:02X}",
+        color.r, color.g, color.b
+    ))
+}
+
+/// Formats the coordinate to attribute value, it is the same as format coordinate.
+fn coordinate_attr(value: f32) -> AttrValue<'static> {
+    format_coordinate(value).into()
+}
 
 /// Converts opacity to string value.
-fn convert_opacity(color: &Color) -> String {
+fn convert_opacity(color: &Color) -> AttrValue<'static> {
     if color.is_nontransparent() {
-        "".to_string()
+        AttrValue::default()
     } else {
-        format_float(color.opacity())
+        format_float(color.opacity()).into()
     }
 }
 
-fn format_option_float(value: Option<f32>) -> String {
+/// Converts the id of defs to url.
+fn convert_url(id: &str) -> AttrValue<'static> {
+    format_attr(format_args!("url(#{id})"))
+}
+
+fn format_option_float(value: Option<f32>) -> AttrValue<'static> {
     if let Some(f) = value {
-        format_float(f)
+        coordinate_attr(f)
     } else {
-        "".to_string()
+        AttrValue::default()
     }
 }
 
 #[derive(Clone, PartialEq, Debug, Default)]
 struct SVGTag<'a> {
     tag: &'a str,
-    attrs: Vec<(&'a str, String)>,
+    attrs: Vec<(&'a str, AttrValue<'a>)>,
     data: Option<String>,
 }
 
-pub fn generate_svg(width: f32, height: f32, x: f32, y: f32, data: String) -> String {
+pub fn generate_svg(
+    width: f32,
+    height: f32,
+    x: f32,
+    y: f32,
+    data: String,
+    aria_label: Option<&str>,
+    description: Option<&str>,
+) -> String {
     let mut attrs = vec![
-        (ATTR_WIDTH, format!("{}", width)),
-        (ATTR_HEIGHT, format!("{}", height)),
-        (ATTR_VIEW_BOX, format!("0 0 {} {}", width, height)),
-        (ATTR_XMLNS, "http://www.w3.org/2000/svg".to_string()),
+        (ATTR_WIDTH, format_attr(format_args!("{}", width))),
+        (ATTR_HEIGHT, format_attr(format_args!("{}", height))),
+        (
+            ATTR_VIEW_BOX,
+            format_attr(format_args!("0 0 {} {}", width, height)),
+        ),
+        (ATTR_XMLNS, "http://www.w3.org/2000/svg".into()),
     ];
     if x != 0.0 {
-        attrs.push((ATTR_X, format!("{}", x)))
+        attrs.push((ATTR_X, format_attr(format_args!("{}", x))))
     }
     if y != 0.0 {
-        attrs.push((ATTR_Y, format!("{}", y)))
+        attrs.push((ATTR_Y, format_attr(format_args!("{}", y))))
+    }
+    if aria_label.is_none() && description.is_none() {
+        return SVGTag::new(TAG_SVG, data, attrs).to_string();
+    }
+    // the title and desc are the accessible name and description of chart
+    attrs.push((ATTR_ROLE, "img".into()));
+    attrs.push((ATTR_ARIA_LABEL, aria_label.unwrap_or_default().into()));
+    let mut data_list = vec![];
+    if let Some(aria_label) = aria_label {
+        data_list.push(SVGTag::new(TAG_TITLE, escape_xml(aria_label), vec![]).to_string());
+    }
+    if let Some(description) = description {
+        data_list.push(SVGTag::new(TAG_DESC, escape_xml(description), vec![]).to_string());
+    }
+    data_list.push(data);
+    SVGTag::new(TAG_SVG, data_list.join("\n"), attrs).to_string()
+}
+
+// The style attributes which can be hoisted into class.
+static STYLE_ATTRS: [&str; 9] = [
+    "fill",
+    "fill-opacity",
+    "stroke",
+    "stroke-opacity",
+    "stroke-width",
+    "stroke-dasharray",
+    "font-family",
+    "font-size",
+    "font-weight",
+];
+
+pub(crate) struct StartTag<'a> {
+    pub name: &'a str,
+    pub attrs: Vec<(&'a str, &'a str)>,
+    // the end of tag, it is "/>" or ">"
+    pub end: &'a str,
+}
+
+impl<'a> StartTag<'a> {
+    /// Gets the value of attribute.
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.attrs
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| *value)
+    }
+}
+
+// Parses the start tag of line, it returns none if the line is not a start tag.
+pub(crate) fn parse_start_tag(line: &str) -> Option<StartTag<'_>> {
+    if !line.starts_with('<') || line.starts_with("</") {
+        return None;
+    }
+    let end = if line.ends_with("/>") {
+        "/>"
+    } else if line.ends_with('>') {
+        ">"
+    } else {
+        return None;
+    };
+    let content = &line[1..line.len() - end.len()];
+    let name_end = content.find(' ').unwrap_or(content.len());
+    let name = &content[..name_end];
+    let mut rest = &content[name_end..];
+    let mut attrs = vec![];
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let index = rest.find("=\"")?;
+        let key = &rest[..index];
+        let value_start = index + 2;
+        let value_end = value_start + rest[value_start..].find('"')?;
+        attrs.push((key, &rest[value_start..value_end]));
+        rest = &rest[value_end + 1..];
+    }
+    Some(StartTag { name, attrs, end })
+}
+
+// Splits the svg into tags and texts, the '>' in the quotes of attribute value
+// doesn't end the tag.
+pub(crate) fn split_tags(svg: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut start = 0;
+    let mut in_tag = false;
+    let mut in_quote = false;
+    for (index, ch) in svg.char_indices() {
+        match ch {
+            '<' if !in_tag => {
+                if index > start {
+                    items.push(&svg[start..index]);
+                }
+                start = index;
+                in_tag = true;
+            }
+            '"' if in_tag => in_quote = !in_quote,
+            '>' if in_tag && !in_quote => {
+                items.push(&svg[start..index + 1]);
+                start = index + 1;
+                in_tag = false;
+            }
+            _ => {}
+        }
+    }
+    if start < svg.len() {
+        items.push(&svg[start..]);
+    }
+    items
+}
+
+// The attributes are removed from minified svg if they are the default value,
+// they are not inherited, so the children are not affected.
+static MINIFIED_DEFAULT_ATTRS: [(&[&str], &str, &str); 6] = [
+    (&["rect", "text", "image", "use"], "x", "0"),
+    (&["rect", "text", "image", "use"], "y", "0"),
+    (&["text"], "dx", "0"),
+    (&["text"], "dy", "0"),
+    (&["circle", "ellipse"], "cx", "0"),
+    (&["circle", "ellipse"], "cy", "0"),
+];
+// The attributes of number, the leading zero of their decimals is removed in minified svg.
+static MINIFIED_NUMBER_ATTRS: [&str; 22] = [
+    "x",
+    "y",
+    "x1",
+    "y1",
+    "x2",
+    "y2",
+    "cx",
+    "cy",
+    "r",
+    "rx",
+    "ry",
+    "dx",
+    "dy",
+    "width",
+    "height",
+    "d",
+    "points",
+    "transform",
+    "stroke-width",
+    "opacity",
+    "fill-opacity",
+    "stroke-opacity",
+];
+
+// Shortens the numbers of attribute value, e.g. 0.26 is converted to .26,
+// they are not rounded again as the coordinates are formatted with the precision.
+fn shorten_numbers(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut number = String::new();
+    let flush = |number: &mut String, result: &mut String| {
+        if number.is_empty() {
+            return;
+        }
+        let mut text = number.clone();
+        if let Some(rest) = text.strip_prefix("0.") {
+            text = format!(".{rest}");
+        } else if let Some(rest) = text.strip_prefix("-0.") {
+            text = format!("-.{rest}");
+        }
+        result.push_str(&text);
+        number.clear();
+    };
+    for ch in value.chars() {
+        // the minus is the start of number
+        if ch.is_ascii_digit() || ch == '.' || (ch == '-' && number.is_empty()) {
+            number.push(ch);
+        } else {
+            flush(&mut number, &mut result);
+            result.push(ch);
+        }
     }
-    SVGTag::new(TAG_SVG, data, attrs).to_string()
+    flush(&mut number, &mut result);
+    result
+}
+
+// Minifies the svg, the newlines, the duplicate attributes and the attributes of
+// default value are removed and the numbers are shortened.
+fn minify_svg(svg: &str) -> String {
+    let mut out = String::with_capacity(svg.len());
+    for item in split_tags(svg) {
+        let Some(tag) = parse_start_tag(item) else {
+            // the text is trimmed per line
+            for line in item.lines() {
+                out.push_str(line.trim());
+            }
+            continue;
+        };
+        out.push('<');
+        out.push_str(tag.name);
+        let mut keys = vec![];
+        for (key, value) in tag.attrs.iter() {
+            if keys.contains(key) {
+                continue;
+            }
+            keys.push(*key);
+            let is_default = MINIFIED_DEFAULT_ATTRS.iter().any(|(tags, name, default)| {
+                tags.contains(&tag.name) && name == key && default == value
+            });
+            if is_default {
+                continue;
+            }
+            let value = if MINIFIED_NUMBER_ATTRS.contains(key) {
+                shorten_numbers(value)
+            } else {
+                value.to_string()
+            };
+            out.push_str(&format!(r#" {key}="{value}""#));
+        }
+        out.push_str(tag.end);
+    }
+    out
+}
+
+/// Converts the root svg to responsive, its width is 100% of container
+/// and the height is scaled by the aspect ratio of view box.
+pub(crate) fn make_responsive(svg: String) -> String {
+    let (head, body) = svg.split_once('\n').unwrap_or((&svg, ""));
+    let Some(tag) = parse_start_tag(head) else {
+        return svg;
+    };
+    let mut out = String::with_capacity(svg.len() + 64);
+    out.push('<');
+    out.push_str(tag.name);
+    for (key, value) in tag.attrs.iter() {
+        if *key == ATTR_HEIGHT {
+            continue;
+        }
+        let value = if *key == ATTR_WIDTH { "100%" } else { value };
+        out.push_str(&format!(r#" {key}="{value}""#));
+    }
+    out.push_str(&format!(r#" {ATTR_PRESERVE_ASPECT_RATIO}="xMidYMid meet""#));
+    out.push_str(tag.end);
+    if svg.len() > head.len() {
+        out.push('\n');
+        out.push_str(body);
+    }
+    out
+}
+
+// Applies the translate and rotate functions of transform to the point,
+// the other functions are ignored.
+fn apply_transform(transform: &str, point: (f32, f32)) -> (f32, f32) {
+    let mut functions = vec![];
+    for item in transform.split(')') {
+        let Some((name, args)) = item.split_once('(') else {
+            continue;
+        };
+        let args: Vec<f32> = args
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter_map(|value| value.parse::<f32>().ok())
+            .collect();
+        functions.push((name.trim(), args));
+    }
+    // the last function of transform list is applied first
+    let (mut x, mut y) = point;
+    for (name, args) in functions.iter().rev() {
+        match (*name, args.as_slice()) {
+            ("translate", [tx]) => x += tx,
+            ("translate", [tx, ty]) => {
+                x += tx;
+                y += ty;
+            }
+            ("rotate", [a, rest @ ..]) => {
+                let (cx, cy) = match rest {
+                    [cx, cy] => (*cx, *cy),
+                    _ => (0.0, 0.0),
+                };
+                let (sin, cos) = a.to_radians().sin_cos();
+                let (dx, dy) = (x - cx, y - cy);
+                x = cx + dx * cos - dy * sin;
+                y = cy + dx * sin + dy * cos;
+            }
+            _ => {}
+        }
+    }
+    (x, y)
+}
+
+fn get_f32_attr(tag: &StartTag, key: &str) -> f32 {
+    tag.get(key)
+        .and_then(|value| value.parse::<f32>().ok())
+        .unwrap_or_default()
+}
+
+/// The box of text in svg, it is measured by the attributes of text element.
+pub(crate) struct TextBox {
+    pub text: String,
+    pub area: Box,
+    // the text is transformed, e.g. rotated, the area is the bounding box
+    pub transformed: bool,
+}
+
+/// Gets the boxes of texts in svg, the texts of nested svg are skipped.
+/// The font family is used if text doesn't set it.
+pub(crate) fn get_text_boxes(svg: &str, font_family: &str) -> Vec<TextBox> {
+    let lines: Vec<&str> = svg.lines().collect();
+    let mut depth = 0;
+    let mut boxes = vec![];
+    for (index, line) in lines.iter().enumerate() {
+        if line.starts_with("<svg") {
+            depth += 1;
+        }
+        if line.ends_with("</svg>") {
+            depth -= 1;
+        }
+        if depth != 1 || !line.starts_with("<text") {
+            continue;
+        }
+        let Some(tag) = parse_start_tag(line) else {
+            continue;
+        };
+        let text = match lines.get(index + 1) {
+            Some(value) if !value.starts_with('<') => unescape_xml(value),
+            _ => continue,
+        };
+        let font_size = get_f32_attr(&tag, ATTR_FONT_SIZE);
+        let Ok(b) = measure_text_width_family(
+            tag.get(ATTR_FONT_FAMILY).unwrap_or(font_family),
+            font_size,
+            &text,
+        ) else {
+            continue;
+        };
+        let text_width = b.width();
+        let text_height = b.height();
+        let x = get_f32_attr(&tag, ATTR_X) + get_f32_attr(&tag, ATTR_DX);
+        let y = get_f32_attr(&tag, ATTR_Y) + get_f32_attr(&tag, ATTR_DY);
+        let left = match tag.get(ATTR_TEXT_ANCHOR) {
+            Some("middle") => x - text_width / 2.0,
+            Some("end") => x - text_width,
+            _ => x,
+        };
+        let top = match tag.get(ATTR_DOMINANT_BASELINE) {
+            Some("hanging") => y,
+            Some("central") | Some("middle") => y - text_height / 2.0,
+            _ => y - text_height,
+        };
+        let mut area = Box {
+            left,
+            top,
+            right: left + text_width,
+            bottom: top + text_height,
+        };
+        if let Some(transform) = tag.get(ATTR_TRANSFORM) {
+            let corners = [
+                (area.left, area.top),
+                (area.right, area.top),
+                (area.left, area.bottom),
+                (area.right, area.bottom),
+            ]
+            .map(|point| apply_transform(transform, point));
+            area = Box {
+                left: corners.iter().map(|p| p.0).fold(f32::MAX, f32::min),
+                top: corners.iter().map(|p| p.1).fold(f32::MAX, f32::min),
+                right: corners.iter().map(|p| p.0).fold(f32::MIN, f32::max),
+                bottom: corners.iter().map(|p| p.1).fold(f32::MIN, f32::max),
+            };
+        }
+        boxes.push(TextBox {
+            text,
+            area,
+            transformed: tag.get(ATTR_TRANSFORM).is_some(),
+        });
+    }
+    boxes
+}
+
+/// Gets the texts of svg which extend beyond the canvas.
+pub(crate) fn get_text_overflows(svg: &str, font_family: &str) -> Vec<TextOverflow> {
+    let Some(root) = svg.lines().next().and_then(parse_start_tag) else {
+        return vec![];
+    };
+    let width = get_f32_attr(&root, ATTR_WIDTH);
+    let height = get_f32_attr(&root, ATTR_HEIGHT);
+    let mut overflows = vec![];
+    for TextBox { text, area, .. } in get_text_boxes(svg, font_family) {
+        let overflow = Box {
+            left: (-area.left).max(0.0),
+            top: (-area.top).max(0.0),
+            right: (area.right - width).max(0.0),
+            bottom: (area.bottom - height).max(0.0),
+        };
+        if overflow.left + overflow.top + overflow.right + overflow.bottom <= 0.0 {
+            continue;
+        }
+        overflows.push(TextOverflow {
+            text,
+            area,
+            overflow,
+        });
+    }
+    overflows
+}
+
+/// Gets the pairs of texts which overlap each other, the transformed texts are skipped
+/// because their bounding boxes are larger than the texts.
+pub(crate) fn get_text_overlaps(svg: &str, font_family: &str) -> Vec<(String, String)> {
+    let boxes: Vec<TextBox> = get_text_boxes(svg, font_family)
+        .into_iter()
+        .filter(|item| !item.transformed)
+        .collect();
+    let mut overlaps = vec![];
+    for (index, item) in boxes.iter().enumerate() {
+        for other in boxes.iter().skip(index + 1) {
+            // the tolerance of 1px avoids the adjacent texts
+            let width = item.area.right.min(other.area.right) - item.area.left.max(other.area.left);
+            let height =
+                item.area.bottom.min(other.area.bottom) - item.area.top.max(other.area.top);
+            if width > 1.0 && height > 1.0 {
+                overlaps.push((item.text.clone(), other.text.clone()));
+            }
+        }
+    }
+    overlaps
+}
+
+/// Hoists the repeated style attributes into class of style element,
+/// it reduces the size of large chart.
+pub(crate) fn hoist_style(svg: String) -> String {
+    // the tags are parsed one by one, a line may contain several elements, e.g. the lines of grid
+    let items = split_tags(&svg);
+    let get_style = |attrs: &[(&str, &str)]| -> String {
+        attrs
+            .iter()
+            .filter(|(key, _)| STYLE_ATTRS.contains(key))
+            .map(|(key, value)| {
+                // the unit of length is required in css
+                if (*key == "font-size" || *key == "stroke-width") && value.parse::<f32>().is_ok() {
+                    format!("{key}:{value}px")
+                } else {
+                    format!("{key}:{value}")
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(";")
+    };
+    // the root svg is not hoisted
+    let Some(root_index) = items
+        .iter()
+        .position(|item| parse_start_tag(item).is_some())
+    else {
+        return svg;
+    };
+    // the styles are ordered by the first appearance
+    let mut styles = vec![];
+    let mut style_counts = HashMap::new();
+    for item in items.iter().skip(root_index + 1) {
+        let Some(tag) = parse_start_tag(item) else {
+            continue;
+        };
+        let style = get_style(&tag.attrs);
+        if style.is_empty() {
+            continue;
+        }
+        let count = style_counts.entry(style.clone()).or_insert(0);
+        if *count == 0 {
+            styles.push(style);
+        }
+        *count += 1;
+    }
+    let class_names: HashMap<&String, String> = styles
+        .iter()
+        .filter(|style| style_counts.get(*style).cloned().unwrap_or_default() > 1)
+        .enumerate()
+        .map(|(index, style)| (style, format!("s{index}")))
+        .collect();
+    if class_names.is_empty() {
+        return svg;
+    }
+
+    let mut class_list: Vec<(&String, &String)> =
+        class_names.iter().map(|(k, v)| (v, *k)).collect();
+    class_list.sort_by_key(|(name, _)| name[1..].parse::<usize>().unwrap_or_default());
+    let css: Vec<String> = class_list
+        .iter()
+        .map(|(name, style)| format!(".{name}{{{style}}}"))
+        .collect();
+
+    let mut result = String::with_capacity(svg.len());
+    for (index, item) in items.iter().enumerate() {
+        if index <= root_index {
+            result.push_str(item);
+            if index == root_index {
+                result.push('\n');
+                result.push_str(&SVGTag::new(TAG_STYLE, css.join("\n"), vec![]).to_string());
+            }
+            continue;
+        }
+        let Some(StartTag { name, attrs, end }) = parse_start_tag(item) else {
+            result.push_str(item);
+            continue;
+        };
+        let Some(class_name) = class_names.get(&get_style(&attrs)) else {
+            result.push_str(item);
+            continue;
+        };
+        result.push('<');
+        result.push_str(name);
+        let mut has_class = false;
+        for (key, attr_value) in attrs.iter() {
+            if STYLE_ATTRS.contains(key) {
+                continue;
+            }
+            if *key == ATTR_CLASS {
+                has_class = true;
+                result.push_str(&format!(" {key}=\"{attr_value} {class_name}\""));
+            } else {
+                result.push_str(&format!(" {key}=\"{attr_value}\""));
+            }
+        }
+        if !has_class {
+            result.push_str(&format!(" {ATTR_CLASS}=\"{class_name}\""));
+        }
+        result.push_str(end);
+    }
+    result
+}
+
+/// Formats the svg, the elements are minified or indented.
+pub(crate) fn format_svg(svg: String, format: &SVGFormat) -> String {
+    match format {
+        SVGFormat::Normal => svg,
+        SVGFormat::Minified => minify_svg(&svg),
+        SVGFormat::Pretty => {
+            let mut depth = 0_usize;
+            let mut lines = vec![];
+            for line in svg.lines() {
+                let line = line.trim();
+                if line.starts_with("</") {
+                    depth = depth.saturating_sub(1);
+                }
+                lines.push(format!("{}{line}", "  ".repeat(depth)));
+                // the element is not closed in the same line
+                if line.starts_with('<')
+                    && !line.starts_with("</")
+                    && !line.ends_with("/>")
+                    && !line.contains("</")
+                {
+                    depth += 1;
+                }
+            }
+            lines.join("\n")
+        }
+        SVGFormat::Normalized => {
+            let mut lines = vec![];
+            // the elements of the same line are split, e.g. the lines of grid
+            for line in svg.replace("><", ">\n<").lines() {
+                let line = line.trim();
+                let Some(mut tag) = parse_start_tag(line) else {
+                    lines.push(line.to_string());
+                    continue;
+                };
+                tag.attrs.sort_by_key(|(key, _)| *key);
+                let mut value = format!("<{}", tag.name);
+                for (key, attr_value) in tag.attrs.iter() {
+                    value.push_str(&format!(r#" {key}="{attr_value}""#));
+                }
+                value.push_str(tag.end);
+                lines.push(value);
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+// Generates the title child of element, it is shown as the native tooltip.
+fn generate_title(title: &Option<String>) -> Option<String> {
+    title
+        .as_ref()
+        .map(|value| SVGTag::new(TAG_TITLE, escape_xml(value), vec![]).to_string())
 }
 
 impl<'a> SVGTag<'a> {
-    pub fn new(tag: &'a str, data: String, attrs: Vec<(&'a str, String)>) -> Self {
+    pub fn new(tag: &'a str, data: String, attrs: Vec<(&'a str, AttrValue<'a>)>) -> Self {
         Self {
             tag,
             attrs,
@@ -107,35 +776,49 @@ impl<'a> SVGTag<'a> {
     }
 }
 
-impl<'a> fmt::Display for SVGTag<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl<'a> SVGTag<'a> {
+    // Writes the start tag with attributes, the empty attribute is skipped.
+    fn write_start_tag(&self, out: &mut String) {
+        out.push('<');
+        out.push_str(self.tag);
+        for (k, v) in self.attrs.iter() {
+            if k.is_empty() || v.is_empty() {
+                continue;
+            }
+            out.push(' ');
+            out.push_str(k);
+            out.push_str("=\"");
+            write_escaped_xml(out, v);
+            out.push('"');
+        }
+    }
+    /// Writes the svg of tag to the output.
+    pub fn write_svg(&self, out: &mut String) {
         if self.tag == TAG_GROUP {
             if let Some(ref data) = self.data {
                 if data.is_empty() {
-                    return write!(f, "");
+                    return;
                 }
             }
         }
-        let mut value = "<".to_string();
-        value.push_str(self.tag);
-        for (k, v) in self.attrs.iter() {
-            if k.is_empty() || v.is_empty() {
-                continue;
-            }
-            value.push(' ');
-            value.push_str(k);
-            value.push_str("=\"");
-            value.push_str(v);
-            value.push('\"');
-        }
+        self.write_start_tag(out);
         if let Some(ref data) = self.data {
-            value.push_str(">\n");
-            value.push_str(data);
-            value.push_str(&format!("\n</{}>", self.tag));
+            out.push_str(">\n");
+            out.push_str(data);
+            out.push_str("\n</");
+            out.push_str(self.tag);
+            out.push('>');
         } else {
-            value.push_str("/>");
+            out.push_str("/>");
         }
-        write!(f, "{}", value)
+    }
+}
+
+impl<'a> fmt::Display for SVGTag<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut value = String::new();
+        self.write_svg(&mut value);
+        f.write_str(&value)
     }
 }
 
@@ -154,8 +837,11 @@ pub enum Component {
     Rect(Rect),
     Polyline(Polyline),
     Circle(Circle),
+    Ellipse(Ellipse),
+    PathSymbol(PathSymbol),
+    Path(Path),
     Polygon(Polygon),
-    Text(Text),
+    Text(std::boxed::Box<Text>),
     SmoothLine(SmoothLine),
     StraightLine(StraightLine),
     SmoothLineFill(SmoothLineFill),
@@ -164,6 +850,17 @@ pub enum Component {
     Axis(Axis),
     Legend(Legend),
     Pie(Pie),
+    Arc(Arc),
+    Sector(Sector),
+    Gradient(Gradient),
+    RadialGradient(RadialGradient),
+    DropShadow(DropShadow),
+    Pattern(Pattern),
+    ClipPath(ClipPath),
+    Group(Group),
+    Inset(Inset),
+    // the rendered svg, e.g. the cached layer of chart
+    Raw(String),
 }
 #[derive(Clone, PartialEq, Debug)]
 
@@ -193,30 +890,30 @@ impl Default for Line {
 }
 
 impl Line {
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         if self.stroke_width <= 0.0 {
-            return "".to_string();
+            return;
         }
         let mut attrs = vec![
-            (ATTR_STROKE_WIDTH, format_float(self.stroke_width)),
-            (ATTR_X1, format_float(self.left)),
-            (ATTR_Y1, format_float(self.top)),
-            (ATTR_X2, format_float(self.right)),
-            (ATTR_Y2, format_float(self.bottom)),
+            (ATTR_STROKE_WIDTH, coordinate_attr(self.stroke_width)),
+            (ATTR_X1, coordinate_attr(self.left)),
+            (ATTR_Y1, coordinate_attr(self.top)),
+            (ATTR_X2, coordinate_attr(self.right)),
+            (ATTR_Y2, coordinate_attr(self.bottom)),
         ];
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
         if let Some(ref stroke_dash_array) = self.stroke_dash_array {
-            attrs.push((ATTR_STROKE_DASH_ARRAY, stroke_dash_array.to_string()));
+            attrs.push((ATTR_STROKE_DASH_ARRAY, stroke_dash_array.as_str().into()));
         }
         SVGTag {
             tag: TAG_LINE,
             attrs,
             data: None,
         }
-        .to_string()
+        .write_svg(out);
     }
 }
 
@@ -224,43 +921,59 @@ impl Line {
 pub struct Rect {
     pub color: Option<Color>,
     pub fill: Option<Color>,
+    // id of the defs(e.g. gradient) to fill, it is preferred to fill color
+    pub fill_id: Option<String>,
     pub left: f32,
     pub top: f32,
     pub width: f32,
     pub height: f32,
     pub rx: Option<f32>,
     pub ry: Option<f32>,
+    // id of the filter(e.g. drop shadow) to apply
+    pub filter_id: Option<String>,
+    pub id: Option<String>,
+    pub class: Option<String>,
+    // tooltip of the element
+    pub title: Option<String>,
 }
 impl Rect {
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         let mut attrs = vec![
-            (ATTR_X, format_float(self.left)),
-            (ATTR_Y, format_float(self.top)),
-            (ATTR_WIDTH, format_float(self.width)),
-            (ATTR_HEIGHT, format_float(self.height)),
+            (ATTR_ID, self.id.clone().unwrap_or_default().into()),
+            (ATTR_CLASS, self.class.clone().unwrap_or_default().into()),
+            (ATTR_X, coordinate_attr(self.left)),
+            (ATTR_Y, coordinate_attr(self.top)),
+            (ATTR_WIDTH, coordinate_attr(self.width)),
+            (ATTR_HEIGHT, coordinate_attr(self.height)),
             (ATTR_RX, format_option_float(self.rx)),
             (ATTR_RY, format_option_float(self.ry)),
         ];
 
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
-        if let Some(color) = self.fill {
+        if let Some(ref fill_id) = self.fill_id {
+            attrs.push((ATTR_FILL, convert_url(fill_id)));
+        } else if let Some(color) = self.fill {
             if color.is_transparent() {
-                attrs.push((ATTR_FILL, "none".to_string()));
+                attrs.push((ATTR_FILL, "none".into()));
             } else {
-                attrs.push((ATTR_FILL, color.hex()));
+                attrs.push((ATTR_FILL, hex_attr(&color)));
                 attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
             }
         }
 
+        if let Some(ref filter_id) = self.filter_id {
+            attrs.push((ATTR_FILTER, convert_url(filter_id)));
+        }
+
         SVGTag {
             tag: TAG_RECT,
             attrs,
-            data: None,
+            data: generate_title(&self.title),
         }
-        .to_string()
+        .write_svg(out);
     }
 }
 
@@ -282,23 +995,23 @@ impl Default for Polyline {
 }
 
 impl Polyline {
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         if self.stroke_width <= 0.0 {
-            return "".to_string();
+            return;
         }
         let points: Vec<String> = self
             .points
             .iter()
-            .map(|p| format!("{},{}", format_float(p.x), format_float(p.y)))
+            .map(|p| format!("{},{}", format_coordinate(p.x), format_coordinate(p.y)))
             .collect();
         let mut attrs = vec![
-            (ATTR_FILL, "none".to_string()),
-            (ATTR_STROKE_WIDTH, format_float(self.stroke_width)),
-            (ATTR_POINTS, points.join(" ")),
+            (ATTR_FILL, "none".into()),
+            (ATTR_STROKE_WIDTH, coordinate_attr(self.stroke_width)),
+            (ATTR_POINTS, points.join(" ").into()),
         ];
 
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
 
@@ -307,7 +1020,7 @@ impl Polyline {
             attrs,
             data: None,
         }
-        .to_string()
+        .write_svg(out);
     }
 }
 
@@ -315,10 +1028,16 @@ impl Polyline {
 pub struct Circle {
     pub stroke_color: Option<Color>,
     pub fill: Option<Color>,
+    // id of the defs(e.g. gradient) to fill, it is preferred to fill color
+    pub fill_id: Option<String>,
     pub stroke_width: f32,
     pub cx: f32,
     pub cy: f32,
     pub r: f32,
+    pub id: Option<String>,
+    pub class: Option<String>,
+    // tooltip of the element
+    pub title: Option<String>,
 }
 
 impl Default for Circle {
@@ -326,29 +1045,37 @@ impl Default for Circle {
         Circle {
             stroke_color: None,
             fill: None,
+            fill_id: None,
             stroke_width: 1.0,
             cx: 0.0,
             cy: 0.0,
             r: 3.0,
+            id: None,
+            class: None,
+            title: None,
         }
     }
 }
 
 impl Circle {
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         let mut attrs = vec![
-            (ATTR_CX, format_float(self.cx)),
-            (ATTR_CY, format_float(self.cy)),
-            (ATTR_R, format_float(self.r)),
-            (ATTR_STROKE_WIDTH, format_float(self.stroke_width)),
+            (ATTR_ID, self.id.clone().unwrap_or_default().into()),
+            (ATTR_CLASS, self.class.clone().unwrap_or_default().into()),
+            (ATTR_CX, coordinate_attr(self.cx)),
+            (ATTR_CY, coordinate_attr(self.cy)),
+            (ATTR_R, coordinate_attr(self.r)),
+            (ATTR_STROKE_WIDTH, coordinate_attr(self.stroke_width)),
         ];
         if let Some(color) = self.stroke_color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
-        let mut fill = "none".to_string();
-        if let Some(color) = self.fill {
-            fill = color.hex();
+        let mut fill: AttrValue = "none".into();
+        if let Some(ref fill_id) = self.fill_id {
+            fill = convert_url(fill_id);
+        } else if let Some(color) = self.fill {
+            fill = hex_attr(&color);
             attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
         }
         attrs.push((ATTR_FILL, fill));
@@ -356,9 +1083,124 @@ impl Circle {
         SVGTag {
             tag: TAG_CIRCLE,
             attrs,
+            data: generate_title(&self.title),
+        }
+        .write_svg(out);
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Ellipse {
+    pub stroke_color: Option<Color>,
+    pub fill: Option<Color>,
+    // id of the defs(e.g. gradient) to fill, it is preferred to fill color
+    pub fill_id: Option<String>,
+    pub stroke_width: f32,
+    pub cx: f32,
+    pub cy: f32,
+    pub rx: f32,
+    pub ry: f32,
+}
+
+impl Default for Ellipse {
+    fn default() -> Self {
+        Ellipse {
+            stroke_color: None,
+            fill: None,
+            fill_id: None,
+            stroke_width: 1.0,
+            cx: 0.0,
+            cy: 0.0,
+            rx: 3.0,
+            ry: 3.0,
+        }
+    }
+}
+
+impl Ellipse {
+    pub fn write_svg(&self, out: &mut String) {
+        let mut attrs = vec![
+            (ATTR_CX, coordinate_attr(self.cx)),
+            (ATTR_CY, coordinate_attr(self.cy)),
+            (ATTR_RX, coordinate_attr(self.rx)),
+            (ATTR_RY, coordinate_attr(self.ry)),
+            (ATTR_STROKE_WIDTH, coordinate_attr(self.stroke_width)),
+        ];
+        if let Some(color) = self.stroke_color {
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
+            attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
+        }
+        let mut fill: AttrValue = "none".into();
+        if let Some(ref fill_id) = self.fill_id {
+            fill = convert_url(fill_id);
+        } else if let Some(color) = self.fill {
+            fill = hex_attr(&color);
+            attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
+        }
+        attrs.push((ATTR_FILL, fill));
+
+        SVGTag {
+            tag: TAG_ELLIPSE,
+            attrs,
             data: None,
         }
-        .to_string()
+        .write_svg(out);
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PathSymbol {
+    // svg path of symbol, it should be drawn in the box from (-1, -1) to (1, 1)
+    pub path: String,
+    pub fill: Option<Color>,
+    pub cx: f32,
+    pub cy: f32,
+    // half size of symbol, the path is scaled by it
+    pub size: f32,
+}
+
+impl Default for PathSymbol {
+    fn default() -> Self {
+        PathSymbol {
+            path: "".to_string(),
+            fill: None,
+            cx: 0.0,
+            cy: 0.0,
+            size: 3.0,
+        }
+    }
+}
+
+impl PathSymbol {
+    pub fn write_svg(&self, out: &mut String) {
+        if self.path.is_empty() || self.size <= 0.0 {
+            return;
+        }
+        let mut attrs = vec![
+            (ATTR_D, self.path.as_str().into()),
+            (
+                ATTR_TRANSFORM,
+                format_attr(format_args!(
+                    "translate({} {}) scale({})",
+                    format_coordinate(self.cx),
+                    format_coordinate(self.cy),
+                    format_coordinate(self.size)
+                )),
+            ),
+        ];
+        if let Some(color) = self.fill {
+            attrs.push((ATTR_FILL, hex_attr(&color)));
+            attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
+        } else {
+            attrs.push((ATTR_FILL, "none".into()));
+        }
+
+        SVGTag {
+            tag: TAG_PATH,
+            attrs,
+            data: None,
+        }
+        .write_svg(out);
     }
 }
 
@@ -378,7 +1220,7 @@ impl Arrow {
             stroke_color: Color::default(),
         }
     }
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         let x_offset = self.width / 2.0;
         let y_offset = self.width / 2.0;
         let points = vec![
@@ -407,7 +1249,7 @@ impl Arrow {
             symbol: None,
             ..Default::default()
         }
-        .svg()
+        .write_svg(out);
     }
 }
 
@@ -415,34 +1257,146 @@ impl Arrow {
 pub struct Polygon {
     pub color: Option<Color>,
     pub fill: Option<Color>,
+    // id of the defs(e.g. gradient) to fill, it is preferred to fill color
+    pub fill_id: Option<String>,
     pub points: Vec<Point>,
+    // the corners are rounded if it is set
+    pub corner_radius: Option<f32>,
 }
 
 impl Polygon {
-    pub fn svg(&self) -> String {
+    fn rounded_path(&self, radius: f32) -> String {
+        let count = self.points.len();
+        let mut path_list = vec![];
+        for (index, p) in self.points.iter().enumerate() {
+            let prev = self.points[(index + count - 1) % count];
+            let next = self.points[(index + 1) % count];
+            let prev_distance = ((prev.x - p.x).powi(2) + (prev.y - p.y).powi(2)).sqrt();
+            let next_distance = ((next.x - p.x).powi(2) + (next.y - p.y).powi(2)).sqrt();
+            // the radius can not be greater than half of the edges
+            let r = radius.min(prev_distance / 2.0).min(next_distance / 2.0);
+            let (start_x, start_y, end_x, end_y) = if r > 0.0 {
+                (
+                    p.x + (prev.x - p.x) * r / prev_distance,
+                    p.y + (prev.y - p.y) * r / prev_distance,
+                    p.x + (next.x - p.x) * r / next_distance,
+                    p.y + (next.y - p.y) * r / next_distance,
+                )
+            } else {
+                (p.x, p.y, p.x, p.y)
+            };
+            let command = if index == 0 { "M" } else { "L" };
+            path_list.push(format!(
+                "{command}{},{} Q{},{} {},{}",
+                format_coordinate(start_x),
+                format_coordinate(start_y),
+                format_coordinate(p.x),
+                format_coordinate(p.y),
+                format_coordinate(end_x),
+                format_coordinate(end_y)
+            ));
+        }
+        path_list.push("Z".to_string());
+        path_list.join(" ")
+    }
+    pub fn write_svg(&self, out: &mut String) {
         if self.points.is_empty() {
-            return "".to_string();
+            return;
         }
-        let points: Vec<String> = self
-            .points
-            .iter()
-            .map(|p| format!("{},{}", format_float(p.x), format_float(p.y)))
-            .collect();
-        let mut attrs = vec![(ATTR_POINTS, points.join(" "))];
+        let mut tag = TAG_POLYGON;
+        let mut attrs = if let Some(radius) = self.corner_radius {
+            tag = TAG_PATH;
+            vec![(ATTR_D, self.rounded_path(radius).into())]
+        } else {
+            let points: Vec<String> = self
+                .points
+                .iter()
+                .map(|p| format!("{},{}", format_coordinate(p.x), format_coordinate(p.y)))
+                .collect();
+            vec![(ATTR_POINTS, points.join(" ").into())]
+        };
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
-        if let Some(color) = self.fill {
-            attrs.push((ATTR_FILL, color.hex()));
+        if let Some(ref fill_id) = self.fill_id {
+            attrs.push((ATTR_FILL, convert_url(fill_id)));
+        } else if let Some(color) = self.fill {
+            attrs.push((ATTR_FILL, hex_attr(&color)));
             attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
         }
         SVGTag {
-            tag: TAG_POLYGON,
+            tag,
             attrs,
             data: None,
         }
-        .to_string()
+        .write_svg(out);
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Path {
+    // svg path data
+    pub d: String,
+    pub stroke_color: Option<Color>,
+    pub stroke_width: f32,
+    // dash array
+    pub stroke_dash_array: Option<String>,
+    pub fill: Option<Color>,
+    // id of the defs(e.g. gradient) to fill, it is preferred to fill color
+    pub fill_id: Option<String>,
+    pub transform: Option<String>,
+}
+
+impl Default for Path {
+    fn default() -> Self {
+        Path {
+            d: "".to_string(),
+            stroke_color: None,
+            stroke_width: 1.0,
+            stroke_dash_array: None,
+            fill: None,
+            fill_id: None,
+            transform: None,
+        }
+    }
+}
+
+impl Path {
+    pub fn write_svg(&self, out: &mut String) {
+        if self.d.is_empty() {
+            return;
+        }
+        let mut attrs = vec![
+            (ATTR_D, self.d.as_str().into()),
+            (
+                ATTR_TRANSFORM,
+                self.transform.clone().unwrap_or_default().into(),
+            ),
+        ];
+        if let Some(color) = self.stroke_color {
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
+            attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
+            attrs.push((ATTR_STROKE_WIDTH, coordinate_attr(self.stroke_width)));
+            attrs.push((
+                ATTR_STROKE_DASH_ARRAY,
+                self.stroke_dash_array.clone().unwrap_or_default().into(),
+            ));
+        }
+        let mut fill: AttrValue = "none".into();
+        if let Some(ref fill_id) = self.fill_id {
+            fill = convert_url(fill_id);
+        } else if let Some(color) = self.fill {
+            fill = hex_attr(&color);
+            attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
+        }
+        attrs.push((ATTR_FILL, fill));
+        SVGTag {
+            tag: TAG_PATH,
+            attrs,
+            data: None,
+        }
+        .write_svg(out);
     }
 }
 
@@ -455,39 +1409,80 @@ pub struct Bubble {
 }
 
 impl Bubble {
-    pub fn svg(&self) -> String {
-        let x = format_float(self.x);
-        let y = format_float(self.y);
-        let r = format_float(self.r);
+    pub fn write_svg(&self, out: &mut String) {
+        let x = format_coordinate(self.x);
+        let y = format_coordinate(self.y);
+        let r = format_coordinate(self.r);
 
         let first = get_pie_point(self.x, self.y, self.r, -140.0);
         let last = get_pie_point(self.x, self.y, self.r, 140.0);
 
         let mut path_list = vec![
-            format!("M {},{}", format_float(first.x), format_float(first.y)),
-            format!("A {r},{r} 0,0,1 {},{y}", format_float(self.x - self.r)),
-            format!("A {r},{r} 0,0,1 {},{y}", format_float(self.x + self.r)),
+            format!(
+                "M {},{}",
+                format_coordinate(first.x),
+                format_coordinate(first.y)
+            ),
+            format!("A {r},{r} 0,0,1 {},{y}", format_coordinate(self.x - self.r)),
+            format!("A {r},{r} 0,0,1 {},{y}", format_coordinate(self.x + self.r)),
             format!(
                 "A {r},{r} 0,0,1 {},{}",
-                format_float(last.x),
-                format_float(last.y)
+                format_coordinate(last.x),
+                format_coordinate(last.y)
+            ),
+            format!("L {x},{}", format_coordinate(self.y + self.r * 1.5)),
+        ];
+
+        path_list.push("Z".to_string());
+
+        let attrs = vec![
+            (ATTR_D, path_list.join(" ").into()),
+            (ATTR_FILL, hex_attr(&self.fill)),
+            (ATTR_FILL_OPACITY, convert_opacity(&self.fill)),
+        ];
+        SVGTag {
+            tag: TAG_PATH,
+            attrs,
+            ..Default::default()
+        }
+        .write_svg(out);
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TextSpan {
+    pub text: String,
+    pub font_size: Option<f32>,
+    pub font_color: Option<Color>,
+    pub font_weight: Option<String>,
+    pub dx: Option<f32>,
+    pub dy: Option<f32>,
+}
+
+impl TextSpan {
+    pub fn write_svg(&self, out: &mut String) {
+        if self.text.is_empty() {
+            return;
+        }
+        let mut attrs = vec![
+            (ATTR_FONT_SIZE, format_option_float(self.font_size)),
+            (ATTR_DX, format_option_float(self.dx)),
+            (ATTR_DY, format_option_float(self.dy)),
+            (
+                ATTR_FONT_WEIGHT,
+                self.font_weight.clone().unwrap_or_default().into(),
             ),
-            format!("L {x},{}", format_float(self.y + self.r * 1.5)),
-        ];
-
-        path_list.push("Z".to_string());
-
-        let attrs = vec![
-            (ATTR_D, path_list.join(" ")),
-            (ATTR_FILL, self.fill.hex()),
-            (ATTR_FILL_OPACITY, convert_opacity(&self.fill)),
         ];
+        if let Some(color) = self.font_color {
+            attrs.push((ATTR_FILL, hex_attr(&color)));
+            attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
+        }
         SVGTag {
-            tag: TAG_PATH,
+            tag: TAG_TSPAN,
             attrs,
-            ..Default::default()
+            data: Some(escape_xml(&self.text)),
         }
-        .to_string()
+        .write_svg(out);
     }
 }
 
@@ -507,14 +1502,72 @@ pub struct Text {
     pub dominant_baseline: Option<String>,
     pub text_anchor: Option<String>,
     pub alignment_baseline: Option<String>,
+    // id of the filter(e.g. drop shadow) to apply
+    pub filter_id: Option<String>,
+    // the text is wrapped in a link if it is set
+    pub link: Option<String>,
+    // rich text spans, they are appended after the text
+    pub spans: Vec<TextSpan>,
+    // horizontal alignment of the x position, the text anchor is preferred
+    pub align: Option<Align>,
+    // vertical alignment of the y position, the dominant baseline is preferred
+    pub vertical_align: Option<VerticalAlign>,
+    // font style, e.g. italic
+    pub font_style: Option<String>,
+    pub letter_spacing: Option<f32>,
+    // text decoration, e.g. underline or line-through
+    pub text_decoration: Option<String>,
+    pub id: Option<String>,
+    pub class: Option<String>,
+    // the text is not escaped if it is raw, e.g. it contains svg markup
+    pub raw: bool,
+    // text direction, it is rtl if the text contains arabic or hebrew by default
+    pub direction: Option<String>,
 }
 
 impl Text {
-    pub fn svg(&self) -> String {
-        if self.text.is_empty() {
-            return "".to_string();
+    pub fn write_svg(&self, out: &mut String) {
+        if self.text.is_empty() && self.spans.is_empty() {
+            return;
         }
+        let dominant_baseline = self.dominant_baseline.clone().unwrap_or_else(|| {
+            match self.vertical_align {
+                Some(VerticalAlign::Top) => "hanging",
+                Some(VerticalAlign::Middle) => "central",
+                Some(VerticalAlign::Bottom) => "text-after-edge",
+                None => "",
+            }
+            .to_string()
+        });
+        let text_anchor = self.text_anchor.clone().unwrap_or_else(|| {
+            match self.align {
+                Some(Align::Left) => "start",
+                Some(Align::Center) => "middle",
+                Some(Align::Right) => "end",
+                None => "",
+            }
+            .to_string()
+        });
+        let direction = self.direction.clone().unwrap_or_else(|| {
+            if is_rtl_text(&self.text) {
+                "rtl".to_string()
+            } else {
+                "".to_string()
+            }
+        });
+        // the start of rtl text is right side, so the anchor is flipped to keep the x position
+        let text_anchor = if direction == "rtl" {
+            match text_anchor.as_str() {
+                "middle" => text_anchor,
+                "end" => "start".to_string(),
+                _ => "end".to_string(),
+            }
+        } else {
+            text_anchor
+        };
         let mut attrs = vec![
+            (ATTR_ID, self.id.clone().unwrap_or_default().into()),
+            (ATTR_CLASS, self.class.clone().unwrap_or_default().into()),
             (ATTR_FONT_SIZE, format_option_float(self.font_size)),
             (ATTR_X, format_option_float(self.x)),
             (ATTR_Y, format_option_float(self.y)),
@@ -522,36 +1575,72 @@ impl Text {
             (ATTR_DY, format_option_float(self.dy)),
             (
                 ATTR_FONT_WEIGHT,
-                self.font_weight.clone().unwrap_or_default(),
+                self.font_weight.clone().unwrap_or_default().into(),
             ),
-            (ATTR_TRANSFORM, self.transform.clone().unwrap_or_default()),
             (
-                ATTR_DOMINANT_BASELINE,
-                self.dominant_baseline.clone().unwrap_or_default(),
+                ATTR_TRANSFORM,
+                self.transform.clone().unwrap_or_default().into(),
             ),
+            (ATTR_DOMINANT_BASELINE, dominant_baseline.into()),
+            (ATTR_TEXT_ANCHOR, text_anchor.into()),
+            (ATTR_DIRECTION, direction.into()),
             (
-                ATTR_TEXT_ANCHOR,
-                self.text_anchor.clone().unwrap_or_default(),
+                ATTR_ALIGNMENT_BASELINE,
+                self.alignment_baseline.clone().unwrap_or_default().into(),
             ),
             (
-                ATTR_ALIGNMENT_BASELINE,
-                self.alignment_baseline.clone().unwrap_or_default(),
+                ATTR_FONT_STYLE,
+                self.font_style.clone().unwrap_or_default().into(),
+            ),
+            (
+                ATTR_LETTER_SPACING,
+                format_option_float(self.letter_spacing),
+            ),
+            (
+                ATTR_TEXT_DECORATION,
+                self.text_decoration.clone().unwrap_or_default().into(),
             ),
         ];
         if let Some(ref font_family) = self.font_family {
-            attrs.push((ATTR_FONT_FAMILY, font_family.clone()));
+            attrs.push((ATTR_FONT_FAMILY, font_family.as_str().into()));
         }
         if let Some(color) = self.font_color {
-            attrs.push((ATTR_FILL, color.hex()));
+            attrs.push((ATTR_FILL, hex_attr(&color)));
             attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
         }
+        if let Some(ref filter_id) = self.filter_id {
+            attrs.push((ATTR_FILTER, convert_url(filter_id)));
+        }
+        let mut data_list = vec![];
+        if !self.text.is_empty() {
+            if self.raw {
+                data_list.push(self.text.clone());
+            } else {
+                data_list.push(escape_xml(&self.text));
+            }
+        }
+        for span in self.spans.iter() {
+            let value = span.svg();
+            if !value.is_empty() {
+                data_list.push(value);
+            }
+        }
+        let data = data_list.join("\n");
 
-        SVGTag {
+        let text = SVGTag {
             tag: TAG_TEXT,
             attrs,
-            data: Some(self.text.clone()),
+            data: Some(data),
+        };
+        if let Some(ref link) = self.link {
+            return SVGTag {
+                tag: TAG_LINK,
+                attrs: vec![(ATTR_HREF, link.as_str().into())],
+                data: Some(text.to_string()),
+            }
+            .write_svg(out);
         }
-        .to_string()
+        text.write_svg(out);
     }
 }
 
@@ -569,6 +1658,8 @@ fn generate_circle_symbol(points: &[Point], c: Circle) -> String {
 #[derive(Clone, PartialEq, Debug)]
 pub struct Pie {
     pub fill: Color,
+    // id of the defs(e.g. gradient) to fill, it is preferred to fill color
+    pub fill_id: Option<String>,
     pub stroke_color: Option<Color>,
     pub cx: f32,
     pub cy: f32,
@@ -577,12 +1668,15 @@ pub struct Pie {
     pub start_angle: f32,
     pub delta: f32,
     pub border_radius: f32,
+    // tooltip of the element
+    pub title: Option<String>,
 }
 
 impl Default for Pie {
     fn default() -> Self {
         Pie {
             fill: (0, 0, 0).into(),
+            fill_id: None,
             stroke_color: None,
             cx: 0.0,
             cy: 0.0,
@@ -591,24 +1685,25 @@ impl Default for Pie {
             start_angle: 0.0,
             delta: 0.0,
             border_radius: 8.0,
+            title: None,
         }
     }
 }
 
 impl Pie {
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         let r = self.r;
-        let r_str = format_float(r);
+        let r_str = format_coordinate(r);
 
         let ir = self.ir;
-        let ir_str = format_float(ir);
+        let ir_str = format_coordinate(ir);
 
         let mut path_list = vec![];
         let mut border_radius = self.border_radius;
         if border_radius != 0.0 && self.r - self.ir < border_radius {
             border_radius = 2.0;
         }
-        let border_radius_str = format_float(border_radius);
+        let border_radius_str = format_coordinate(border_radius);
         let border_angle = 2.0_f32;
         let start_angle = self.start_angle;
         let end_angle = start_angle + self.delta;
@@ -617,15 +1712,15 @@ impl Pie {
         if self.ir == 0.0 {
             path_list.push(format!(
                 "M{},{}",
-                format_float(self.cx),
-                format_float(self.cy),
+                format_coordinate(self.cx),
+                format_coordinate(self.cy),
             ));
         } else {
             let point = get_pie_point(self.cx, self.cy, self.ir + border_radius, start_angle);
             path_list.push(format!(
                 "M{},{}",
-                format_float(point.x),
-                format_float(point.y)
+                format_coordinate(point.x),
+                format_coordinate(point.y)
             ));
         }
 
@@ -633,16 +1728,16 @@ impl Pie {
         let point = get_pie_point(self.cx, self.cy, self.r - border_radius, start_angle);
         path_list.push(format!(
             "L{},{}",
-            format_float(point.x),
-            format_float(point.y)
+            format_coordinate(point.x),
+            format_coordinate(point.y)
         ));
 
         // 左上圆角
         let point = get_pie_point(self.cx, self.cy, self.r, start_angle + border_angle);
         path_list.push(format!(
             "A{border_radius_str} {border_radius_str} 0 0 1 {},{}",
-            format_float(point.x),
-            format_float(point.y)
+            format_coordinate(point.x),
+            format_coordinate(point.y)
         ));
 
         // 大圆弧
@@ -656,32 +1751,32 @@ impl Pie {
             );
             path_list.push(format!(
                 "A{r_str} {r_str} 0 0 1 {},{}",
-                format_float(point.x),
-                format_float(point.y)
+                format_coordinate(point.x),
+                format_coordinate(point.y)
             ));
         }
 
         let point = get_pie_point(self.cx, self.cy, self.r, end_angle - border_angle);
         path_list.push(format!(
             "A{r_str} {r_str} 0 0 1 {},{}",
-            format_float(point.x),
-            format_float(point.y)
+            format_coordinate(point.x),
+            format_coordinate(point.y)
         ));
 
         // 右上圆角
         let point = get_pie_point(self.cx, self.cy, self.r - border_radius, end_angle);
         path_list.push(format!(
             "A{border_radius_str} {border_radius_str} 0 0 1 {},{}",
-            format_float(point.x),
-            format_float(point.y)
+            format_coordinate(point.x),
+            format_coordinate(point.y)
         ));
 
         // 右侧直线
         let point = get_pie_point(self.cx, self.cy, self.ir + border_radius, end_angle);
         path_list.push(format!(
             "L{},{}",
-            format_float(point.x),
-            format_float(point.y)
+            format_coordinate(point.x),
+            format_coordinate(point.y)
         ));
 
         if self.ir > 0.0 {
@@ -689,8 +1784,8 @@ impl Pie {
             let point = get_pie_point(self.cx, self.cy, self.ir, end_angle - border_angle);
             path_list.push(format!(
                 "A{border_radius_str} {border_radius_str} 0 0 1 {},{}",
-                format_float(point.x),
-                format_float(point.y)
+                format_coordinate(point.x),
+                format_coordinate(point.y)
             ));
 
             // 小圆弧
@@ -699,36 +1794,230 @@ impl Pie {
                 let point = get_pie_point(self.cx, self.cy, self.ir, end_angle - 180.0);
                 path_list.push(format!(
                     "A{ir_str} {ir_str} 0 0 0 {},{}",
-                    format_float(point.x),
-                    format_float(point.y)
+                    format_coordinate(point.x),
+                    format_coordinate(point.y)
                 ));
             }
 
             let point = get_pie_point(self.cx, self.cy, self.ir, start_angle + border_angle);
             path_list.push(format!(
                 "A{ir_str} {ir_str} 0 0 0 {},{}",
-                format_float(point.x),
-                format_float(point.y)
+                format_coordinate(point.x),
+                format_coordinate(point.y)
             ));
 
             // 左下圆角
             let point = get_pie_point(self.cx, self.cy, self.ir + border_radius, start_angle);
             path_list.push(format!(
                 "A{border_radius_str} {border_radius_str} 0 0 1 {},{}",
-                format_float(point.x),
-                format_float(point.y)
+                format_coordinate(point.x),
+                format_coordinate(point.y)
             ));
         }
 
         path_list.push("Z".to_string());
 
-        let mut attrs = vec![
-            (ATTR_D, path_list.join(" ")),
-            (ATTR_FILL, self.fill.hex()),
-            (ATTR_FILL_OPACITY, convert_opacity(&self.fill)),
-        ];
+        let mut attrs = if let Some(ref fill_id) = self.fill_id {
+            vec![
+                (ATTR_D, path_list.join(" ").into()),
+                (ATTR_FILL, convert_url(fill_id)),
+            ]
+        } else {
+            vec![
+                (ATTR_D, path_list.join(" ").into()),
+                (ATTR_FILL, hex_attr(&self.fill)),
+                (ATTR_FILL_OPACITY, convert_opacity(&self.fill)),
+            ]
+        };
+        if let Some(color) = self.stroke_color {
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
+            attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
+        }
+        SVGTag {
+            tag: TAG_PATH,
+            attrs,
+            data: generate_title(&self.title),
+        }
+        .write_svg(out);
+    }
+}
+
+// Generates the path of arc from the start angle to the end angle,
+// the angle is clockwise from the top.
+fn generate_arc_path(
+    cx: f32,
+    cy: f32,
+    r: f32,
+    start_angle: f32,
+    end_angle: f32,
+    move_to: bool,
+) -> String {
+    let (start_angle, end_angle, sweep) = if end_angle >= start_angle {
+        (start_angle, end_angle.min(start_angle + 359.99), 1)
+    } else {
+        (start_angle, end_angle.max(start_angle - 359.99), 0)
+    };
+    let large_arc = if (end_angle - start_angle).abs() > 180.0 {
+        1
+    } else {
+        0
+    };
+    let start = get_pie_point(cx, cy, r, start_angle);
+    let end = get_pie_point(cx, cy, r, end_angle);
+    let r_str = format_coordinate(r);
+    let command = if move_to { "M" } else { "L" };
+    format!(
+        "{command}{},{} A{r_str} {r_str} 0 {large_arc} {sweep} {},{}",
+        format_coordinate(start.x),
+        format_coordinate(start.y),
+        format_coordinate(end.x),
+        format_coordinate(end.y)
+    )
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Arc {
+    pub stroke_color: Color,
+    pub stroke_width: f32,
+    // line cap of the arc, e.g. round
+    pub stroke_linecap: Option<String>,
+    pub cx: f32,
+    pub cy: f32,
+    pub r: f32,
+    // angle is clockwise from the top
+    pub start_angle: f32,
+    pub end_angle: f32,
+}
+
+impl Default for Arc {
+    fn default() -> Self {
+        Arc {
+            stroke_color: (0, 0, 0).into(),
+            stroke_width: 1.0,
+            stroke_linecap: None,
+            cx: 0.0,
+            cy: 0.0,
+            r: 0.0,
+            start_angle: 0.0,
+            end_angle: 0.0,
+        }
+    }
+}
+
+impl Arc {
+    pub fn write_svg(&self, out: &mut String) {
+        if self.r <= 0.0 || self.start_angle == self.end_angle {
+            return;
+        }
+        SVGTag {
+            tag: TAG_PATH,
+            attrs: vec![
+                (
+                    ATTR_D,
+                    generate_arc_path(
+                        self.cx,
+                        self.cy,
+                        self.r,
+                        self.start_angle,
+                        self.end_angle,
+                        true,
+                    )
+                    .into(),
+                ),
+                (ATTR_STROKE, hex_attr(&self.stroke_color)),
+                (ATTR_STROKE_OPACITY, convert_opacity(&self.stroke_color)),
+                (ATTR_STROKE_WIDTH, coordinate_attr(self.stroke_width)),
+                (
+                    ATTR_STROKE_LINECAP,
+                    self.stroke_linecap.clone().unwrap_or_default().into(),
+                ),
+                (ATTR_FILL, "none".into()),
+            ],
+            ..Default::default()
+        }
+        .write_svg(out);
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Sector {
+    pub fill: Color,
+    // id of the defs(e.g. gradient) to fill, it is preferred to fill color
+    pub fill_id: Option<String>,
+    pub stroke_color: Option<Color>,
+    pub cx: f32,
+    pub cy: f32,
+    pub r: f32,
+    // inner radius, the sector is an annular sector if it is not zero
+    pub ir: f32,
+    // angle is clockwise from the top
+    pub start_angle: f32,
+    pub end_angle: f32,
+    // the corners are rounded if it is set
+    pub corner_radius: Option<f32>,
+}
+
+impl Sector {
+    pub fn write_svg(&self, out: &mut String) {
+        if self.r <= 0.0 || self.start_angle == self.end_angle {
+            return;
+        }
+        let (start_angle, end_angle) = if self.start_angle < self.end_angle {
+            (self.start_angle, self.end_angle)
+        } else {
+            (self.end_angle, self.start_angle)
+        };
+        if let Some(corner_radius) = self.corner_radius {
+            return Pie {
+                fill: self.fill,
+                fill_id: self.fill_id.clone(),
+                stroke_color: self.stroke_color,
+                cx: self.cx,
+                cy: self.cy,
+                r: self.r,
+                ir: self.ir,
+                start_angle,
+                delta: end_angle - start_angle,
+                border_radius: corner_radius,
+                ..Default::default()
+            }
+            .write_svg(out);
+        }
+        let mut path_list = vec![generate_arc_path(
+            self.cx,
+            self.cy,
+            self.r,
+            start_angle,
+            end_angle,
+            true,
+        )];
+        if self.ir > 0.0 {
+            path_list.push(generate_arc_path(
+                self.cx,
+                self.cy,
+                self.ir,
+                end_angle,
+                start_angle,
+                false,
+            ));
+        } else {
+            path_list.push(format!(
+                "L{},{}",
+                format_coordinate(self.cx),
+                format_coordinate(self.cy)
+            ));
+        }
+        path_list.push("Z".to_string());
+
+        let mut attrs = vec![(ATTR_D, path_list.join(" ").into())];
+        if let Some(ref fill_id) = self.fill_id {
+            attrs.push((ATTR_FILL, convert_url(fill_id)));
+        } else {
+            attrs.push((ATTR_FILL, hex_attr(&self.fill)));
+            attrs.push((ATTR_FILL_OPACITY, convert_opacity(&self.fill)));
+        }
         if let Some(color) = self.stroke_color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
         SVGTag {
@@ -736,7 +2025,7 @@ impl Pie {
             attrs,
             ..Default::default()
         }
-        .to_string()
+        .write_svg(out);
     }
 }
 
@@ -749,12 +2038,13 @@ struct BaseLine {
     pub is_smooth: bool,
     pub close: bool,
     pub stroke_dash_array: Option<String>,
+    pub filter_id: Option<String>,
 }
 
 impl BaseLine {
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         if self.points.is_empty() || self.stroke_width <= 0.0 {
-            return "".to_string();
+            return;
         }
         let path = if self.is_smooth {
             SmoothCurve {
@@ -772,8 +2062,8 @@ impl BaseLine {
                 arr.push(format!(
                     "{} {} {}",
                     action,
-                    format_float(p.x),
-                    format_float(p.y)
+                    format_coordinate(p.x),
+                    format_coordinate(p.y)
                 ));
             }
             if self.close {
@@ -783,29 +2073,31 @@ impl BaseLine {
         };
 
         let mut attrs = vec![
-            (ATTR_D, path),
-            (ATTR_STROKE_WIDTH, format_float(self.stroke_width)),
+            (ATTR_D, path.into()),
+            (ATTR_STROKE_WIDTH, coordinate_attr(self.stroke_width)),
         ];
         if let Some(fill) = self.fill {
-            attrs.push((ATTR_FILL, fill.hex()));
+            attrs.push((ATTR_FILL, hex_attr(&fill)));
             attrs.push((ATTR_FILL_OPACITY, convert_opacity(&fill)));
         } else {
-            attrs.push((ATTR_FILL, "none".to_string()));
+            attrs.push((ATTR_FILL, "none".into()));
         }
 
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
         if let Some(stroke_dash_array) = &self.stroke_dash_array {
-            attrs.push((ATTR_STROKE_DASH_ARRAY, stroke_dash_array.to_string()));
+            attrs.push((ATTR_STROKE_DASH_ARRAY, stroke_dash_array.as_str().into()));
+        }
+        if let Some(ref filter_id) = self.filter_id {
+            attrs.push((ATTR_FILTER, convert_url(filter_id)));
         }
-        let line_svg = SVGTag {
+        let line = SVGTag {
             tag: TAG_PATH,
             attrs,
             data: None,
-        }
-        .to_string();
+        };
         let symbol_svg = if let Some(ref symbol) = self.symbol {
             match symbol {
                 Symbol::Circle(r, fill) => generate_circle_symbol(
@@ -818,6 +2110,22 @@ impl BaseLine {
                         ..Default::default()
                     },
                 ),
+                // path symbol is scaled by the double stroke width
+                Symbol::Path(path) => self
+                    .points
+                    .iter()
+                    .map(|p| {
+                        PathSymbol {
+                            path: path.clone(),
+                            fill: self.color,
+                            cx: p.x,
+                            cy: p.y,
+                            size: self.stroke_width * 2.0,
+                        }
+                        .svg()
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n"),
                 Symbol::None => "".to_string(),
             }
         } else {
@@ -825,14 +2133,14 @@ impl BaseLine {
         };
 
         if symbol_svg.is_empty() {
-            line_svg
+            line.write_svg(out);
         } else {
             SVGTag {
                 tag: TAG_GROUP,
-                data: Some([line_svg, symbol_svg].join("\n")),
+                data: Some([line.to_string(), symbol_svg].join("\n")),
                 ..Default::default()
             }
-            .to_string()
+            .write_svg(out);
         }
     }
 }
@@ -844,6 +2152,8 @@ pub struct SmoothLine {
     pub stroke_width: f32,
     pub symbol: Option<Symbol>,
     pub stroke_dash_array: Option<String>,
+    // id of the filter(e.g. drop shadow) to apply
+    pub filter_id: Option<String>,
 }
 
 impl Default for SmoothLine {
@@ -854,12 +2164,13 @@ impl Default for SmoothLine {
             stroke_width: 1.0,
             symbol: Some(Symbol::Circle(2.0, None)),
             stroke_dash_array: None,
+            filter_id: None,
         }
     }
 }
 
 impl SmoothLine {
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         BaseLine {
             color: self.color,
             fill: None,
@@ -869,14 +2180,17 @@ impl SmoothLine {
             is_smooth: true,
             close: false,
             stroke_dash_array: self.stroke_dash_array.clone(),
+            filter_id: self.filter_id.clone(),
         }
-        .svg()
+        .write_svg(out);
     }
 }
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct SmoothLineFill {
     pub fill: Color,
+    // id of the defs(e.g. gradient) to fill, it is preferred to fill color
+    pub fill_id: Option<String>,
     pub points: Vec<Point>,
     pub bottom: f32,
 }
@@ -885,6 +2199,7 @@ impl Default for SmoothLineFill {
     fn default() -> Self {
         SmoothLineFill {
             fill: (255, 255, 255, 255).into(),
+            fill_id: None,
             points: vec![],
             bottom: 0.0,
         }
@@ -892,9 +2207,9 @@ impl Default for SmoothLineFill {
 }
 
 impl SmoothLineFill {
-    pub fn svg(&self) -> String {
-        if self.points.is_empty() || self.fill.is_transparent() {
-            return "".to_string();
+    pub fn write_svg(&self, out: &mut String) {
+        if self.points.is_empty() || (self.fill_id.is_none() && self.fill.is_transparent()) {
+            return;
         }
         let mut path = SmoothCurve {
             points: self.points.clone(),
@@ -905,26 +2220,46 @@ impl SmoothLineFill {
         let last = self.points[self.points.len() - 1];
         let first = self.points[0];
         let fill_path = [
-            format!("M {} {}", format_float(last.x), format_float(last.y)),
-            format!("L {} {}", format_float(last.x), format_float(self.bottom)),
-            format!("L {} {}", format_float(first.x), format_float(self.bottom)),
-            format!("L {} {}", format_float(first.x), format_float(first.y)),
+            format!(
+                "M {} {}",
+                format_coordinate(last.x),
+                format_coordinate(last.y)
+            ),
+            format!(
+                "L {} {}",
+                format_coordinate(last.x),
+                format_coordinate(self.bottom)
+            ),
+            format!(
+                "L {} {}",
+                format_coordinate(first.x),
+                format_coordinate(self.bottom)
+            ),
+            format!(
+                "L {} {}",
+                format_coordinate(first.x),
+                format_coordinate(first.y)
+            ),
         ]
         .join(" ");
         path.push_str(&fill_path);
 
-        let attrs = vec![
-            (ATTR_D, path),
-            (ATTR_FILL, self.fill.hex()),
-            (ATTR_FILL_OPACITY, convert_opacity(&self.fill)),
-        ];
+        let attrs = if let Some(ref fill_id) = self.fill_id {
+            vec![(ATTR_D, path.into()), (ATTR_FILL, convert_url(fill_id))]
+        } else {
+            vec![
+                (ATTR_D, path.into()),
+                (ATTR_FILL, hex_attr(&self.fill)),
+                (ATTR_FILL_OPACITY, convert_opacity(&self.fill)),
+            ]
+        };
 
         SVGTag {
             tag: TAG_PATH,
             attrs,
             data: None,
         }
-        .to_string()
+        .write_svg(out);
     }
 }
 
@@ -937,6 +2272,8 @@ pub struct StraightLine {
     pub symbol: Option<Symbol>,
     pub close: bool,
     pub stroke_dash_array: Option<String>,
+    // id of the filter(e.g. drop shadow) to apply
+    pub filter_id: Option<String>,
 }
 
 impl Default for StraightLine {
@@ -949,12 +2286,13 @@ impl Default for StraightLine {
             symbol: Some(Symbol::Circle(2.0, None)),
             close: false,
             stroke_dash_array: None,
+            filter_id: None,
         }
     }
 }
 
 impl StraightLine {
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         BaseLine {
             color: self.color,
             fill: self.fill,
@@ -964,23 +2302,26 @@ impl StraightLine {
             is_smooth: false,
             close: self.close,
             stroke_dash_array: self.stroke_dash_array.clone(),
+            filter_id: self.filter_id.clone(),
         }
-        .svg()
+        .write_svg(out);
     }
 }
 
 #[derive(Clone, PartialEq, Debug, Default)]
 pub struct StraightLineFill {
     pub fill: Color,
+    // id of the defs(e.g. gradient) to fill, it is preferred to fill color
+    pub fill_id: Option<String>,
     pub points: Vec<Point>,
     pub bottom: f32,
     pub close: bool,
 }
 
 impl StraightLineFill {
-    pub fn svg(&self) -> String {
-        if self.points.is_empty() || self.fill.is_transparent() {
-            return "".to_string();
+    pub fn write_svg(&self, out: &mut String) {
+        if self.points.is_empty() || (self.fill_id.is_none() && self.fill.is_transparent()) {
+            return;
         }
         let mut points = self.points.clone();
         let last = points[self.points.len() - 1];
@@ -997,25 +2338,32 @@ impl StraightLineFill {
             arr.push(format!(
                 "{} {} {}",
                 action,
-                format_float(p.x),
-                format_float(p.y)
+                format_coordinate(p.x),
+                format_coordinate(p.y)
             ));
         }
         if self.close {
             arr.push('Z'.to_string());
         }
-        let attrs = vec![
-            (ATTR_D, arr.join(" ")),
-            (ATTR_FILL, self.fill.hex()),
-            (ATTR_FILL_OPACITY, convert_opacity(&self.fill)),
-        ];
+        let attrs = if let Some(ref fill_id) = self.fill_id {
+            vec![
+                (ATTR_D, arr.join(" ").into()),
+                (ATTR_FILL, convert_url(fill_id)),
+            ]
+        } else {
+            vec![
+                (ATTR_D, arr.join(" ").into()),
+                (ATTR_FILL, hex_attr(&self.fill)),
+                (ATTR_FILL_OPACITY, convert_opacity(&self.fill)),
+            ]
+        };
 
         SVGTag {
             tag: TAG_PATH,
             attrs,
             data: None,
         }
-        .to_string()
+        .write_svg(out);
     }
 }
 
@@ -1034,9 +2382,9 @@ pub struct Grid {
 }
 
 impl Grid {
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         if (self.verticals == 0 && self.horizontals == 0) || self.stroke_width <= 0.0 {
-            return "".to_string();
+            return;
         }
         let mut points = vec![];
         if self.verticals != 0 {
@@ -1076,7 +2424,7 @@ impl Grid {
 
         let mut attrs = vec![];
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
 
@@ -1085,7 +2433,7 @@ impl Grid {
             attrs,
             data: Some(data.join("")),
         }
-        .to_string()
+        .write_svg(out);
     }
 }
 
@@ -1110,6 +2458,8 @@ pub struct Axis {
     pub tick_length: f32,
     pub tick_start: usize,
     pub tick_interval: usize,
+    // prefix of the label id, e.g. x-axis makes the id x-axis-label-0
+    pub label_id_prefix: Option<String>,
 }
 impl Default for Axis {
     fn default() -> Self {
@@ -1133,12 +2483,18 @@ impl Default for Axis {
             tick_length: 5.0,
             tick_start: 0,
             tick_interval: 0,
+            label_id_prefix: None,
         }
     }
 }
 
 impl Axis {
     pub fn svg(&self) -> Result<String> {
+        let mut out = String::new();
+        self.write_svg(&mut out)?;
+        Ok(out)
+    }
+    pub fn write_svg(&self, out: &mut String) -> Result<()> {
         let left = self.left;
         let top = self.top;
         let width = self.width;
@@ -1148,7 +2504,7 @@ impl Axis {
         let mut attrs = vec![];
         let mut is_transparent = false;
         if let Some(color) = self.stroke_color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, hex_attr(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
 
             is_transparent = color.is_transparent();
@@ -1206,8 +2562,9 @@ impl Axis {
                 .map(|item| format_string(item, formatter))
                 .collect();
             if self.position == Position::Top || self.position == Position::Bottom {
-                let f = font::get_font(&self.font_family).context(GetFontSnafu)?;
-                let total_measure = font::measure_text(f, font_size, &text_list.join(" "));
+                let fonts = font::get_fonts(&self.font_family).context(GetFontSnafu)?;
+                let total_measure =
+                    font::measure_text_fonts(&fonts, font_size, &text_list.join(" "));
                 // 位置不够
                 if total_measure.width() > axis_length {
                     text_unit_count += (total_measure.width() / axis_length).ceil() as usize;
@@ -1270,7 +2627,7 @@ impl Axis {
         let name_rotate = self.name_rotate / std::f32::consts::PI * 180.0;
         if !text_list.is_empty() {
             let name_gap = self.name_gap;
-            let f = font::get_font(&self.font_family).context(GetFontSnafu)?;
+            let fonts = font::get_fonts(&self.font_family).context(GetFontSnafu)?;
             let mut data_len = self.data.len();
             let is_name_align_start = self.name_align == Align::Left;
             if is_name_align_start {
@@ -1282,7 +2639,7 @@ impl Axis {
                 if index % text_unit_count != 0 {
                     continue;
                 }
-                let b = font::measure_text(f, font_size, text);
+                let b = font::measure_text_fonts(&fonts, font_size, text);
                 let mut unit_offset = unit * index as f32 + unit / 2.0;
                 if is_name_align_start {
                     unit_offset -= unit / 2.0;
@@ -1340,13 +2697,21 @@ impl Axis {
                         y,
                         transform,
                         text_anchor,
+                        id: self
+                            .label_id_prefix
+                            .as_ref()
+                            .map(|prefix| format!("{prefix}-label-{index}")),
+                        class: self
+                            .label_id_prefix
+                            .as_ref()
+                            .map(|prefix| format!("{prefix}-label")),
                         ..Default::default()
                     }
                     .svg(),
                 );
             }
         };
-        Ok(SVGTag {
+        SVGTag {
             tag: TAG_GROUP,
             data: Some(
                 [
@@ -1362,7 +2727,8 @@ impl Axis {
             ),
             ..Default::default()
         }
-        .to_string())
+        .write_svg(out);
+        Ok(())
     }
 }
 
@@ -1390,13 +2756,15 @@ pub(crate) fn measure_legends(
     (width + margin, widths)
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Hash, Debug, Default)]
 pub enum LegendCategory {
     #[default]
     Normal,
     RoundRect,
     Circle,
     Rect,
+    // the legend matches the category and style of series
+    Auto,
 }
 
 #[derive(Clone, PartialEq, Debug, Default)]
@@ -1411,9 +2779,14 @@ pub struct Legend {
     pub left: f32,
     pub top: f32,
     pub category: LegendCategory,
+    // dash array of the legend line
+    pub stroke_dash_array: Option<String>,
+    // id and class of legend item, they can be used to toggle series
+    pub id: Option<String>,
+    pub class: Option<String>,
 }
 impl Legend {
-    pub fn svg(&self) -> String {
+    pub fn write_svg(&self, out: &mut String) {
         let stroke_width = 2.0;
         let mut data: Vec<String> = vec![];
         match self.category {
@@ -1444,6 +2817,7 @@ impl Legend {
                         height,
                         rx: Some(2.0),
                         ry: Some(2.0),
+                        ..Default::default()
                     }
                     .svg(),
                 );
@@ -1457,6 +2831,7 @@ impl Legend {
                         cx: self.left + LEGEND_WIDTH * 0.6,
                         cy: self.top + LEGEND_HEIGHT / 2.0,
                         r: 5.5,
+                        ..Default::default()
                     }
                     .svg(),
                 );
@@ -1470,7 +2845,7 @@ impl Legend {
                         top: self.top + LEGEND_HEIGHT / 2.0,
                         right: self.left + LEGEND_WIDTH,
                         bottom: self.top + LEGEND_HEIGHT / 2.0,
-                        ..Default::default()
+                        stroke_dash_array: self.stroke_dash_array.clone(),
                     }
                     .svg(),
                 );
@@ -1482,40 +2857,507 @@ impl Legend {
                         cx: self.left + LEGEND_WIDTH / 2.0,
                         cy: self.top + LEGEND_HEIGHT / 2.0,
                         r: 5.5,
+                        ..Default::default()
                     }
                     .svg(),
                 );
             }
         }
-        data.push(
-            Text {
-                text: self.text.clone(),
-                font_family: Some(self.font_family.clone()),
-                font_color: self.font_color,
-                font_size: Some(self.font_size),
-                font_weight: self.font_weight.clone(),
-                x: Some(self.left + LEGEND_WIDTH + LEGEND_TEXT_MARGIN),
-                y: Some(self.top + self.font_size),
-                ..Default::default()
-            }
-            .svg(),
-        );
+        data.push(
+            Text {
+                text: self.text.clone(),
+                font_family: Some(self.font_family.clone()),
+                font_color: self.font_color,
+                font_size: Some(self.font_size),
+                font_weight: self.font_weight.clone(),
+                x: Some(self.left + LEGEND_WIDTH + LEGEND_TEXT_MARGIN),
+                y: Some(self.top + self.font_size),
+                ..Default::default()
+            }
+            .svg(),
+        );
+        SVGTag {
+            tag: TAG_GROUP,
+            attrs: vec![
+                (ATTR_ID, self.id.clone().unwrap_or_default().into()),
+                (ATTR_CLASS, self.class.clone().unwrap_or_default().into()),
+            ],
+            data: Some(data.join("\n")),
+        }
+        .write_svg(out);
+    }
+}
+
+fn generate_gradient_stops(stops: &[(f32, Color)]) -> String {
+    let stops: Vec<String> = stops
+        .iter()
+        .map(|(offset, color)| {
+            SVGTag {
+                tag: TAG_STOP,
+                attrs: vec![
+                    (ATTR_OFFSET, format_float(*offset).into()),
+                    (ATTR_STOP_COLOR, hex_attr(color)),
+                    (ATTR_STOP_OPACITY, convert_opacity(color)),
+                ],
+                data: None,
+            }
+            .to_string()
+        })
+        .collect();
+    stops.join("\n")
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Gradient {
+    pub id: String,
+    pub direction: Direction,
+    // offset(0-1) and color of the gradient stops
+    pub stops: Vec<(f32, Color)>,
+}
+
+impl Gradient {
+    pub fn write_svg(&self, out: &mut String) {
+        if self.id.is_empty() || self.stops.is_empty() {
+            return;
+        }
+        let (x1, y1, x2, y2) = match self.direction {
+            Direction::TopToBottom => ("0", "0", "0", "1"),
+            Direction::BottomToTop => ("0", "1", "0", "0"),
+            Direction::LeftToRight => ("0", "0", "1", "0"),
+            Direction::RightToLeft => ("1", "0", "0", "0"),
+        };
+        let gradient = SVGTag::new(
+            TAG_LINEAR_GRADIENT,
+            generate_gradient_stops(&self.stops),
+            vec![
+                (ATTR_ID, self.id.as_str().into()),
+                (ATTR_X1, x1.into()),
+                (ATTR_Y1, y1.into()),
+                (ATTR_X2, x2.into()),
+                (ATTR_Y2, y2.into()),
+            ],
+        )
+        .to_string();
+        SVGTag::new(TAG_DEFS, gradient, vec![]).write_svg(out);
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct RadialGradient {
+    pub id: String,
+    // center and radius, they are the fraction of the bounding box
+    // unless the gradient uses user space
+    pub cx: f32,
+    pub cy: f32,
+    pub r: f32,
+    // use the coordinate of canvas instead of the bounding box
+    pub user_space: bool,
+    // offset(0-1) and color of the gradient stops
+    pub stops: Vec<(f32, Color)>,
+}
+
+impl Default for RadialGradient {
+    fn default() -> Self {
+        RadialGradient {
+            id: "".to_string(),
+            cx: 0.5,
+            cy: 0.5,
+            r: 0.5,
+            user_space: false,
+            stops: vec![],
+        }
+    }
+}
+
+impl RadialGradient {
+    pub fn write_svg(&self, out: &mut String) {
+        if self.id.is_empty() || self.stops.is_empty() {
+            return;
+        }
+        let mut attrs = vec![
+            (ATTR_ID, self.id.as_str().into()),
+            (ATTR_CX, coordinate_attr(self.cx)),
+            (ATTR_CY, coordinate_attr(self.cy)),
+            (ATTR_R, coordinate_attr(self.r)),
+        ];
+        if self.user_space {
+            attrs.push((ATTR_GRADIENT_UNITS, "userSpaceOnUse".into()));
+        }
+        let gradient = SVGTag::new(
+            TAG_RADIAL_GRADIENT,
+            generate_gradient_stops(&self.stops),
+            attrs,
+        )
+        .to_string();
+        SVGTag::new(TAG_DEFS, gradient, vec![]).write_svg(out);
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DropShadow {
+    pub id: String,
+    pub dx: f32,
+    pub dy: f32,
+    pub std_deviation: f32,
+    pub color: Color,
+}
+
+impl DropShadow {
+    pub fn write_svg(&self, out: &mut String) {
+        if self.id.is_empty() {
+            return;
+        }
+        let shadow = SVGTag {
+            tag: TAG_FE_DROP_SHADOW,
+            attrs: vec![
+                (ATTR_DX, coordinate_attr(self.dx)),
+                (ATTR_DY, coordinate_attr(self.dy)),
+                (ATTR_STD_DEVIATION, coordinate_attr(self.std_deviation)),
+                (ATTR_FLOOD_COLOR, hex_attr(&self.color)),
+                (ATTR_FLOOD_OPACITY, convert_opacity(&self.color)),
+            ],
+            data: None,
+        }
+        .to_string();
+        // enlarge the filter region to avoid clipping the shadow
+        let filter = SVGTag::new(
+            TAG_FILTER,
+            shadow,
+            vec![
+                (ATTR_ID, self.id.as_str().into()),
+                (ATTR_X, "-20%".into()),
+                (ATTR_Y, "-20%".into()),
+                (ATTR_WIDTH, "140%".into()),
+                (ATTR_HEIGHT, "140%".into()),
+            ],
+        )
+        .to_string();
+        SVGTag::new(TAG_DEFS, filter, vec![]).write_svg(out);
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Pattern {
+    pub id: String,
+    pub category: PatternCategory,
+    pub color: Color,
+    pub background: Option<Color>,
+    // size of the pattern tile
+    pub size: f32,
+    pub stroke_width: f32,
+}
+
+impl Default for Pattern {
+    fn default() -> Self {
+        Pattern {
+            id: "".to_string(),
+            category: PatternCategory::DiagonalHatch,
+            color: (0, 0, 0).into(),
+            background: None,
+            size: 8.0,
+            stroke_width: 1.5,
+        }
+    }
+}
+
+impl Pattern {
+    pub fn write_svg(&self, out: &mut String) {
+        if self.id.is_empty() || self.size <= 0.0 {
+            return;
+        }
+        let size = self.size;
+        let mut data = vec![];
+        if let Some(background) = self.background {
+            data.push(
+                Rect {
+                    fill: Some(background),
+                    width: size,
+                    height: size,
+                    ..Default::default()
+                }
+                .svg(),
+            );
+        }
+        let hatch = |d: String| -> String {
+            SVGTag {
+                tag: TAG_PATH,
+                attrs: vec![
+                    (ATTR_D, d.into()),
+                    (ATTR_STROKE, hex_attr(&self.color)),
+                    (ATTR_STROKE_OPACITY, convert_opacity(&self.color)),
+                    (ATTR_STROKE_WIDTH, coordinate_attr(self.stroke_width)),
+                ],
+                data: None,
+            }
+            .to_string()
+        };
+        let s = format_coordinate(size);
+        // the corners are extended to keep the lines continuous between tiles
+        let diagonal = format!(
+            "M0,{s} L{s},0 M-1,1 L1,-1 M{},{} L{},{}",
+            format_coordinate(size - 1.0),
+            format_coordinate(size + 1.0),
+            format_coordinate(size + 1.0),
+            format_coordinate(size - 1.0)
+        );
+        match self.category {
+            PatternCategory::DiagonalHatch => data.push(hatch(diagonal)),
+            PatternCategory::CrossHatch => {
+                data.push(hatch(diagonal));
+                data.push(hatch(format!(
+                    "M0,0 L{s},{s} M-1,{} L1,{} M{},-1 L{},1",
+                    format_coordinate(size - 1.0),
+                    format_coordinate(size + 1.0),
+                    format_coordinate(size - 1.0),
+                    format_coordinate(size + 1.0)
+                )));
+            }
+            PatternCategory::Dots => data.push(
+                Circle {
+                    fill: Some(self.color),
+                    stroke_width: 0.0,
+                    cx: size / 2.0,
+                    cy: size / 2.0,
+                    r: size / 4.0,
+                    ..Default::default()
+                }
+                .svg(),
+            ),
+        }
+        let pattern = SVGTag::new(
+            TAG_PATTERN,
+            data.join("\n"),
+            vec![
+                (ATTR_ID, self.id.as_str().into()),
+                (ATTR_PATTERN_UNITS, "userSpaceOnUse".into()),
+                (ATTR_WIDTH, s.as_str().into()),
+                (ATTR_HEIGHT, s.as_str().into()),
+            ],
+        )
+        .to_string();
+        SVGTag::new(TAG_DEFS, pattern, vec![]).write_svg(out);
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ClipPath {
+    pub id: String,
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl ClipPath {
+    pub fn write_svg(&self, out: &mut String) {
+        let rect = SVGTag {
+            tag: TAG_RECT,
+            attrs: vec![
+                (ATTR_X, coordinate_attr(self.left)),
+                (ATTR_Y, coordinate_attr(self.top)),
+                (ATTR_WIDTH, coordinate_attr(self.width)),
+                (ATTR_HEIGHT, coordinate_attr(self.height)),
+            ],
+            data: None,
+        }
+        .to_string();
+        let clip_path = SVGTag::new(
+            TAG_CLIP_PATH,
+            rect,
+            vec![(ATTR_ID, self.id.as_str().into())],
+        )
+        .to_string();
+        SVGTag::new(TAG_DEFS, clip_path, vec![]).write_svg(out);
+    }
+}
+
+/// Inset chart which is rendered in the area of parent chart,
+/// the svg of inset is scaled to fit the area.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Inset {
+    // the area of inset, it is relative to the parent chart
+    pub area: Box,
+    // the svg of inset chart, e.g. the svg of a small line chart
+    pub svg: String,
+}
+
+impl Inset {
+    pub fn new(area: Box, svg: String) -> Self {
+        Inset { area, svg }
+    }
+    pub fn write_svg(&self, out: &mut String) {
+        let Some(tag) = self.svg.lines().next().and_then(parse_start_tag) else {
+            return;
+        };
+        if tag.name != TAG_SVG {
+            return;
+        }
+        // the size of inset svg is used as view box, so it is scaled to the area
+        let get_size = |key: &str, value: f32| -> f32 {
+            tag.get(key)
+                .and_then(|value| value.parse::<f32>().ok())
+                .unwrap_or(value)
+        };
+        let width = get_size(ATTR_WIDTH, self.area.width());
+        let height = get_size(ATTR_HEIGHT, self.area.height());
+        SVGTag::new(
+            TAG_SVG,
+            self.svg.clone(),
+            vec![
+                (ATTR_X, coordinate_attr(self.area.left)),
+                (ATTR_Y, coordinate_attr(self.area.top)),
+                (ATTR_WIDTH, coordinate_attr(self.area.width())),
+                (ATTR_HEIGHT, coordinate_attr(self.area.height())),
+                (
+                    ATTR_VIEW_BOX,
+                    format_attr(format_args!("0 0 {} {}", width, height)),
+                ),
+            ],
+        )
+        .write_svg(out);
+    }
+}
+
+/// The components shared by canvas and its children, they are locked by mutex
+/// only if they are rendered in parallel.
+#[cfg(feature = "parallel")]
+pub(crate) type SharedComponents = std::sync::Arc<Mutex<Vec<Component>>>;
+#[cfg(not(feature = "parallel"))]
+pub(crate) type SharedComponents = Rc<RefCell<Vec<Component>>>;
+
+/// Calls the function with the mutable shared components.
+#[cfg(feature = "parallel")]
+pub(crate) fn with_shared_components<T>(
+    components: &SharedComponents,
+    f: impl FnOnce(&mut Vec<Component>) -> T,
+) -> T {
+    f(&mut components.lock().unwrap())
+}
+/// Calls the function with the mutable shared components.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn with_shared_components<T>(
+    components: &SharedComponents,
+    f: impl FnOnce(&mut Vec<Component>) -> T,
+) -> T {
+    f(&mut components.borrow_mut())
+}
+
+#[derive(Default)]
+pub struct Group {
+    pub id: Option<String>,
+    pub class: Option<String>,
+    // transform of group, e.g. translate(10 10) rotate(45)
+    pub transform: Option<String>,
+    pub opacity: Option<f32>,
+    // accessible name of the group, e.g. the name of series
+    pub aria_label: Option<String>,
+    // id of the clip path to apply
+    pub clip_path_id: Option<String>,
+    // the groups of z index are reordered by it when the canvas is rendered,
+    // the higher one is drawn on top
+    pub z_index: Option<i32>,
+    pub(crate) components: SharedComponents,
+}
+
+impl Group {
+    /// Calls the function with the components of group.
+    pub fn with_components<T>(&self, f: impl FnOnce(&mut Vec<Component>) -> T) -> T {
+        with_shared_components(&self.components, f)
+    }
+    fn tag(&self) -> SVGTag<'_> {
+        let mut attrs = vec![
+            (ATTR_ID, self.id.clone().unwrap_or_default().into()),
+            (ATTR_CLASS, self.class.clone().unwrap_or_default().into()),
+            (
+                ATTR_TRANSFORM,
+                self.transform.clone().unwrap_or_default().into(),
+            ),
+            (ATTR_OPACITY, format_option_float(self.opacity)),
+            (
+                ATTR_ARIA_LABEL,
+                self.aria_label.clone().unwrap_or_default().into(),
+            ),
+        ];
+        if let Some(ref clip_path_id) = self.clip_path_id {
+            attrs.push((ATTR_CLIP_PATH, convert_url(clip_path_id)));
+        }
         SVGTag {
             tag: TAG_GROUP,
-            data: Some(data.join("\n")),
-            ..Default::default()
+            attrs,
+            data: None,
+        }
+    }
+    /// Wraps the svg of components in the group.
+    pub fn svg(&self, data: String) -> String {
+        let mut tag = self.tag();
+        tag.data = Some(data);
+        tag.to_string()
+    }
+    /// Writes the group to the output, the data of group is written by the function.
+    /// Nothing is written if the data is empty.
+    pub fn write_svg<E>(
+        &self,
+        out: &mut String,
+        write_data: impl FnOnce(&mut String) -> std::result::Result<(), E>,
+    ) -> std::result::Result<(), E> {
+        let start = out.len();
+        self.tag().write_start_tag(out);
+        out.push_str(">\n");
+        let data_start = out.len();
+        write_data(out)?;
+        if out.len() == data_start {
+            out.truncate(start);
+        } else {
+            out.push_str("\n</");
+            out.push_str(TAG_GROUP);
+            out.push('>');
         }
-        .to_string()
+        Ok(())
     }
 }
 
+impl_svg!(
+    Line,
+    Rect,
+    Polyline,
+    Circle,
+    Ellipse,
+    PathSymbol,
+    Arrow,
+    Polygon,
+    Path,
+    Bubble,
+    TextSpan,
+    Text,
+    Pie,
+    Arc,
+    Sector,
+    SmoothLine,
+    SmoothLineFill,
+    StraightLine,
+    StraightLineFill,
+    Grid,
+    Legend,
+    Gradient,
+    RadialGradient,
+    DropShadow,
+    Pattern,
+    ClipPath,
+    Inset
+);
+
 #[cfg(test)]
 mod tests {
     use super::{
-        Arrow, Axis, Bubble, Circle, Grid, Legend, LegendCategory, Line, Pie, Polygon, Polyline,
-        Rect, SmoothLine, SmoothLineFill, StraightLine, StraightLineFill, Text,
+        coordinate_attr, format_attr, Arc, Arrow, Axis, Bubble, Circle, ClipPath, Cow, DropShadow,
+        Ellipse, Gradient, Grid, Legend, LegendCategory, Line, Path, PathSymbol, Pattern, Pie,
+        Polygon, Polyline, RadialGradient, Rect, Sector, SmoothLine, SmoothLineFill, StraightLine,
+        StraightLineFill, Text, TextSpan,
+    };
+    use crate::{
+        Align, CubicBezier, Direction, PatternCategory, Position, Symbol, VerticalAlign,
+        DEFAULT_FONT_FAMILY,
     };
-    use crate::{Align, Position, Symbol, DEFAULT_FONT_FAMILY};
     use pretty_assertions::assert_eq;
     #[test]
     fn line() {
@@ -1593,6 +3435,7 @@ mod tests {
                 height: 20.0,
                 rx: Some(3.0),
                 ry: Some(4.0),
+                ..Default::default()
             }
             .svg()
         );
@@ -1608,6 +3451,7 @@ mod tests {
                 height: 20.0,
                 rx: Some(3.0),
                 ry: Some(4.0),
+                ..Default::default()
             }
             .svg()
         );
@@ -1623,6 +3467,20 @@ mod tests {
             }
             .svg()
         );
+
+        assert_eq!(
+            r###"<rect x="0" y="0" width="50" height="20" fill="url(#gradient)"/>"###,
+            Rect {
+                fill: Some((255, 255, 255).into()),
+                fill_id: Some("gradient".to_string()),
+                left: 0.0,
+                top: 0.0,
+                width: 50.0,
+                height: 20.0,
+                ..Default::default()
+            }
+            .svg()
+        );
     }
 
     #[test]
@@ -1709,6 +3567,7 @@ mod tests {
                 cx: 10.0,
                 cy: 10.0,
                 r: 3.0,
+                ..Default::default()
             }
             .svg()
         );
@@ -1722,6 +3581,7 @@ mod tests {
                 cx: 10.0,
                 cy: 10.0,
                 r: 3.0,
+                ..Default::default()
             }
             .svg()
         );
@@ -1735,6 +3595,27 @@ mod tests {
                 cx: 10.0,
                 cy: 10.0,
                 r: 3.0,
+                ..Default::default()
+            }
+            .svg()
+        );
+    }
+
+    #[test]
+    fn path_symbol() {
+        let c = PathSymbol::default();
+        assert_eq!("", c.path);
+        assert_eq!(3.0, c.size);
+        assert_eq!("", c.svg());
+
+        assert_eq!(
+            r###"<path d="M 0 -1 L 1 1 L -1 1 Z" transform="translate(10 20) scale(4)" fill="#FF0000"/>"###,
+            PathSymbol {
+                path: "M 0 -1 L 1 1 L -1 1 Z".to_string(),
+                fill: Some((255, 0, 0).into()),
+                cx: 10.0,
+                cy: 20.0,
+                size: 4.0,
             }
             .svg()
         );
@@ -1754,6 +3635,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn clip_path() {
+        assert_eq!(
+            r###"<defs>
+<clipPath id="clip-1">
+<rect x="10" y="20" width="300" height="200"/>
+</clipPath>
+</defs>"###,
+            ClipPath {
+                id: "clip-1".to_string(),
+                left: 10.0,
+                top: 20.0,
+                width: 300.0,
+                height: 200.0,
+            }
+            .svg()
+        );
+    }
+
+    #[test]
+    fn ellipse() {
+        assert_eq!(
+            r###"<ellipse cx="50" cy="40" rx="30" ry="20" stroke-width="1" stroke="#000000" fill="#FFFFFF"/>"###,
+            Ellipse {
+                stroke_color: Some((0, 0, 0).into()),
+                fill: Some((255, 255, 255).into()),
+                cx: 50.0,
+                cy: 40.0,
+                rx: 30.0,
+                ry: 20.0,
+                ..Default::default()
+            }
+            .svg()
+        );
+    }
+
     #[test]
     fn polygon() {
         assert_eq!(
@@ -1767,6 +3684,7 @@ mod tests {
                     (20.0, 60.0).into(),
                     (30.0, 20.0).into(),
                 ],
+                ..Default::default()
             }
             .svg()
         );
@@ -1781,6 +3699,17 @@ mod tests {
                     (20.0, 60.0).into(),
                     (30.0, 20.0).into(),
                 ],
+                ..Default::default()
+            }
+            .svg()
+        );
+        assert_eq!(
+            r###"<path d="M4,3 Q0,0 5,0 L35,0 Q40,0 40,5 L40,25 Q40,30 36,27 Z" stroke="#000000"/>"###,
+            Polygon {
+                color: Some((0, 0, 0).into()),
+                points: vec![(0.0, 0.0).into(), (40.0, 0.0).into(), (40.0, 30.0).into()],
+                corner_radius: Some(5.0),
+                ..Default::default()
             }
             .svg()
         );
@@ -1795,6 +3724,7 @@ mod tests {
                     (20.0, 60.0).into(),
                     (30.0, 20.0).into(),
                 ],
+                ..Default::default()
             }
             .svg()
         );
@@ -1832,6 +3762,170 @@ Hello World!
             }
             .svg()
         );
+
+        assert_eq!(
+            r###"<text font-size="24" x="0" y="30" font-weight="bold" fill="#000000">
+1,024
+<tspan font-size="12" dx="2" fill="#6E7079">
+MB
+</tspan>
+</text>"###,
+            Text {
+                text: "1,024".to_string(),
+                font_size: Some(24.0),
+                font_color: Some((0, 0, 0).into()),
+                font_weight: Some("bold".to_string()),
+                x: Some(0.0),
+                y: Some(30.0),
+                spans: vec![TextSpan {
+                    text: "MB".to_string(),
+                    font_size: Some(12.0),
+                    font_color: Some((110, 112, 121).into()),
+                    dx: Some(2.0),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }
+            .svg()
+        );
+
+        assert_eq!(
+            r###"<text x="100" y="50" dominant-baseline="central" text-anchor="end">
+Hello World!
+</text>"###,
+            Text {
+                text: "Hello World!".to_string(),
+                x: Some(100.0),
+                y: Some(50.0),
+                align: Some(Align::Right),
+                vertical_align: Some(VerticalAlign::Middle),
+                ..Default::default()
+            }
+            .svg()
+        );
+
+        assert_eq!(
+            r###"<text x="0" y="20">
+R&amp;D &lt;2024&gt;
+</text>"###,
+            Text {
+                text: "R&D <2024>".to_string(),
+                x: Some(0.0),
+                y: Some(20.0),
+                ..Default::default()
+            }
+            .svg()
+        );
+        assert_eq!(
+            r###"<text x="0" y="20">
+R&amp;D
+</text>"###,
+            Text {
+                text: "R&amp;D".to_string(),
+                x: Some(0.0),
+                y: Some(20.0),
+                raw: true,
+                ..Default::default()
+            }
+            .svg()
+        );
+
+        assert_eq!(
+            r###"<text x="0" y="20" font-style="italic" letter-spacing="2" text-decoration="underline">
+Hello World!
+</text>"###,
+            Text {
+                text: "Hello World!".to_string(),
+                x: Some(0.0),
+                y: Some(20.0),
+                font_style: Some("italic".to_string()),
+                letter_spacing: Some(2.0),
+                text_decoration: Some("underline".to_string()),
+                ..Default::default()
+            }
+            .svg()
+        );
+    }
+
+    #[test]
+    fn path() {
+        assert_eq!(
+            r###"<path d="M10 80 C40 10, 65 10, 95 80" stroke="#5470C6" stroke-width="2" stroke-dasharray="4,2" fill="none"/>"###,
+            Path {
+                d: CubicBezier {
+                    start: (10.0, 80.0).into(),
+                    control1: (40.0, 10.0).into(),
+                    control2: (65.0, 10.0).into(),
+                    end: (95.0, 80.0).into(),
+                }
+                .to_string(),
+                stroke_color: Some((84, 112, 198).into()),
+                stroke_width: 2.0,
+                stroke_dash_array: Some("4,2".to_string()),
+                ..Default::default()
+            }
+            .svg()
+        );
+        assert_eq!(
+            r###"<path d="M0 0 L10 0 L10 10 Z" transform="translate(5 5)" fill="#000000"/>"###,
+            Path {
+                d: "M0 0 L10 0 L10 10 Z".to_string(),
+                fill: Some((0, 0, 0).into()),
+                transform: Some("translate(5 5)".to_string()),
+                ..Default::default()
+            }
+            .svg()
+        );
+    }
+
+    #[test]
+    fn arc() {
+        assert_eq!(
+            r###"<path d="M100,20 A80 80 0 1 1 20,100" stroke="#5470C6" stroke-width="10" stroke-linecap="round" fill="none"/>"###,
+            Arc {
+                stroke_color: (84, 112, 198).into(),
+                stroke_width: 10.0,
+                stroke_linecap: Some("round".to_string()),
+                cx: 100.0,
+                cy: 100.0,
+                r: 80.0,
+                start_angle: 0.0,
+                end_angle: 270.0,
+            }
+            .svg()
+        );
+    }
+
+    #[test]
+    fn sector() {
+        assert_eq!(
+            r###"<path d="M100,20 A80 80 0 0 1 180,100 L100,100 Z" fill="#5470C6"/>"###,
+            Sector {
+                fill: (84, 112, 198).into(),
+                cx: 100.0,
+                cy: 100.0,
+                r: 80.0,
+                start_angle: 0.0,
+                end_angle: 90.0,
+                ..Default::default()
+            }
+            .svg()
+        );
+        assert_eq!(
+            r###"<path d="M180,100 A80 80 0 1 1 30.7,60 L65.4,80 A40 40 0 1 0 140,100 Z" fill="#5470C6" stroke="#FFFFFF"/>"###,
+            Sector {
+                fill: (84, 112, 198).into(),
+                stroke_color: Some((255, 255, 255).into()),
+                cx: 100.0,
+                cy: 100.0,
+                r: 80.0,
+                ir: 40.0,
+                start_angle: 90.0,
+                end_angle: 300.0,
+                ..Default::default()
+            }
+            .svg()
+        );
     }
 
     #[test]
@@ -1862,6 +3956,7 @@ Hello World!
             start_angle: 45.0,
             delta: 45.0,
             border_radius: 0.0,
+            ..Default::default()
         };
         assert_eq!(
             r###"<path d="M250,250 L426.8,73.2 A0 0 0 0 1 432.8,79.5 A250 250 0 0 1 499.8,241.3 A0 0 0 0 1 500,250 L250,250 Z" fill="#000000" fill-opacity="0.5" stroke="#000000"/>"###,
@@ -2021,6 +4116,7 @@ Hello World!
                     (40.0, 50.0).into(),
                 ],
                 bottom: 100.0,
+                ..Default::default()
             }
             .svg()
         );
@@ -2188,4 +4284,146 @@ Line
             .svg()
         );
     }
+
+    #[test]
+    fn gradient() {
+        assert_eq!(
+            r###"<defs>
+<linearGradient id="series-gradient-0" x1="0" y1="0" x2="0" y2="1">
+<stop offset="0" stop-color="#5470C6"/>
+<stop offset="1" stop-color="#5470C6" stop-opacity="0"/>
+</linearGradient>
+</defs>"###,
+            Gradient {
+                id: "series-gradient-0".to_string(),
+                direction: Direction::TopToBottom,
+                stops: vec![
+                    (0.0, (84, 112, 198).into()),
+                    (1.0, (84, 112, 198, 0).into())
+                ],
+            }
+            .svg()
+        );
+        assert_eq!("", Gradient::default().svg());
+    }
+
+    #[test]
+    fn radial_gradient() {
+        assert_eq!(
+            r###"<defs>
+<radialGradient id="radial" cx="0.5" cy="0.5" r="0.5">
+<stop offset="0" stop-color="#FFFFFF"/>
+<stop offset="1" stop-color="#5470C6"/>
+</radialGradient>
+</defs>"###,
+            RadialGradient {
+                id: "radial".to_string(),
+                stops: vec![(0.0, (255, 255, 255).into()), (1.0, (84, 112, 198).into())],
+                ..Default::default()
+            }
+            .svg()
+        );
+        assert_eq!(
+            r###"<defs>
+<radialGradient id="radial" cx="100" cy="80" r="50" gradientUnits="userSpaceOnUse">
+<stop offset="0.2" stop-color="#FFFFFF"/>
+</radialGradient>
+</defs>"###,
+            RadialGradient {
+                id: "radial".to_string(),
+                cx: 100.0,
+                cy: 80.0,
+                r: 50.0,
+                user_space: true,
+                stops: vec![(0.2, (255, 255, 255).into())],
+            }
+            .svg()
+        );
+        assert_eq!(
+            r###"<circle cx="10" cy="10" r="5" stroke-width="1" fill="url(#radial)"/>"###,
+            Circle {
+                fill_id: Some("radial".to_string()),
+                cx: 10.0,
+                cy: 10.0,
+                r: 5.0,
+                ..Default::default()
+            }
+            .svg()
+        );
+    }
+
+    #[test]
+    fn drop_shadow() {
+        assert_eq!(
+            r###"<defs>
+<filter id="shadow" x="-20%" y="-20%" width="140%" height="140%">
+<feDropShadow dx="0" dy="2" stdDeviation="3" flood-color="#000000" flood-opacity="0.3"/>
+</filter>
+</defs>"###,
+            DropShadow {
+                id: "shadow".to_string(),
+                dx: 0.0,
+                dy: 2.0,
+                std_deviation: 3.0,
+                color: (0, 0, 0, 77).into(),
+            }
+            .svg()
+        );
+        assert_eq!(
+            r###"<rect x="0" y="0" width="50" height="20" filter="url(#shadow)"/>"###,
+            Rect {
+                width: 50.0,
+                height: 20.0,
+                filter_id: Some("shadow".to_string()),
+                ..Default::default()
+            }
+            .svg()
+        );
+    }
+
+    #[test]
+    fn pattern() {
+        assert_eq!(
+            r###"<defs>
+<pattern id="pattern" patternUnits="userSpaceOnUse" width="8" height="8">
+<rect x="0" y="0" width="8" height="8" fill="#5470C6" fill-opacity="0.2"/>
+<path d="M0,8 L8,0 M-1,1 L1,-1 M7,9 L9,7" stroke="#5470C6" stroke-width="1.5"/>
+</pattern>
+</defs>"###,
+            Pattern {
+                id: "pattern".to_string(),
+                color: (84, 112, 198).into(),
+                background: Some((84, 112, 198, 51).into()),
+                ..Default::default()
+            }
+            .svg()
+        );
+        assert_eq!(
+            r###"<defs>
+<pattern id="pattern" patternUnits="userSpaceOnUse" width="8" height="8">
+<circle cx="4" cy="4" r="2" stroke-width="0" fill="#000000"/>
+</pattern>
+</defs>"###,
+            Pattern {
+                id: "pattern".to_string(),
+                category: PatternCategory::Dots,
+                ..Default::default()
+            }
+            .svg()
+        );
+    }
+    #[test]
+    fn attr_value() {
+        assert_eq!("0", coordinate_attr(-0.0001));
+        assert_eq!("12.5", coordinate_attr(12.5));
+
+        // the arguments without placeholder are borrowed
+        let value = format_attr(format_args!("userSpaceOnUse"));
+        assert!(matches!(value, Cow::Borrowed("userSpaceOnUse")));
+
+        let id = "shadow".to_string();
+        let value = format_attr(format_args!("url(#{id})"));
+        assert!(matches!(value, Cow::Owned(..)));
+        assert_eq!("url(#shadow)", value);
+    }
 }
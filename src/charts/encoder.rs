@@ -2,6 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use arc_swap::ArcSwap;
 use image::ImageFormat;
 use once_cell::sync::OnceCell;
 use resvg::{tiny_skia, usvg};
@@ -28,8 +29,9 @@ pub enum Error {
 }
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+static GLOBAL_FONT_DB: OnceCell<ArcSwap<fontdb::Database>> = OnceCell::new();
+
 pub(crate) fn get_or_init_fontdb(fonts: Option<Vec<&[u8]>>) -> Arc<fontdb::Database> {
-    static GLOBAL_FONT_DB: OnceCell<Arc<fontdb::Database>> = OnceCell::new();
     GLOBAL_FONT_DB
         .get_or_init(|| {
             let mut fontdb = fontdb::Database::new();
@@ -40,9 +42,18 @@ pub(crate) fn get_or_init_fontdb(fonts: Option<Vec<&[u8]>>) -> Arc<fontdb::Datab
             } else {
                 fontdb.load_system_fonts();
             }
-            Arc::new(fontdb)
+            ArcSwap::from_pointee(fontdb)
         })
-        .clone()
+        .load_full()
+}
+
+/// Adds the font data to font database, it is used by the registered font.
+pub(crate) fn add_font_to_fontdb(data: &[u8]) {
+    let mut fontdb = (*get_or_init_fontdb(None)).clone();
+    fontdb.load_font_data(data.to_vec());
+    if let Some(value) = GLOBAL_FONT_DB.get() {
+        value.store(Arc::new(fontdb));
+    }
 }
 
 fn save_image(svg: &str, format: image::ImageFormat) -> Result<Vec<u8>> {
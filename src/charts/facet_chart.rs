@@ -0,0 +1,297 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::canvas;
+use super::color::get_color;
+use super::common::*;
+use super::component::{
+    generate_svg, measure_legends, Legend, LegendCategory, Rect, Text, LEGEND_HEIGHT, LEGEND_MARGIN,
+};
+use super::theme::{get_default_theme_name, get_theme};
+use super::util::*;
+use super::{measure_text_width_family, BarChart, LineChart};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FacetChartType {
+    #[default]
+    Line,
+    Bar,
+}
+
+/// Small multiples of line or bar chart, the series are split by facet key
+/// and rendered as a grid of charts with the same y axis range and a shared legend.
+#[derive(Clone, Debug, Default)]
+pub struct FacetChart {
+    pub chart_type: FacetChartType,
+    // the facet key and series, the series of same key are rendered in one chart
+    pub data: Vec<(String, Series)>,
+    pub x_axis_data: Vec<String>,
+    pub theme: String,
+    // the count of charts per row
+    pub columns: usize,
+    // the size of each facet chart
+    pub width: f32,
+    pub height: f32,
+    pub gap: f32,
+    pub margin: Box,
+    pub title_text: String,
+    pub title_font_size: f32,
+}
+
+impl FacetChart {
+    /// Creates a facet chart with custom theme.
+    pub fn new_with_theme(
+        chart_type: FacetChartType,
+        data: Vec<(String, Series)>,
+        x_axis_data: Vec<String>,
+        theme: &str,
+    ) -> FacetChart {
+        FacetChart {
+            chart_type,
+            data,
+            x_axis_data,
+            theme: theme.to_string(),
+            columns: 2,
+            width: 300.0,
+            height: 200.0,
+            gap: 10.0,
+            margin: (10.0).into(),
+            title_font_size: 18.0,
+            ..Default::default()
+        }
+    }
+    /// Creates a facet chart with default theme.
+    pub fn new(
+        chart_type: FacetChartType,
+        data: Vec<(String, Series)>,
+        x_axis_data: Vec<String>,
+    ) -> FacetChart {
+        FacetChart::new_with_theme(chart_type, data, x_axis_data, &get_default_theme_name())
+    }
+    /// Gets the facet keys and the series names in order of appearance.
+    fn keys_and_names(&self) -> (Vec<String>, Vec<String>) {
+        let mut keys: Vec<String> = vec![];
+        let mut names: Vec<String> = vec![];
+        for (key, series) in self.data.iter() {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+            if !names.contains(&series.name) {
+                names.push(series.name.clone());
+            }
+        }
+        (keys, names)
+    }
+    /// Gets the y axis range of all series, it is shared by the facet charts.
+    fn y_axis_range(&self, split_number: usize) -> (f32, f32) {
+        let mut data_list = vec![];
+        for (_, series) in self.data.iter() {
+            if series.visible {
+                data_list.append(series.get_data().as_mut());
+            }
+        }
+        if data_list.is_empty() {
+            return (0.0, 0.0);
+        }
+        let values = get_axis_values(AxisValueParams {
+            data_list,
+            split_number,
+            ..Default::default()
+        });
+        (values.min, values.max)
+    }
+    /// Converts the facet chart to svg.
+    pub fn svg(&self) -> canvas::Result<String> {
+        let theme = get_theme(&self.theme);
+        let (keys, names) = self.keys_and_names();
+        let (axis_min, axis_max) = self.y_axis_range(theme.y_axis_split_number);
+        let columns = self.columns.max(1).min(keys.len().max(1));
+        let rows = (keys.len() + columns - 1) / columns;
+        let width = self.margin.left
+            + self.margin.right
+            + columns as f32 * self.width
+            + (columns - 1) as f32 * self.gap;
+
+        let mut arr = vec![];
+        let mut top = self.margin.top;
+        if !self.title_text.is_empty() {
+            let title_height = measure_text_width_family(
+                &theme.font_family,
+                self.title_font_size,
+                &self.title_text,
+            )?
+            .height();
+            arr.push(
+                Text {
+                    text: self.title_text.clone(),
+                    font_family: Some(theme.font_family.clone()),
+                    font_size: Some(self.title_font_size),
+                    font_color: Some(theme.title_font_color),
+                    x: Some(width / 2.0),
+                    y: Some(top + title_height / 2.0),
+                    align: Some(Align::Center),
+                    vertical_align: Some(VerticalAlign::Middle),
+                    ..Default::default()
+                }
+                .svg(),
+            );
+            top += title_height + self.gap;
+        }
+
+        // the shared legend of all facet charts
+        if !names.is_empty() {
+            let legends: Vec<&str> = names.iter().map(|item| item.as_str()).collect();
+            let (legend_width, legend_widths) =
+                measure_legends(&theme.font_family, theme.legend_font_size, &legends);
+            let category = match self.chart_type {
+                FacetChartType::Line => LegendCategory::Normal,
+                FacetChartType::Bar => LegendCategory::Rect,
+            };
+            let mut left = ((width - legend_width) / 2.0).max(self.margin.left);
+            for (index, name) in names.iter().enumerate() {
+                let color = get_color(&theme.series_colors, index);
+                arr.push(
+                    Legend {
+                        text: name.clone(),
+                        font_size: theme.legend_font_size,
+                        font_family: theme.font_family.clone(),
+                        font_color: Some(theme.legend_font_color),
+                        stroke_color: Some(color),
+                        fill: Some(color),
+                        left,
+                        top,
+                        category: category.clone(),
+                        ..Default::default()
+                    }
+                    .svg(),
+                );
+                left += legend_widths[index] + LEGEND_MARGIN;
+            }
+            top += LEGEND_HEIGHT + self.gap;
+        }
+
+        for (index, key) in keys.iter().enumerate() {
+            let mut series_list: Vec<Series> = self
+                .data
+                .iter()
+                .filter(|(item, _)| item == key)
+                .map(|(_, series)| series.clone())
+                .collect();
+            let x = self.margin.left + (index % columns) as f32 * (self.width + self.gap);
+            let y = top + (index / columns) as f32 * (self.height + self.gap);
+            // the color of series is the same in all facet charts
+            for series in series_list.iter_mut() {
+                series.index = names.iter().position(|name| name == &series.name);
+            }
+            let svg = match self.chart_type {
+                FacetChartType::Line => {
+                    let mut c = LineChart::new_with_theme(
+                        series_list,
+                        self.x_axis_data.clone(),
+                        &self.theme,
+                    );
+                    (c.width, c.height, c.x, c.y) = (self.width, self.height, x, y);
+                    c.title_text = key.clone();
+                    c.legend_show = Some(false);
+                    c.y_axis_configs[0].axis_min = Some(axis_min);
+                    c.y_axis_configs[0].axis_max = Some(axis_max);
+                    c.svg()?
+                }
+                FacetChartType::Bar => {
+                    let mut c =
+                        BarChart::new_with_theme(vec![], self.x_axis_data.clone(), &self.theme);
+                    // bar chart resets the index of series, so sets the series list later
+                    c.series_list = series_list;
+                    (c.width, c.height, c.x, c.y) = (self.width, self.height, x, y);
+                    c.title_text = key.clone();
+                    c.legend_show = Some(false);
+                    c.y_axis_configs[0].axis_min = Some(axis_min);
+                    c.y_axis_configs[0].axis_max = Some(axis_max);
+                    c.svg()?
+                }
+            };
+            arr.push(svg);
+        }
+        let height = top
+            + rows as f32 * self.height
+            + rows.saturating_sub(1) as f32 * self.gap
+            + self.margin.bottom;
+
+        arr.insert(
+            0,
+            Rect {
+                fill: Some(theme.background_color),
+                left: 0.0,
+                top: 0.0,
+                width,
+                height,
+                ..Default::default()
+            }
+            .svg(),
+        );
+
+        Ok(generate_svg(
+            width,
+            height,
+            0.0,
+            0.0,
+            arr.join("\n"),
+            None,
+            None,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FacetChart, FacetChartType};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn facet_chart_basic() {
+        let mut facet_chart = FacetChart::new(
+            FacetChartType::Line,
+            vec![
+                (
+                    "North".to_string(),
+                    ("Email", vec![120.0, 132.0, 101.0, 134.0]).into(),
+                ),
+                (
+                    "North".to_string(),
+                    ("Direct", vec![320.0, 332.0, 301.0, 334.0]).into(),
+                ),
+                (
+                    "South".to_string(),
+                    ("Email", vec![220.0, 182.0, 191.0, 234.0]).into(),
+                ),
+                (
+                    "South".to_string(),
+                    ("Direct", vec![150.0, 232.0, 201.0, 154.0]).into(),
+                ),
+                (
+                    "East".to_string(),
+                    ("Direct", vec![420.0, 332.0, 401.0, 434.0]).into(),
+                ),
+            ],
+            vec![
+                "Q1".to_string(),
+                "Q2".to_string(),
+                "Q3".to_string(),
+                "Q4".to_string(),
+            ],
+        );
+        facet_chart.title_text = "Sales by Region".to_string();
+        assert_eq!(
+            include_str!("../../asset/facet_chart/basic.svg"),
+            facet_chart.svg().unwrap()
+        );
+
+        facet_chart.chart_type = FacetChartType::Bar;
+        facet_chart.columns = 3;
+        assert_eq!(
+            include_str!("../../asset/facet_chart/bar.svg"),
+            facet_chart.svg().unwrap()
+        );
+    }
+}
@@ -2,12 +2,17 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use super::component::{parse_start_tag, StartTag};
 use super::util::*;
+use ahash::AHashMap;
+use arc_swap::ArcSwap;
+use base64::Engine;
 use fontdue::layout::{CoordinateSystem, Layout, TextStyle};
 use fontdue::Font;
-use once_cell::sync::OnceCell;
-use snafu::Snafu;
+use once_cell::sync::{Lazy, OnceCell};
+use snafu::{ResultExt, Snafu};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -15,6 +20,11 @@ pub enum Error {
     FontNotFound { name: String },
     #[snafu(display("Error parse font: {message}"))]
     ParseFont { message: String },
+    #[snafu(display("Io {file}: {source}"))]
+    Io {
+        file: String,
+        source: std::io::Error,
+    },
 }
 
 impl From<&str> for Error {
@@ -53,12 +63,14 @@ pub fn get_or_try_init_fonts(fonts: Option<Vec<&[u8]>>) -> Result<&'static HashM
         // init fonts, will returns an error if load font fails.
         let font = fontdue::Font::from_bytes(DEFAULT_FONT_DATA, fontdue::FontSettings::default())?;
         m.insert(DEFAULT_FONT_FAMILY.to_string(), font);
+        add_font_data(DEFAULT_FONT_FAMILY, DEFAULT_FONT_DATA);
         let mut font_datas = vec![DEFAULT_FONT_DATA];
         if let Some(value) = fonts {
             for data in value.iter() {
                 let font = fontdue::Font::from_bytes(*data, fontdue::FontSettings::default())?;
                 let family = get_family_from_font(&font);
                 if !family.is_empty() {
+                    add_font_data(&family, data);
                     m.insert(family, font);
                     font_datas.push(*data);
                 }
@@ -69,8 +81,73 @@ pub fn get_or_try_init_fonts(fonts: Option<Vec<&[u8]>>) -> Result<&'static HashM
         Ok(m)
     })
 }
+type FontDatas = AHashMap<String, Arc<Vec<u8>>>;
+// The data of fonts, it is used to embed font or convert text to outline.
+static FONT_DATA_MAP: Lazy<ArcSwap<FontDatas>> =
+    Lazy::new(|| ArcSwap::from_pointee(AHashMap::new()));
+
+fn add_font_data(name: &str, data: &[u8]) {
+    let mut m: FontDatas = AHashMap::new();
+    for (name, data) in FONT_DATA_MAP.load().iter() {
+        m.insert(name.to_string(), data.clone());
+    }
+    m.insert(name.to_string(), Arc::new(data.to_vec()));
+    FONT_DATA_MAP.store(Arc::new(m));
+}
+
+/// Gets the data of font, the default font is returned if not found.
+pub(crate) fn get_font_data(name: &str) -> Option<Arc<Vec<u8>>> {
+    let m = FONT_DATA_MAP.load();
+    m.get(name).or_else(|| m.get(DEFAULT_FONT_FAMILY)).cloned()
+}
+
+type RegisteredFonts = AHashMap<String, &'static Font>;
+static REGISTERED_FONTS: Lazy<ArcSwap<RegisteredFonts>> =
+    Lazy::new(|| ArcSwap::from_pointee(AHashMap::new()));
+
+/// Registers the font(ttf or otf) with name, it can be used for text measurement and image encoding.
+/// The font is kept until the end of program, so it should be registered once.
+pub fn register_font(name: &str, data: &[u8]) -> Result<()> {
+    // init the default fonts first
+    get_or_try_init_fonts(None)?;
+    let font = fontdue::Font::from_bytes(data, fontdue::FontSettings::default())?;
+    let mut m: RegisteredFonts = AHashMap::new();
+    for (name, font) in REGISTERED_FONTS.load().iter() {
+        m.insert(name.to_string(), *font);
+    }
+    m.insert(
+        name.to_string(),
+        std::boxed::Box::leak(std::boxed::Box::new(font)),
+    );
+    REGISTERED_FONTS.store(Arc::new(m));
+    add_font_data(name, data);
+    #[cfg(feature = "image-encoder")]
+    crate::add_font_to_fontdb(data);
+    Ok(())
+}
+
+/// Registers the font from file, the name of font is the font family of it,
+/// or the name of file if the font family is not found.
+pub fn register_font_file(path: &str) -> Result<()> {
+    let data = std::fs::read(path).context(IoSnafu {
+        file: path.to_string(),
+    })?;
+    let font = fontdue::Font::from_bytes(data.as_slice(), fontdue::FontSettings::default())?;
+    let mut name = get_family_from_font(&font);
+    if name.is_empty() {
+        name = std::path::Path::new(path)
+            .file_stem()
+            .map(|item| item.to_string_lossy().to_string())
+            .unwrap_or_default();
+    }
+    register_font(&name, &data)
+}
+
 /// Gets font by font family.
 pub fn get_font(name: &str) -> Result<&Font> {
+    if let Some(font) = REGISTERED_FONTS.load().get(name) {
+        return Ok(*font);
+    }
     let fonts = get_or_try_init_fonts(None)?;
     if let Some(font) = fonts.get(name).or_else(|| fonts.get(DEFAULT_FONT_FAMILY)) {
         Ok(font)
@@ -81,6 +158,33 @@ pub fn get_font(name: &str) -> Result<&Font> {
         .fail()
     }
 }
+/// Splits the font family chain, e.g. Roboto, "Noto Sans SC".
+pub(crate) fn split_font_family(font_family: &str) -> Vec<&str> {
+    font_family
+        .split(',')
+        .map(|item| item.trim().trim_matches(|c| c == '"' || c == '\''))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Gets the fonts of font family chain, the unknown font is ignored.
+/// The default font is returned if none of them is found.
+pub fn get_fonts(font_family: &str) -> Result<Vec<&Font>> {
+    let fonts = get_or_try_init_fonts(None)?;
+    let registered_fonts = REGISTERED_FONTS.load();
+    let mut result = vec![];
+    for name in split_font_family(font_family) {
+        if let Some(font) = registered_fonts.get(name) {
+            result.push(*font);
+        } else if let Some(font) = fonts.get(name) {
+            result.push(font);
+        }
+    }
+    if result.is_empty() {
+        result.push(get_font(font_family)?);
+    }
+    Ok(result)
+}
 /// Gets all supported font family
 pub fn get_font_families() -> Result<Vec<String>> {
     let fonts = get_or_try_init_fonts(None)?;
@@ -88,11 +192,40 @@ pub fn get_font_families() -> Result<Vec<String>> {
     for (name, _) in fonts.iter() {
         families.push(name.to_string());
     }
+    for name in REGISTERED_FONTS.load().keys() {
+        if !families.contains(name) {
+            families.push(name.to_string());
+        }
+    }
     Ok(families)
 }
 
-/// Measures the display area of text of a specified font size.
-pub fn measure_text(font: &Font, font_size: f32, text: &str) -> Box {
+// The metrics of glyph which are used to measure text.
+#[derive(Clone, Copy, Debug, Default)]
+struct GlyphMetrics {
+    advance: f32,
+    // offset of glyph from the position of pen
+    x: f32,
+    // offset of glyph from the baseline
+    y: f32,
+    width: f32,
+    height: f32,
+    // the glyph is not found in font
+    missing: bool,
+}
+
+// The glyph metrics of a font with a specified size.
+#[derive(Debug, Default)]
+struct FontSizeMetrics {
+    ascent: f32,
+    glyphs: AHashMap<char, GlyphMetrics>,
+}
+
+// The glyph metrics cache, it is keyed by the hash of font and the font size.
+static GLYPH_METRICS_CACHE: Lazy<Mutex<AHashMap<(usize, u32), FontSizeMetrics>>> =
+    Lazy::new(|| Mutex::new(AHashMap::new()));
+
+fn measure_text_by_layout(font: &Font, font_size: f32, text: &str) -> Box {
     let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
     layout.append(&[font], &TextStyle::new(text, font_size, 0));
 
@@ -115,10 +248,84 @@ pub fn measure_text(font: &Font, font_size: f32, text: &str) -> Box {
     }
 }
 
-/// Measures the display area of text of a specified font size and font family.
+/// Measures the display area of text of a specified font size with the fallback fonts,
+/// the glyph of each character is got from the first font which has it.
+/// The glyph metrics are cached, it is the same as the layout of single line text.
+pub fn measure_text_fonts(fonts: &[&Font], font_size: f32, text: &str) -> Box {
+    if fonts.is_empty() {
+        return Box::default();
+    }
+    // the control characters and line breaks change the layout, measure them by layout
+    if text
+        .chars()
+        .any(|ch| ch.is_control() || ch == '\u{2028}' || ch == '\u{2029}')
+    {
+        return measure_text_by_layout(fonts[0], font_size, text);
+    }
+    let mut cache = GLYPH_METRICS_CACHE.lock().unwrap();
+    let mut get_glyph = |font: &Font, ch: char| -> (f32, GlyphMetrics) {
+        let metrics = cache
+            .entry((font.file_hash(), font_size.to_bits()))
+            .or_insert_with(|| FontSizeMetrics {
+                ascent: font
+                    .horizontal_line_metrics(font_size)
+                    .map(|item| item.ascent.ceil().max(0.0))
+                    .unwrap_or_default(),
+                ..Default::default()
+            });
+        let g = *metrics.glyphs.entry(ch).or_insert_with(|| {
+            let index = font.lookup_glyph_index(ch);
+            let m = font.metrics_indexed(index, font_size);
+            GlyphMetrics {
+                advance: m.advance_width.ceil(),
+                x: m.bounds.xmin.floor(),
+                y: (-m.bounds.height - m.bounds.ymin).floor(),
+                width: m.width as f32,
+                height: m.height as f32,
+                missing: index == 0,
+            }
+        });
+        (metrics.ascent, g)
+    };
+
+    let mut glyphs = Vec::with_capacity(text.len());
+    let mut ascent = 0.0_f32;
+    for ch in text.chars() {
+        let mut value = get_glyph(fonts[0], ch);
+        for font in fonts.iter().skip(1) {
+            if !value.1.missing {
+                break;
+            }
+            let fallback = get_glyph(font, ch);
+            if !fallback.1.missing {
+                value = fallback;
+            }
+        }
+        // the baseline is the max ascent of fonts
+        ascent = ascent.max(value.0);
+        glyphs.push(value.1);
+    }
+
+    let mut position = 0.0_f32;
+    let mut right = 0.0_f32;
+    let mut bottom = 0.0_f32;
+    for g in glyphs.iter() {
+        right = right.max(position + g.x + g.width);
+        bottom = bottom.max(ascent + g.y + g.height);
+        position += g.advance;
+    }
+    Box {
+        right,
+        bottom,
+        ..Default::default()
+    }
+}
+
+/// Measures the display area of text of a specified font size and font family,
+/// the font family can be a fallback chain, e.g. Roboto, Noto Sans SC.
 pub fn measure_text_width_family(font_family: &str, font_size: f32, text: &str) -> Result<Box> {
-    let font = get_font(font_family)?;
-    Ok(measure_text(font, font_size, text))
+    let fonts = get_fonts(font_family)?;
+    Ok(measure_text_fonts(&fonts, font_size, text))
 }
 
 /// Gets the max width of multi text.
@@ -127,10 +334,10 @@ pub fn measure_max_text_width_family(
     font_size: f32,
     texts: Vec<&str>,
 ) -> Result<Box> {
-    let font = get_font(font_family)?;
+    let fonts = get_fonts(font_family)?;
     let mut result = Box::default();
     for item in texts.iter() {
-        let b = measure_text(font, font_size, item);
+        let b = measure_text_fonts(&fonts, font_size, item);
         if b.width() > result.width() {
             result = b;
         }
@@ -145,8 +352,8 @@ pub fn text_wrap_fit(
     text: &str,
     width: f32,
 ) -> Result<Vec<String>> {
-    let font = get_font(font_family)?;
-    let b = measure_text(font, font_size, text);
+    let fonts = get_fonts(font_family)?;
+    let b = measure_text_fonts(&fonts, font_size, text);
     if b.width() <= width {
         return Ok(vec![text.to_string()]);
     }
@@ -155,7 +362,7 @@ pub fn text_wrap_fit(
     let mut result = vec![];
     for item in text.chars() {
         let new_str = current.clone() + &item.to_string();
-        let b = measure_text(font, font_size, &new_str);
+        let b = measure_text_fonts(&fonts, font_size, &new_str);
         if b.width() > width {
             result.push(current);
             current = item.to_string();
@@ -169,9 +376,215 @@ pub fn text_wrap_fit(
     Ok(result)
 }
 
+/// Embeds the fonts used by svg as base64 font face in style element,
+/// the svg is rendered the same even if the font is not installed.
+pub(crate) fn embed_fonts(svg: String) -> String {
+    let mut families: Vec<&str> = vec![];
+    for line in svg.lines() {
+        if let Some(font_family) = parse_start_tag(line).and_then(|tag| tag.get("font-family")) {
+            for family in split_font_family(font_family) {
+                if !families.contains(&family) {
+                    families.push(family);
+                }
+            }
+        }
+    }
+    let mut font_faces = vec![];
+    for family in families.iter() {
+        // only the font which is loaded can be embedded
+        let Some(data) = FONT_DATA_MAP.load().get(*family).cloned() else {
+            continue;
+        };
+        let format = if data.starts_with(b"OTTO") {
+            "otf"
+        } else {
+            "ttf"
+        };
+        font_faces.push(format!(
+            "@font-face{{font-family:\"{family}\";src:url(data:font/{format};base64,{})}}",
+            base64::engine::general_purpose::STANDARD.encode(data.as_slice())
+        ));
+    }
+    if font_faces.is_empty() {
+        return svg;
+    }
+    let mut lines: Vec<&str> = svg.lines().collect();
+    let style = format!("<style>\n{}\n</style>", font_faces.join("\n"));
+    lines.insert(1, &style);
+    lines.join("\n")
+}
+
+// Writes the outline of glyph as path.
+struct OutlineBuilder {
+    d: String,
+    x: f32,
+    y: f32,
+    scale: f32,
+}
+
+impl OutlineBuilder {
+    fn point(&self, x: f32, y: f32) -> String {
+        // the y axis of font is up
+        format!(
+            "{},{}",
+            format_coordinate(self.x + x * self.scale),
+            format_coordinate(self.y - y * self.scale)
+        )
+    }
+}
+
+impl ttf_parser::OutlineBuilder for OutlineBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.d.push_str(&format!("M{p}"));
+    }
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.d.push_str(&format!("L{p}"));
+    }
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let p1 = self.point(x1, y1);
+        let p = self.point(x, y);
+        self.d.push_str(&format!("Q{p1} {p}"));
+    }
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let p1 = self.point(x1, y1);
+        let p2 = self.point(x2, y2);
+        let p = self.point(x, y);
+        self.d.push_str(&format!("C{p1} {p2} {p}"));
+    }
+    fn close(&mut self) {
+        self.d.push('Z');
+    }
+}
+
+// Converts the text to path of outline, it returns none if the text can not be converted.
+fn text_to_outline(tag: &StartTag, text: &str) -> Option<String> {
+    let get_float = |key: &str| -> Option<f32> {
+        if let Some(value) = tag.get(key) {
+            value.parse::<f32>().ok()
+        } else {
+            Some(0.0)
+        }
+    };
+    // the default font size of browser
+    let font_size = tag
+        .get("font-size")
+        .map(|value| value.parse::<f32>().ok())
+        .unwrap_or(Some(16.0))?;
+    let font_family = tag.get("font-family").unwrap_or(DEFAULT_FONT_FAMILY);
+    let mut data_list: Vec<Arc<Vec<u8>>> = split_font_family(font_family)
+        .iter()
+        .filter_map(|name| FONT_DATA_MAP.load().get(*name).cloned())
+        .collect();
+    if data_list.is_empty() {
+        data_list.push(get_font_data(font_family)?);
+    }
+    let faces = data_list
+        .iter()
+        .map(|data| ttf_parser::Face::parse(data.as_slice(), 0).ok())
+        .collect::<Option<Vec<ttf_parser::Face>>>()?;
+    // the metrics of baseline are from the first font
+    let face = &faces[0];
+    let scale = font_size / face.units_per_em() as f32;
+    let letter_spacing = get_float("letter-spacing")?;
+
+    // the glyph is got from the first font which has it
+    // the characters of rtl text are drawn from right to left
+    let is_rtl = tag.get("direction") == Some("rtl");
+    let chars: Vec<char> = if is_rtl {
+        text.chars().rev().collect()
+    } else {
+        text.chars().collect()
+    };
+    let glyphs: Vec<(&ttf_parser::Face, ttf_parser::GlyphId, f32)> = chars
+        .into_iter()
+        .map(|ch| {
+            let (face, id) = faces
+                .iter()
+                .find_map(|face| face.glyph_index(ch).map(|id| (face, id)))
+                .unwrap_or((&faces[0], ttf_parser::GlyphId::default()));
+            let advance = face.glyph_hor_advance(id).unwrap_or_default() as f32 * font_size
+                / face.units_per_em() as f32;
+            (face, id, advance + letter_spacing)
+        })
+        .collect();
+    let width: f32 = glyphs.iter().map(|(_, _, advance)| advance).sum();
+    let mut x = get_float("x")? + get_float("dx")?;
+    // the start of rtl text is the right side
+    match (tag.get("text-anchor").unwrap_or_default(), is_rtl) {
+        ("middle", _) => x -= width / 2.0,
+        ("end", false) | ("start", true) | ("", true) => x -= width,
+        _ => {}
+    }
+    let ascender = face.ascender() as f32 * scale;
+    let descender = face.descender() as f32 * scale;
+    let mut y = get_float("y")? + get_float("dy")?;
+    match tag.get("dominant-baseline").unwrap_or_default() {
+        "middle" => {
+            y += face.x_height().unwrap_or_default() as f32 * scale / 2.0;
+        }
+        "central" => y += (ascender + descender) / 2.0,
+        "hanging" | "text-before-edge" => y += ascender,
+        "text-after-edge" | "ideographic" => y += descender,
+        _ => {}
+    }
+
+    let mut builder = OutlineBuilder {
+        d: String::new(),
+        x,
+        y,
+        scale,
+    };
+    for (face, id, advance) in glyphs.iter() {
+        builder.scale = font_size / face.units_per_em() as f32;
+        face.outline_glyph(*id, &mut builder);
+        builder.x += advance;
+    }
+    let mut value = "<path".to_string();
+    for key in ["id", "class", "transform", "fill", "fill-opacity", "filter"] {
+        if let Some(attr_value) = tag.get(key) {
+            value.push_str(&format!(" {key}=\"{attr_value}\""));
+        }
+    }
+    value.push_str(&format!(" d=\"{}\"/>", builder.d));
+    Some(value)
+}
+
+/// Converts all text to path of outline, the svg is rendered the same everywhere.
+/// The text with tspan is kept.
+pub(crate) fn convert_text_to_outline(svg: String) -> String {
+    let lines: Vec<&str> = svg.lines().collect();
+    let mut result = Vec::with_capacity(lines.len());
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        // the text element is three lines: start tag, text and end tag
+        if index + 2 < lines.len()
+            && lines[index + 2] == "</text>"
+            && !lines[index + 1].contains('<')
+        {
+            let path = parse_start_tag(line)
+                .filter(|tag| tag.name == "text")
+                .and_then(|tag| text_to_outline(&tag, &unescape_xml(lines[index + 1])));
+            if let Some(path) = path {
+                result.push(path);
+                index += 3;
+                continue;
+            }
+        }
+        result.push(line.to_string());
+        index += 1;
+    }
+    result.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{get_font, get_font_families, measure_text_width_family, text_wrap_fit};
+    use super::{
+        get_font, get_font_families, measure_text_by_layout, measure_text_fonts,
+        measure_text_width_family, split_font_family, text_wrap_fit,
+    };
     use pretty_assertions::assert_eq;
     #[test]
     fn measure_text() {
@@ -187,6 +600,41 @@ mod tests {
         assert_eq!("Roboto", get_font_families().unwrap().join(","));
     }
     #[test]
+    fn measure_text_cache() {
+        let font = get_font("Roboto").unwrap();
+        for font_size in [10.0, 12.5, 14.0, 28.0] {
+            for text in [
+                "",
+                "Hello World!",
+                "Mon Tue Wed",
+                "-1,234.56%",
+                "中文字体",
+                "first\nsecond",
+            ] {
+                assert_eq!(
+                    measure_text_by_layout(font, font_size, text).to_string(),
+                    measure_text_fonts(&[font], font_size, text).to_string()
+                );
+            }
+        }
+    }
+    #[test]
+    fn font_family_chain() {
+        assert_eq!(
+            vec!["Roboto", "Noto Sans SC", "Apple Color Emoji"],
+            split_font_family(r#"Roboto, "Noto Sans SC",'Apple Color Emoji'"#)
+        );
+        let text = "Hello 世界";
+        assert_eq!(
+            measure_text_width_family("Roboto", 14.0, text)
+                .unwrap()
+                .to_string(),
+            measure_text_width_family("Unknown, Roboto", 14.0, text)
+                .unwrap()
+                .to_string()
+        );
+    }
+    #[test]
     fn wrap_fit() {
         let name = "Roboto";
         let result = text_wrap_fit(name, 14.0, "An event-driven, non-blocking I/O platform for writing asynchronous I/O backed applications", 100.0).unwrap();
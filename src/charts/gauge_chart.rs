@@ -0,0 +1,447 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::canvas;
+use super::color::*;
+use super::common::*;
+use super::component::*;
+use super::params::*;
+use super::theme::{get_default_option, get_default_theme_name, get_theme, Theme};
+use super::util::*;
+use super::Canvas;
+use crate::charts::measure_text_width_family;
+
+#[derive(Clone, Debug, Default)]
+pub struct GaugePointer {
+    pub value: f32,
+    // it is the series color of pointer index if not set
+    pub color: Option<Color>,
+    // the width of needle base
+    pub width: f32,
+    // the length of needle, it is the ratio(0-1) of radius
+    pub length: f32,
+}
+
+impl GaugePointer {
+    pub fn new(value: f32) -> Self {
+        GaugePointer {
+            value,
+            width: 8.0,
+            length: 0.7,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GaugeBand {
+    // the band is from the end of previous band(or min value) to this value
+    pub end: f32,
+    pub color: Color,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GaugeChart {
+    pub width: f32,
+    pub height: f32,
+    pub x: f32,
+    pub y: f32,
+    pub font_family: String,
+    pub background_color: Color,
+
+    // title
+    pub title_text: String,
+    pub title_font_size: f32,
+    pub title_font_color: Color,
+    pub title_font_weight: Option<String>,
+
+    // the range of gauge
+    pub min: f32,
+    pub max: f32,
+    // the angle is clockwise from the top
+    pub start_angle: f32,
+    pub end_angle: f32,
+    // the thickness of gauge arc
+    pub stroke_width: f32,
+    // the color of arc which is not covered by bands
+    pub track_color: Color,
+    // the colored threshold arcs, e.g. green, yellow and red
+    pub bands: Vec<GaugeBand>,
+    // the needles, e.g. current and target value
+    pub pointers: Vec<GaugePointer>,
+    pub series_colors: Vec<Color>,
+
+    // tick
+    pub split_number: usize,
+    pub tick_color: Color,
+    pub tick_font_size: f32,
+    pub tick_font_color: Color,
+    // formatter of tick label, e.g. {c}%
+    pub tick_formatter: String,
+
+    // label of the first pointer value below the center
+    pub label_show: bool,
+    pub label_font_size: f32,
+    pub label_font_color: Color,
+    pub label_font_weight: Option<String>,
+    pub label_formatter: String,
+}
+
+impl GaugeChart {
+    fn fill_option(&mut self, data: &str) -> canvas::Result<serde_json::Value> {
+        let data: serde_json::Value = serde_json::from_str(data)?;
+        let theme = get_string_from_value(&data, "theme").unwrap_or_default();
+        self.fill_theme(get_theme(&theme));
+
+        if let Some(width) = get_f32_from_value(&data, "width") {
+            self.width = width;
+        }
+        if let Some(height) = get_f32_from_value(&data, "height") {
+            self.height = height;
+        }
+        if let Some(x) = get_f32_from_value(&data, "x") {
+            self.x = x;
+        }
+        if let Some(y) = get_f32_from_value(&data, "y") {
+            self.y = y;
+        }
+        if let Some(font_family) = get_string_from_value(&data, "font_family") {
+            self.font_family = font_family;
+        }
+        if let Some(background_color) = get_color_from_value(&data, "background_color") {
+            self.background_color = background_color;
+        }
+        if let Some(title_text) = get_string_from_value(&data, "title_text") {
+            self.title_text = title_text;
+        }
+        if let Some(title_font_size) = get_f32_from_value(&data, "title_font_size") {
+            self.title_font_size = title_font_size;
+        }
+        if let Some(title_font_color) = get_color_from_value(&data, "title_font_color") {
+            self.title_font_color = title_font_color;
+        }
+        if let Some(title_font_weight) = get_string_from_value(&data, "title_font_weight") {
+            self.title_font_weight = Some(title_font_weight);
+        }
+        if let Some(min) = get_f32_from_value(&data, "min") {
+            self.min = min;
+        }
+        if let Some(max) = get_f32_from_value(&data, "max") {
+            self.max = max;
+        }
+        if let Some(start_angle) = get_f32_from_value(&data, "start_angle") {
+            self.start_angle = start_angle;
+        }
+        if let Some(end_angle) = get_f32_from_value(&data, "end_angle") {
+            self.end_angle = end_angle;
+        }
+        if let Some(stroke_width) = get_f32_from_value(&data, "stroke_width") {
+            self.stroke_width = stroke_width;
+        }
+        if let Some(track_color) = get_color_from_value(&data, "track_color") {
+            self.track_color = track_color;
+        }
+        if let Some(arr) = data.get("bands").and_then(|value| value.as_array()) {
+            self.bands = arr
+                .iter()
+                .map(|item| GaugeBand {
+                    end: get_f32_from_value(item, "end").unwrap_or_default(),
+                    color: get_color_from_value(item, "color").unwrap_or(self.track_color),
+                })
+                .collect();
+        }
+        if let Some(arr) = data.get("pointers").and_then(|value| value.as_array()) {
+            self.pointers = arr
+                .iter()
+                .map(|item| {
+                    let mut pointer =
+                        GaugePointer::new(get_f32_from_value(item, "value").unwrap_or_default());
+                    pointer.color = get_color_from_value(item, "color");
+                    if let Some(width) = get_f32_from_value(item, "width") {
+                        pointer.width = width;
+                    }
+                    if let Some(length) = get_f32_from_value(item, "length") {
+                        pointer.length = length;
+                    }
+                    pointer
+                })
+                .collect();
+        }
+        if let Some(series_colors) = get_color_slice_from_value(&data, "series_colors") {
+            self.series_colors = series_colors;
+        }
+        if let Some(split_number) = get_usize_from_value(&data, "split_number") {
+            self.split_number = split_number;
+        }
+        if let Some(tick_color) = get_color_from_value(&data, "tick_color") {
+            self.tick_color = tick_color;
+        }
+        if let Some(tick_font_size) = get_f32_from_value(&data, "tick_font_size") {
+            self.tick_font_size = tick_font_size;
+        }
+        if let Some(tick_font_color) = get_color_from_value(&data, "tick_font_color") {
+            self.tick_font_color = tick_font_color;
+        }
+        if let Some(tick_formatter) = get_string_from_value(&data, "tick_formatter") {
+            self.tick_formatter = tick_formatter;
+        }
+        if let Some(label_show) = get_bool_from_value(&data, "label_show") {
+            self.label_show = label_show;
+        }
+        if let Some(label_font_size) = get_f32_from_value(&data, "label_font_size") {
+            self.label_font_size = label_font_size;
+        }
+        if let Some(label_font_color) = get_color_from_value(&data, "label_font_color") {
+            self.label_font_color = label_font_color;
+        }
+        if let Some(label_font_weight) = get_string_from_value(&data, "label_font_weight") {
+            self.label_font_weight = Some(label_font_weight);
+        }
+        if let Some(label_formatter) = get_string_from_value(&data, "label_formatter") {
+            self.label_formatter = label_formatter;
+        }
+        Ok(data)
+    }
+    /// Creates a gauge chart from json.
+    pub fn from_json(data: &str) -> canvas::Result<GaugeChart> {
+        let mut g = GaugeChart::new_with_theme(vec![], "");
+        g.fill_option(data)?;
+        Ok(g)
+    }
+    /// Creates a gauge chart with custom theme.
+    pub fn new_with_theme(pointers: Vec<GaugePointer>, theme: &str) -> GaugeChart {
+        let mut g = GaugeChart {
+            pointers,
+            width: 300.0,
+            height: 260.0,
+            max: 100.0,
+            start_angle: -120.0,
+            end_angle: 120.0,
+            stroke_width: 15.0,
+            split_number: 10,
+            tick_font_size: 12.0,
+            label_show: true,
+            label_font_size: 24.0,
+            label_font_weight: Some("bold".to_string()),
+            ..Default::default()
+        };
+        g.fill_theme(get_theme(theme));
+        g
+    }
+    fn fill_theme(&mut self, t: std::sync::Arc<Theme>) {
+        self.font_family.clone_from(&t.font_family);
+        self.background_color = t.background_color;
+
+        self.title_font_color = t.title_font_color;
+        self.title_font_size = t.title_font_size;
+        self.title_font_weight.clone_from(&t.title_font_weight);
+
+        self.track_color = t.grid_stroke_color;
+        self.series_colors.clone_from(&t.series_colors);
+        self.tick_color = t.x_axis_stroke_color;
+        self.tick_font_color = t.x_axis_font_color;
+        self.label_font_color = t.title_font_color;
+
+        if let Some(font_family) = &get_default_option().font_family {
+            self.font_family.clone_from(font_family);
+        }
+    }
+    /// Creates a gauge chart with default theme.
+    pub fn new(pointers: Vec<GaugePointer>) -> GaugeChart {
+        GaugeChart::new_with_theme(pointers, &get_default_theme_name())
+    }
+    /// Gets the angle of value, it is clamped in the range of gauge.
+    fn get_angle(&self, value: f32) -> f32 {
+        let range = self.max - self.min;
+        if range <= 0.0 {
+            return self.start_angle;
+        }
+        let percent = ((value - self.min) / range).clamp(0.0, 1.0);
+        self.start_angle + (self.end_angle - self.start_angle) * percent
+    }
+    /// Converts gauge chart to svg.
+    pub fn svg(&self) -> canvas::Result<String> {
+        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        c.rect(Rect {
+            fill: Some(self.background_color),
+            left: 0.0,
+            top: 0.0,
+            width: self.width,
+            height: self.height,
+            ..Default::default()
+        });
+
+        let mut top = 0.0;
+        if !self.title_text.is_empty() {
+            let title_box = measure_text_width_family(
+                &self.font_family,
+                self.title_font_size,
+                &self.title_text,
+            )?;
+            c.text(Text {
+                text: self.title_text.clone(),
+                font_family: Some(self.font_family.clone()),
+                font_size: Some(self.title_font_size),
+                font_weight: self.title_font_weight.clone(),
+                font_color: Some(self.title_font_color),
+                x: Some(self.width / 2.0),
+                y: Some(title_box.height()),
+                align: Some(Align::Center),
+                ..Default::default()
+            });
+            top = title_box.height() + 10.0;
+        }
+
+        let padding = 10.0;
+        let half_stroke_width = self.stroke_width / 2.0;
+        let height = self.height - top;
+        // the height of arc below the center
+        let bottom_ratio = if self.start_angle <= -180.0 || self.end_angle >= 180.0 {
+            1.0
+        } else {
+            (-(self.start_angle.to_radians().cos()))
+                .max(-(self.end_angle.to_radians().cos()))
+                .max(0.0)
+        };
+        let r = (self.width / 2.0).min(height / (1.0 + bottom_ratio)) - padding - half_stroke_width;
+        if r <= 0.0 {
+            return c.svg();
+        }
+        let cx = self.width / 2.0;
+        let cy = top + padding + half_stroke_width + r;
+
+        // the bands cover the track from min value
+        c.arc(Arc {
+            stroke_color: self.track_color,
+            stroke_width: self.stroke_width,
+            cx,
+            cy,
+            r,
+            start_angle: self.start_angle,
+            end_angle: self.end_angle,
+            ..Default::default()
+        });
+        let mut band_start = self.min;
+        for band in self.bands.iter() {
+            c.arc(Arc {
+                stroke_color: band.color,
+                stroke_width: self.stroke_width,
+                cx,
+                cy,
+                r,
+                start_angle: self.get_angle(band_start),
+                end_angle: self.get_angle(band.end),
+                ..Default::default()
+            });
+            band_start = band.end;
+        }
+
+        // ticks and labels inside the arc
+        let split_number = self.split_number.max(1);
+        let tick_r = r - half_stroke_width - 2.0;
+        for i in 0..=split_number {
+            let value = self.min + (self.max - self.min) * i as f32 / split_number as f32;
+            let angle = self.get_angle(value);
+            let start = get_pie_point(cx, cy, tick_r, angle);
+            let end = get_pie_point(cx, cy, tick_r - 8.0, angle);
+            c.line(Line {
+                color: Some(self.tick_color),
+                left: start.x,
+                top: start.y,
+                right: end.x,
+                bottom: end.y,
+                ..Default::default()
+            });
+            let point = get_pie_point(cx, cy, tick_r - 8.0 - self.tick_font_size, angle);
+            c.text(Text {
+                text: format_string(&format_label_float(value), &self.tick_formatter),
+                font_family: Some(self.font_family.clone()),
+                font_size: Some(self.tick_font_size),
+                font_color: Some(self.tick_font_color),
+                x: Some(point.x),
+                y: Some(point.y),
+                align: Some(Align::Center),
+                vertical_align: Some(VerticalAlign::Middle),
+                ..Default::default()
+            });
+        }
+
+        // the needle is a triangle from the base to the tip
+        for (index, pointer) in self.pointers.iter().enumerate() {
+            let color = pointer
+                .color
+                .unwrap_or_else(|| get_color(&self.series_colors, index));
+            let angle = self.get_angle(pointer.value);
+            let half_width = pointer.width / 2.0;
+            c.polygon(Polygon {
+                fill: Some(color),
+                points: vec![
+                    get_pie_point(cx, cy, r * pointer.length, angle),
+                    get_pie_point(cx, cy, half_width, angle + 90.0),
+                    get_pie_point(cx, cy, half_width, angle + 180.0),
+                    get_pie_point(cx, cy, half_width, angle - 90.0),
+                ],
+                ..Default::default()
+            });
+            c.circle(Circle {
+                fill: Some(color),
+                cx,
+                cy,
+                r: half_width,
+                ..Default::default()
+            });
+        }
+
+        if let Some(pointer) = self.pointers.first().filter(|_| self.label_show) {
+            c.text(Text {
+                text: format_string(&format_label_float(pointer.value), &self.label_formatter),
+                font_family: Some(self.font_family.clone()),
+                font_size: Some(self.label_font_size),
+                font_weight: self.label_font_weight.clone(),
+                font_color: Some(self.label_font_color),
+                x: Some(cx),
+                y: Some(cy + r * bottom_ratio.max(0.5) * 0.6),
+                align: Some(Align::Center),
+                vertical_align: Some(VerticalAlign::Middle),
+                ..Default::default()
+            });
+        }
+        c.svg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GaugeBand, GaugeChart, GaugePointer};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn gauge_chart_basic() {
+        let mut target = GaugePointer::new(80.0);
+        target.width = 4.0;
+        target.length = 0.85;
+        let mut gauge_chart = GaugeChart::new(vec![GaugePointer::new(62.5), target]);
+        gauge_chart.title_text = "Sales Target".to_string();
+        gauge_chart.tick_formatter = "{c}%".to_string();
+        gauge_chart.label_formatter = "{c}%".to_string();
+        gauge_chart.bands = vec![
+            GaugeBand {
+                end: 30.0,
+                color: (238, 102, 102).into(),
+            },
+            GaugeBand {
+                end: 70.0,
+                color: (250, 200, 88).into(),
+            },
+            GaugeBand {
+                end: 100.0,
+                color: (145, 204, 117).into(),
+            },
+        ];
+        assert_eq!(
+            include_str!("../../asset/gauge_chart/basic.svg"),
+            gauge_chart.svg().unwrap()
+        );
+    }
+}
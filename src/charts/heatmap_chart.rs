@@ -2,13 +2,17 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::canvas;
+use super::canvas::{self, ChartError};
 use super::color::*;
 use super::common::*;
 use super::component::*;
 use super::font::measure_max_text_width_family;
+use super::locale::LocaleGuard;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme, Theme, DEFAULT_Y_AXIS_WIDTH};
+use super::theme::{
+    find_theme, get_default_theme_name, get_theme, EffectiveStyle, Theme, DEFAULT_FOOTER_FONT_SIZE,
+    DEFAULT_Y_AXIS_WIDTH,
+};
 use super::util::*;
 use super::Canvas;
 use crate::charts::measure_text_width_family;
@@ -50,8 +54,8 @@ impl HeatmapSeries {
             return self.max_color;
         }
         let percent = (value - self.min) / (self.max - self.min);
-        let get_value = |max, min| {
-            let offset = if max > min { max - min } else { min - max };
+        let get_value = |max: u8, min: u8| {
+            let offset = max.abs_diff(min);
             let offset = (offset as f32 * percent) as u8;
             if max > min {
                 min + offset
@@ -68,6 +72,54 @@ impl HeatmapSeries {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum HeatmapSort {
+    // by the sum of row or column values
+    SumAsc,
+    SumDesc,
+    // by the text of axis label
+    Alphabetical,
+    // by the listed labels, the others keep the original order at the end
+    Custom(Vec<String>),
+}
+
+impl HeatmapSort {
+    /// Gets the sorted indexes of labels.
+    fn sort(&self, labels: &[String], sums: &[f32]) -> Vec<usize> {
+        let mut indexes: Vec<usize> = (0..labels.len()).collect();
+        match self {
+            HeatmapSort::SumAsc => indexes.sort_by(|a, b| sums[*a].total_cmp(&sums[*b])),
+            HeatmapSort::SumDesc => indexes.sort_by(|a, b| sums[*b].total_cmp(&sums[*a])),
+            HeatmapSort::Alphabetical => indexes.sort_by(|a, b| labels[*a].cmp(&labels[*b])),
+            HeatmapSort::Custom(order) => indexes.sort_by_key(|index| {
+                order
+                    .iter()
+                    .position(|item| item == &labels[*index])
+                    .unwrap_or(order.len())
+            }),
+        }
+        indexes
+    }
+}
+
+fn get_heatmap_sort_from_value(value: &serde_json::Value, key: &str) -> Option<HeatmapSort> {
+    let value = value.get(key)?;
+    if let Some(arr) = value.as_array() {
+        let order = arr
+            .iter()
+            .filter_map(|item| item.as_str())
+            .map(|item| item.to_string())
+            .collect();
+        return Some(HeatmapSort::Custom(order));
+    }
+    match value.as_str()? {
+        "sum_asc" => Some(HeatmapSort::SumAsc),
+        "sum_desc" => Some(HeatmapSort::SumDesc),
+        "alphabetical" => Some(HeatmapSort::Alphabetical),
+        _ => None,
+    }
+}
+
 #[derive(Clone, Debug, Default, Chart)]
 pub struct HeatmapChart {
     pub width: f32,
@@ -87,8 +139,15 @@ pub struct HeatmapChart {
     pub title_font_size: f32,
     pub title_font_color: Color,
     pub title_font_weight: Option<String>,
+    // font style of title, e.g. italic
+    pub title_font_style: Option<String>,
+    pub title_letter_spacing: Option<f32>,
+    // text decoration of title, e.g. underline
+    pub title_text_decoration: Option<String>,
     pub title_margin: Option<Box>,
     pub title_align: Align,
+    pub title_shadow: Option<Shadow>,
+    pub title_link: Option<String>,
     pub title_height: f32,
 
     // sub title
@@ -96,10 +155,23 @@ pub struct HeatmapChart {
     pub sub_title_font_size: f32,
     pub sub_title_font_color: Color,
     pub sub_title_font_weight: Option<String>,
+    // font style of sub title, e.g. italic
+    pub sub_title_font_style: Option<String>,
+    pub sub_title_letter_spacing: Option<f32>,
+    // text decoration of sub title, e.g. underline
+    pub sub_title_text_decoration: Option<String>,
     pub sub_title_margin: Option<Box>,
     pub sub_title_align: Align,
     pub sub_title_height: f32,
 
+    // footer
+    pub footer_text: String,
+    pub footer_font_size: f32,
+    pub footer_font_color: Color,
+    pub footer_font_weight: Option<String>,
+    pub footer_margin: Option<Box>,
+    pub footer_align: Align,
+
     // legend
     pub legend_font_size: f32,
     pub legend_font_color: Color,
@@ -108,6 +180,8 @@ pub struct HeatmapChart {
     pub legend_margin: Option<Box>,
     pub legend_category: LegendCategory,
     pub legend_show: Option<bool>,
+    pub legend_position: Option<Position>,
+    pub legend_order: Option<Vec<String>>,
 
     // x axis
     pub x_axis_data: Vec<String>,
@@ -121,15 +195,27 @@ pub struct HeatmapChart {
     pub x_axis_margin: Option<Box>,
     pub x_axis_hidden: bool,
     pub x_boundary_gap: Option<bool>,
+    // the range(start and end index, inclusive) of categories to render, the axes are recalculated
+    pub x_range: Option<(usize, usize)>,
+    // the overview strip of all categories below the chart, the x range is highlighted
+    pub x_range_overview: bool,
+    // the order of columns, it is applied before layout
+    pub x_axis_sort: Option<HeatmapSort>,
 
     // y axis
     pub y_axis_hidden: bool,
     pub y_axis_data: Vec<String>,
+    // the order of rows from top to bottom, it is applied before layout
+    pub y_axis_sort: Option<HeatmapSort>,
     y_axis_configs: Vec<YAxisConfig>,
 
     // grid
     grid_stroke_color: Color,
     grid_stroke_width: f32,
+    // the color of grid line at zero value, it is not highlighted if not set
+    zero_line_color: Option<Color>,
+    // the reference line of statistic computed across all visible series
+    guide_line: Option<GuideLine>,
 
     // series
     pub series_stroke_width: f32,
@@ -137,10 +223,41 @@ pub struct HeatmapChart {
     pub series_label_font_size: f32,
     pub series_label_font_weight: Option<String>,
     pub series_label_formatter: String,
+    pub series_label_overlap: LabelOverlap,
+    pub series_shadow: Option<Shadow>,
     pub series_colors: Vec<Color>,
     pub series_symbol: Option<Symbol>,
     pub series_smooth: bool,
     pub series_fill: bool,
+    pub series_fill_opacity: Option<f32>,
+    pub max_points_per_series: Option<usize>,
+    pub annotations: Vec<Annotation>,
+    // emit the stable id and class of elements, e.g. series-0-bar-3
+    pub element_ids: bool,
+    // native tooltip of elements, e.g. Mon: Email = 120
+    pub tooltip_show: bool,
+    // accessible name and description of chart
+    pub aria_label: Option<String>,
+    pub description: Option<String>,
+    pub svg_format: SVGFormat,
+    pub coordinate_precision: Option<usize>,
+    pub style_hoisting: bool,
+    pub font_embedding: FontEmbedding,
+    // emit the svg of 100% width, the height is scaled by aspect ratio
+    pub responsive: bool,
+    // mirrored layout of right-to-left locale, the y axis is on the right side
+    pub rtl: bool,
+    // locale of number and date names, e.g. de-DE
+    pub locale: Option<String>,
+    // the placeholder of empty series, the text is "No data" if not set
+    pub empty_text: Option<String>,
+    pub empty_icon: bool,
+    // the inset charts, they are rendered after the series
+    pub insets: Vec<Inset>,
+    // the emphasized series or points, the others are faded
+    pub emphasis: Option<Emphasis>,
+    // the selected category which is indicated by crosshair line and band
+    pub axis_pointer: Option<AxisPointer>,
 }
 
 impl HeatmapChart {
@@ -225,6 +342,8 @@ impl HeatmapChart {
         if let Some(y_axis_hidden) = get_bool_from_value(&value, "y_axis_hidden") {
             h.y_axis_hidden = y_axis_hidden;
         }
+        h.x_axis_sort = get_heatmap_sort_from_value(&value, "x_axis_sort");
+        h.y_axis_sort = get_heatmap_sort_from_value(&value, "y_axis_sort");
         Ok(h)
     }
     /// Creates a heatmap chart with default theme.
@@ -268,7 +387,18 @@ impl HeatmapChart {
     }
     /// Converts heatmap chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = self.render_empty_state()? {
+            return Ok(svg);
+        }
+        let _locale = LocaleGuard::new(self.locale.as_deref());
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        c.aria_label = self.aria_label.clone();
+        c.description = self.description.clone();
+        c.svg_format = self.svg_format.clone();
+        c.coordinate_precision = self.coordinate_precision;
+        c.style_hoisting = self.style_hoisting;
+        c.font_embedding = self.font_embedding.clone();
+        c.responsive = self.responsive;
 
         if self.x_axis_data.is_empty() || self.y_axis_data.is_empty() {
             return Err(canvas::Error::Params {
@@ -286,13 +416,25 @@ impl HeatmapChart {
 
         let title_height = self.render_title(c.child(Box::default()));
 
-        let legend_height = self.render_legend(c.child(Box::default()));
+        let footer_height = self.render_footer(c.child(Box::default()));
+
+        let legend_box = self.render_legend(c.child(Box {
+            bottom: footer_height,
+            ..Default::default()
+        }));
         // get the max height of title and legend
-        let axis_top = if legend_height > title_height {
-            legend_height
+        let axis_top = if legend_box.top > title_height {
+            legend_box.top
         } else {
             title_height
         };
+        // the legend on the other sides and footer take the space of chart
+        c = c.child(Box {
+            left: legend_box.left,
+            right: legend_box.right,
+            bottom: legend_box.bottom + footer_height,
+            ..Default::default()
+        });
         let axis_height = c.height() - x_axis_height - axis_top;
 
         // minus the height of top text area
@@ -302,6 +444,47 @@ impl HeatmapChart {
                 ..Default::default()
             });
         }
+        let mut data = vec![None; self.x_axis_data.len() * self.y_axis_data.len()];
+        for item in self.series.data.iter() {
+            if item.index < data.len() {
+                data[item.index] = Some(item.value);
+            }
+        }
+        let get_value =
+            |i: usize, j: usize| data.get(i * self.y_axis_data.len() + j).cloned().flatten();
+        // the source column of each column
+        let x_order = if let Some(ref sort) = self.x_axis_sort {
+            let sums: Vec<f32> = (0..self.x_axis_data.len())
+                .map(|j| {
+                    (0..self.y_axis_data.len())
+                        .filter_map(|i| get_value(i, j))
+                        .sum()
+                })
+                .collect();
+            sort.sort(&self.x_axis_data, &sums)
+        } else {
+            (0..self.x_axis_data.len()).collect()
+        };
+        // the source row of each row, the row starts from bottom
+        let y_order = if let Some(ref sort) = self.y_axis_sort {
+            let sums: Vec<f32> = (0..self.y_axis_data.len())
+                .map(|i| {
+                    (0..self.x_axis_data.len())
+                        .filter_map(|j| get_value(i, j))
+                        .sum()
+                })
+                .collect();
+            let mut order = sort.sort(&self.y_axis_data, &sums);
+            order.reverse();
+            order
+        } else {
+            (0..self.y_axis_data.len()).collect()
+        };
+        let x_axis_data: Vec<String> = x_order
+            .iter()
+            .map(|j| self.x_axis_data[*j].clone())
+            .collect();
+
         let mut y_axis_width = 0.0;
         if !self.y_axis_hidden {
             let max_text_width_box = measure_max_text_width_family(
@@ -311,7 +494,10 @@ impl HeatmapChart {
             )?;
             y_axis_width = max_text_width_box.width() + self.margin.left;
             // y axis
-            let mut y_axis_data = self.y_axis_data.clone();
+            let mut y_axis_data: Vec<String> = y_order
+                .iter()
+                .map(|i| self.y_axis_data[*i].clone())
+                .collect();
             y_axis_data.reverse();
             self.render_y_axis(
                 c.child_left_top(Box::default()),
@@ -330,17 +516,10 @@ impl HeatmapChart {
                     left: y_axis_width,
                     ..Default::default()
                 }),
-                self.x_axis_data.clone(),
+                x_axis_data,
                 axis_width,
             );
         }
-        let mut data = vec![None; self.x_axis_data.len() * self.y_axis_data.len()];
-        for item in self.series.data.iter() {
-            if item.index < data.len() {
-                data[item.index] = Some(item.value);
-            }
-        }
-
         let x_unit = (axis_width - 1.0) / self.x_axis_data.len() as f32;
         let y_unit = (axis_height - 1.0) / self.y_axis_data.len() as f32;
         let mut c1 = c.child(Box {
@@ -348,22 +527,25 @@ impl HeatmapChart {
             ..Default::default()
         });
         let y_axis_count = self.y_axis_data.len();
-        for i in 0..y_axis_count {
-            for j in 0..self.x_axis_data.len() {
-                let index = i * self.y_axis_data.len() + j;
+        for (i, row) in y_order.iter().enumerate() {
+            for (j, column) in x_order.iter().enumerate() {
                 let x = x_unit * j as f32;
                 // position of y axis starts from bottom
                 let y = y_unit * (y_axis_count - i - 1) as f32;
                 let mut text = "".to_string();
                 let mut font_color = self.series.min_font_color;
-                let color = if let Some(value) = data[index] {
-                    let percent = (value - self.series.min) / (self.series.max - self.series.min);
-                    if percent >= 0.8 {
-                        font_color = self.series.max_font_color;
-                    }
+                let color = if let Some(value) = get_value(*row, *column) {
+                    let color = self.series.get_color(value);
+                    // the font color which has more contrast with the cell
+                    font_color = color
+                        .contrast_color_of(&[
+                            self.series.min_font_color,
+                            self.series.max_font_color,
+                        ])
+                        .unwrap_or(font_color);
 
                     text = format_series_value(value, &self.series_label_formatter);
-                    self.series.get_color(value)
+                    color
                 } else {
                     let mut color_index = j;
                     if i % 2 != 0 {
@@ -395,30 +577,25 @@ impl HeatmapChart {
                     ..Default::default()
                 });
                 if !text.is_empty() {
-                    let mut x1 = x + x_unit / 2.0;
-                    let y1 = y + y_unit / 2.0;
-                    if let Ok(b) = measure_text_width_family(
-                        &self.font_family,
-                        self.series_label_font_size,
-                        &text,
-                    ) {
-                        x1 -= b.width() / 2.0;
-                    }
                     c1.text(Text {
                         text,
                         font_family: Some(self.font_family.clone()),
                         font_color: Some(font_color),
                         font_size: Some(self.series_label_font_size),
                         font_weight: self.series_label_font_weight.clone(),
-                        dominant_baseline: Some("central".to_string()),
-                        x: Some(x1),
-                        y: Some(y1),
+                        align: Some(Align::Center),
+                        vertical_align: Some(VerticalAlign::Middle),
+                        x: Some(x + x_unit / 2.0),
+                        y: Some(y + y_unit / 2.0),
                         ..Default::default()
                     });
                 }
             }
         }
 
+        // only the annotations of pixel coordinates are supported
+        self.render_annotations(c.child(Box::default()), &[], 0.0, 0);
+        self.render_insets(c.child(Box::default()));
         c.svg()
     }
 }
@@ -427,7 +604,7 @@ impl HeatmapChart {
 mod tests {
     use crate::THEME_DARK;
 
-    use super::HeatmapChart;
+    use super::{HeatmapChart, HeatmapSort};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -567,4 +744,51 @@ mod tests {
             heatmap_chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn heatmap_chart_sort() {
+        let x_axis_data = vec![
+            "12a", "1a", "2a", "3a", "4a", "5a", "6a", "7a", "8a", "9a", "10a", "11a", "12p", "1p",
+            "2p", "3p", "4p", "5p", "6p", "7p", "8p", "9p", "10p", "11p",
+        ]
+        .iter()
+        .map(|item| item.to_string())
+        .collect();
+        let y_axis_data = [
+            "Saturday",
+            "Friday",
+            "Thursday",
+            "Wednesday",
+            "Tuesday",
+            "Monday",
+            "Sunday",
+        ]
+        .iter()
+        .map(|item| item.to_string())
+        .collect();
+        let mut heatmap_chart = HeatmapChart::new(
+            vec![
+                (0, 9.0),
+                (1, 3.0),
+                (7, 3.0),
+                (12, 3.0),
+                (24, 12.0),
+                (28, 10.0),
+                (31, 8.0),
+                (50, 4.0),
+                (63, 2.0),
+            ],
+            x_axis_data,
+            y_axis_data,
+        );
+        heatmap_chart.width = 800.0;
+        heatmap_chart.series.max = 10.0;
+        heatmap_chart.x_axis_sort = Some(HeatmapSort::SumDesc);
+        heatmap_chart.y_axis_sort = Some(HeatmapSort::Alphabetical);
+
+        assert_eq!(
+            include_str!("../../asset/heatmap_chart/sort.svg"),
+            heatmap_chart.svg().unwrap()
+        );
+    }
 }
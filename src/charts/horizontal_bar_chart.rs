@@ -2,12 +2,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::canvas;
+use super::canvas::{self, ChartError};
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::locale::LocaleGuard;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme, Theme, DEFAULT_Y_AXIS_WIDTH};
+use super::theme::{
+    find_theme, get_default_theme_name, get_theme, EffectiveStyle, Theme, DEFAULT_FOOTER_FONT_SIZE,
+    DEFAULT_Y_AXIS_WIDTH,
+};
 use super::util::*;
 use super::Canvas;
 use crate::charts::measure_text_width_family;
@@ -31,8 +35,15 @@ pub struct HorizontalBarChart {
     pub title_font_size: f32,
     pub title_font_color: Color,
     pub title_font_weight: Option<String>,
+    // font style of title, e.g. italic
+    pub title_font_style: Option<String>,
+    pub title_letter_spacing: Option<f32>,
+    // text decoration of title, e.g. underline
+    pub title_text_decoration: Option<String>,
     pub title_margin: Option<Box>,
     pub title_align: Align,
+    pub title_shadow: Option<Shadow>,
+    pub title_link: Option<String>,
     pub title_height: f32,
 
     // sub title
@@ -40,10 +51,23 @@ pub struct HorizontalBarChart {
     pub sub_title_font_size: f32,
     pub sub_title_font_color: Color,
     pub sub_title_font_weight: Option<String>,
+    // font style of sub title, e.g. italic
+    pub sub_title_font_style: Option<String>,
+    pub sub_title_letter_spacing: Option<f32>,
+    // text decoration of sub title, e.g. underline
+    pub sub_title_text_decoration: Option<String>,
     pub sub_title_margin: Option<Box>,
     pub sub_title_align: Align,
     pub sub_title_height: f32,
 
+    // footer
+    pub footer_text: String,
+    pub footer_font_size: f32,
+    pub footer_font_color: Color,
+    pub footer_font_weight: Option<String>,
+    pub footer_margin: Option<Box>,
+    pub footer_align: Align,
+
     // legend
     pub legend_font_size: f32,
     pub legend_font_color: Color,
@@ -52,6 +76,8 @@ pub struct HorizontalBarChart {
     pub legend_margin: Option<Box>,
     pub legend_category: LegendCategory,
     pub legend_show: Option<bool>,
+    pub legend_position: Option<Position>,
+    pub legend_order: Option<Vec<String>>,
 
     // x axis
     pub x_axis_data: Vec<String>,
@@ -64,6 +90,10 @@ pub struct HorizontalBarChart {
     pub x_axis_name_rotate: f32,
     pub x_axis_margin: Option<Box>,
     pub x_boundary_gap: Option<bool>,
+    // the range(start and end index, inclusive) of categories to render, the axes are recalculated
+    pub x_range: Option<(usize, usize)>,
+    // the overview strip of all categories below the chart, the x range is highlighted
+    pub x_range_overview: bool,
 
     // y axis
     pub y_axis_configs: Vec<YAxisConfig>,
@@ -71,6 +101,10 @@ pub struct HorizontalBarChart {
     // grid
     pub grid_stroke_color: Color,
     pub grid_stroke_width: f32,
+    // the color of grid line at zero value, it is not highlighted if not set
+    pub zero_line_color: Option<Color>,
+    // the reference line of statistic computed across all visible series
+    pub guide_line: Option<GuideLine>,
 
     // series
     pub series_stroke_width: f32,
@@ -78,11 +112,54 @@ pub struct HorizontalBarChart {
     pub series_label_font_size: f32,
     pub series_label_font_weight: Option<String>,
     pub series_label_formatter: String,
+    pub series_label_overlap: LabelOverlap,
+    pub series_shadow: Option<Shadow>,
     pub series_label_position: Option<Position>,
     pub series_colors: Vec<Color>,
     pub series_symbol: Option<Symbol>,
     pub series_smooth: bool,
     pub series_fill: bool,
+    pub series_fill_opacity: Option<f32>,
+    pub max_points_per_series: Option<usize>,
+
+    pub bar_width: Option<f32>,
+    pub bar_max_width: Option<f32>,
+    // gap between the bars of series
+    pub bar_gap: Option<f32>,
+    // gap between the bars and the edge of category
+    pub bar_category_gap: Option<f32>,
+    // the category axis is rendered at the zero position of value axis,
+    // it is useful for diverging bars, e.g. profit and loss
+    pub x_axis_on_zero: bool,
+    // the height of bar, the chart height is computed to fit all categories if it is set
+    pub auto_bar_height: Option<f32>,
+    pub annotations: Vec<Annotation>,
+    // emit the stable id and class of elements, e.g. series-0-bar-3
+    pub element_ids: bool,
+    // native tooltip of elements, e.g. Mon: Email = 120
+    pub tooltip_show: bool,
+    // accessible name and description of chart
+    pub aria_label: Option<String>,
+    pub description: Option<String>,
+    pub svg_format: SVGFormat,
+    pub coordinate_precision: Option<usize>,
+    pub style_hoisting: bool,
+    pub font_embedding: FontEmbedding,
+    // emit the svg of 100% width, the height is scaled by aspect ratio
+    pub responsive: bool,
+    // mirrored layout of right-to-left locale, the y axis is on the right side
+    pub rtl: bool,
+    // locale of number and date names, e.g. de-DE
+    pub locale: Option<String>,
+    // the placeholder of empty series, the text is "No data" if not set
+    pub empty_text: Option<String>,
+    pub empty_icon: bool,
+    // the inset charts, they are rendered after the series
+    pub insets: Vec<Inset>,
+    // the emphasized series or points, the others are faded
+    pub emphasis: Option<Emphasis>,
+    // the selected category which is indicated by crosshair line and band
+    pub axis_pointer: Option<AxisPointer>,
 }
 
 impl HorizontalBarChart {
@@ -97,6 +174,24 @@ impl HorizontalBarChart {
         {
             h.series_label_position = Some(series_label_position);
         }
+        if let Some(bar_width) = get_f32_from_value(&value, "bar_width") {
+            h.bar_width = Some(bar_width);
+        }
+        if let Some(bar_max_width) = get_f32_from_value(&value, "bar_max_width") {
+            h.bar_max_width = Some(bar_max_width);
+        }
+        if let Some(bar_gap) = get_f32_from_value(&value, "bar_gap") {
+            h.bar_gap = Some(bar_gap);
+        }
+        if let Some(bar_category_gap) = get_f32_from_value(&value, "bar_category_gap") {
+            h.bar_category_gap = Some(bar_category_gap);
+        }
+        if let Some(x_axis_on_zero) = get_bool_from_value(&value, "x_axis_on_zero") {
+            h.x_axis_on_zero = x_axis_on_zero;
+        }
+        if let Some(auto_bar_height) = get_f32_from_value(&value, "auto_bar_height") {
+            h.auto_bar_height = Some(auto_bar_height);
+        }
         Ok(h)
     }
     /// Creates a horizontal bar with custom theme.
@@ -120,23 +215,68 @@ impl HorizontalBarChart {
     }
     /// Converts horizontal bar chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = self.render_empty_state()? {
+            return Ok(svg);
+        }
+        let _locale = LocaleGuard::new(self.locale.as_deref());
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        c.aria_label = self.aria_label.clone();
+        c.description = self.description.clone();
+        c.svg_format = self.svg_format.clone();
+        c.coordinate_precision = self.coordinate_precision;
+        c.style_hoisting = self.style_hoisting;
+        c.font_embedding = self.font_embedding.clone();
+        c.responsive = self.responsive;
 
         self.render_background(c.child(Box::default()));
         c.margin = self.margin.clone();
 
         let title_height = self.render_title(c.child(Box::default()));
 
-        let legend_height = self.render_legend(c.child(Box::default()));
+        let footer_height = self.render_footer(c.child(Box::default()));
+
+        let legend_box = self.render_legend(c.child(Box {
+            bottom: footer_height,
+            ..Default::default()
+        }));
         // get the max height of title and legend
-        let axis_top = if legend_height > title_height {
-            legend_height
+        let axis_top = if legend_box.top > title_height {
+            legend_box.top
         } else {
             title_height
         };
+        // the legend on the other sides and footer take the space of chart
+        c = c.child(Box {
+            left: legend_box.left,
+            right: legend_box.right,
+            bottom: legend_box.bottom + footer_height,
+            ..Default::default()
+        });
 
         let x_axis_height = 25.0_f32;
         let axis_height = c.height() - axis_top - x_axis_height;
+        // the chart is rendered again with the height which fits all categories
+        if let Some(bar_height) = self.auto_bar_height {
+            let series_count = self
+                .series_list
+                .iter()
+                .filter(|series| series.visible)
+                .count()
+                .max(1) as f32;
+            let category_count = self
+                .series_list
+                .first()
+                .map(|series| series.data.len())
+                .unwrap_or_default();
+            let category_height = bar_height * series_count
+                + self.bar_gap.unwrap_or(3.0) * (series_count - 1.0)
+                + self.bar_category_gap.unwrap_or(5.0) * 2.0;
+            let mut chart = self.clone();
+            chart.auto_bar_height = None;
+            chart.bar_width = Some(bar_height);
+            chart.height = self.height - axis_height + category_height * category_count as f32;
+            return chart.svg();
+        }
         // minus the height of top text area
         if axis_top > 0.0 {
             c = c.child(Box {
@@ -159,7 +299,30 @@ impl HorizontalBarChart {
 
         let y_axis_width = max_width + 5.0;
 
-        c.axis(Axis {
+        let mut data_list = vec![];
+        for series in self.series_list.iter().filter(|item| item.visible) {
+            data_list.append(series.get_data().as_mut());
+        }
+        let x_axis_config = self.get_y_axis_config(0);
+        let x_axis_values = get_axis_values(AxisValueParams {
+            data_list,
+            split_number: x_axis_config.axis_split_number,
+            include_zero: x_axis_config.axis_include_zero,
+            symmetric: x_axis_config.axis_symmetric,
+            nice: x_axis_config.axis_nice,
+            max_significant_digits: x_axis_config.axis_max_significant_digits,
+            ..Default::default()
+        });
+        let x_axis_width = c.width() - y_axis_width;
+        // the bar grows from zero, so the bar of negative value grows leftward
+        let zero_x = x_axis_width - x_axis_values.get_zero_offset_height(x_axis_width);
+        let category_axis_left = if self.x_axis_on_zero { zero_x } else { 0.0 };
+
+        c.child(Box {
+            left: category_axis_left,
+            ..Default::default()
+        })
+        .axis(Axis {
             position: Position::Left,
             height: axis_height,
             width: y_axis_width,
@@ -174,18 +337,6 @@ impl HorizontalBarChart {
             ..Default::default()
         });
 
-        let mut data_list = vec![];
-        for series in self.series_list.iter() {
-            data_list.append(series.data.clone().as_mut());
-        }
-        let x_axis_config = self.get_y_axis_config(0);
-        let x_axis_values = get_axis_values(AxisValueParams {
-            data_list,
-            split_number: x_axis_config.axis_split_number,
-            ..Default::default()
-        });
-
-        let x_axis_width = c.width() - y_axis_width;
         c.child(Box {
             left: y_axis_width,
             top: axis_height,
@@ -229,41 +380,82 @@ impl HorizontalBarChart {
             });
             let max_width = c1.width();
             let unit_height = c1.height() / self.series_list[0].data.len() as f32;
-            let bar_chart_margin = 5.0_f32;
-            let bar_chart_gap = 3.0_f32;
-
-            let bar_chart_margin_height = bar_chart_margin * 2.0;
-            let bar_chart_gap_height = bar_chart_gap * (self.series_list.len() - 1) as f32;
-            let bar_height = (unit_height - bar_chart_margin_height - bar_chart_gap_height)
-                / self.series_list.len() as f32;
+            let bar_params = BarParams {
+                width: self.bar_width,
+                max_width: self.bar_max_width,
+                gap: self.bar_gap,
+                category_gap: self.bar_category_gap,
+                ..Default::default()
+            };
+            // the hidden series doesn't take the place of bar
+            let visible_series_list: Vec<(usize, &Series)> = self
+                .series_list
+                .iter()
+                .enumerate()
+                .filter(|(_, series)| series.visible)
+                .collect();
+            let bar_size = get_bar_size(&bar_params, unit_height, visible_series_list.len());
+            let bar_chart_margin = bar_size.margin;
+            let bar_chart_gap = bar_size.gap;
+            let bar_height = bar_size.width;
             let half_bar_height = bar_height / 2.0;
 
             let mut series_labels_list = vec![];
-            for (index, series) in self.series_list.iter().enumerate() {
+            for (bar_index, (index, series)) in visible_series_list.into_iter().enumerate() {
                 let color = get_color(&self.series_colors, series.index.unwrap_or(index));
 
+                let fill_id =
+                    series.append_fill_defs(&mut c1, series.index.unwrap_or(index), color);
                 let mut series_labels = vec![];
-                let series_data_count = series.data.len();
-                for (i, p) in series.data.iter().enumerate() {
+                let data = series.get_data();
+                let series_data_count = data.len();
+                for (i, p) in data.iter().enumerate() {
                     let value = p.to_owned();
-                    if value == NIL_VALUE {
-                        continue;
-                    }
                     let mut top =
                         unit_height * (series_data_count - i - 1) as f32 + bar_chart_margin;
-                    top += (bar_height + bar_chart_gap) * index as f32;
+                    top += (bar_height + bar_chart_gap) * bar_index as f32;
+                    if series.show_background {
+                        c1.rect(Rect {
+                            fill: Some(series.get_background_color()),
+                            top,
+                            width: max_width,
+                            height: bar_height,
+                            ..Default::default()
+                        });
+                    }
+                    if is_nil_value(value) {
+                        continue;
+                    }
 
                     let x = max_width - x_axis_values.get_offset_height(value, max_width);
+                    let mut fill = color;
+                    if value < 0.0 {
+                        fill = series.negative_color.unwrap_or(color);
+                    }
+                    if let Some(item_color) = series.get_item_color(i) {
+                        fill = item_color;
+                    }
                     c1.rect(Rect {
-                        fill: Some(color),
+                        fill: Some(fill),
+                        fill_id: fill_id.clone(),
+                        left: x.min(zero_x),
                         top,
-                        width: x,
+                        width: (x - zero_x).abs(),
                         height: bar_height,
+                        id: self.element_ids.then(|| format!("series-{index}-bar-{i}")),
+                        class: self
+                            .element_ids
+                            .then(|| format!("series-bar series-{index}")),
+                        title: self.get_tooltip(series, i, value),
                         ..Default::default()
                     });
                     series_labels.push(SeriesLabel {
                         point: (x, top + half_bar_height).into(),
-                        text: format_series_value(value, &self.series_label_formatter),
+                        text: self.format_series_label(series, i, value),
+                        id: self
+                            .element_ids
+                            .then(|| format!("series-{index}-label-{i}")),
+                        ..Default::default()
                     })
                 }
                 if series.label_show {
@@ -280,6 +472,13 @@ impl HorizontalBarChart {
                     let mut dy = None;
                     let mut dx = Some(3.0);
                     let mut x = Some(series_label.point.x);
+                    let mut font_color = self.series_label_font_color;
+                    // the label inside uses the contrast color of bar
+                    if series_label_position == Position::Inside {
+                        if let Some(fill) = series_label.fill {
+                            font_color = fill.contrast_color();
+                        }
+                    }
                     if let Ok(value) = measure_text_width_family(
                         &self.font_family,
                         self.series_label_font_size,
@@ -288,15 +487,19 @@ impl HorizontalBarChart {
                         dy = Some(value.height() / 2.0 - 2.0);
                         if series_label_position == Position::Inside {
                             dx = None;
-                            let offset = series_label.point.x - value.width();
+                            let left = series_label.point.x.min(zero_x);
+                            let offset = (series_label.point.x - zero_x).abs() - value.width();
                             if offset <= 0.0 {
-                                x = Some(1.0);
+                                x = Some(left + 1.0);
                             } else {
-                                x = Some(offset / 2.0);
+                                x = Some(left + offset / 2.0);
                             }
                         } else if series_label_position == Position::Left {
                             x = Some(0.0);
                             dx = Some(-value.width());
+                        } else if series_label.point.x < zero_x {
+                            // the label of negative value is on the left of bar
+                            dx = Some(-value.width() - 3.0);
                         }
                     }
                     c1.text(Text {
@@ -304,16 +507,21 @@ impl HorizontalBarChart {
                         dx,
                         dy,
                         font_family: Some(self.font_family.clone()),
-                        font_color: Some(self.series_label_font_color),
+                        font_color: Some(font_color),
                         font_size: Some(self.series_label_font_size),
                         x,
                         y: Some(series_label.point.y),
+                        id: series_label.id.clone(),
+                        class: series_label.id.as_ref().map(|_| "series-label".to_string()),
                         ..Default::default()
                     });
                 }
             }
         }
 
+        // only the annotations of pixel coordinates are supported
+        self.render_annotations(c.child(Box::default()), &[], 0.0, 0);
+        self.render_insets(c.child(Box::default()));
         c.svg()
     }
 }
@@ -427,4 +635,55 @@ mod tests {
             horizontal_bar_chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn horizontal_bar_chart_all_nil_value() {
+        let mut horizontal_bar_chart = HorizontalBarChart::new(
+            vec![("2011", vec![NIL_VALUE, NIL_VALUE]).into()],
+            vec!["Brazil".to_string(), "Indonesia".to_string()],
+        );
+        horizontal_bar_chart.title_text = "World Population".to_string();
+        // the zero baseline is not clamped to the empty range of values
+        assert_eq!(true, horizontal_bar_chart.svg().is_ok());
+    }
+
+    #[test]
+    fn horizontal_bar_chart_diverging() {
+        let mut horizontal_bar_chart = HorizontalBarChart::new(
+            vec![("Profit", vec![320.0, -132.0, 101.0, -234.0, 190.0]).into()],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        horizontal_bar_chart.title_text = "Profit and Loss".to_string();
+        horizontal_bar_chart.x_axis_on_zero = true;
+        horizontal_bar_chart.series_list[0].label_show = true;
+        horizontal_bar_chart.series_list[0].negative_color = Some((238, 102, 102).into());
+        assert_eq!(
+            include_str!("../../asset/horizontal_bar_chart/diverging.svg"),
+            horizontal_bar_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn horizontal_bar_chart_auto_height() {
+        let mut horizontal_bar_chart = HorizontalBarChart::new(
+            vec![(
+                "2011",
+                (1..=12_u32).map(|i| (i * 1000) as f32).collect::<Vec<_>>(),
+            )
+                .into()],
+            (1..=12).map(|i| format!("Country {i}")).collect(),
+        );
+        horizontal_bar_chart.title_text = "Auto Height".to_string();
+        horizontal_bar_chart.auto_bar_height = Some(20.0);
+        assert_eq!(
+            include_str!("../../asset/horizontal_bar_chart/auto_height.svg"),
+            horizontal_bar_chart.svg().unwrap()
+        );
+    }
 }
@@ -2,12 +2,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::canvas;
+use super::canvas::{self, ChartError};
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::locale::LocaleGuard;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme, Theme, DEFAULT_Y_AXIS_WIDTH};
+use super::theme::{
+    find_theme, get_default_theme_name, get_theme, EffectiveStyle, Theme, DEFAULT_FOOTER_FONT_SIZE,
+    DEFAULT_Y_AXIS_WIDTH,
+};
 use super::util::*;
 use super::Canvas;
 use crate::charts::measure_text_width_family;
@@ -31,8 +35,15 @@ pub struct LineChart {
     pub title_font_size: f32,
     pub title_font_color: Color,
     pub title_font_weight: Option<String>,
+    // font style of title, e.g. italic
+    pub title_font_style: Option<String>,
+    pub title_letter_spacing: Option<f32>,
+    // text decoration of title, e.g. underline
+    pub title_text_decoration: Option<String>,
     pub title_margin: Option<Box>,
     pub title_align: Align,
+    pub title_shadow: Option<Shadow>,
+    pub title_link: Option<String>,
     pub title_height: f32,
 
     // sub title
@@ -40,10 +51,23 @@ pub struct LineChart {
     pub sub_title_font_size: f32,
     pub sub_title_font_color: Color,
     pub sub_title_font_weight: Option<String>,
+    // font style of sub title, e.g. italic
+    pub sub_title_font_style: Option<String>,
+    pub sub_title_letter_spacing: Option<f32>,
+    // text decoration of sub title, e.g. underline
+    pub sub_title_text_decoration: Option<String>,
     pub sub_title_margin: Option<Box>,
     pub sub_title_align: Align,
     pub sub_title_height: f32,
 
+    // footer
+    pub footer_text: String,
+    pub footer_font_size: f32,
+    pub footer_font_color: Color,
+    pub footer_font_weight: Option<String>,
+    pub footer_margin: Option<Box>,
+    pub footer_align: Align,
+
     // legend
     pub legend_font_size: f32,
     pub legend_font_color: Color,
@@ -52,6 +76,8 @@ pub struct LineChart {
     pub legend_margin: Option<Box>,
     pub legend_category: LegendCategory,
     pub legend_show: Option<bool>,
+    pub legend_position: Option<Position>,
+    pub legend_order: Option<Vec<String>>,
 
     // x axis
     pub x_axis_data: Vec<String>,
@@ -65,6 +91,10 @@ pub struct LineChart {
     pub x_axis_margin: Option<Box>,
     pub x_axis_hidden: bool,
     pub x_boundary_gap: Option<bool>,
+    // the range(start and end index, inclusive) of categories to render, the axes are recalculated
+    pub x_range: Option<(usize, usize)>,
+    // the overview strip of all categories below the chart, the x range is highlighted
+    pub x_range_overview: bool,
 
     // y axis
     pub y_axis_hidden: bool,
@@ -73,6 +103,10 @@ pub struct LineChart {
     // grid
     pub grid_stroke_color: Color,
     pub grid_stroke_width: f32,
+    // the color of grid line at zero value, it is not highlighted if not set
+    pub zero_line_color: Option<Color>,
+    // the reference line of statistic computed across all visible series
+    pub guide_line: Option<GuideLine>,
 
     // series
     pub series_stroke_width: f32,
@@ -80,10 +114,41 @@ pub struct LineChart {
     pub series_label_font_size: f32,
     pub series_label_font_weight: Option<String>,
     pub series_label_formatter: String,
+    pub series_label_overlap: LabelOverlap,
+    pub series_shadow: Option<Shadow>,
     pub series_colors: Vec<Color>,
     pub series_symbol: Option<Symbol>,
     pub series_smooth: bool,
     pub series_fill: bool,
+    pub series_fill_opacity: Option<f32>,
+    pub max_points_per_series: Option<usize>,
+    pub annotations: Vec<Annotation>,
+    // emit the stable id and class of elements, e.g. series-0-bar-3
+    pub element_ids: bool,
+    // native tooltip of elements, e.g. Mon: Email = 120
+    pub tooltip_show: bool,
+    // accessible name and description of chart
+    pub aria_label: Option<String>,
+    pub description: Option<String>,
+    pub svg_format: SVGFormat,
+    pub coordinate_precision: Option<usize>,
+    pub style_hoisting: bool,
+    pub font_embedding: FontEmbedding,
+    // emit the svg of 100% width, the height is scaled by aspect ratio
+    pub responsive: bool,
+    // mirrored layout of right-to-left locale, the y axis is on the right side
+    pub rtl: bool,
+    // locale of number and date names, e.g. de-DE
+    pub locale: Option<String>,
+    // the placeholder of empty series, the text is "No data" if not set
+    pub empty_text: Option<String>,
+    pub empty_icon: bool,
+    // the inset charts, they are rendered after the series
+    pub insets: Vec<Inset>,
+    // the emphasized series or points, the others are faded
+    pub emphasis: Option<Emphasis>,
+    // the selected category which is indicated by crosshair line and band
+    pub axis_pointer: Option<AxisPointer>,
 }
 
 impl LineChart {
@@ -129,7 +194,7 @@ impl LineChart {
     ) {
         let mut c = c;
         for (index, series) in series_list.iter().enumerate() {
-            if series.mark_lines.is_empty() {
+            if !series.visible || series.mark_lines.is_empty() {
                 continue;
             }
             let y_axis_values = if series.y_axis_index >= y_axis_values_list.len() {
@@ -138,10 +203,19 @@ impl LineChart {
                 y_axis_values_list[series.y_axis_index]
             };
             let color = get_color(&self.series_colors, series.index.unwrap_or(index));
+            // the mark lines are drawn in the same order of series
+            let mut c = if self.series_list.iter().any(|item| item.z_index != 0) {
+                c.group(Group {
+                    z_index: Some(series.z_index),
+                    ..Default::default()
+                })
+            } else {
+                c.clone()
+            };
             let values: Vec<_> = series
-                .data
+                .get_data()
                 .iter()
-                .filter(|x| *x.to_owned() != NIL_VALUE)
+                .filter(|x| !is_nil_value(*x.to_owned()))
                 .map(|x| x.to_owned())
                 .collect();
             let mut sum = 0.0;
@@ -149,9 +223,6 @@ impl LineChart {
             let mut max = f32::MIN;
             for value in values.iter() {
                 let v = *value;
-                if v == NIL_VALUE {
-                    continue;
-                }
                 sum += v;
                 if v > max {
                     max = v;
@@ -194,7 +265,7 @@ impl LineChart {
                 });
                 let line_height = 20.0;
                 c.text(Text {
-                    text: format_float(value),
+                    text: format_label_float(value),
                     font_family: Some(self.font_family.clone()),
                     font_size: Some(self.series_label_font_size),
                     line_height: Some(line_height),
@@ -206,10 +277,71 @@ impl LineChart {
             }
         }
     }
+    /// Converts the line chart to sparkline, the axes, grid, legend and title are hidden.
+    /// The end point and the min/max points of first series are marked if they are enabled.
+    pub fn sparkline(&mut self, end_point: bool, min_max: bool) {
+        self.width = 120.0;
+        self.height = 30.0;
+        // the room of markers
+        self.margin = (3.0).into();
+        self.title_text = "".to_string();
+        self.sub_title_text = "".to_string();
+        self.legend_show = Some(false);
+        self.x_axis_hidden = true;
+        self.y_axis_hidden = true;
+        self.grid_stroke_width = 0.0;
+        // the x axis is hidden, so the data of x axis is only used for the count of points
+        if self.x_axis_data.is_empty() {
+            let count = self
+                .series_list
+                .iter()
+                .map(|series| series.data.len())
+                .max()
+                .unwrap_or_default();
+            self.x_axis_data = (0..count).map(|index| index.to_string()).collect();
+        }
+        self.series_symbol = None;
+        self.x_boundary_gap = Some(false);
+        // the y axis starts from the min value instead of zero
+        let values: Vec<f32> = self
+            .series_list
+            .iter()
+            .flat_map(|series| series.data.iter().copied())
+            .filter(|value| !is_nil_value(*value))
+            .collect();
+        let min = values.iter().copied().fold(f32::MAX, f32::min);
+        let max = values.iter().copied().fold(f32::MIN, f32::max);
+        if min < max {
+            self.y_axis_configs[0].axis_min = Some(min - (max - min) * 0.1);
+        }
+        if let Some(series) = self.series_list.first() {
+            let color = get_color(&self.series_colors, series.index.unwrap_or_default());
+            self.annotations
+                .extend(get_sparkline_annotations(series, color, end_point, min_max));
+        }
+    }
     /// Converts line chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
-        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
-
+        self.render_with_layout().map(|(svg, _)| svg)
+    }
+    /// Converts line chart to svg, the static layer(background, title, legend, grid and axes)
+    /// is reused from the cache if the options except series data are not changed.
+    pub fn svg_with_cache(&self, cache: &LayerCache) -> canvas::Result<String> {
+        self.render(Some(cache)).map(|(svg, _)| svg)
+    }
+    /// Converts line chart to svg, and returns the layout of plot area and series elements.
+    pub fn render_with_layout(&self) -> canvas::Result<(String, ChartLayout)> {
+        self.render(None)
+    }
+    /// Renders the static layer of chart, e.g. background, title, legend, grid and axes.
+    fn render_static_layer(
+        &self,
+        c: &mut Canvas,
+        left_y_axis_values: &AxisValues,
+        right_y_axis_values: &AxisValues,
+        left_y_axis_width: f32,
+        right_y_axis_width: f32,
+    ) -> StaticLayer {
         self.render_background(c.child(Box::default()));
         let mut x_axis_height = self.x_axis_height;
         if self.x_axis_hidden {
@@ -219,35 +351,37 @@ impl LineChart {
 
         let title_height = self.render_title(c.child(Box::default()));
 
-        let legend_height = self.render_legend(c.child(Box::default()));
+        let footer_height = self.render_footer(c.child(Box::default()));
+
+        let legend_box = self.render_legend(c.child(Box {
+            bottom: footer_height,
+            ..Default::default()
+        }));
         // get the max height of title and legend
-        let axis_top = if legend_height > title_height {
-            legend_height
+        let axis_top = if legend_box.top > title_height {
+            legend_box.top
         } else {
             title_height
         };
-
-        let (left_y_axis_values, mut left_y_axis_width) = self.get_y_axis_values(0);
-        if self.y_axis_hidden {
-            left_y_axis_width = 0.0;
-        }
-        let mut exist_right_y_axis = false;
-        for series in self.series_list.iter() {
-            if series.y_axis_index != 0 {
-                exist_right_y_axis = true;
-            }
-        }
-        let mut right_y_axis_values = AxisValues::default();
-        let mut right_y_axis_width = 0.0_f32;
-        if exist_right_y_axis {
-            (right_y_axis_values, right_y_axis_width) = self.get_y_axis_values(1);
-        }
+        // the legend on the other sides and footer take the space of chart
+        *c = c.child(Box {
+            left: legend_box.left,
+            right: legend_box.right,
+            bottom: legend_box.bottom + footer_height,
+            ..Default::default()
+        });
 
         let axis_height = c.height() - x_axis_height - axis_top;
         let axis_width = c.width() - left_y_axis_width - right_y_axis_width;
+        // the first y axis is on the right side of rtl layout
+        let (left_width, right_width) = if self.rtl {
+            (right_y_axis_width, left_y_axis_width)
+        } else {
+            (left_y_axis_width, right_y_axis_width)
+        };
         // minus the height of top text area
         if axis_top > 0.0 {
-            c = c.child(Box {
+            *c = c.child(Box {
                 top: axis_top,
                 ..Default::default()
             });
@@ -255,17 +389,42 @@ impl LineChart {
 
         self.render_grid(
             c.child(Box {
-                left: left_y_axis_width,
+                left: left_width,
+                ..Default::default()
+            }),
+            axis_width,
+            axis_height,
+        );
+        self.render_zero_line(
+            c.child(Box {
+                left: left_width,
+                ..Default::default()
+            }),
+            left_y_axis_values,
+            axis_width,
+            axis_height,
+        );
+        self.render_axis_pointer(
+            c.child(Box {
+                left: left_width,
                 ..Default::default()
             }),
             axis_width,
             axis_height,
+            self.x_axis_data.len(),
         );
 
         // y axis
         if left_y_axis_width > 0.0 {
             self.render_y_axis(
-                c.child(Box::default()),
+                c.child(Box {
+                    left: if self.rtl {
+                        c.width() - left_y_axis_width
+                    } else {
+                        0.0
+                    },
+                    ..Default::default()
+                }),
                 left_y_axis_values.data.clone(),
                 axis_height,
                 left_y_axis_width,
@@ -275,7 +434,11 @@ impl LineChart {
         if right_y_axis_width > 0.0 {
             self.render_y_axis(
                 c.child(Box {
-                    left: c.width() - right_y_axis_width,
+                    left: if self.rtl {
+                        0.0
+                    } else {
+                        c.width() - right_y_axis_width
+                    },
                     ..Default::default()
                 }),
                 right_y_axis_values.data.clone(),
@@ -290,8 +453,8 @@ impl LineChart {
             self.render_x_axis(
                 c.child(Box {
                     top: c.height() - x_axis_height,
-                    left: left_y_axis_width,
-                    right: right_y_axis_width,
+                    left: left_width,
+                    right: right_width,
                     ..Default::default()
                 }),
                 self.x_axis_data.clone(),
@@ -300,13 +463,93 @@ impl LineChart {
         }
 
         // line point
+        StaticLayer {
+            margin: c.margin.clone(),
+            x_axis_height,
+            axis_width,
+            axis_height,
+            left_width,
+            right_width,
+            ..Default::default()
+        }
+    }
+    /// Renders the chart, the static layer is taken from the cache if it is set.
+    fn render(&self, cache: Option<&LayerCache>) -> canvas::Result<(String, ChartLayout)> {
+        if let Some(chart) = self.get_x_range_chart() {
+            return chart.render(cache);
+        }
+        if let Some(svg) = self.render_empty_state()? {
+            return Ok((svg, ChartLayout::default()));
+        }
+        let _locale = LocaleGuard::new(self.locale.as_deref());
+        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        c.aria_label = self.aria_label.clone();
+        c.description = self.description.clone();
+        c.svg_format = self.svg_format.clone();
+        c.coordinate_precision = self.coordinate_precision;
+        c.style_hoisting = self.style_hoisting;
+        c.font_embedding = self.font_embedding.clone();
+        c.responsive = self.responsive;
+
+        let (left_y_axis_values, mut left_y_axis_width) = self.get_y_axis_values(0);
+        if self.y_axis_hidden {
+            left_y_axis_width = 0.0;
+        }
+        let mut exist_right_y_axis = false;
+        for series in self.series_list.iter() {
+            if series.y_axis_index != 0 {
+                exist_right_y_axis = true;
+            }
+        }
+        let mut right_y_axis_values = AxisValues::default();
+        let mut right_y_axis_width = 0.0_f32;
+        if exist_right_y_axis {
+            (right_y_axis_values, right_y_axis_width) = self.get_y_axis_values(1);
+        }
+
+        // the static layer is reused if the options except series data are not changed
+        let key = cache.map(|_| {
+            self.get_layer_key(
+                &[&left_y_axis_values, &right_y_axis_values],
+                (self.x_axis_hidden, self.y_axis_hidden),
+            )
+        });
+        let cached_layer = cache.zip(key).and_then(|(cache, key)| cache.get(key));
+        let layer = if let Some(layer) = cached_layer {
+            c.raw(layer.svg.clone());
+            layer
+        } else {
+            let mut layer = self.render_static_layer(
+                &mut c,
+                &left_y_axis_values,
+                &right_y_axis_values,
+                left_y_axis_width,
+                right_y_axis_width,
+            );
+            if let Some((cache, key)) = cache.zip(key) {
+                layer.key = key;
+                layer.svg = c.freeze()?;
+                cache.set(layer.clone());
+            }
+            layer
+        };
+        c.margin = layer.margin.clone();
+        let StaticLayer {
+            x_axis_height,
+            axis_width,
+            axis_height,
+            left_width,
+            right_width,
+            ..
+        } = layer;
+
         let y_axis_values_list = vec![&left_y_axis_values, &right_y_axis_values];
         let max_height = c.height() - x_axis_height;
         let line_series_list: Vec<&Series> = self.series_list.iter().collect();
-        let series_labels_list = self.render_line(
+        let (series_labels_list, layout_items) = self.render_line(
             c.child(Box {
-                left: left_y_axis_width,
-                right: right_y_axis_width,
+                left: left_width,
+                right: right_width,
                 ..Default::default()
             }),
             &line_series_list,
@@ -317,31 +560,66 @@ impl LineChart {
         );
         self.render_series_label(
             c.child(Box {
-                left: left_y_axis_width,
-                right: right_y_axis_width,
+                left: left_width,
+                right: right_width,
                 ..Default::default()
             }),
             series_labels_list,
         );
+        self.render_guide_line(
+            c.child(Box {
+                left: left_width,
+                ..Default::default()
+            }),
+            &left_y_axis_values,
+            axis_width,
+            axis_height,
+        );
 
         self.render_mark_line(
             c.child(Box {
-                left: left_y_axis_width,
-                right: right_y_axis_width,
+                left: left_width,
+                right: right_width,
                 ..Default::default()
             }),
             &self.series_list,
             &y_axis_values_list,
             max_height,
         );
-        c.svg()
+        self.render_annotations(
+            c.child(Box {
+                left: left_width,
+                right: right_width,
+                ..Default::default()
+            }),
+            &y_axis_values_list,
+            max_height,
+            self.x_axis_data.len(),
+        );
+        self.render_insets(c.child(Box::default()));
+        let layout = ChartLayout {
+            plot_area: c
+                .child(Box {
+                    left: left_width,
+                    right: right_width,
+                    bottom: x_axis_height,
+                    ..Default::default()
+                })
+                .area(),
+            items: layout_items,
+        };
+        Ok((c.svg()?, layout))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::LineChart;
-    use crate::{Align, Box, MarkLine, MarkLineCategory, MarkPoint, MarkPointCategory, NIL_VALUE};
+    use crate::{
+        Align, Annotation, AnnotationPoint, AnnotationShape, AxisPointer, Box, EndLabel, Inset,
+        LabelOverlap, MarkLine, MarkLineCategory, MarkPoint, MarkPointCategory, PieChart, Position,
+        Series, SeriesCategory, SeriesTransform, Symbol, SymbolVisibility, TrendType, NIL_VALUE,
+    };
     use pretty_assertions::assert_eq;
     #[test]
     fn line_chart_basic() {
@@ -454,6 +732,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn line_chart_connect_nulls() {
+        let mut line_chart = LineChart::new(
+            vec![
+                Series::from_options(
+                    "Email".to_string(),
+                    vec![
+                        Some(120.0),
+                        None,
+                        Some(101.0),
+                        Some(134.0),
+                        None,
+                        Some(230.0),
+                        Some(210.0),
+                    ],
+                ),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, f32::NAN, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Connect Nulls".to_string();
+        line_chart.series_list[0].connect_nulls = true;
+        assert_eq!(
+            include_str!("../../asset/line_chart/connect_nulls.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_label_overlap() {
+        let mut line_chart = LineChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![125.0, 182.0, 104.0, 234.0, 95.0]).into(),
+                ("Direct", vec![128.0, 140.0, 160.0, 180.0, 200.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        line_chart.title_text = "Label Overlap".to_string();
+        line_chart.series_label_overlap = LabelOverlap::Hide;
+        line_chart.series_list[0].label_show = true;
+        line_chart.series_list[1].label_show = true;
+        line_chart.series_list[1].label_priority = 1;
+        line_chart.series_list[2].label_show = true;
+        assert_eq!(
+            include_str!("../../asset/line_chart/label_overlap.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
     #[test]
     fn line_chart_align_left() {
         let mut line_chart = LineChart::new(
@@ -772,4 +1118,832 @@ mod tests {
             line_chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn line_chart_series_style() {
+        let mut line_chart = LineChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+                ("Forecast", vec![320.0, 332.0, 301.0, 334.0, 390.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        line_chart.title_text = "Series Style".to_string();
+        line_chart.series_list[0].stroke_width = Some(4.0);
+        line_chart.series_list[1].opacity = Some(0.4);
+        line_chart.series_list[2].stroke_dash_array = Some("4,2".to_string());
+        line_chart.series_list[2].stroke_width = Some(1.0);
+        assert_eq!(
+            include_str!("../../asset/line_chart/series_style.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_symbol_visibility() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+                (
+                    "Direct",
+                    vec![320.0, 332.0, 301.0, 334.0, 390.0, 330.0, 320.0],
+                )
+                    .into(),
+                (
+                    "Search Engine",
+                    vec![820.0, 932.0, 901.0, 934.0, 1290.0, 1330.0, 1320.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Symbol Visibility".to_string();
+        line_chart.series_list[0].symbol_visibility = SymbolVisibility::Last;
+        line_chart.series_list[0].symbol_size = Some(5.0);
+        line_chart.series_list[1].symbol_visibility = SymbolVisibility::Every(2);
+        line_chart.series_list[2].symbol = Some(Symbol::None);
+        line_chart.series_list[3].symbol_visibility = SymbolVisibility::Indexes(vec![1, 4]);
+        assert_eq!(
+            include_str!("../../asset/line_chart/symbol_visibility.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_path_symbol() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Path Symbol".to_string();
+        line_chart.series_list[0].symbol = Some(Symbol::Path(
+            "M 0 -1 L 0.22 -0.31 L 0.95 -0.31 L 0.36 0.12 L 0.59 0.81 L 0 0.38 L -0.59 0.81 L -0.36 0.12 L -0.95 -0.31 L -0.22 -0.31 Z".to_string(),
+        ));
+        line_chart.series_list[0].symbol_size = Some(6.0);
+        line_chart.series_list[1].symbol = Some(Symbol::Path("M 0 -1 L 1 1 L -1 1 Z".to_string()));
+        line_chart.series_list[1].symbol_visibility = SymbolVisibility::Last;
+        assert_eq!(
+            include_str!("../../asset/line_chart/path_symbol.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_area_baseline() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Area Baseline".to_string();
+        line_chart.series_fill = true;
+        line_chart.series_fill_opacity = Some(0.2);
+        line_chart.series_list[1].area_baseline = Some(250.0);
+        assert_eq!(
+            include_str!("../../asset/line_chart/area_baseline.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_band() {
+        let mut line_chart = LineChart::new(
+            vec![(
+                "Forecast",
+                vec![150.0, 230.0, 224.0, 218.0, 135.0, 147.0, 260.0],
+            )
+                .into()],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Confidence Band".to_string();
+        line_chart.series_list[0].category = Some(SeriesCategory::Band);
+        line_chart.series_list[0].lower_data =
+            vec![120.0, 200.0, 190.0, NIL_VALUE, 100.0, 110.0, 220.0];
+        line_chart.series_list[0].upper_data =
+            vec![180.0, 260.0, 250.0, NIL_VALUE, 170.0, 190.0, 300.0];
+        assert_eq!(
+            include_str!("../../asset/line_chart/band.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_trend_line() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Trend Line".to_string();
+        line_chart.series_list[0].trend_line = Some(TrendType::Linear);
+        line_chart.series_list[1].trend_line = Some(TrendType::MovingAverage(3));
+        assert_eq!(
+            include_str!("../../asset/line_chart/trend_line.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_transform() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Cumsum",
+                    vec![120.0, 132.0, 101.0, NIL_VALUE, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Diff",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Transform".to_string();
+        line_chart.series_list[0].transform = Some(SeriesTransform::Cumsum);
+        line_chart.series_list[1].transform = Some(SeriesTransform::Diff);
+        assert_eq!(
+            vec![120.0, 252.0, 353.0, NIL_VALUE, 443.0, 673.0, 883.0],
+            line_chart.series_list[0].get_data()
+        );
+        assert_eq!(
+            vec![NIL_VALUE, -38.0, 9.0, 43.0, 56.0, 40.0, -20.0],
+            line_chart.series_list[1].get_data()
+        );
+        assert_eq!(
+            include_str!("../../asset/line_chart/transform.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_legend_right() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+                (
+                    "Direct",
+                    vec![320.0, 332.0, 301.0, 334.0, 390.0, 330.0, 320.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Legend Right".to_string();
+        line_chart.legend_position = Some(Position::Right);
+        assert_eq!(
+            include_str!("../../asset/line_chart/legend_right.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_legend_bottom() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+                (
+                    "Direct",
+                    vec![320.0, 332.0, 301.0, 334.0, 390.0, 330.0, 320.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Legend Bottom".to_string();
+        line_chart.legend_position = Some(Position::Bottom);
+        assert_eq!(
+            include_str!("../../asset/line_chart/legend_bottom.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_legend_order() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "email_count",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "union_ads_count",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+                (
+                    "direct_count",
+                    vec![320.0, 332.0, 301.0, 334.0, 390.0, 330.0, 320.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Legend Order".to_string();
+        line_chart.series_list[0].legend_text = Some("Email".to_string());
+        line_chart.series_list[2].legend_text = Some("Direct".to_string());
+        line_chart.legend_order = Some(vec!["direct_count".to_string(), "email_count".to_string()]);
+        assert_eq!(
+            include_str!("../../asset/line_chart/legend_order.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_multi_line_title() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Weekly Report\nMarketing".to_string();
+        line_chart.title_link = Some("https://example.com/report".to_string());
+        line_chart.title_align = Align::Center;
+        line_chart.sub_title_text = "Email\nUnion Ads".to_string();
+        line_chart.sub_title_align = Align::Center;
+        line_chart.legend_align = Align::Right;
+        assert_eq!(
+            include_str!("../../asset/line_chart/multi_line_title.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_annotations() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Annotations".to_string();
+        line_chart.annotations = vec![
+            Annotation {
+                shape: AnnotationShape::Text("launch day".to_string()),
+                point: AnnotationPoint::Pixel(80.0, 60.0),
+                ..Default::default()
+            },
+            Annotation {
+                shape: AnnotationShape::Arrow(AnnotationPoint::Data(5, 330.0)),
+                point: AnnotationPoint::Pixel(160.0, 80.0),
+                color: Some((238, 102, 102).into()),
+                ..Default::default()
+            },
+            Annotation {
+                shape: AnnotationShape::Circle(8.0),
+                point: AnnotationPoint::Data(4, 90.0),
+                color: Some((84, 112, 198).into()),
+                ..Default::default()
+            },
+            Annotation {
+                shape: AnnotationShape::Line(AnnotationPoint::Data(6, 310.0)),
+                point: AnnotationPoint::Data(0, 220.0),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(
+            include_str!("../../asset/line_chart/annotations.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_title_emphasis() {
+        let mut line_chart = LineChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        line_chart.title_text = "Weekly Report".to_string();
+        line_chart.title_align = Align::Center;
+        line_chart.title_font_style = Some("italic".to_string());
+        line_chart.title_letter_spacing = Some(2.0);
+        line_chart.sub_title_text = "Marketing".to_string();
+        line_chart.sub_title_align = Align::Center;
+        line_chart.sub_title_text_decoration = Some("underline".to_string());
+        line_chart.annotations = vec![Annotation {
+            shape: AnnotationShape::Text("launch day".to_string()),
+            point: AnnotationPoint::Pixel(80.0, 60.0),
+            font_style: Some("italic".to_string()),
+            text_decoration: Some("line-through".to_string()),
+            ..Default::default()
+        }];
+        assert_eq!(
+            include_str!("../../asset/line_chart/title_emphasis.svg"),
+            line_chart.svg().unwrap()
+        );
+
+        let mut json_chart = LineChart::from_json(
+            r###"{
+                "title_text": "Weekly Report",
+                "title_align": "center",
+                "title_font_style": "italic",
+                "title_letter_spacing": 2,
+                "sub_title_text": "Marketing",
+                "sub_title_align": "center",
+                "sub_title_text_decoration": "underline",
+                "series_list": [
+                    {"name": "Email", "data": [120.0, 132.0, 101.0, 134.0, 90.0]},
+                    {"name": "Union Ads", "data": [220.0, 182.0, 191.0, 234.0, 290.0]}
+                ],
+                "x_axis_data": ["Mon", "Tue", "Wed", "Thu", "Fri"]
+            }"###,
+        )
+        .unwrap();
+        json_chart.annotations = line_chart.annotations.clone();
+        assert_eq!(line_chart.svg().unwrap(), json_chart.svg().unwrap());
+    }
+
+    #[test]
+    fn line_chart_inset() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Inset".to_string();
+        let mut pie_chart = PieChart::new(vec![
+            ("Email", vec![1017.0]).into(),
+            ("Union Ads", vec![1757.0]).into(),
+        ]);
+        pie_chart.legend_show = Some(false);
+        pie_chart.background_color = (0, 0, 0, 0).into();
+        line_chart.insets = vec![Inset::new(
+            Box {
+                left: 460.0,
+                top: 40.0,
+                right: 580.0,
+                bottom: 120.0,
+            },
+            pie_chart.svg().unwrap(),
+        )];
+        assert_eq!(
+            include_str!("../../asset/line_chart/inset.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_sparkline() {
+        let mut line_chart = LineChart::new(
+            vec![(
+                "Email",
+                vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+            )
+                .into()],
+            vec![],
+        );
+        line_chart.sparkline(true, true);
+        assert_eq!(
+            include_str!("../../asset/line_chart/sparkline.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_tooltip() {
+        let mut line_chart = LineChart::new(
+            vec![
+                ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+                ("Union Ads", vec![220.0, 182.0, 191.0, 234.0, 290.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        line_chart.title_text = "Tooltip".to_string();
+        line_chart.tooltip_show = true;
+        assert_eq!(
+            include_str!("../../asset/line_chart/tooltip.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_negative() {
+        let mut line_chart = LineChart::new(
+            vec![
+                ("Profit", vec![120.0, -132.0, 101.0, -34.0, 90.0]).into(),
+                ("Cost", vec![-80.0, 52.0, -91.0, 134.0, -60.0]).into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        line_chart.title_text = "Negative Value".to_string();
+        line_chart.series_fill = true;
+        line_chart.series_fill_opacity = Some(0.2);
+        line_chart.zero_line_color = Some((70, 70, 70).into());
+        assert_eq!(
+            include_str!("../../asset/line_chart/negative.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_end_label() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "End Label".to_string();
+        line_chart.series_list[0].end_label = Some(EndLabel::Last);
+        line_chart.series_list[1].end_label = Some(EndLabel::Both);
+        assert_eq!(
+            include_str!("../../asset/line_chart/end_label.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_end_label_single_point() {
+        for (end_label, count) in [
+            (EndLabel::First, 1),
+            (EndLabel::Last, 1),
+            (EndLabel::Both, 1),
+        ] {
+            let mut line_chart =
+                LineChart::new(vec![("Email", vec![123.0]).into()], vec!["Mon".to_string()]);
+            line_chart.series_list[0].end_label = Some(end_label);
+            let svg = line_chart.svg().unwrap();
+            assert_eq!(count, svg.matches("\n123\n</text>").count());
+        }
+    }
+
+    #[test]
+    fn line_chart_item_colors() {
+        let mut line_chart = LineChart::new(
+            vec![(
+                "Email",
+                vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+            )
+                .into()],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Item Colors".to_string();
+        // highlight the point of the max value
+        line_chart.series_list[0].colors =
+            Some(vec![None, None, None, None, None, Some("#a90000".into())]);
+        assert_eq!(
+            include_str!("../../asset/line_chart/item_colors.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_axis_pointer() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "Axis Pointer".to_string();
+        line_chart.axis_pointer = Some(AxisPointer {
+            index: 4,
+            ..Default::default()
+        });
+        assert_eq!(
+            include_str!("../../asset/line_chart/axis_pointer.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_x_range() {
+        let mut line_chart = LineChart::new(
+            vec![
+                (
+                    "Email",
+                    vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                )
+                    .into(),
+                (
+                    "Union Ads",
+                    vec![220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                )
+                    .into(),
+            ],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+                "Sun".to_string(),
+            ],
+        );
+        line_chart.title_text = "X Range".to_string();
+        line_chart.series_list[1].start_index = 1;
+        line_chart.x_range = Some((2, 4));
+        assert_eq!(
+            include_str!("../../asset/line_chart/x_range.svg"),
+            line_chart.svg().unwrap()
+        );
+
+        line_chart.x_range_overview = true;
+        assert_eq!(
+            include_str!("../../asset/line_chart/x_range_overview.svg"),
+            line_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn line_chart_x_range_out_of_categories() {
+        // the empty state is rendered for empty x axis data
+        let mut line_chart = LineChart::new(vec![("a", vec![]).into()], vec![]);
+        let svg = line_chart.svg().unwrap();
+        line_chart.x_range = Some((0, 3));
+        assert_eq!(svg, line_chart.svg().unwrap());
+
+        // the range after the last category is ignored
+        let mut line_chart = LineChart::new(
+            vec![("Email", vec![120.0, 132.0]).into()],
+            vec!["Mon".to_string(), "Tue".to_string()],
+        );
+        let svg = line_chart.svg().unwrap();
+        line_chart.x_range = Some((2, 3));
+        assert_eq!(svg, line_chart.svg().unwrap());
+    }
+
+    #[test]
+    fn line_chart_mark_point_position() {
+        let mut line_chart = LineChart::new(
+            vec![("Email", vec![10.0, NIL_VALUE, 37.0, 21.0, 15.0, 12.0]).into()],
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+                "Sat".to_string(),
+            ],
+        );
+        line_chart.series_list[0].mark_points = vec![MarkPoint {
+            category: MarkPointCategory::Max,
+        }];
+        let svg = line_chart.svg().unwrap();
+        assert!(svg.contains("\n37\n</text>"));
+        assert!(!svg.contains("\n21\n</text>"));
+
+        // the max value is kept by downsampling, but its label index is changed
+        line_chart.series_list[0].data = vec![10.0, 11.0, 12.0, 13.0, 37.0, 14.0, 10.0, 11.0];
+        line_chart.x_axis_data = (0..8).map(|i| i.to_string()).collect();
+        line_chart.max_points_per_series = Some(4);
+        let svg = line_chart.svg().unwrap();
+        assert!(svg.contains("\n37\n</text>"));
+    }
 }
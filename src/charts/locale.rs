@@ -0,0 +1,290 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use ahash::AHashMap;
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+pub static LOCALE_EN_US: &str = "en-US";
+pub static LOCALE_DE_DE: &str = "de-DE";
+pub static LOCALE_FR_FR: &str = "fr-FR";
+pub static LOCALE_ZH_CN: &str = "zh-CN";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Locale {
+    pub decimal_separator: String,
+    pub thousands_separator: String,
+    // the names of month, from January to December
+    pub months: Vec<String>,
+    pub short_months: Vec<String>,
+    // the names of weekday, from Sunday to Saturday
+    pub weekdays: Vec<String>,
+    pub short_weekdays: Vec<String>,
+}
+
+fn to_strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|item| item.to_string()).collect()
+}
+
+static EN_US_LOCALE: Lazy<Locale> = Lazy::new(|| Locale {
+    decimal_separator: ".".to_string(),
+    thousands_separator: ",".to_string(),
+    months: to_strings(&[
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ]),
+    short_months: to_strings(&[
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ]),
+    weekdays: to_strings(&[
+        "Sunday",
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+    ]),
+    short_weekdays: to_strings(&["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"]),
+});
+
+static DE_DE_LOCALE: Lazy<Locale> = Lazy::new(|| Locale {
+    decimal_separator: ",".to_string(),
+    thousands_separator: ".".to_string(),
+    months: to_strings(&[
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ]),
+    short_months: to_strings(&[
+        "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+    ]),
+    weekdays: to_strings(&[
+        "Sonntag",
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+    ]),
+    short_weekdays: to_strings(&["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"]),
+});
+
+static FR_FR_LOCALE: Lazy<Locale> = Lazy::new(|| Locale {
+    decimal_separator: ",".to_string(),
+    // narrow no-break space
+    thousands_separator: "\u{202f}".to_string(),
+    months: to_strings(&[
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ]),
+    short_months: to_strings(&[
+        "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.",
+        "déc.",
+    ]),
+    weekdays: to_strings(&[
+        "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+    ]),
+    short_weekdays: to_strings(&["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."]),
+});
+
+static ZH_CN_LOCALE: Lazy<Locale> = Lazy::new(|| Locale {
+    decimal_separator: ".".to_string(),
+    thousands_separator: ",".to_string(),
+    months: to_strings(&[
+        "一月",
+        "二月",
+        "三月",
+        "四月",
+        "五月",
+        "六月",
+        "七月",
+        "八月",
+        "九月",
+        "十月",
+        "十一月",
+        "十二月",
+    ]),
+    short_months: to_strings(&[
+        "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+    ]),
+    weekdays: to_strings(&[
+        "星期日",
+        "星期一",
+        "星期二",
+        "星期三",
+        "星期四",
+        "星期五",
+        "星期六",
+    ]),
+    short_weekdays: to_strings(&["周日", "周一", "周二", "周三", "周四", "周五", "周六"]),
+});
+
+type Locales = AHashMap<String, Arc<Locale>>;
+static LOCALE_MAP: Lazy<ArcSwap<Locales>> = Lazy::new(|| {
+    let mut m = AHashMap::new();
+    m.insert(LOCALE_EN_US.to_string(), Arc::new(EN_US_LOCALE.clone()));
+    m.insert(LOCALE_DE_DE.to_string(), Arc::new(DE_DE_LOCALE.clone()));
+    m.insert(LOCALE_FR_FR.to_string(), Arc::new(FR_FR_LOCALE.clone()));
+    m.insert(LOCALE_ZH_CN.to_string(), Arc::new(ZH_CN_LOCALE.clone()));
+    ArcSwap::from_pointee(m)
+});
+
+/// Add locale of charts
+pub fn add_locale(name: &str, data: Locale) {
+    let mut m: Locales = AHashMap::new();
+    for (name, data) in LOCALE_MAP.load().iter() {
+        m.insert(name.to_string(), data.clone());
+    }
+    m.insert(name.to_string(), Arc::new(data));
+    LOCALE_MAP.store(Arc::new(m))
+}
+
+/// Get the locale of charts, it returns en-US locale if not found.
+pub fn get_locale(name: &str) -> Arc<Locale> {
+    if let Some(locale) = LOCALE_MAP.load().get(name) {
+        locale.clone()
+    } else {
+        Arc::new(EN_US_LOCALE.clone())
+    }
+}
+
+/// List the locale name
+pub fn list_locale_name() -> Vec<String> {
+    let mut locales = vec![];
+    for name in LOCALE_MAP.load().keys() {
+        locales.push(name.to_string());
+    }
+    locales
+}
+
+thread_local! {
+    static CURRENT_LOCALE: RefCell<Option<Arc<Locale>>> = const { RefCell::new(None) };
+}
+
+/// Sets the locale of current thread for formatting,
+/// the previous locale is restored when it is dropped.
+pub(crate) struct LocaleGuard {
+    prev: Option<Arc<Locale>>,
+}
+
+impl LocaleGuard {
+    pub fn new(name: Option<&str>) -> Self {
+        LocaleGuard::with_locale(name.map(get_locale))
+    }
+    /// Sets the locale of current thread, it is used to pass the locale
+    /// to other threads, e.g. the workers of parallel rendering.
+    pub fn with_locale(locale: Option<Arc<Locale>>) -> Self {
+        let prev = CURRENT_LOCALE.with(|item| item.replace(locale));
+        LocaleGuard { prev }
+    }
+}
+
+/// Gets the locale of current thread, it is none if not set.
+#[cfg(feature = "parallel")]
+pub(crate) fn get_current_locale() -> Option<Arc<Locale>> {
+    CURRENT_LOCALE.with(|item| item.borrow().clone())
+}
+
+impl Drop for LocaleGuard {
+    fn drop(&mut self) {
+        let prev = self.prev.take();
+        CURRENT_LOCALE.with(|item| item.replace(prev));
+    }
+}
+
+/// Localizes the formatted number, e.g. 1,234.5 to 1.234,5 for de-DE.
+/// The number is not changed if the locale is not set.
+pub(crate) fn localize_number(value: String) -> String {
+    CURRENT_LOCALE.with(|item| {
+        let Some(ref locale) = *item.borrow() else {
+            return value;
+        };
+        let mut result = String::with_capacity(value.len());
+        for ch in value.chars() {
+            match ch {
+                '.' => result.push_str(&locale.decimal_separator),
+                ',' => result.push_str(&locale.thousands_separator),
+                _ => result.push(ch),
+            }
+        }
+        result
+    })
+}
+
+/// Replaces the month and weekday of formatter with the name of locale,
+/// the value is the month(1-12) or weekday(0-6, 0 is Sunday).
+pub(crate) fn format_date_name(value: &str, formatter: &str) -> String {
+    let index = value.trim().parse::<usize>().ok();
+    let locale = CURRENT_LOCALE
+        .with(|item| item.borrow().clone())
+        .unwrap_or_else(|| Arc::new(EN_US_LOCALE.clone()));
+    let get_name = |names: &[String], index: Option<usize>| -> String {
+        index
+            .and_then(|index| names.get(index))
+            .cloned()
+            .unwrap_or_else(|| value.to_string())
+    };
+    let month = index.and_then(|index| index.checked_sub(1));
+    formatter
+        .replace("{short_month}", &get_name(&locale.short_months, month))
+        .replace("{month}", &get_name(&locale.months, month))
+        .replace("{short_weekday}", &get_name(&locale.short_weekdays, index))
+        .replace("{weekday}", &get_name(&locale.weekdays, index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_date_name, get_locale, localize_number, LocaleGuard, LOCALE_DE_DE};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn locale_format() {
+        assert_eq!("1,234.5", localize_number("1,234.5".to_string()));
+        assert_eq!("Mar", format_date_name("3", "{short_month}"));
+        {
+            let _locale = LocaleGuard::new(Some(LOCALE_DE_DE));
+            assert_eq!("1.234,5", localize_number("1,234.5".to_string()));
+            assert_eq!("März", format_date_name("3", "{month}"));
+            assert_eq!("Mo", format_date_name("1", "{short_weekday}"));
+            assert_eq!("x", format_date_name("x", "{weekday}"));
+        }
+        // the locale is restored
+        assert_eq!("1,234.5", localize_number("1,234.5".to_string()));
+        assert_eq!(",", get_locale("unknown").thousands_separator);
+    }
+}
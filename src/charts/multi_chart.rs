@@ -5,14 +5,30 @@
 use super::canvas;
 use super::component::generate_svg;
 use super::component::Rect;
-use super::params::{get_color_from_value, get_f32_from_value, get_margin_from_value};
+use super::params::{
+    get_color_from_value, get_f32_from_value, get_margin_from_value, get_string_from_value,
+    get_usize_from_value,
+};
 use super::{
-    BarChart, CandlestickChart, CanvasResult, HorizontalBarChart, LineChart, PieChart, RadarChart,
-    ScatterChart, TableChart,
+    measure_text_width_family, BarChart, CandlestickChart, CanvasResult, HeatmapChart,
+    HorizontalBarChart, LineChart, PieChart, RadarChart, ScatterChart, TableChart,
+    DEFAULT_FONT_FAMILY,
 };
-use super::{Box, Color};
+use super::{Align, Box, Color, Text, VerticalAlign};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use substring::Substring;
 
+static ID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r##"(id="|url\(#|href="#)"##).unwrap());
+
+// The ids of child chart are prefixed with its index, so the ids and
+// references of different child charts don't conflict with each other.
+fn namespace_ids(svg: &str, chart_index: usize) -> String {
+    ID_REGEX
+        .replace_all(svg, format!("${{1}}chart-{chart_index}-"))
+        .to_string()
+}
+
 pub enum ChildChart {
     Bar(BarChart, Option<(f32, f32)>),
     Candlestick(CandlestickChart, Option<(f32, f32)>),
@@ -22,6 +38,7 @@ pub enum ChildChart {
     Radar(RadarChart, Option<(f32, f32)>),
     Scatter(ScatterChart, Option<(f32, f32)>),
     Table(TableChart, Option<(f32, f32)>),
+    Heatmap(HeatmapChart, Option<(f32, f32)>),
 }
 #[derive(Default)]
 pub struct MultiChart {
@@ -29,6 +46,13 @@ pub struct MultiChart {
     pub gap: f32,
     pub margin: Box,
     pub background_color: Option<Color>,
+    // the count of charts per row, one chart per row if it is not set
+    pub columns: Option<usize>,
+    // the shared title above the child charts
+    pub title_text: String,
+    pub title_font_size: f32,
+    pub title_font_color: Color,
+    pub font_family: String,
 }
 struct ChildChartResult {
     svg: String,
@@ -36,6 +60,65 @@ struct ChildChartResult {
     bottom: f32,
 }
 
+impl ChildChart {
+    fn position(&self) -> Option<(f32, f32)> {
+        match self {
+            ChildChart::Bar(_, position)
+            | ChildChart::Candlestick(_, position)
+            | ChildChart::HorizontalBar(_, position)
+            | ChildChart::Line(_, position)
+            | ChildChart::Pie(_, position)
+            | ChildChart::Radar(_, position)
+            | ChildChart::Scatter(_, position)
+            | ChildChart::Table(_, position)
+            | ChildChart::Heatmap(_, position) => *position,
+        }
+    }
+    fn position_mut(&mut self) -> (&mut f32, &mut f32) {
+        match self {
+            ChildChart::Bar(c, _) => (&mut c.x, &mut c.y),
+            ChildChart::Candlestick(c, _) => (&mut c.x, &mut c.y),
+            ChildChart::HorizontalBar(c, _) => (&mut c.x, &mut c.y),
+            ChildChart::Line(c, _) => (&mut c.x, &mut c.y),
+            ChildChart::Pie(c, _) => (&mut c.x, &mut c.y),
+            ChildChart::Radar(c, _) => (&mut c.x, &mut c.y),
+            ChildChart::Scatter(c, _) => (&mut c.x, &mut c.y),
+            ChildChart::Table(c, _) => (&mut c.x, &mut c.y),
+            ChildChart::Heatmap(c, _) => (&mut c.x, &mut c.y),
+        }
+    }
+    /// Converts the child chart to svg at (left, top),
+    /// the fixed position of chart is preferred.
+    fn svg(&mut self, point: Option<(f32, f32)>) -> CanvasResult<ChildChartResult> {
+        let position = self.position();
+        let (cx, cy) = self.position_mut();
+        if let Some((x, y)) = position {
+            *cx = x;
+            *cy = y;
+        } else if let Some((left, top)) = point {
+            *cx = cx.max(left);
+            *cy = top;
+        }
+        let (svg, x, y, width, height) = match self {
+            ChildChart::Bar(c, _) => (c.svg()?, c.x, c.y, c.width, c.height),
+            ChildChart::Candlestick(c, _) => (c.svg()?, c.x, c.y, c.width, c.height),
+            ChildChart::HorizontalBar(c, _) => (c.svg()?, c.x, c.y, c.width, c.height),
+            ChildChart::Line(c, _) => (c.svg()?, c.x, c.y, c.width, c.height),
+            ChildChart::Pie(c, _) => (c.svg()?, c.x, c.y, c.width, c.height),
+            ChildChart::Radar(c, _) => (c.svg()?, c.x, c.y, c.width, c.height),
+            ChildChart::Scatter(c, _) => (c.svg()?, c.x, c.y, c.width, c.height),
+            // the height of table will be recount
+            ChildChart::Table(c, _) => (c.svg()?, c.x, c.y, c.width, c.height),
+            ChildChart::Heatmap(c, _) => (c.svg()?, c.x, c.y, c.width, c.height),
+        };
+        Ok(ChildChartResult {
+            svg,
+            right: x + width,
+            bottom: y + height,
+        })
+    }
+}
+
 impl MultiChart {
     /// Creates a multi chart from json.
     pub fn from_json(data: &str) -> canvas::Result<MultiChart> {
@@ -54,6 +137,21 @@ impl MultiChart {
         if let Some(background_color) = get_color_from_value(&value, "background_color") {
             multi_chart.background_color = Some(background_color);
         }
+        if let Some(columns) = get_usize_from_value(&value, "columns") {
+            multi_chart.columns = Some(columns);
+        }
+        if let Some(title_text) = get_string_from_value(&value, "title_text") {
+            multi_chart.title_text = title_text;
+        }
+        if let Some(title_font_size) = get_f32_from_value(&value, "title_font_size") {
+            multi_chart.title_font_size = title_font_size;
+        }
+        if let Some(title_font_color) = get_color_from_value(&value, "title_font_color") {
+            multi_chart.title_font_color = title_font_color;
+        }
+        if let Some(font_family) = get_string_from_value(&value, "font_family") {
+            multi_chart.font_family = font_family;
+        }
         if let Some(child_charts) = value.get("child_charts") {
             if let Some(values) = child_charts.as_array() {
                 for item in values.iter() {
@@ -115,6 +213,10 @@ impl MultiChart {
                             let chart = CandlestickChart::from_json(&str)?;
                             multi_chart.add(ChildChart::Candlestick(chart, position));
                         }
+                        "heatmap" => {
+                            let chart = HeatmapChart::from_json(&str)?;
+                            multi_chart.add(ChildChart::Heatmap(chart, position));
+                        }
                         _ => {
                             let chart = BarChart::from_json(&str)?;
                             multi_chart.add(ChildChart::Bar(chart, position));
@@ -131,6 +233,9 @@ impl MultiChart {
             charts: vec![],
             gap: 10.0,
             margin: (10.0).into(),
+            title_font_size: 18.0,
+            title_font_color: (70, 70, 70).into(),
+            font_family: DEFAULT_FONT_FAMILY.to_string(),
             ..Default::default()
         }
     }
@@ -139,186 +244,44 @@ impl MultiChart {
         self.charts.push(c);
     }
     /// Converts the chart to svg.
+    /// The child charts are laid out in rows of `columns`(one chart per row by default),
+    /// except the charts with fixed position.
     pub fn svg(&mut self) -> CanvasResult<String> {
         let mut arr = vec![];
         let mut y = 0.0;
         let mut x = 0.0;
-        let margin_top = self.margin.top;
         let margin_left = self.margin.left;
-        for item in self.charts.iter_mut() {
-            let result = match item {
-                ChildChart::Bar(c, position) => {
-                    c.y = y;
-                    // fix postion, no need  gap
-                    if let Some((x, y)) = position {
-                        y.clone_into(&mut c.y);
-                        x.clone_into(&mut c.x);
-                    } else if y == 0.0 {
-                        c.y = margin_top;
-                    } else {
-                        // not the first chart and not set position
-                        y += self.gap;
-                        c.y = y;
-                    }
-                    if position.is_none() {
-                        c.x = c.x.max(margin_left);
-                    }
-
-                    ChildChartResult {
-                        svg: c.svg()?,
-                        right: c.x + c.width,
-                        bottom: c.y + c.height,
-                    }
-                }
-                ChildChart::Candlestick(c, position) => {
-                    c.y = y;
-                    if let Some((x, y)) = position {
-                        y.clone_into(&mut c.y);
-                        x.clone_into(&mut c.x);
-                    } else if y == 0.0 {
-                        c.y = margin_top;
-                    } else {
-                        // not the first chart and not set position
-                        y += self.gap;
-                        c.y = y;
-                    }
-                    if position.is_none() {
-                        c.x = c.x.max(margin_left);
-                    }
-
-                    ChildChartResult {
-                        svg: c.svg()?,
-                        right: c.x + c.width,
-                        bottom: c.y + c.height,
-                    }
-                }
-                ChildChart::HorizontalBar(c, position) => {
-                    c.y = y;
-                    if let Some((x, y)) = position {
-                        y.clone_into(&mut c.y);
-                        x.clone_into(&mut c.x);
-                    } else if y == 0.0 {
-                        c.y = margin_top;
-                    } else {
-                        y += self.gap;
-                        c.y = y;
-                    }
-                    if position.is_none() {
-                        c.x = c.x.max(margin_left);
-                    }
-
-                    ChildChartResult {
-                        svg: c.svg()?,
-                        right: c.x + c.width,
-                        bottom: c.y + c.height,
-                    }
-                }
-                ChildChart::Line(c, position) => {
-                    c.y = y;
-                    if let Some((x, y)) = position {
-                        y.clone_into(&mut c.y);
-                        x.clone_into(&mut c.x);
-                    } else if y == 0.0 {
-                        c.y = margin_top;
-                    } else {
-                        y += self.gap;
-                        c.y = y;
-                    }
-                    if position.is_none() {
-                        c.x = c.x.max(margin_left);
-                    }
-
-                    ChildChartResult {
-                        svg: c.svg()?,
-                        right: c.x + c.width,
-                        bottom: c.y + c.height,
-                    }
-                }
-                ChildChart::Pie(c, position) => {
-                    c.y = y;
-                    if let Some((x, y)) = position {
-                        y.clone_into(&mut c.y);
-                        x.clone_into(&mut c.x);
-                    } else if y == 0.0 {
-                        c.y = margin_top;
-                    } else {
-                        y += self.gap;
-                        c.y = y;
-                    }
-                    if position.is_none() {
-                        c.x = c.x.max(margin_left);
-                    }
-
-                    ChildChartResult {
-                        svg: c.svg()?,
-                        right: c.x + c.width,
-                        bottom: c.y + c.height,
-                    }
-                }
-                ChildChart::Radar(c, position) => {
-                    c.y = y;
-                    if let Some((x, y)) = position {
-                        y.clone_into(&mut c.y);
-                        x.clone_into(&mut c.x);
-                    } else if y == 0.0 {
-                        c.y = margin_top;
-                    } else {
-                        y += self.gap;
-                        c.y = y;
-                    }
-                    if position.is_none() {
-                        c.x = c.x.max(margin_left);
-                    }
-
-                    ChildChartResult {
-                        svg: c.svg()?,
-                        right: c.x + c.width,
-                        bottom: c.y + c.height,
-                    }
-                }
-                ChildChart::Scatter(c, position) => {
-                    c.y = y;
-                    if let Some((x, y)) = position {
-                        y.clone_into(&mut c.y);
-                        x.clone_into(&mut c.x);
-                    } else if y == 0.0 {
-                        c.y = margin_top;
-                    } else {
-                        y += self.gap;
-                        c.y = y;
-                    }
-                    if position.is_none() {
-                        c.x = c.x.max(margin_left);
-                    }
-
-                    ChildChartResult {
-                        svg: c.svg()?,
-                        right: c.x + c.width,
-                        bottom: c.y + c.height,
-                    }
-                }
-                ChildChart::Table(c, position) => {
-                    c.y = y;
-                    if let Some((x, y)) = position {
-                        y.clone_into(&mut c.y);
-                        x.clone_into(&mut c.x);
-                    } else if y == 0.0 {
-                        c.y = margin_top;
-                    } else {
-                        y += self.gap;
-                        c.y = y;
-                    }
-                    if position.is_none() {
-                        c.x = c.x.max(margin_left);
-                    }
-                    // the height will be recount
-                    let svg = c.svg()?;
-                    ChildChartResult {
-                        svg,
-                        right: c.x + c.width,
-                        bottom: c.y + c.height,
-                    }
+        let mut title_height = 0.0;
+        if !self.title_text.is_empty() {
+            title_height = measure_text_width_family(
+                &self.font_family,
+                self.title_font_size,
+                &self.title_text,
+            )?
+            .height();
+        }
+        let mut top = self.margin.top;
+        if title_height > 0.0 {
+            top += title_height + self.gap;
+        }
+        let columns = self.columns.unwrap_or(1).max(1);
+        let mut index = 0;
+        let mut row_top = top;
+        let mut left = margin_left;
+        for (chart_index, item) in self.charts.iter_mut().enumerate() {
+            let result = if item.position().is_some() {
+                // fix postion, no need gap
+                item.svg(None)?
+            } else {
+                // the first chart of row
+                if index % columns == 0 {
+                    row_top = if y == 0.0 { top } else { y + self.gap };
+                    left = margin_left;
                 }
+                index += 1;
+                let result = item.svg(Some((left, row_top)))?;
+                left = result.right + self.gap;
+                result
             };
             if result.bottom > y {
                 y = result.bottom;
@@ -326,11 +289,29 @@ impl MultiChart {
             if result.right > x {
                 x = result.right;
             }
-            arr.push(result.svg);
+            arr.push(namespace_ids(&result.svg, chart_index));
         }
         x += self.margin.right;
         y += self.margin.bottom;
 
+        if title_height > 0.0 {
+            arr.insert(
+                0,
+                Text {
+                    text: self.title_text.clone(),
+                    font_family: Some(self.font_family.clone()),
+                    font_size: Some(self.title_font_size),
+                    font_color: Some(self.title_font_color),
+                    x: Some(x / 2.0),
+                    y: Some(self.margin.top + title_height / 2.0),
+                    align: Some(Align::Center),
+                    vertical_align: Some(VerticalAlign::Middle),
+                    ..Default::default()
+                }
+                .svg(),
+            );
+        }
+
         if let Some(background_color) = self.background_color {
             arr.insert(
                 0,
@@ -346,7 +327,7 @@ impl MultiChart {
             );
         }
 
-        Ok(generate_svg(x, y, 0.0, 0.0, arr.join("\n")))
+        Ok(generate_svg(x, y, 0.0, 0.0, arr.join("\n"), None, None))
     }
 }
 
@@ -355,7 +336,7 @@ mod tests {
     use super::{ChildChart, MultiChart};
     use crate::{
         BarChart, CandlestickChart, HorizontalBarChart, LineChart, PieChart, RadarChart,
-        ScatterChart, TableChart,
+        ScatterChart, Series, TableChart,
     };
     use pretty_assertions::assert_eq;
     #[test]
@@ -628,4 +609,70 @@ mod tests {
             charts.svg().unwrap()
         );
     }
+
+    #[test]
+    fn multi_chart_grid() {
+        let mut charts = MultiChart::new();
+        charts.columns = Some(2);
+        charts.title_text = "Dashboard".to_string();
+        let series_list = vec![
+            ("Email", vec![120.0, 132.0, 101.0, 134.0, 90.0]).into(),
+            ("Direct", vec![320.0, 332.0, 301.0, 334.0, 390.0]).into(),
+        ];
+        let x_axis_data = vec![
+            "Mon".to_string(),
+            "Tue".to_string(),
+            "Wed".to_string(),
+            "Thu".to_string(),
+            "Fri".to_string(),
+        ];
+        let mut bar_chart = BarChart::new(series_list.clone(), x_axis_data.clone());
+        bar_chart.width = 300.0;
+        bar_chart.height = 200.0;
+        charts.add(ChildChart::Bar(bar_chart, None));
+
+        let mut line_chart = LineChart::new(series_list, x_axis_data);
+        line_chart.width = 300.0;
+        line_chart.height = 200.0;
+        charts.add(ChildChart::Line(line_chart, None));
+
+        let mut pie_chart = PieChart::new(vec![
+            ("rose 1", vec![40.0]).into(),
+            ("rose 2", vec![38.0]).into(),
+            ("rose 3", vec![32.0]).into(),
+        ]);
+        pie_chart.width = 300.0;
+        pie_chart.height = 200.0;
+        charts.add(ChildChart::Pie(pie_chart, None));
+
+        assert_eq!(
+            include_str!("../../asset/multi_chart/grid.svg"),
+            charts.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn multi_chart_element_ids() {
+        let mut charts = MultiChart::new();
+        let series_list: Vec<Series> = vec![("Email", vec![120.0, 132.0, 101.0]).into()];
+        let x_axis_data = vec!["Mon".to_string(), "Tue".to_string(), "Wed".to_string()];
+        for _ in 0..2 {
+            let mut bar_chart = BarChart::new(series_list.clone(), x_axis_data.clone());
+            bar_chart.element_ids = true;
+            charts.add(ChildChart::Bar(bar_chart, None));
+        }
+        let svg = charts.svg().unwrap();
+        assert!(svg.contains(r#"id="chart-0-legend-item-0""#));
+        assert!(svg.contains(r#"id="chart-1-legend-item-0""#));
+
+        let ids: Vec<&str> = svg
+            .split(r#"id=""#)
+            .skip(1)
+            .filter_map(|item| item.split('"').next())
+            .collect();
+        let mut unique_ids = ids.clone();
+        unique_ids.sort();
+        unique_ids.dedup();
+        assert_eq!(ids.len(), unique_ids.len());
+    }
 }
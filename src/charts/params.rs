@@ -2,9 +2,14 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::{Align, Box, Color, LegendCategory, Series, SeriesCategory, Theme, YAxisConfig};
+use super::{
+    Align, AxisPointer, Box, Color, Emphasis, Inset, LegendCategory, Series, SeriesCategory, Theme,
+    YAxisConfig,
+};
 use crate::{
-    MarkLine, MarkLineCategory, MarkPoint, MarkPointCategory, Position, Symbol, NIL_VALUE,
+    Direction, EndLabel, FontEmbedding, GuideLine, GuideLineCategory, LabelOverlap, MarkLine,
+    MarkLineCategory, MarkPoint, MarkPointCategory, PatternCategory, Position, SVGFormat,
+    SeriesTransform, Shadow, Symbol, SymbolVisibility, TrendType, NIL_VALUE,
 };
 use std::sync::Arc;
 
@@ -169,6 +174,7 @@ pub(crate) fn get_legend_category_from_value(
                 "rect" => LegendCategory::Rect,
                 "round_rect" => LegendCategory::RoundRect,
                 "circle" => LegendCategory::Circle,
+                "auto" => LegendCategory::Auto,
                 _ => LegendCategory::Normal,
             };
             return Some(value);
@@ -258,6 +264,20 @@ pub(crate) fn get_y_axis_config_from_value(t: Arc<Theme>, item: &serde_json::Val
     if let Some(axis_max) = get_f32_from_value(item, "axis_max") {
         y_config.axis_max = Some(axis_max);
     }
+    if let Some(axis_include_zero) = get_bool_from_value(item, "axis_include_zero") {
+        y_config.axis_include_zero = axis_include_zero;
+    }
+    if let Some(axis_symmetric) = get_bool_from_value(item, "axis_symmetric") {
+        y_config.axis_symmetric = axis_symmetric;
+    }
+    if let Some(axis_nice) = get_bool_from_value(item, "axis_nice") {
+        y_config.axis_nice = axis_nice;
+    }
+    if let Some(axis_max_significant_digits) =
+        get_usize_from_value(item, "axis_max_significant_digits")
+    {
+        y_config.axis_max_significant_digits = Some(axis_max_significant_digits);
+    }
     y_config
 }
 
@@ -280,6 +300,106 @@ pub(crate) fn get_y_axis_configs_from_value(
     None
 }
 
+/// Gets gradient(start color, end color, direction) from serde json,
+/// e.g. ["#5470C6", "#91CC75", "top_to_bottom"].
+pub(crate) fn get_gradient_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Option<(Color, Color, Direction)> {
+    let arr = value.get(key)?.as_array()?;
+    let get_color = |index: usize| -> Option<Color> {
+        let color = arr.get(index)?.as_str()?;
+        Some(color.into())
+    };
+    let direction = match arr
+        .get(2)
+        .and_then(|item| item.as_str())
+        .unwrap_or_default()
+    {
+        "bottom_to_top" => Direction::BottomToTop,
+        "left_to_right" => Direction::LeftToRight,
+        "right_to_left" => Direction::RightToLeft,
+        _ => Direction::TopToBottom,
+    };
+    Some((get_color(0)?, get_color(1)?, direction))
+}
+
+/// Gets shadow value from serde json, the omitted fields use default value.
+pub(crate) fn get_shadow_from_value(value: &serde_json::Value, key: &str) -> Option<Shadow> {
+    let data = value.get(key)?;
+    if !data.is_object() {
+        return None;
+    }
+    let mut shadow = Shadow::default();
+    if let Some(dx) = get_f32_from_value(data, "dx") {
+        shadow.dx = dx;
+    }
+    if let Some(dy) = get_f32_from_value(data, "dy") {
+        shadow.dy = dy;
+    }
+    if let Some(blur) = get_f32_from_value(data, "blur") {
+        shadow.blur = blur;
+    }
+    if let Some(color) = get_color_from_value(data, "color") {
+        shadow.color = color;
+    }
+    Some(shadow)
+}
+
+/// Gets pattern category from serde json.
+pub(crate) fn get_pattern_category_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Option<PatternCategory> {
+    let value = get_string_from_value(value, key)?;
+    let category = match value.as_str() {
+        "cross_hatch" => PatternCategory::CrossHatch,
+        "dots" => PatternCategory::Dots,
+        _ => PatternCategory::DiagonalHatch,
+    };
+    Some(category)
+}
+
+/// Gets label overlap value from serde json.
+pub(crate) fn get_label_overlap_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Option<LabelOverlap> {
+    if let Some(value) = get_string_from_value(value, key) {
+        let overlap = match value.as_str() {
+            "hide" => LabelOverlap::Hide,
+            "shift" => LabelOverlap::Shift,
+            _ => LabelOverlap::None,
+        };
+        return Some(overlap);
+    }
+    None
+}
+
+/// Gets svg format value from serde json.
+pub(crate) fn get_svg_format_from_value(value: &serde_json::Value, key: &str) -> Option<SVGFormat> {
+    let format = match get_string_from_value(value, key)?.as_str() {
+        "minified" => SVGFormat::Minified,
+        "pretty" => SVGFormat::Pretty,
+        "normalized" => SVGFormat::Normalized,
+        _ => SVGFormat::Normal,
+    };
+    Some(format)
+}
+
+/// Gets font embedding value from serde json.
+pub(crate) fn get_font_embedding_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Option<FontEmbedding> {
+    let embedding = match get_string_from_value(value, key)?.as_str() {
+        "base64" => FontEmbedding::Base64,
+        "outline" => FontEmbedding::Outline,
+        _ => FontEmbedding::None,
+    };
+    Some(embedding)
+}
+
 /// Gets color value from serde json.
 pub(crate) fn get_color_slice_from_value(
     value: &serde_json::Value,
@@ -298,6 +418,75 @@ pub(crate) fn get_color_slice_from_value(
     None
 }
 
+/// Gets inset list from serde json, the inset without svg is ignored.
+pub(crate) fn get_insets_from_value(value: &serde_json::Value, key: &str) -> Option<Vec<Inset>> {
+    if let Some(arr) = value.get(key) {
+        if let Some(values) = arr.as_array() {
+            return Some(
+                values
+                    .iter()
+                    .filter_map(|item| {
+                        let svg = get_string_from_value(item, "svg")?;
+                        let area = get_margin_from_value(item, "area").unwrap_or_default();
+                        Some(Inset::new(area, svg))
+                    })
+                    .collect(),
+            );
+        }
+    }
+    None
+}
+
+/// Gets guide line from serde json, the category can be "mean", "median" or percentile like "p95".
+pub(crate) fn get_guide_line_from_value(value: &serde_json::Value, key: &str) -> Option<GuideLine> {
+    let value = value.get(key)?;
+    if !value.is_object() {
+        return None;
+    }
+    let category = get_string_from_value(value, "category").unwrap_or_default();
+    let category = match category.as_str() {
+        "median" => GuideLineCategory::Median,
+        _ => {
+            if let Some(Ok(percentile)) = category.strip_prefix('p').map(|v| v.parse::<f32>()) {
+                GuideLineCategory::Percentile(percentile)
+            } else {
+                GuideLineCategory::Mean
+            }
+        }
+    };
+    Some(GuideLine {
+        category,
+        color: get_color_from_value(value, "color"),
+        label: get_string_from_value(value, "label"),
+    })
+}
+
+/// Gets axis pointer from serde json.
+pub(crate) fn get_axis_pointer_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Option<AxisPointer> {
+    let value = value.get(key)?;
+    Some(AxisPointer {
+        index: get_usize_from_value(value, "index")?,
+        line_color: get_color_from_value(value, "line_color"),
+        band_color: get_color_from_value(value, "band_color"),
+    })
+}
+
+/// Gets emphasis from serde json.
+pub(crate) fn get_emphasis_from_value(value: &serde_json::Value, key: &str) -> Option<Emphasis> {
+    let value = value.get(key)?;
+    if !value.is_object() {
+        return None;
+    }
+    Some(Emphasis {
+        series_index: get_usize_from_value(value, "series_index"),
+        data_indexes: get_usize_slice_from_value(value, "data_indexes").unwrap_or_default(),
+        fade_opacity: get_f32_from_value(value, "fade_opacity"),
+    })
+}
+
 /// Gets string value from serde json.
 pub(crate) fn get_string_from_value(value: &serde_json::Value, key: &str) -> Option<String> {
     if let Some(s) = value.get(key) {
@@ -316,6 +505,7 @@ pub(crate) fn get_position_from_value(value: &serde_json::Value, key: &str) -> O
     if let Some(value) = get_string_from_value(value, key) {
         let p = match value.as_str() {
             "inside" => Position::Inside,
+            "inside_top" => Position::InsideTop,
             "top" => Position::Top,
             "right" => Position::Right,
             "bottom" => Position::Bottom,
@@ -328,13 +518,14 @@ pub(crate) fn get_position_from_value(value: &serde_json::Value, key: &str) -> O
 
 /// Gets color value from serde json.
 pub(crate) fn get_color_from_value(value: &serde_json::Value, key: &str) -> Option<Color> {
-    if let Some(s) = get_string_from_value(value, key) {
-        return Some(s.as_str().into());
-    }
-    None
+    get_string_from_value(value, key).and_then(|s| s.parse().ok())
 }
 
-fn get_series_category_from_value(value: &serde_json::Value, key: &str) -> Option<SeriesCategory> {
+/// Gets series category value from serde json.
+pub(crate) fn get_series_category_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Option<SeriesCategory> {
     if let Some(value) = value.get(key) {
         if value.is_null() {
             return None;
@@ -343,6 +534,7 @@ fn get_series_category_from_value(value: &serde_json::Value, key: &str) -> Optio
             return match value.to_lowercase().as_str() {
                 "line" => Some(SeriesCategory::Line),
                 "bar" => Some(SeriesCategory::Bar),
+                "band" => Some(SeriesCategory::Band),
                 _ => None,
             };
         }
@@ -356,7 +548,9 @@ pub(crate) fn get_series_symbol_from_value(value: &serde_json::Value, key: &str)
         if value.is_null() {
             return Some(Symbol::None);
         }
-        // only supports circle
+        if let Some(path) = get_string_from_value(value, "path") {
+            return Some(Symbol::Path(path));
+        }
         let color = get_color_from_value(value, "color");
         let radius = get_f32_from_value(value, "radius").unwrap_or(3.0);
         return Some(Symbol::Circle(radius, color));
@@ -364,6 +558,75 @@ pub(crate) fn get_series_symbol_from_value(value: &serde_json::Value, key: &str)
     None
 }
 
+/// Gets series transform from serde json.
+fn get_series_transform_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Option<SeriesTransform> {
+    let transform = match get_string_from_value(value, key)?.as_str() {
+        "cumsum" => SeriesTransform::Cumsum,
+        "diff" => SeriesTransform::Diff,
+        "percent_change" => SeriesTransform::PercentChange,
+        _ => return None,
+    };
+    Some(transform)
+}
+
+/// Gets trend type from serde json,
+/// it can be "linear" or an object with category and value, e.g. {"category": "polynomial", "value": 2}.
+pub(crate) fn get_trend_type_from_value(value: &serde_json::Value, key: &str) -> Option<TrendType> {
+    let value = value.get(key)?;
+    let (category, n) = if let Some(category) = value.as_str() {
+        (category.to_string(), None)
+    } else {
+        (
+            get_string_from_value(value, "category")?,
+            value.get("value").and_then(|v| v.as_u64()),
+        )
+    };
+    match category.as_str() {
+        "linear" => Some(TrendType::Linear),
+        "polynomial" => Some(TrendType::Polynomial(n.unwrap_or(2) as usize)),
+        "moving_average" => Some(TrendType::MovingAverage(n.unwrap_or(3) as usize)),
+        _ => None,
+    }
+}
+
+/// Gets symbol visibility from serde json,
+/// it can be "all", "last", a number for every nth point or an array of indexes.
+pub(crate) fn get_symbol_visibility_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Option<SymbolVisibility> {
+    let value = value.get(key)?;
+    if let Some(n) = value.as_u64() {
+        return Some(SymbolVisibility::Every(n as usize));
+    }
+    if let Some(arr) = value.as_array() {
+        let indexes = arr
+            .iter()
+            .filter_map(|item| item.as_u64().map(|v| v as usize))
+            .collect();
+        return Some(SymbolVisibility::Indexes(indexes));
+    }
+    let visibility = match value.as_str()? {
+        "last" => SymbolVisibility::Last,
+        _ => SymbolVisibility::All,
+    };
+    Some(visibility)
+}
+
+/// Gets end label from serde json, it can be "first", "last" or "both".
+pub(crate) fn get_end_label_from_value(value: &serde_json::Value, key: &str) -> Option<EndLabel> {
+    let end_label = match get_string_from_value(value, key)?.as_str() {
+        "first" => EndLabel::First,
+        "last" => EndLabel::Last,
+        "both" => EndLabel::Both,
+        _ => return None,
+    };
+    Some(end_label)
+}
+
 fn get_mark_lines(value: &serde_json::Value, key: &str) -> Vec<MarkLine> {
     let mut mark_lines = vec![];
     if let Some(data) = value.get(key) {
@@ -430,6 +693,8 @@ fn get_series_from_value(value: &serde_json::Value) -> Option<Series> {
     Some(Series {
         name,
         data,
+        visible: get_bool_from_value(value, "visible").unwrap_or(true),
+        legend_text: get_string_from_value(value, "legend_text"),
         index: get_usize_from_value(value, "index"),
         y_axis_index: get_usize_from_value(value, "y_axis_index").unwrap_or_default(),
         label_show: get_bool_from_value(value, "label_show").unwrap_or_default(),
@@ -439,6 +704,32 @@ fn get_series_from_value(value: &serde_json::Value) -> Option<Series> {
         mark_points: get_mark_points(value, "mark_points"),
         colors: get_series_colors_from_value(value, "colors"),
         stroke_dash_array: get_string_from_value(value, "stroke_dash_array"),
+        stroke_width: get_f32_from_value(value, "stroke_width"),
+        opacity: get_f32_from_value(value, "opacity"),
+        symbol: get_series_symbol_from_value(value, "symbol"),
+        symbol_size: get_f32_from_value(value, "symbol_size"),
+        symbol_visibility: get_symbol_visibility_from_value(value, "symbol_visibility")
+            .unwrap_or_default(),
+        area_baseline: get_f32_from_value(value, "area_baseline"),
+        lower_data: get_f32_slice_from_value(value, "lower_data").unwrap_or_default(),
+        upper_data: get_f32_slice_from_value(value, "upper_data").unwrap_or_default(),
+        trend_line: get_trend_type_from_value(value, "trend_line"),
+        transform: get_series_transform_from_value(value, "transform"),
+        connect_nulls: get_bool_from_value(value, "connect_nulls").unwrap_or_default(),
+        label_formatter: get_string_from_value(value, "label_formatter"),
+        label_precision: get_usize_from_value(value, "label_precision"),
+        label_position: get_position_from_value(value, "label_position"),
+        label_priority: get_usize_from_value(value, "label_priority").unwrap_or_default(),
+        show_background: get_bool_from_value(value, "show_background").unwrap_or_default(),
+        background_color: get_color_from_value(value, "background_color"),
+        fill_gradient: get_gradient_from_value(value, "fill_gradient"),
+        fill_pattern: get_pattern_category_from_value(value, "fill_pattern"),
+        negative_color: get_color_from_value(value, "negative_color"),
+        z_index: get_f32_from_value(value, "z_index")
+            .map(|value| value as i32)
+            .unwrap_or_default(),
+        end_label: get_end_label_from_value(value, "end_label"),
+        stack: get_string_from_value(value, "stack"),
     })
 }
 
@@ -14,15 +14,45 @@ pub struct QuadraticBezier {
 }
 impl fmt::Display for QuadraticBezier {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let m = format!("{} {}", format_float(self.x1), format_float(self.y1));
+        let m = format!(
+            "{} {}",
+            format_coordinate(self.x1),
+            format_coordinate(self.y1)
+        );
         let x = (self.x1 + self.x1) / 2.0;
         let y = self.y1 + (self.y2 - self.y1) / 2.0;
-        let q = format!("{} {}", format_float(x), format_float(y));
-        let end = format!("{} {}", format_float(self.x2), format_float(self.y2));
+        let q = format!("{} {}", format_coordinate(x), format_coordinate(y));
+        let end = format!(
+            "{} {}",
+            format_coordinate(self.x2),
+            format_coordinate(self.y2)
+        );
         write!(f, "M{m} Q{q}, {end}")
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct CubicBezier {
+    pub start: Point,
+    pub control1: Point,
+    pub control2: Point,
+    pub end: Point,
+}
+impl fmt::Display for CubicBezier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let format_point =
+            |p: &Point| format!("{} {}", format_coordinate(p.x), format_coordinate(p.y));
+        write!(
+            f,
+            "M{} C{}, {}, {}",
+            format_point(&self.start),
+            format_point(&self.control1),
+            format_point(&self.control2),
+            format_point(&self.end)
+        )
+    }
+}
+
 #[derive(Clone, PartialEq, Debug, Default)]
 struct ControlPoint {
     left: Option<Point>,
@@ -105,8 +135,8 @@ impl fmt::Display for SmoothCurve {
             if index == 0 {
                 arr.push(format!(
                     "M{},{}",
-                    format_float(point.x),
-                    format_float(point.y)
+                    format_coordinate(point.x),
+                    format_coordinate(point.y)
                 ));
             }
             let cp1 = control_points[index].right;
@@ -126,20 +156,20 @@ impl fmt::Display for SmoothCurve {
             if let Some(next_point_value) = next_point {
                 let next_point = format!(
                     "{} {}",
-                    format_float(next_point_value.x),
-                    format_float(next_point_value.y)
+                    format_coordinate(next_point_value.x),
+                    format_coordinate(next_point_value.y)
                 );
                 if let Some(cp1_value) = cp1 {
                     if let Some(cp2_value) = cp2 {
                         let c1 = format!(
                             "{} {}",
-                            format_float(cp1_value.x),
-                            format_float(cp1_value.y)
+                            format_coordinate(cp1_value.x),
+                            format_coordinate(cp1_value.y)
                         );
                         let c2 = format!(
                             "{} {}",
-                            format_float(cp2_value.x),
-                            format_float(cp2_value.y)
+                            format_coordinate(cp2_value.x),
+                            format_coordinate(cp2_value.y)
                         );
                         arr.push(format!("C{}, {}, {}", c1, c2, next_point));
                         continue;
@@ -147,7 +177,7 @@ impl fmt::Display for SmoothCurve {
                 }
                 let p = cp1.unwrap_or(cp2.unwrap_or_default());
 
-                let q = format!("{} {}", format_float(p.x), format_float(p.y));
+                let q = format!("{} {}", format_coordinate(p.x), format_coordinate(p.y));
                 arr.push(format!("Q{}, {}", q, next_point));
             }
         }
@@ -157,7 +187,7 @@ impl fmt::Display for SmoothCurve {
 
 #[cfg(test)]
 mod tests {
-    use super::{QuadraticBezier, SmoothCurve};
+    use super::{CubicBezier, QuadraticBezier, SmoothCurve};
     use pretty_assertions::assert_eq;
     #[test]
     fn quadratic_bezier() {
@@ -171,6 +201,18 @@ mod tests {
         assert_eq!("M10 30 Q10 20, 30 10", str);
     }
 
+    #[test]
+    fn cubic_bezier() {
+        let str = CubicBezier {
+            start: (10.0, 80.0).into(),
+            control1: (40.0, 10.0).into(),
+            control2: (65.0, 10.0).into(),
+            end: (95.0, 80.0).into(),
+        }
+        .to_string();
+        assert_eq!("M10 80 C40 10, 65 10, 95 80", str);
+    }
+
     #[test]
     fn smooth_curve() {
         let str = SmoothCurve {
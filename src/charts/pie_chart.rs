@@ -2,12 +2,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::canvas;
+use super::canvas::{self, ChartError};
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::locale::LocaleGuard;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme, Theme, DEFAULT_Y_AXIS_WIDTH};
+use super::theme::{
+    find_theme, get_default_theme_name, get_theme, EffectiveStyle, Theme, DEFAULT_FOOTER_FONT_SIZE,
+    DEFAULT_Y_AXIS_WIDTH,
+};
 use super::util::*;
 use super::Canvas;
 use crate::charts::measure_text_width_family;
@@ -32,8 +36,15 @@ pub struct PieChart {
     pub title_font_size: f32,
     pub title_font_color: Color,
     pub title_font_weight: Option<String>,
+    // font style of title, e.g. italic
+    pub title_font_style: Option<String>,
+    pub title_letter_spacing: Option<f32>,
+    // text decoration of title, e.g. underline
+    pub title_text_decoration: Option<String>,
     pub title_margin: Option<Box>,
     pub title_align: Align,
+    pub title_shadow: Option<Shadow>,
+    pub title_link: Option<String>,
     pub title_height: f32,
 
     // sub title
@@ -41,10 +52,23 @@ pub struct PieChart {
     pub sub_title_font_size: f32,
     pub sub_title_font_color: Color,
     pub sub_title_font_weight: Option<String>,
+    // font style of sub title, e.g. italic
+    pub sub_title_font_style: Option<String>,
+    pub sub_title_letter_spacing: Option<f32>,
+    // text decoration of sub title, e.g. underline
+    pub sub_title_text_decoration: Option<String>,
     pub sub_title_margin: Option<Box>,
     pub sub_title_align: Align,
     pub sub_title_height: f32,
 
+    // footer
+    pub footer_text: String,
+    pub footer_font_size: f32,
+    pub footer_font_color: Color,
+    pub footer_font_weight: Option<String>,
+    pub footer_margin: Option<Box>,
+    pub footer_align: Align,
+
     // legend
     pub legend_font_size: f32,
     pub legend_font_color: Color,
@@ -53,11 +77,27 @@ pub struct PieChart {
     pub legend_margin: Option<Box>,
     pub legend_category: LegendCategory,
     pub legend_show: Option<bool>,
+    pub legend_position: Option<Position>,
+    pub legend_order: Option<Vec<String>>,
 
     pub radius: f32,
     pub inner_radius: f32,
     pub rose_type: Option<bool>,
+    // the area of rose slice is proportional to value instead of the radius,
+    // so the large value is not over-emphasized
+    pub rose_area: bool,
     pub border_radius: Option<f32>,
+    // the label is inside the slice if it is Position::Inside
+    pub series_label_position: Option<Position>,
+    // the outside labels are stacked in the left and right columns,
+    // and connected to the slices by elbow lines
+    pub series_label_column: bool,
+    // the slices below the percentage(e.g. 0.05) are merged into one slice
+    pub other_threshold: Option<f32>,
+    // the name of merged slice, default is "Other"
+    pub other_label: Option<String>,
+    // the color of merged slice, default is the next color of series
+    pub other_color: Option<Color>,
 
     // x axis
     pub x_axis_data: Vec<String>,
@@ -70,6 +110,10 @@ pub struct PieChart {
     pub x_axis_name_rotate: f32,
     pub x_axis_margin: Option<Box>,
     pub x_boundary_gap: Option<bool>,
+    // the range(start and end index, inclusive) of categories to render, the axes are recalculated
+    pub x_range: Option<(usize, usize)>,
+    // the overview strip of all categories below the chart, the x range is highlighted
+    pub x_range_overview: bool,
 
     // y axis
     pub y_axis_configs: Vec<YAxisConfig>,
@@ -77,6 +121,10 @@ pub struct PieChart {
     // grid
     pub grid_stroke_color: Color,
     pub grid_stroke_width: f32,
+    // the color of grid line at zero value, it is not highlighted if not set
+    pub zero_line_color: Option<Color>,
+    // the reference line of statistic computed across all visible series
+    pub guide_line: Option<GuideLine>,
 
     // series
     pub series_stroke_width: f32,
@@ -84,10 +132,41 @@ pub struct PieChart {
     pub series_label_font_size: f32,
     pub series_label_font_weight: Option<String>,
     pub series_label_formatter: String,
+    pub series_label_overlap: LabelOverlap,
+    pub series_shadow: Option<Shadow>,
     pub series_colors: Vec<Color>,
     pub series_symbol: Option<Symbol>,
     pub series_smooth: bool,
     pub series_fill: bool,
+    pub series_fill_opacity: Option<f32>,
+    pub max_points_per_series: Option<usize>,
+    pub annotations: Vec<Annotation>,
+    // emit the stable id and class of elements, e.g. series-0-bar-3
+    pub element_ids: bool,
+    // native tooltip of elements, e.g. Mon: Email = 120
+    pub tooltip_show: bool,
+    // accessible name and description of chart
+    pub aria_label: Option<String>,
+    pub description: Option<String>,
+    pub svg_format: SVGFormat,
+    pub coordinate_precision: Option<usize>,
+    pub style_hoisting: bool,
+    pub font_embedding: FontEmbedding,
+    // emit the svg of 100% width, the height is scaled by aspect ratio
+    pub responsive: bool,
+    // mirrored layout of right-to-left locale, the y axis is on the right side
+    pub rtl: bool,
+    // locale of number and date names, e.g. de-DE
+    pub locale: Option<String>,
+    // the placeholder of empty series, the text is "No data" if not set
+    pub empty_text: Option<String>,
+    pub empty_icon: bool,
+    // the inset charts, they are rendered after the series
+    pub insets: Vec<Inset>,
+    // the emphasized series or points, the others are faded
+    pub emphasis: Option<Emphasis>,
+    // the selected category which is indicated by crosshair line and band
+    pub axis_pointer: Option<AxisPointer>,
 }
 
 impl PieChart {
@@ -113,9 +192,29 @@ impl PieChart {
         if let Some(rose_type) = get_bool_from_value(&value, "rose_type") {
             p.rose_type = Some(rose_type);
         }
+        if let Some(rose_area) = get_bool_from_value(&value, "rose_area") {
+            p.rose_area = rose_area;
+        }
         if let Some(border_radius) = get_f32_from_value(&value, "border_radius") {
             p.border_radius = Some(border_radius);
         }
+        if let Some(series_label_position) =
+            get_position_from_value(&value, "series_label_position")
+        {
+            p.series_label_position = Some(series_label_position);
+        }
+        if let Some(series_label_column) = get_bool_from_value(&value, "series_label_column") {
+            p.series_label_column = series_label_column;
+        }
+        if let Some(other_threshold) = get_f32_from_value(&value, "other_threshold") {
+            p.other_threshold = Some(other_threshold);
+        }
+        if let Some(other_label) = get_string_from_value(&value, "other_label") {
+            p.other_label = Some(other_label);
+        }
+        if let Some(other_color) = get_color_from_value(&value, "other_color") {
+            p.other_color = Some(other_color);
+        }
         Ok(p)
     }
     /// Creates a pie chart with custom theme.
@@ -132,22 +231,108 @@ impl PieChart {
     pub fn new(series_list: Vec<Series>) -> PieChart {
         PieChart::new_with_theme(series_list, &get_default_theme_name())
     }
+    /// Gets the pie chart whose small slices are merged into other slice,
+    /// it returns none if less than two slices are below the threshold.
+    fn merge_small_slices(&self) -> Option<PieChart> {
+        let threshold = self.other_threshold?;
+        let values: Vec<f32> = self
+            .series_list
+            .iter()
+            .map(|item| {
+                item.data
+                    .iter()
+                    .filter(|value| !is_nil_value(**value))
+                    .sum()
+            })
+            .collect();
+        let sum: f32 = self
+            .series_list
+            .iter()
+            .zip(values.iter())
+            .filter(|(series, _)| series.visible)
+            .map(|(_, value)| *value)
+            .sum();
+        if sum <= 0.0 {
+            return None;
+        }
+        let is_small =
+            |index: usize| self.series_list[index].visible && values[index] / sum < threshold;
+        if (0..values.len()).filter(|index| is_small(*index)).count() < 2 {
+            return None;
+        }
+        let mut p = self.clone();
+        p.other_threshold = None;
+        p.series_list = vec![];
+        let mut other_value = 0.0;
+        for (index, series) in self.series_list.iter().enumerate() {
+            if is_small(index) {
+                other_value += values[index];
+                continue;
+            }
+            // keep the color of series
+            let mut series = series.clone();
+            series.index = Some(series.index.unwrap_or(index));
+            p.series_list.push(series);
+        }
+        let other_label = self
+            .other_label
+            .clone()
+            .unwrap_or_else(|| "Other".to_string());
+        let mut other: Series = (other_label.as_str(), vec![other_value]).into();
+        other.index = Some(self.series_list.len());
+        if let Some(other_color) = self.other_color {
+            other.index = Some(p.series_colors.len());
+            p.series_colors.push(other_color);
+        }
+        p.series_list.push(other);
+        Some(p)
+    }
     /// Converts pie chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        self.render_with_layout().map(|(svg, _)| svg)
+    }
+    /// Converts pie chart to svg, and returns the layout of plot area and slices.
+    pub fn render_with_layout(&self) -> canvas::Result<(String, ChartLayout)> {
+        if let Some(p) = self.merge_small_slices() {
+            return p.render_with_layout();
+        }
+        if let Some(svg) = self.render_empty_state()? {
+            return Ok((svg, ChartLayout::default()));
+        }
+        let _locale = LocaleGuard::new(self.locale.as_deref());
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        c.aria_label = self.aria_label.clone();
+        c.description = self.description.clone();
+        c.svg_format = self.svg_format.clone();
+        c.coordinate_precision = self.coordinate_precision;
+        c.style_hoisting = self.style_hoisting;
+        c.font_embedding = self.font_embedding.clone();
+        c.responsive = self.responsive;
 
         self.render_background(c.child(Box::default()));
         c.margin = self.margin.clone();
 
         let title_height = self.render_title(c.child(Box::default()));
 
-        let legend_height = self.render_legend(c.child(Box::default()));
+        let footer_height = self.render_footer(c.child(Box::default()));
+
+        let legend_box = self.render_legend(c.child(Box {
+            bottom: footer_height,
+            ..Default::default()
+        }));
         // get the max height of title and legend
-        let axis_top = if legend_height > title_height {
-            legend_height
+        let axis_top = if legend_box.top > title_height {
+            legend_box.top
         } else {
             title_height
         };
+        // the legend on the other sides and footer take the space of chart
+        c = c.child(Box {
+            left: legend_box.left,
+            right: legend_box.right,
+            bottom: legend_box.bottom + footer_height,
+            ..Default::default()
+        });
         if axis_top > 0.0 {
             c = c.child(Box {
                 top: axis_top,
@@ -158,7 +343,16 @@ impl PieChart {
         let values: Vec<f32> = self
             .series_list
             .iter()
-            .map(|item| item.data.iter().sum())
+            .map(|item| {
+                // the hidden series is excluded from pie
+                if !item.visible {
+                    return 0.0;
+                }
+                item.data
+                    .iter()
+                    .filter(|value| !is_nil_value(**value))
+                    .sum()
+            })
             .collect();
         let mut max = 0.0;
         let mut sum = 0.0;
@@ -190,12 +384,25 @@ impl PieChart {
             series_label_formatter = "{a}: {d}".to_string();
         }
         let rose_type = self.rose_type.unwrap_or_default();
+        let label_inside = self.series_label_position == Some(Position::Inside);
 
         let mut prev_quadrant = u8::MAX;
         let mut prev_end_y = f32::MAX;
+        let mut pie_items = vec![];
+        let mut label_boxes = vec![];
+        // the index of pie item and whether the label is on the left column
+        let mut column_items = vec![];
+        let mut layout_items = vec![];
         for (index, series) in self.series_list.iter().enumerate() {
+            if !series.visible {
+                continue;
+            }
             let value = values[index];
             let mut cr = value / max * (r - self.inner_radius) + self.inner_radius;
+            if self.rose_area {
+                let ir = self.inner_radius;
+                cr = (ir * ir + value / max * (r * r - ir * ir)).sqrt();
+            }
             let color = get_color(&self.series_colors, series.index.unwrap_or(index));
             // normal pie
             if !rose_type {
@@ -214,15 +421,94 @@ impl PieChart {
                 ir: self.inner_radius,
                 start_angle,
                 delta,
+                title: self
+                    .tooltip_show
+                    .then(|| format!("{} = {}", series.name, format_label_float(value))),
                 ..Default::default()
             };
             if let Some(border_radius) = self.border_radius {
                 pie.border_radius = border_radius;
             }
-
-            c.pie(pie);
+            // the slice is approximated by polygon, the arc is split every 10 degrees
+            let steps = (delta / 10.0).ceil().max(1.0) as usize;
+            let get_arc_points = |radius: f32| -> Vec<Point> {
+                (0..=steps)
+                    .map(|i| {
+                        let angle = start_angle + delta * i as f32 / steps as f32;
+                        let point = get_pie_point(cx, cy, radius, angle);
+                        (c.margin.left + point.x, c.margin.top + point.y).into()
+                    })
+                    .collect()
+            };
+            let mut points = get_arc_points(cr);
+            if self.inner_radius > 0.0 {
+                points.extend(get_arc_points(self.inner_radius).into_iter().rev());
+            } else {
+                points.push((c.margin.left + cx, c.margin.top + cy).into());
+            }
+            layout_items.push(LayoutItem {
+                series_index: series.index.unwrap_or(index),
+                data_index: 0,
+                value,
+                point: get_pie_point(
+                    c.margin.left + cx,
+                    c.margin.top + cy,
+                    (self.inner_radius + cr) / 2.0,
+                    start_angle + delta / 2.0,
+                ),
+                area: get_box_of_points(&points),
+                shape: LayoutShape::Polygon(points),
+            });
 
             let angle = start_angle + half_delta;
+            let label_option = LabelOption {
+                series_name: series.name.clone(),
+                value,
+                percentage: value / sum,
+                formatter: series_label_formatter.clone(),
+                ..Default::default()
+            };
+            let label_text = label_option.format();
+            let label_size = measure_text_width_family(
+                &self.font_family,
+                self.series_label_font_size,
+                &label_text,
+            )
+            .ok();
+            let mut text = Text {
+                text: label_text,
+                font_family: Some(self.font_family.clone()),
+                font_size: Some(self.series_label_font_size),
+                font_color: Some(self.series_label_font_color),
+                ..Default::default()
+            };
+
+            // the label is placed at the middle of slice with the contrast color
+            if label_inside {
+                let center = get_pie_point(cx, cy, (self.inner_radius + cr) / 2.0, angle);
+                let (width, height) = label_size
+                    .map(|b| (b.width(), b.height()))
+                    .unwrap_or_default();
+                let label_margin = Box {
+                    left: center.x - width / 2.0,
+                    top: center.y + height / 2.0 - 2.0,
+                    ..Default::default()
+                };
+                label_boxes.push((
+                    Box {
+                        left: label_margin.left,
+                        top: label_margin.top - height,
+                        right: label_margin.left + width,
+                        bottom: label_margin.top,
+                    },
+                    series.label_priority,
+                ));
+                text.font_color = Some(color.contrast_color());
+                pie_items.push((pie, None, label_margin, text));
+                start_angle += delta;
+                continue;
+            }
+
             let mut points = vec![];
             points.push(get_pie_point(cx, cy, cr, angle));
             let mut end = get_pie_point(cx, cy, r + label_offset, angle);
@@ -233,8 +519,9 @@ impl PieChart {
                 prev_end_y = f32::MAX;
                 prev_quadrant = quadrant;
             }
-            // label overlap
-            if (end.y - prev_end_y).abs() < self.series_label_font_size {
+            // label overlap, the labels of column are stacked later
+            if !self.series_label_column && (end.y - prev_end_y).abs() < self.series_label_font_size
+            {
                 if quadrant == 1 || quadrant == 4 {
                     end.y = prev_end_y + self.series_label_font_size;
                 } else {
@@ -246,6 +533,9 @@ impl PieChart {
             points.push(end);
 
             let is_left = angle > 180.0;
+            if self.series_label_column {
+                column_items.push((pie_items.len(), is_left));
+            }
             if is_left {
                 end.x -= label_offset;
             } else {
@@ -256,53 +546,141 @@ impl PieChart {
                 top: end.y + 5.0,
                 ..Default::default()
             };
-            let label_option = LabelOption {
-                series_name: series.name.clone(),
-                value,
-                percentage: value / sum,
-                formatter: series_label_formatter.clone(),
-                ..Default::default()
-            };
-            let label_text = label_option.format();
 
-            if is_left {
-                if let Ok(b) = measure_text_width_family(
-                    &self.font_family,
-                    self.series_label_font_size,
-                    &label_text,
-                ) {
+            let mut label_box = Box::default();
+            if let Some(b) = label_size {
+                if is_left {
                     label_margin.left -= b.width();
                 }
-            } else {
+                label_box.right = b.width();
+                label_box.top = -b.height();
+            }
+            if !is_left {
                 label_margin.left += 3.0;
             }
+            label_box.left += label_margin.left;
+            label_box.right += label_margin.left;
+            label_box.top += label_margin.top;
+            label_box.bottom = label_margin.top;
+            label_boxes.push((label_box, series.label_priority));
 
             points.push(end);
-            c.smooth_line(SmoothLine {
+            let line = SmoothLine {
                 color: Some(color),
                 points,
                 symbol: None,
                 ..Default::default()
-            });
-
-            c.child(label_margin).text(Text {
-                text: label_text,
-                font_family: Some(self.font_family.clone()),
-                font_size: Some(self.series_label_font_size),
-                font_color: Some(self.series_label_font_color),
-                ..Default::default()
-            });
+            };
+            pie_items.push((pie, Some(line), label_margin, text));
 
             start_angle += delta;
         }
 
-        c.svg()
+        // the labels of each column are stacked from top to bottom,
+        // and moved up if they are out of the bottom of chart
+        for left_column in [true, false] {
+            let mut items: Vec<usize> = column_items
+                .iter()
+                .filter(|(_, is_left)| *is_left == left_column)
+                .map(|(index, _)| *index)
+                .collect();
+            let get_y = |index: &usize| {
+                pie_items[*index]
+                    .1
+                    .as_ref()
+                    .map(|line| line.points[1].y)
+                    .unwrap_or_default()
+            };
+            items.sort_by(|a, b| get_y(a).total_cmp(&get_y(b)));
+            let mut y_list: Vec<f32> = items.iter().map(get_y).collect();
+            let label_height = self.series_label_font_size + 2.0;
+            for i in 0..y_list.len() {
+                let min_y = if i == 0 {
+                    label_height
+                } else {
+                    y_list[i - 1] + label_height
+                };
+                y_list[i] = y_list[i].max(min_y);
+            }
+            let mut max_y = c.height() - 5.0;
+            for y in y_list.iter_mut().rev() {
+                *y = y.min(max_y);
+                max_y = *y - label_height;
+            }
+            let column_x = if left_column {
+                cx - r - label_offset * 2.0
+            } else {
+                cx + r + label_offset * 2.0
+            };
+            for (index, y) in items.into_iter().zip(y_list) {
+                let (_, line, label_margin, _) = &mut pie_items[index];
+                if let Some(line) = line {
+                    line.points[1].y = y;
+                    line.points[2] = (column_x, y).into();
+                }
+                let label_box = &mut label_boxes[index].0;
+                let (width, height) = (label_box.width(), label_box.height());
+                label_margin.left = if left_column {
+                    column_x - width
+                } else {
+                    column_x + 3.0
+                };
+                label_margin.top = y + 5.0;
+                *label_box = Box {
+                    left: label_margin.left,
+                    top: label_margin.top - height,
+                    right: label_margin.left + width,
+                    bottom: label_margin.top,
+                };
+            }
+        }
+
+        let offsets = match self.series_label_overlap {
+            LabelOverlap::None => vec![Some(0.0); pie_items.len()],
+            LabelOverlap::Hide => resolve_label_overlap(&label_boxes, false),
+            LabelOverlap::Shift => resolve_label_overlap(&label_boxes, true),
+        };
+        for ((pie, line, mut label_margin, text), offset) in pie_items.into_iter().zip(offsets) {
+            c.pie(pie);
+            // the overlapping label is hidden with its line
+            let Some(offset) = offset else {
+                continue;
+            };
+            // move the end of label line with the label
+            if let Some(mut line) = line {
+                for point in line.points.iter_mut().skip(1) {
+                    point.y += offset;
+                }
+                // the elbow line of column label is straight
+                if self.series_label_column {
+                    c.polyline(Polyline {
+                        color: line.color,
+                        stroke_width: line.stroke_width,
+                        points: line.points,
+                    });
+                } else {
+                    c.smooth_line(line);
+                }
+            }
+            label_margin.top += offset;
+            c.child(label_margin).text(text);
+        }
+
+        // only the annotations of pixel coordinates are supported
+        self.render_annotations(c.child(Box::default()), &[], 0.0, 0);
+        self.render_insets(c.child(Box::default()));
+        let layout = ChartLayout {
+            plot_area: c.area(),
+            items: layout_items,
+        };
+        Ok((c.svg()?, layout))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::PieChart;
+    use crate::Position;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -410,4 +788,106 @@ mod tests {
             pie_chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn pie_label_inside() {
+        let mut pie_chart = PieChart::new(vec![
+            ("rose 1", vec![40.0]).into(),
+            ("rose 2", vec![38.0]).into(),
+            ("rose 3", vec![32.0]).into(),
+            ("rose 4", vec![30.0]).into(),
+            ("rose 5", vec![28.0]).into(),
+        ]);
+        pie_chart.rose_type = Some(false);
+        pie_chart.series_label_formatter = "{d}".to_string();
+        pie_chart.series_label_position = Some(Position::Inside);
+        pie_chart.title_text = "Pie Chart".to_string();
+        assert_eq!(
+            include_str!("../../asset/pie_chart/label_inside.svg"),
+            pie_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn pie_label_column() {
+        let mut pie_chart = PieChart::new(vec![
+            ("Search", vec![1048.0]).into(),
+            ("Direct", vec![735.0]).into(),
+            ("Email", vec![580.0]).into(),
+            ("Union Ads", vec![20.0]).into(),
+            ("Video Ads", vec![15.0]).into(),
+            ("Social", vec![12.0]).into(),
+            ("Forum", vec![8.0]).into(),
+            ("Blog", vec![6.0]).into(),
+        ]);
+        pie_chart.rose_type = Some(false);
+        pie_chart.inner_radius = 60.0;
+        pie_chart.series_label_column = true;
+        pie_chart.title_text = "Traffic Source".to_string();
+        assert_eq!(
+            include_str!("../../asset/pie_chart/label_column.svg"),
+            pie_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn pie_other_slice() {
+        let mut pie_chart = PieChart::new(vec![
+            ("Search", vec![1048.0]).into(),
+            ("Direct", vec![735.0]).into(),
+            ("Email", vec![580.0]).into(),
+            ("Union Ads", vec![20.0]).into(),
+            ("Video Ads", vec![15.0]).into(),
+            ("Social", vec![12.0]).into(),
+            ("Forum", vec![8.0]).into(),
+            ("Blog", vec![6.0]).into(),
+        ]);
+        pie_chart.rose_type = Some(false);
+        pie_chart.legend_show = Some(true);
+        pie_chart.other_threshold = Some(0.02);
+        pie_chart.other_color = Some((150, 150, 150).into());
+        pie_chart.title_text = "Traffic Source".to_string();
+        assert_eq!(
+            include_str!("../../asset/pie_chart/other_slice.svg"),
+            pie_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn pie_rose_area() {
+        let mut pie_chart = PieChart::new(vec![
+            ("rose 1", vec![400.0]).into(),
+            ("rose 2", vec![38.0]).into(),
+            ("rose 3", vec![32.0]).into(),
+            ("rose 4", vec![30.0]).into(),
+            ("rose 5", vec![28.0]).into(),
+            ("rose 6", vec![26.0]).into(),
+            ("rose 7", vec![22.0]).into(),
+            ("rose 8", vec![18.0]).into(),
+        ]);
+        pie_chart.rose_area = true;
+        pie_chart.title_text = "Nightingale Chart".to_string();
+        pie_chart.sub_title_text = "Fake Data".to_string();
+        assert_eq!(
+            include_str!("../../asset/pie_chart/rose_area.svg"),
+            pie_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn pie_chart_layout() {
+        let pie_chart = PieChart::new(vec![
+            ("rose 1", vec![40.0]).into(),
+            ("rose 2", vec![30.0]).into(),
+            ("rose 3", vec![30.0]).into(),
+        ]);
+        let (svg, layout) = pie_chart.render_with_layout().unwrap();
+        assert_eq!(pie_chart.svg().unwrap(), svg);
+        assert_eq!(3, layout.items.len());
+        let map = layout.image_map("pie", |item| {
+            vec![("data-series".to_string(), item.series_index.to_string())]
+        });
+        assert_eq!(3, map.matches(r#"<area shape="poly""#).count());
+        assert!(map.contains(r#"data-series="2""#));
+    }
 }
@@ -0,0 +1,286 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::canvas;
+use super::color::*;
+use super::common::*;
+use super::component::*;
+use super::params::*;
+use super::theme::{get_default_option, get_default_theme_name, get_theme, Theme};
+use super::util::*;
+use super::Canvas;
+use crate::charts::measure_text_width_family;
+
+#[derive(Clone, Debug, Default)]
+pub struct ProgressChart {
+    pub width: f32,
+    pub height: f32,
+    pub x: f32,
+    pub y: f32,
+    pub font_family: String,
+    pub background_color: Color,
+
+    // title
+    pub title_text: String,
+    pub title_font_size: f32,
+    pub title_font_color: Color,
+    pub title_font_weight: Option<String>,
+
+    // the progress value, it is between 0 and 1
+    pub value: f32,
+    // half circle from left to right, it is full circle by default
+    pub half: bool,
+    // the thickness of progress arc
+    pub stroke_width: f32,
+    pub track_color: Color,
+    pub progress_color: Color,
+    pub round_cap: bool,
+
+    // label in the middle, it is the percentage of value if not set
+    pub label_text: Option<String>,
+    pub label_font_size: f32,
+    pub label_font_color: Color,
+    pub label_font_weight: Option<String>,
+}
+
+impl ProgressChart {
+    fn fill_option(&mut self, data: &str) -> canvas::Result<serde_json::Value> {
+        let data: serde_json::Value = serde_json::from_str(data)?;
+        let theme = get_string_from_value(&data, "theme").unwrap_or_default();
+        self.fill_theme(get_theme(&theme));
+
+        if let Some(width) = get_f32_from_value(&data, "width") {
+            self.width = width;
+        }
+        if let Some(height) = get_f32_from_value(&data, "height") {
+            self.height = height;
+        }
+        if let Some(x) = get_f32_from_value(&data, "x") {
+            self.x = x;
+        }
+        if let Some(y) = get_f32_from_value(&data, "y") {
+            self.y = y;
+        }
+        if let Some(font_family) = get_string_from_value(&data, "font_family") {
+            self.font_family = font_family;
+        }
+        if let Some(background_color) = get_color_from_value(&data, "background_color") {
+            self.background_color = background_color;
+        }
+        if let Some(title_text) = get_string_from_value(&data, "title_text") {
+            self.title_text = title_text;
+        }
+        if let Some(title_font_size) = get_f32_from_value(&data, "title_font_size") {
+            self.title_font_size = title_font_size;
+        }
+        if let Some(title_font_color) = get_color_from_value(&data, "title_font_color") {
+            self.title_font_color = title_font_color;
+        }
+        if let Some(title_font_weight) = get_string_from_value(&data, "title_font_weight") {
+            self.title_font_weight = Some(title_font_weight);
+        }
+        if let Some(value) = get_f32_from_value(&data, "value") {
+            self.value = value;
+        }
+        if let Some(half) = get_bool_from_value(&data, "half") {
+            self.half = half;
+        }
+        if let Some(stroke_width) = get_f32_from_value(&data, "stroke_width") {
+            self.stroke_width = stroke_width;
+        }
+        if let Some(track_color) = get_color_from_value(&data, "track_color") {
+            self.track_color = track_color;
+        }
+        if let Some(progress_color) = get_color_from_value(&data, "progress_color") {
+            self.progress_color = progress_color;
+        }
+        if let Some(round_cap) = get_bool_from_value(&data, "round_cap") {
+            self.round_cap = round_cap;
+        }
+        if let Some(label_text) = get_string_from_value(&data, "label_text") {
+            self.label_text = Some(label_text);
+        }
+        if let Some(label_font_size) = get_f32_from_value(&data, "label_font_size") {
+            self.label_font_size = label_font_size;
+        }
+        if let Some(label_font_color) = get_color_from_value(&data, "label_font_color") {
+            self.label_font_color = label_font_color;
+        }
+        if let Some(label_font_weight) = get_string_from_value(&data, "label_font_weight") {
+            self.label_font_weight = Some(label_font_weight);
+        }
+        Ok(data)
+    }
+    /// Creates a progress chart from json.
+    pub fn from_json(data: &str) -> canvas::Result<ProgressChart> {
+        let mut p = ProgressChart::new_with_theme(0.0, "");
+        p.fill_option(data)?;
+        Ok(p)
+    }
+    /// Creates a progress chart with custom theme.
+    pub fn new_with_theme(value: f32, theme: &str) -> ProgressChart {
+        let mut p = ProgressChart {
+            value,
+            width: 200.0,
+            height: 200.0,
+            stroke_width: 20.0,
+            round_cap: true,
+            label_font_size: 28.0,
+            label_font_weight: Some("bold".to_string()),
+            ..Default::default()
+        };
+        p.fill_theme(get_theme(theme));
+        p
+    }
+    fn fill_theme(&mut self, t: std::sync::Arc<Theme>) {
+        self.font_family.clone_from(&t.font_family);
+        self.background_color = t.background_color;
+
+        self.title_font_color = t.title_font_color;
+        self.title_font_size = t.title_font_size;
+        self.title_font_weight.clone_from(&t.title_font_weight);
+
+        self.track_color = t.grid_stroke_color;
+        self.progress_color = get_color(&t.series_colors, 0);
+        self.label_font_color = t.title_font_color;
+
+        if let Some(font_family) = &get_default_option().font_family {
+            self.font_family.clone_from(font_family);
+        }
+    }
+    /// Creates a progress chart with default theme.
+    pub fn new(value: f32) -> ProgressChart {
+        ProgressChart::new_with_theme(value, &get_default_theme_name())
+    }
+    /// Converts progress chart to svg.
+    pub fn svg(&self) -> canvas::Result<String> {
+        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        c.rect(Rect {
+            fill: Some(self.background_color),
+            left: 0.0,
+            top: 0.0,
+            width: self.width,
+            height: self.height,
+            ..Default::default()
+        });
+
+        let mut top = 0.0;
+        if !self.title_text.is_empty() {
+            let title_box = measure_text_width_family(
+                &self.font_family,
+                self.title_font_size,
+                &self.title_text,
+            )?;
+            c.text(Text {
+                text: self.title_text.clone(),
+                font_family: Some(self.font_family.clone()),
+                font_size: Some(self.title_font_size),
+                font_weight: self.title_font_weight.clone(),
+                font_color: Some(self.title_font_color),
+                x: Some(self.width / 2.0),
+                y: Some(title_box.height()),
+                align: Some(Align::Center),
+                ..Default::default()
+            });
+            top = title_box.height() + 10.0;
+        }
+
+        let padding = 10.0;
+        let half_stroke_width = self.stroke_width / 2.0;
+        let height = self.height - top;
+        let value = self.value.clamp(0.0, 1.0);
+        // the angle is clockwise from the top
+        let (r, cx, cy, start_angle, sweep_angle) = if self.half {
+            let r = (self.width / 2.0).min(height) - padding - half_stroke_width;
+            let cy = top + (height + r) / 2.0;
+            (r, self.width / 2.0, cy, -90.0, 180.0)
+        } else {
+            let r = self.width.min(height) / 2.0 - padding - half_stroke_width;
+            (r, self.width / 2.0, top + height / 2.0, 0.0, 360.0)
+        };
+        if r <= 0.0 {
+            return c.svg();
+        }
+        let stroke_linecap = if self.round_cap {
+            Some("round".to_string())
+        } else {
+            None
+        };
+        // the arc path of full circle is empty, so it is drawn as circle
+        let mut draw_arc = |color: Color, end_angle: f32| {
+            if end_angle - start_angle >= 360.0 {
+                c.circle(Circle {
+                    stroke_color: Some(color),
+                    stroke_width: self.stroke_width,
+                    cx,
+                    cy,
+                    r,
+                    ..Default::default()
+                });
+            } else {
+                c.arc(Arc {
+                    stroke_color: color,
+                    stroke_width: self.stroke_width,
+                    stroke_linecap: stroke_linecap.clone(),
+                    cx,
+                    cy,
+                    r,
+                    start_angle,
+                    end_angle,
+                });
+            }
+        };
+        draw_arc(self.track_color, start_angle + sweep_angle);
+        if value > 0.0 {
+            draw_arc(self.progress_color, start_angle + sweep_angle * value);
+        }
+
+        let label_text = self
+            .label_text
+            .clone()
+            .unwrap_or_else(|| format!("{}%", format_label_float(self.value * 100.0)));
+        c.text(Text {
+            text: label_text,
+            font_family: Some(self.font_family.clone()),
+            font_size: Some(self.label_font_size),
+            font_weight: self.label_font_weight.clone(),
+            font_color: Some(self.label_font_color),
+            x: Some(cx),
+            y: Some(cy),
+            align: Some(Align::Center),
+            // the label of half circle is above the center
+            vertical_align: Some(if self.half {
+                VerticalAlign::Bottom
+            } else {
+                VerticalAlign::Middle
+            }),
+            ..Default::default()
+        });
+        c.svg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProgressChart;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn progress_chart_basic() {
+        let mut progress_chart = ProgressChart::new(0.68);
+        progress_chart.title_text = "Completion".to_string();
+        assert_eq!(
+            include_str!("../../asset/progress_chart/basic.svg"),
+            progress_chart.svg().unwrap()
+        );
+
+        progress_chart.half = true;
+        progress_chart.round_cap = false;
+        progress_chart.height = 140.0;
+        assert_eq!(
+            include_str!("../../asset/progress_chart/half.svg"),
+            progress_chart.svg().unwrap()
+        );
+    }
+}
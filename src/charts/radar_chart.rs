@@ -2,12 +2,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::canvas;
+use super::canvas::{self, ChartError};
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::locale::LocaleGuard;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme, Theme, DEFAULT_Y_AXIS_WIDTH};
+use super::theme::{
+    find_theme, get_default_theme_name, get_theme, EffectiveStyle, Theme, DEFAULT_FOOTER_FONT_SIZE,
+    DEFAULT_Y_AXIS_WIDTH,
+};
 use super::util::*;
 use super::Canvas;
 use crate::charts::measure_text_width_family;
@@ -28,6 +32,15 @@ impl From<(&str, f32)> for RadarIndicator {
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum RadarScale {
+    // each indicator is scaled to its own max value
+    #[default]
+    Indicator,
+    // all indicators are scaled to the max value of them
+    Shared,
+}
+
 fn get_radar_indicator_list_from_value(value: &serde_json::Value) -> Option<Vec<RadarIndicator>> {
     if let Some(data) = value.get("indicators") {
         if let Some(arr) = data.as_array() {
@@ -62,8 +75,15 @@ pub struct RadarChart {
     pub title_font_size: f32,
     pub title_font_color: Color,
     pub title_font_weight: Option<String>,
+    // font style of title, e.g. italic
+    pub title_font_style: Option<String>,
+    pub title_letter_spacing: Option<f32>,
+    // text decoration of title, e.g. underline
+    pub title_text_decoration: Option<String>,
     pub title_margin: Option<Box>,
     pub title_align: Align,
+    pub title_shadow: Option<Shadow>,
+    pub title_link: Option<String>,
     pub title_height: f32,
 
     // sub title
@@ -71,10 +91,23 @@ pub struct RadarChart {
     pub sub_title_font_size: f32,
     pub sub_title_font_color: Color,
     pub sub_title_font_weight: Option<String>,
+    // font style of sub title, e.g. italic
+    pub sub_title_font_style: Option<String>,
+    pub sub_title_letter_spacing: Option<f32>,
+    // text decoration of sub title, e.g. underline
+    pub sub_title_text_decoration: Option<String>,
     pub sub_title_margin: Option<Box>,
     pub sub_title_align: Align,
     pub sub_title_height: f32,
 
+    // footer
+    pub footer_text: String,
+    pub footer_font_size: f32,
+    pub footer_font_color: Color,
+    pub footer_font_weight: Option<String>,
+    pub footer_margin: Option<Box>,
+    pub footer_align: Align,
+
     // legend
     pub legend_font_size: f32,
     pub legend_font_color: Color,
@@ -83,6 +116,8 @@ pub struct RadarChart {
     pub legend_margin: Option<Box>,
     pub legend_category: LegendCategory,
     pub legend_show: Option<bool>,
+    pub legend_position: Option<Position>,
+    pub legend_order: Option<Vec<String>>,
 
     // x axis
     pub x_axis_data: Vec<String>,
@@ -95,6 +130,10 @@ pub struct RadarChart {
     pub x_axis_name_rotate: f32,
     pub x_axis_margin: Option<Box>,
     pub x_boundary_gap: Option<bool>,
+    // the range(start and end index, inclusive) of categories to render, the axes are recalculated
+    pub x_range: Option<(usize, usize)>,
+    // the overview strip of all categories below the chart, the x range is highlighted
+    pub x_range_overview: bool,
 
     // y axis
     pub y_axis_configs: Vec<YAxisConfig>,
@@ -102,6 +141,10 @@ pub struct RadarChart {
     // grid
     pub grid_stroke_color: Color,
     pub grid_stroke_width: f32,
+    // the color of grid line at zero value, it is not highlighted if not set
+    pub zero_line_color: Option<Color>,
+    // the reference line of statistic computed across all visible series
+    pub guide_line: Option<GuideLine>,
 
     // series
     pub series_stroke_width: f32,
@@ -109,13 +152,48 @@ pub struct RadarChart {
     pub series_label_font_size: f32,
     pub series_label_font_weight: Option<String>,
     pub series_label_formatter: String,
+    pub series_label_overlap: LabelOverlap,
+    pub series_shadow: Option<Shadow>,
     pub series_colors: Vec<Color>,
     pub series_symbol: Option<Symbol>,
     pub series_smooth: bool,
     pub series_fill: bool,
+    pub series_fill_opacity: Option<f32>,
+    pub max_points_per_series: Option<usize>,
 
     // indicators
     pub indicators: Vec<RadarIndicator>,
+    pub scale: RadarScale,
+    // the labels of rings, they are percentages of indicator scale
+    // and values of shared scale
+    pub ring_label_show: bool,
+    pub annotations: Vec<Annotation>,
+    // emit the stable id and class of elements, e.g. series-0-bar-3
+    pub element_ids: bool,
+    // native tooltip of elements, e.g. Mon: Email = 120
+    pub tooltip_show: bool,
+    // accessible name and description of chart
+    pub aria_label: Option<String>,
+    pub description: Option<String>,
+    pub svg_format: SVGFormat,
+    pub coordinate_precision: Option<usize>,
+    pub style_hoisting: bool,
+    pub font_embedding: FontEmbedding,
+    // emit the svg of 100% width, the height is scaled by aspect ratio
+    pub responsive: bool,
+    // mirrored layout of right-to-left locale, the y axis is on the right side
+    pub rtl: bool,
+    // locale of number and date names, e.g. de-DE
+    pub locale: Option<String>,
+    // the placeholder of empty series, the text is "No data" if not set
+    pub empty_text: Option<String>,
+    pub empty_icon: bool,
+    // the inset charts, they are rendered after the series
+    pub insets: Vec<Inset>,
+    // the emphasized series or points, the others are faded
+    pub emphasis: Option<Emphasis>,
+    // the selected category which is indicated by crosshair line and band
+    pub axis_pointer: Option<AxisPointer>,
 }
 
 impl RadarChart {
@@ -128,6 +206,15 @@ impl RadarChart {
         if let Some(indicators) = get_radar_indicator_list_from_value(&data) {
             r.indicators = indicators;
         }
+        if let Some(scale) = get_string_from_value(&data, "scale") {
+            r.scale = match scale.as_str() {
+                "shared" => RadarScale::Shared,
+                _ => RadarScale::Indicator,
+            };
+        }
+        if let Some(ring_label_show) = get_bool_from_value(&data, "ring_label_show") {
+            r.ring_label_show = ring_label_show;
+        }
         Ok(r)
     }
     /// Creates a radar chart with custom theme.
@@ -151,25 +238,48 @@ impl RadarChart {
     }
     /// Converts bar chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = self.render_empty_state()? {
+            return Ok(svg);
+        }
+        let _locale = LocaleGuard::new(self.locale.as_deref());
         if self.indicators.len() < 3 {
             return Err(canvas::Error::Params {
                 message: "The count of indicator should be >= 3".to_string(),
             });
         }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        c.aria_label = self.aria_label.clone();
+        c.description = self.description.clone();
+        c.svg_format = self.svg_format.clone();
+        c.coordinate_precision = self.coordinate_precision;
+        c.style_hoisting = self.style_hoisting;
+        c.font_embedding = self.font_embedding.clone();
+        c.responsive = self.responsive;
 
         self.render_background(c.child(Box::default()));
         c.margin = self.margin.clone();
 
         let title_height = self.render_title(c.child(Box::default()));
 
-        let legend_height = self.render_legend(c.child(Box::default()));
+        let footer_height = self.render_footer(c.child(Box::default()));
+
+        let legend_box = self.render_legend(c.child(Box {
+            bottom: footer_height,
+            ..Default::default()
+        }));
         // get the max height of title and legend
-        let axis_top = if legend_height > title_height {
-            legend_height
+        let axis_top = if legend_box.top > title_height {
+            legend_box.top
         } else {
             title_height
         };
+        // the legend on the other sides and footer take the space of chart
+        c = c.child(Box {
+            left: legend_box.left,
+            right: legend_box.right,
+            bottom: legend_box.bottom + footer_height,
+            ..Default::default()
+        });
         if axis_top > 0.0 {
             c = c.child(Box {
                 top: axis_top,
@@ -178,7 +288,7 @@ impl RadarChart {
         }
 
         let mut max_values: Vec<f32> = vec![0.0; self.indicators.len()];
-        for series in self.series_list.iter() {
+        for series in self.series_list.iter().filter(|item| item.visible) {
             for (index, item) in series.data.iter().enumerate() {
                 if index < max_values.len() && *item > max_values[index] {
                     max_values[index] = *item
@@ -192,6 +302,15 @@ impl RadarChart {
                 item.max = max_values[index];
             }
         }
+        if self.scale == RadarScale::Shared {
+            let max = indicators
+                .iter()
+                .map(|item| item.max)
+                .fold(0.0_f32, f32::max);
+            for item in indicators.iter_mut() {
+                item.max = max;
+            }
+        }
 
         let offset = 40.0;
         let r = c.height() / 2.0 - offset;
@@ -214,6 +333,27 @@ impl RadarChart {
                 ..Default::default()
             });
         }
+        // the ring labels are along the first indicator
+        if self.ring_label_show {
+            for i in 1..=round_count {
+                let ratio = i as f32 / round_count as f32;
+                let text = if self.scale == RadarScale::Shared {
+                    format_label_float(indicators[0].max * ratio)
+                } else {
+                    format!("{}%", format_label_float(ratio * 100.0))
+                };
+                c.text(Text {
+                    text,
+                    font_size: Some(self.x_axis_font_size),
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(self.x_axis_font_color),
+                    x: Some(cx + 3.0),
+                    y: Some(cy - r * ratio),
+                    vertical_align: Some(VerticalAlign::Top),
+                    ..Default::default()
+                });
+            }
+        }
         for (index, item) in indicators.iter().enumerate() {
             let current_angle = angle * index as f32;
             let p = get_pie_point(cx, cy, r, current_angle);
@@ -269,14 +409,19 @@ impl RadarChart {
 
         let mut label_positions = vec![];
         for (index, series) in self.series_list.iter().enumerate() {
+            if !series.visible {
+                continue;
+            }
             let color = get_color(&self.series_colors, series.index.unwrap_or(index));
             let mut points = vec![];
             for (i, item) in indicators.iter().enumerate() {
                 if let Some(value) = series.data.get(i) {
+                    // nil value is treated as zero
+                    let value = if is_nil_value(*value) { 0.0 } else { *value };
                     let mut ir = if item.max <= 0.0 {
                         0.0
                     } else {
-                        *value / item.max * r
+                        value / item.max * r
                     };
 
                     if ir > r {
@@ -284,8 +429,7 @@ impl RadarChart {
                     }
                     let p = get_pie_point(cx, cy, ir, angle * i as f32);
                     if series.label_show {
-                        let label =
-                            format_series_value(value.to_owned(), &self.series_label_formatter);
+                        let label = format_series_value(value, &self.series_label_formatter);
                         label_positions.push((p, label));
                     }
                     points.push(p);
@@ -323,13 +467,16 @@ impl RadarChart {
             });
         }
 
+        // only the annotations of pixel coordinates are supported
+        self.render_annotations(c.child(Box::default()), &[], 0.0, 0);
+        self.render_insets(c.child(Box::default()));
         c.svg()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::RadarChart;
+    use super::{RadarChart, RadarScale};
     use crate::Series;
     use pretty_assertions::assert_eq;
 
@@ -474,4 +621,36 @@ mod tests {
             radar_chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn radar_shared_scale() {
+        let mut radar_chart = RadarChart::new(
+            vec![
+                (
+                    "Allocated Budget",
+                    vec![4200.0, 3000.0, 20000.0, 35000.0, 50000.0, 18000.0],
+                )
+                    .into(),
+                (
+                    "Actual Spending",
+                    vec![5000.0, 14000.0, 28000.0, 26000.0, 42000.0, 21000.0],
+                )
+                    .into(),
+            ],
+            vec![
+                ("Sales", 6500.0).into(),
+                ("Administration", 16000.0).into(),
+                ("Information Technology", 30000.0).into(),
+                ("Customer Support", 38000.0).into(),
+                ("Development", 52000.0).into(),
+                ("Marketing", 25000.0).into(),
+            ],
+        );
+        radar_chart.scale = RadarScale::Shared;
+        radar_chart.ring_label_show = true;
+        assert_eq!(
+            include_str!("../../asset/radar_chart/shared_scale.svg"),
+            radar_chart.svg().unwrap()
+        );
+    }
 }
@@ -2,12 +2,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::canvas;
+use super::canvas::{self, ChartError};
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::locale::LocaleGuard;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme, Theme, DEFAULT_Y_AXIS_WIDTH};
+use super::theme::{
+    find_theme, get_default_theme_name, get_theme, EffectiveStyle, Theme, DEFAULT_FOOTER_FONT_SIZE,
+    DEFAULT_Y_AXIS_WIDTH,
+};
 use super::util::*;
 use super::Canvas;
 use crate::charts::measure_text_width_family;
@@ -32,8 +36,15 @@ pub struct ScatterChart {
     pub title_font_size: f32,
     pub title_font_color: Color,
     pub title_font_weight: Option<String>,
+    // font style of title, e.g. italic
+    pub title_font_style: Option<String>,
+    pub title_letter_spacing: Option<f32>,
+    // text decoration of title, e.g. underline
+    pub title_text_decoration: Option<String>,
     pub title_margin: Option<Box>,
     pub title_align: Align,
+    pub title_shadow: Option<Shadow>,
+    pub title_link: Option<String>,
     pub title_height: f32,
 
     // sub title
@@ -41,10 +52,23 @@ pub struct ScatterChart {
     pub sub_title_font_size: f32,
     pub sub_title_font_color: Color,
     pub sub_title_font_weight: Option<String>,
+    // font style of sub title, e.g. italic
+    pub sub_title_font_style: Option<String>,
+    pub sub_title_letter_spacing: Option<f32>,
+    // text decoration of sub title, e.g. underline
+    pub sub_title_text_decoration: Option<String>,
     pub sub_title_margin: Option<Box>,
     pub sub_title_align: Align,
     pub sub_title_height: f32,
 
+    // footer
+    pub footer_text: String,
+    pub footer_font_size: f32,
+    pub footer_font_color: Color,
+    pub footer_font_weight: Option<String>,
+    pub footer_margin: Option<Box>,
+    pub footer_align: Align,
+
     // legend
     pub legend_font_size: f32,
     pub legend_font_color: Color,
@@ -53,6 +77,8 @@ pub struct ScatterChart {
     pub legend_margin: Option<Box>,
     pub legend_category: LegendCategory,
     pub legend_show: Option<bool>,
+    pub legend_position: Option<Position>,
+    pub legend_order: Option<Vec<String>>,
 
     // x axis
     pub x_axis_data: Vec<String>,
@@ -67,6 +93,10 @@ pub struct ScatterChart {
     pub x_axis_config: YAxisConfig,
     pub x_axis_hidden: bool,
     pub x_boundary_gap: Option<bool>,
+    // the range(start and end index, inclusive) of categories to render, the axes are recalculated
+    pub x_range: Option<(usize, usize)>,
+    // the overview strip of all categories below the chart, the x range is highlighted
+    pub x_range_overview: bool,
 
     // y axis
     pub y_axis_hidden: bool,
@@ -75,6 +105,10 @@ pub struct ScatterChart {
     // grid
     pub grid_stroke_color: Color,
     pub grid_stroke_width: f32,
+    // the color of grid line at zero value, it is not highlighted if not set
+    pub zero_line_color: Option<Color>,
+    // the reference line of statistic computed across all visible series
+    pub guide_line: Option<GuideLine>,
 
     // series
     pub series_stroke_width: f32,
@@ -82,13 +116,44 @@ pub struct ScatterChart {
     pub series_label_font_size: f32,
     pub series_label_font_weight: Option<String>,
     pub series_label_formatter: String,
+    pub series_label_overlap: LabelOverlap,
+    pub series_shadow: Option<Shadow>,
     pub series_colors: Vec<Color>,
     pub series_symbol: Option<Symbol>,
     pub series_smooth: bool,
     pub series_fill: bool,
+    pub series_fill_opacity: Option<f32>,
+    pub max_points_per_series: Option<usize>,
 
     // symbol
     pub series_symbol_sizes: Vec<f32>,
+    pub annotations: Vec<Annotation>,
+    // emit the stable id and class of elements, e.g. series-0-bar-3
+    pub element_ids: bool,
+    // native tooltip of elements, e.g. Mon: Email = 120
+    pub tooltip_show: bool,
+    // accessible name and description of chart
+    pub aria_label: Option<String>,
+    pub description: Option<String>,
+    pub svg_format: SVGFormat,
+    pub coordinate_precision: Option<usize>,
+    pub style_hoisting: bool,
+    pub font_embedding: FontEmbedding,
+    // emit the svg of 100% width, the height is scaled by aspect ratio
+    pub responsive: bool,
+    // mirrored layout of right-to-left locale, the y axis is on the right side
+    pub rtl: bool,
+    // locale of number and date names, e.g. de-DE
+    pub locale: Option<String>,
+    // the placeholder of empty series, the text is "No data" if not set
+    pub empty_text: Option<String>,
+    pub empty_icon: bool,
+    // the inset charts, they are rendered after the series
+    pub insets: Vec<Inset>,
+    // the emphasized series or points, the others are faded
+    pub emphasis: Option<Emphasis>,
+    // the selected category which is indicated by crosshair line and band
+    pub axis_pointer: Option<AxisPointer>,
 }
 
 impl ScatterChart {
@@ -142,7 +207,18 @@ impl ScatterChart {
     }
     /// Converts scatter chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = self.render_empty_state()? {
+            return Ok(svg);
+        }
+        let _locale = LocaleGuard::new(self.locale.as_deref());
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        c.aria_label = self.aria_label.clone();
+        c.description = self.description.clone();
+        c.svg_format = self.svg_format.clone();
+        c.coordinate_precision = self.coordinate_precision;
+        c.style_hoisting = self.style_hoisting;
+        c.font_embedding = self.font_embedding.clone();
+        c.responsive = self.responsive;
 
         self.render_background(c.child(Box::default()));
         let mut x_axis_height = self.x_axis_height;
@@ -153,19 +229,31 @@ impl ScatterChart {
 
         let title_height = self.render_title(c.child(Box::default()));
 
-        let legend_height = self.render_legend(c.child(Box::default()));
+        let footer_height = self.render_footer(c.child(Box::default()));
+
+        let legend_box = self.render_legend(c.child(Box {
+            bottom: footer_height,
+            ..Default::default()
+        }));
         // get the max height of title and legend
-        let axis_top = if legend_height > title_height {
-            legend_height
+        let axis_top = if legend_box.top > title_height {
+            legend_box.top
         } else {
             title_height
         };
+        // the legend on the other sides and footer take the space of chart
+        c = c.child(Box {
+            left: legend_box.left,
+            right: legend_box.right,
+            bottom: legend_box.bottom + footer_height,
+            ..Default::default()
+        });
 
         let y_axis_config = self.get_y_axis_config(0);
 
         let mut y_axis_data_list = vec![];
         let mut x_axis_data_list = vec![];
-        for series in self.series_list.iter() {
+        for series in self.series_list.iter().filter(|item| item.visible) {
             for (index, data) in series.data.iter().enumerate() {
                 if index % 2 == 0 {
                     x_axis_data_list.push(*data);
@@ -181,6 +269,10 @@ impl ScatterChart {
             min: y_axis_config.axis_min,
             max: y_axis_config.axis_max,
             thousands_format: false,
+            include_zero: y_axis_config.axis_include_zero,
+            symmetric: y_axis_config.axis_symmetric,
+            nice: y_axis_config.axis_nice,
+            max_significant_digits: y_axis_config.axis_max_significant_digits,
         });
         let y_axis_width = if self.y_axis_hidden {
             0.0
@@ -200,6 +292,8 @@ impl ScatterChart {
 
         let axis_height = c.height() - x_axis_height - axis_top;
         let axis_width = c.width() - y_axis_width;
+        // the y axis is on the right side of rtl layout
+        let left_width = if self.rtl { 0.0 } else { y_axis_width };
         // minus the height of top text area
         if axis_top > 0.0 {
             c = c.child(Box {
@@ -211,7 +305,7 @@ impl ScatterChart {
         // grid
         self.render_grid(
             c.child(Box {
-                left: y_axis_width,
+                left: left_width,
                 ..Default::default()
             }),
             axis_width,
@@ -219,7 +313,7 @@ impl ScatterChart {
         );
         let x_axis_width = c.width() - y_axis_width;
         c.child(Box {
-            left: y_axis_width,
+            left: left_width,
             ..Default::default()
         })
         .grid(Grid {
@@ -235,7 +329,10 @@ impl ScatterChart {
         // y axis
         if !self.y_axis_hidden {
             self.render_y_axis(
-                c.child(Box::default()),
+                c.child(Box {
+                    left: if self.rtl { axis_width } else { 0.0 },
+                    ..Default::default()
+                }),
                 y_axis_values.data.clone(),
                 axis_height,
                 y_axis_width,
@@ -249,6 +346,10 @@ impl ScatterChart {
             split_number: self.x_axis_config.axis_split_number,
             min: self.x_axis_config.axis_min,
             max: self.x_axis_config.axis_max,
+            include_zero: self.x_axis_config.axis_include_zero,
+            symmetric: self.x_axis_config.axis_symmetric,
+            nice: self.x_axis_config.axis_nice,
+            max_significant_digits: self.x_axis_config.axis_max_significant_digits,
             ..Default::default()
         });
         let x_axis_formatter = &self
@@ -262,7 +363,7 @@ impl ScatterChart {
             self.render_x_axis(
                 c.child(Box {
                     top: c.height() - x_axis_height,
-                    left: y_axis_width,
+                    left: left_width,
                     ..Default::default()
                 }),
                 x_axis_values
@@ -276,25 +377,32 @@ impl ScatterChart {
 
         // render dot
         let mut content_canvas = c.child(Box {
-            left: y_axis_width,
+            left: left_width,
             ..Default::default()
         });
         let default_symbol_size = 10.0_f32;
         for (index, series) in self.series_list.iter().enumerate() {
+            if !series.visible {
+                continue;
+            }
             let mut color = get_color(&self.series_colors, series.index.unwrap_or(index));
             let symbol_size = self
                 .series_symbol_sizes
                 .get(series.index.unwrap_or(index))
                 .unwrap_or(&default_symbol_size);
             color = color.with_alpha(210);
-            for chunk in series.data.chunks(2) {
+            for (i, chunk) in series.data.chunks(2).enumerate() {
                 if chunk.len() != 2 {
                     continue;
                 }
                 let x = content_width - x_axis_values.get_offset_height(chunk[0], content_width);
                 let y = y_axis_values.get_offset_height(chunk[1], content_height);
+                let fill = series
+                    .get_item_color(i)
+                    .map(|item_color| item_color.with_alpha(210))
+                    .unwrap_or(color);
                 content_canvas.circle(Circle {
-                    fill: Some(color),
+                    fill: Some(fill),
                     cx: x,
                     cy: y,
                     r: *symbol_size,
@@ -303,6 +411,9 @@ impl ScatterChart {
             }
         }
 
+        // only the annotations of pixel coordinates are supported
+        self.render_annotations(c.child(Box::default()), &[], 0.0, 0);
+        self.render_insets(c.child(Box::default()));
         c.svg()
     }
 }
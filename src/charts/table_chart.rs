@@ -8,9 +8,9 @@ use super::common::*;
 use super::component::*;
 use super::font::text_wrap_fit;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme, Theme};
+use super::theme::{get_default_option, get_default_theme_name, get_theme, Theme};
 use super::util::*;
-use super::Canvas;
+use super::{BarChart, Canvas, LineChart};
 use crate::charts::measure_text_width_family;
 use std::sync::Arc;
 
@@ -22,6 +22,17 @@ pub struct TableCellStyle {
     pub indexes: Vec<usize>,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct TableSparkline {
+    // index of the column, the body cells of it are rendered as sparkline
+    pub column: usize,
+    // it is line sparkline if not set
+    pub category: Option<SeriesCategory>,
+    // data of each body row
+    pub data: Vec<Vec<f32>>,
+    pub color: Option<Color>,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct TableChart {
     pub width: f32,
@@ -69,6 +80,8 @@ pub struct TableChart {
     pub body_background_colors: Vec<Color>,
 
     pub cell_styles: Vec<TableCellStyle>,
+    // the columns of inline trend, the text of cells is not rendered
+    pub sparklines: Vec<TableSparkline>,
 }
 
 impl TableChart {
@@ -92,6 +105,9 @@ impl TableChart {
         if let Some(font_family) = get_string_from_value(&data, "font_family") {
             self.font_family = font_family;
         }
+        if let Some(background_color) = get_color_from_value(&data, "background_color") {
+            self.background_color = background_color;
+        }
         if let Some(title_text) = get_string_from_value(&data, "title_text") {
             self.title_text = title_text;
         }
@@ -160,6 +176,37 @@ impl TableChart {
             }
         }
 
+        if let Some(data) = data.get("sparklines") {
+            if let Some(arr) = data.as_array() {
+                let mut sparklines = vec![];
+                for item in arr.iter() {
+                    let mut sparkline = TableSparkline {
+                        column: get_usize_from_value(item, "column").unwrap_or_default(),
+                        category: get_series_category_from_value(item, "category"),
+                        color: get_color_from_value(item, "color"),
+                        ..Default::default()
+                    };
+                    if let Some(rows) = item.get("data").and_then(|value| value.as_array()) {
+                        sparkline.data = rows
+                            .iter()
+                            .map(|row| {
+                                row.as_array()
+                                    .map(|values| {
+                                        values
+                                            .iter()
+                                            .filter_map(|value| value.as_f64())
+                                            .map(|value| value as f32)
+                                            .collect()
+                                    })
+                                    .unwrap_or_default()
+                            })
+                            .collect();
+                    }
+                    sparklines.push(sparkline);
+                }
+                self.sparklines = sparklines;
+            }
+        }
         if let Some(data) = data.get("data") {
             if let Some(arr) = data.as_array() {
                 let mut data_list = vec![];
@@ -273,6 +320,14 @@ impl TableChart {
         self.body_font_color = t.sub_title_font_color;
         self.body_background_colors.clone_from(&t.table_body_colors);
         self.border_color = t.table_border_color;
+
+        let option = get_default_option();
+        if let Some(font_family) = &option.font_family {
+            self.font_family.clone_from(font_family);
+        }
+        if let Some(width) = option.width {
+            self.width = width;
+        }
     }
     /// Creates a table chart with default theme.
     pub fn new(data: Vec<Vec<String>>) -> TableChart {
@@ -336,6 +391,43 @@ impl TableChart {
         }
         title_height
     }
+    /// Renders the sparkline of body row, it returns none if the data of row is empty.
+    fn render_sparkline(
+        &self,
+        sparkline: &TableSparkline,
+        row: usize,
+        area: &Box,
+    ) -> canvas::Result<Option<String>> {
+        let Some(data) = sparkline.data.get(row).filter(|data| !data.is_empty()) else {
+            return Ok(None);
+        };
+        let series_list = vec![Series::new("".to_string(), data.clone())];
+        let svg = if sparkline.category == Some(SeriesCategory::Bar) {
+            let mut chart = BarChart::new(series_list, vec![]);
+            chart.sparkline(false, false);
+            (chart.width, chart.height) = (area.width(), area.height());
+            chart.margin = Box::default();
+            chart.background_color = Color::transparent();
+            if let Some(color) = sparkline.color {
+                chart.series_colors = vec![color];
+            }
+            chart.svg()?
+        } else {
+            let mut chart = LineChart::new(series_list, vec![]);
+            chart.sparkline(true, false);
+            (chart.width, chart.height) = (area.width(), area.height());
+            chart.background_color = Color::transparent();
+            chart.series_stroke_width = 1.5;
+            if let Some(color) = sparkline.color {
+                chart.series_colors = vec![color];
+                chart.annotations.iter_mut().for_each(|item| {
+                    item.color = Some(color);
+                });
+            }
+            chart.svg()?
+        };
+        Ok(Some(svg))
+    }
     /// Converts bar chart to svg.
     pub fn svg(&mut self) -> canvas::Result<String> {
         if self.data.is_empty() {
@@ -470,7 +562,11 @@ impl TableChart {
                 font_weight.clone_from(&self.header_font_weight);
                 self.header_background_color
             } else {
-                self.body_background_colors[(i - 1) % body_background_color_count]
+                // the background of chart is used if the body colors are not set
+                self.body_background_colors
+                    .get((i - 1) % body_background_color_count.max(1))
+                    .copied()
+                    .unwrap_or(self.background_color)
             };
 
             let row_padding = if is_header {
@@ -530,6 +626,25 @@ impl TableChart {
                     }
                 }
 
+                let sparkline = self
+                    .sparklines
+                    .iter()
+                    .find(|item| !is_header && item.column == j);
+                if let Some(sparkline) = sparkline {
+                    let area = Box {
+                        left: left + row_padding.left,
+                        top: top + row_padding.top,
+                        right: left + span_width - row_padding.right,
+                        bottom: top + row_height - row_padding.bottom,
+                    };
+                    if let Some(svg) = self.render_sparkline(sparkline, i - 1, &area)? {
+                        c.inset(Inset::new(area, svg));
+                    }
+                    right += span_width;
+                    left = right;
+                    continue;
+                }
+
                 for (index, item) in content_list.iter().enumerate() {
                     let mut dx = None;
                     if let Ok(measurement) =
@@ -586,8 +701,8 @@ impl TableChart {
 
 #[cfg(test)]
 mod tests {
-    use super::{TableCellStyle, TableChart};
-    use crate::{Align, THEME_ANT, THEME_DARK, THEME_GRAFANA};
+    use super::{TableCellStyle, TableChart, TableSparkline};
+    use crate::{Align, SeriesCategory, THEME_ANT, THEME_DARK, THEME_GRAFANA};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -803,4 +918,69 @@ mod tests {
             table_chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn table_without_body_colors() {
+        let mut table_chart = TableChart::from_json(
+            r##"{
+                "background_color": "#F5F5F5",
+                "data": [
+                    ["Name", "Price"],
+                    ["Datadog Inc", "97.32"],
+                    ["Hashicorp Inc", "28.66"]
+                ]
+            }"##,
+        )
+        .unwrap();
+        // the background of chart is used for the body rows
+        table_chart.body_background_colors = vec![];
+        assert_eq!(
+            include_str!("../../asset/table_chart/without_body_colors.svg"),
+            table_chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn table_sparkline() {
+        let mut table_chart = TableChart::new(vec![
+            vec![
+                "Name".to_string(),
+                "Price".to_string(),
+                "Trend".to_string(),
+                "Volume".to_string(),
+            ],
+            vec![
+                "Datadog Inc".to_string(),
+                "97.32".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ],
+            vec![
+                "Gitlab Inc".to_string(),
+                "51.63".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ],
+        ]);
+        table_chart.sparklines = vec![
+            TableSparkline {
+                column: 2,
+                data: vec![
+                    vec![98.0, 102.0, 101.0, 99.0, 97.3],
+                    vec![46.0, 48.0, 47.5, 50.0, 51.6],
+                ],
+                ..Default::default()
+            },
+            TableSparkline {
+                column: 3,
+                category: Some(SeriesCategory::Bar),
+                data: vec![vec![12.0, 18.0, 9.0, 15.0], vec![6.0, 8.0, 12.0, 10.0]],
+                color: Some((145, 204, 117).into()),
+            },
+        ];
+        assert_eq!(
+            include_str!("../../asset/table_chart/sparkline.svg"),
+            table_chart.svg().unwrap()
+        );
+    }
 }
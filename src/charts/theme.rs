@@ -2,9 +2,14 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use super::canvas;
 use super::color::Color;
 use super::common::Align;
 use super::font::DEFAULT_FONT_FAMILY;
+use super::params::{
+    get_align_from_value, get_bool_from_value, get_color_from_value, get_color_slice_from_value,
+    get_f32_from_value, get_margin_from_value, get_string_from_value, get_usize_from_value,
+};
 use super::util::Box;
 use ahash::AHashMap;
 use arc_swap::ArcSwap;
@@ -25,6 +30,7 @@ pub static DEFAULT_Y_AXIS_WIDTH: f32 = 40.0;
 pub static DEFAULT_Y_AXIS_NAME_GAP: f32 = 8.0;
 pub static DEFAULT_Y_AXIS_SPLIT_NUMBER: usize = 6;
 pub static DEFAULT_FONT_SIZE: f32 = 14.0;
+pub static DEFAULT_FOOTER_FONT_SIZE: f32 = 12.0;
 
 pub static DEFAULT_SERIES_STROKE_WIDTH: f32 = 2.0;
 
@@ -95,6 +101,235 @@ pub struct Theme {
     pub table_border_color: Color,
 }
 
+impl Theme {
+    /// Creates a theme from json value, the base theme is light if not set.
+    fn from_value(value: &serde_json::Value) -> Theme {
+        let base = get_string_from_value(value, "base").unwrap_or_default();
+        let mut theme = Theme::from_base(&base);
+        if let Some(is_light) = get_bool_from_value(value, "is_light") {
+            theme.is_light = is_light;
+        }
+        if let Some(font_family) = get_string_from_value(value, "font_family") {
+            theme.font_family = font_family;
+        }
+        if let Some(margin) = get_margin_from_value(value, "margin") {
+            theme.margin = margin;
+        }
+        if let Some(width) = get_f32_from_value(value, "width") {
+            theme.width = width;
+        }
+        if let Some(height) = get_f32_from_value(value, "height") {
+            theme.height = height;
+        }
+        if let Some(background_color) = get_color_from_value(value, "background_color") {
+            theme.background_color = background_color;
+        }
+        if let Some(title_font_size) = get_f32_from_value(value, "title_font_size") {
+            theme.title_font_size = title_font_size;
+        }
+        if let Some(title_font_color) = get_color_from_value(value, "title_font_color") {
+            theme.title_font_color = title_font_color;
+        }
+        if let Some(title_font_weight) = get_string_from_value(value, "title_font_weight") {
+            theme.title_font_weight = Some(title_font_weight);
+        }
+        if let Some(title_margin) = get_margin_from_value(value, "title_margin") {
+            theme.title_margin = Some(title_margin);
+        }
+        if let Some(title_align) = get_align_from_value(value, "title_align") {
+            theme.title_align = title_align;
+        }
+        if let Some(title_height) = get_f32_from_value(value, "title_height") {
+            theme.title_height = title_height;
+        }
+        if let Some(sub_title_font_size) = get_f32_from_value(value, "sub_title_font_size") {
+            theme.sub_title_font_size = sub_title_font_size;
+        }
+        if let Some(sub_title_font_color) = get_color_from_value(value, "sub_title_font_color") {
+            theme.sub_title_font_color = sub_title_font_color;
+        }
+        if let Some(sub_title_margin) = get_margin_from_value(value, "sub_title_margin") {
+            theme.sub_title_margin = Some(sub_title_margin);
+        }
+        if let Some(sub_title_align) = get_align_from_value(value, "sub_title_align") {
+            theme.sub_title_align = sub_title_align;
+        }
+        if let Some(sub_title_height) = get_f32_from_value(value, "sub_title_height") {
+            theme.sub_title_height = sub_title_height;
+        }
+        if let Some(legend_font_size) = get_f32_from_value(value, "legend_font_size") {
+            theme.legend_font_size = legend_font_size;
+        }
+        if let Some(legend_font_color) = get_color_from_value(value, "legend_font_color") {
+            theme.legend_font_color = legend_font_color;
+        }
+        if let Some(legend_align) = get_align_from_value(value, "legend_align") {
+            theme.legend_align = legend_align;
+        }
+        if let Some(legend_margin) = get_margin_from_value(value, "legend_margin") {
+            theme.legend_margin = Some(legend_margin);
+        }
+        if let Some(x_axis_font_size) = get_f32_from_value(value, "x_axis_font_size") {
+            theme.x_axis_font_size = x_axis_font_size;
+        }
+        if let Some(x_axis_stroke_color) = get_color_from_value(value, "x_axis_stroke_color") {
+            theme.x_axis_stroke_color = x_axis_stroke_color;
+        }
+        if let Some(x_axis_font_color) = get_color_from_value(value, "x_axis_font_color") {
+            theme.x_axis_font_color = x_axis_font_color;
+        }
+        if let Some(x_axis_name_gap) = get_f32_from_value(value, "x_axis_name_gap") {
+            theme.x_axis_name_gap = x_axis_name_gap;
+        }
+        if let Some(x_axis_height) = get_f32_from_value(value, "x_axis_height") {
+            theme.x_axis_height = x_axis_height;
+        }
+        if let Some(y_axis_font_size) = get_f32_from_value(value, "y_axis_font_size") {
+            theme.y_axis_font_size = y_axis_font_size;
+        }
+        if let Some(y_axis_font_color) = get_color_from_value(value, "y_axis_font_color") {
+            theme.y_axis_font_color = y_axis_font_color;
+        }
+        if let Some(y_axis_stroke_color) = get_color_from_value(value, "y_axis_stroke_color") {
+            theme.y_axis_stroke_color = y_axis_stroke_color;
+        }
+        if let Some(y_axis_split_number) = get_usize_from_value(value, "y_axis_split_number") {
+            theme.y_axis_split_number = y_axis_split_number;
+        }
+        if let Some(y_axis_name_gap) = get_f32_from_value(value, "y_axis_name_gap") {
+            theme.y_axis_name_gap = y_axis_name_gap;
+        }
+        if let Some(grid_stroke_color) = get_color_from_value(value, "grid_stroke_color") {
+            theme.grid_stroke_color = grid_stroke_color;
+        }
+        if let Some(grid_stroke_width) = get_f32_from_value(value, "grid_stroke_width") {
+            theme.grid_stroke_width = grid_stroke_width;
+        }
+        if let Some(series_stroke_width) = get_f32_from_value(value, "series_stroke_width") {
+            theme.series_stroke_width = series_stroke_width;
+        }
+        if let Some(series_label_font_size) = get_f32_from_value(value, "series_label_font_size") {
+            theme.series_label_font_size = series_label_font_size;
+        }
+        if let Some(series_label_font_color) =
+            get_color_from_value(value, "series_label_font_color")
+        {
+            theme.series_label_font_color = series_label_font_color;
+        }
+        if let Some(series_colors) = get_color_slice_from_value(value, "series_colors") {
+            theme.series_colors = series_colors;
+        }
+        if let Some(table_header_color) = get_color_from_value(value, "table_header_color") {
+            theme.table_header_color = table_header_color;
+        }
+        if let Some(table_body_colors) = get_color_slice_from_value(value, "table_body_colors") {
+            theme.table_body_colors = table_body_colors;
+        }
+        if let Some(table_border_color) = get_color_from_value(value, "table_border_color") {
+            theme.table_border_color = table_border_color;
+        }
+        theme
+    }
+    /// Creates a theme from json, the fields not set are inherited from the base theme,
+    /// e.g. {"base": "dark", "series_colors": ["#FF6600"]}.
+    pub fn from_json(data: &str) -> canvas::Result<Theme> {
+        let value: serde_json::Value = serde_json::from_str(data)?;
+        Ok(Theme::from_value(&value))
+    }
+    /// Creates a theme from toml, the fields are the same as json.
+    pub fn from_toml(data: &str) -> canvas::Result<Theme> {
+        let value: serde_json::Value = toml::from_str(data)?;
+        Ok(Theme::from_value(&value))
+    }
+    /// Creates a theme based on the registered theme, e.g. light or dark.
+    pub fn from_base(name: &str) -> Theme {
+        get_theme(name).as_ref().clone()
+    }
+    /// Sets the font family of theme.
+    pub fn with_font_family(mut self, font_family: &str) -> Self {
+        self.font_family = font_family.to_string();
+        self
+    }
+    /// Sets the background color of theme, it also changes the light mode.
+    pub fn with_background_color(mut self, color: Color) -> Self {
+        self.background_color = color;
+        self.is_light = color.is_light();
+        self
+    }
+    /// Sets the font color of title, legend, axis and series label.
+    pub fn with_font_color(mut self, color: Color) -> Self {
+        self.title_font_color = color;
+        self.sub_title_font_color = color;
+        self.legend_font_color = color;
+        self.x_axis_font_color = color;
+        self.y_axis_font_color = color;
+        self.series_label_font_color = color;
+        self
+    }
+    /// Sets the stroke color of x and y axis.
+    pub fn with_axis_stroke_color(mut self, color: Color) -> Self {
+        self.x_axis_stroke_color = color;
+        self.y_axis_stroke_color = color;
+        self
+    }
+    /// Sets the stroke color and width of grid.
+    pub fn with_grid_style(mut self, color: Color, width: f32) -> Self {
+        self.grid_stroke_color = color;
+        self.grid_stroke_width = width;
+        self
+    }
+    /// Sets the colors of series.
+    pub fn with_series_colors(mut self, colors: Vec<Color>) -> Self {
+        self.series_colors = colors;
+        self
+    }
+    /// Inverts the lightness of background, font, axis, grid and table colors,
+    /// the series colors are preserved.
+    fn invert(&self) -> Theme {
+        let invert = |color: Color| {
+            let (h, s, l) = color.hsl();
+            Color::from_hsl(h, s, 1.0 - l, color.a)
+        };
+        let mut theme = self.clone();
+        theme.is_light = !self.is_light;
+        theme.background_color = invert(self.background_color);
+        theme.title_font_color = invert(self.title_font_color);
+        theme.sub_title_font_color = invert(self.sub_title_font_color);
+        theme.legend_font_color = invert(self.legend_font_color);
+        theme.x_axis_stroke_color = invert(self.x_axis_stroke_color);
+        theme.x_axis_font_color = invert(self.x_axis_font_color);
+        theme.y_axis_font_color = invert(self.y_axis_font_color);
+        theme.y_axis_stroke_color = invert(self.y_axis_stroke_color);
+        theme.grid_stroke_color = invert(self.grid_stroke_color);
+        theme.series_label_font_color = invert(self.series_label_font_color);
+        theme.table_header_color = invert(self.table_header_color);
+        theme.table_body_colors = self.table_body_colors.iter().map(|c| invert(*c)).collect();
+        theme.table_border_color = invert(self.table_border_color);
+        theme
+    }
+    /// Derives the dark variant of theme, it returns the same theme if it is dark.
+    pub fn to_dark(&self) -> Theme {
+        if self.is_light {
+            self.invert()
+        } else {
+            self.clone()
+        }
+    }
+    /// Derives the light variant of theme, it returns the same theme if it is light.
+    pub fn to_light(&self) -> Theme {
+        if self.is_light {
+            self.clone()
+        } else {
+            self.invert()
+        }
+    }
+    /// Sets the margin of chart.
+    pub fn with_margin(mut self, margin: Box) -> Self {
+        self.margin = margin;
+        self
+    }
+}
+
 static LIGHT_THEME: Lazy<Theme> = Lazy::new(|| {
     let x_axis_color = (110, 112, 121).into();
     let font_color: Color = (70, 70, 70).into();
@@ -756,6 +991,54 @@ static SHADCN_THEME: Lazy<Theme> = Lazy::new(|| {
     }
 });
 
+/// Resolves the style of chart by the cascade:
+/// explicit value → default option → theme → built-in light theme.
+/// The unset value is `None`, so the explicit zero size or transparent color is kept.
+pub struct EffectiveStyle {
+    option: Arc<DefaultOption>,
+    theme: Arc<Theme>,
+}
+
+impl EffectiveStyle {
+    /// Creates a style resolver of the theme, the built-in light theme is used if it is none.
+    pub fn new(theme: Option<Arc<Theme>>) -> Self {
+        EffectiveStyle {
+            option: get_default_option(),
+            theme: theme.unwrap_or_else(|| Arc::new(LIGHT_THEME.clone())),
+        }
+    }
+    /// Gets the resolved theme.
+    pub fn theme(&self) -> Arc<Theme> {
+        self.theme.clone()
+    }
+    /// Resolves the size value of theme, e.g. font size.
+    pub fn size(&self, value: Option<f32>, get: fn(&Theme) -> f32) -> f32 {
+        value.unwrap_or_else(|| get(&self.theme))
+    }
+    /// Resolves the color value of theme.
+    pub fn color(&self, value: Option<Color>, get: fn(&Theme) -> Color) -> Color {
+        value.unwrap_or_else(|| get(&self.theme))
+    }
+    /// Resolves the width, the default option is used before theme.
+    pub fn width(&self, value: Option<f32>) -> f32 {
+        value.or(self.option.width).unwrap_or(self.theme.width)
+    }
+    /// Resolves the height, the default option is used before theme.
+    pub fn height(&self, value: Option<f32>) -> f32 {
+        value.or(self.option.height).unwrap_or(self.theme.height)
+    }
+    /// Resolves the font family, the default option is used before theme.
+    pub fn font_family(&self, value: Option<String>) -> String {
+        value
+            .or_else(|| self.option.font_family.clone())
+            .unwrap_or_else(|| self.theme.font_family.clone())
+    }
+    /// Resolves the series colors.
+    pub fn series_colors(&self, value: Option<Vec<Color>>) -> Vec<Color> {
+        value.unwrap_or_else(|| self.theme.series_colors.clone())
+    }
+}
+
 type Themes = AHashMap<String, Arc<Theme>>;
 static THEME_MAP: Lazy<ArcSwap<Themes>> = Lazy::new(|| {
     let mut m = AHashMap::new();
@@ -772,8 +1055,14 @@ static THEME_MAP: Lazy<ArcSwap<Themes>> = Lazy::new(|| {
     ArcSwap::from_pointee(m)
 });
 
-/// Add theme of charts
+/// Add theme of charts, it is the same as register_theme.
 pub fn add_theme(name: &str, data: Theme) {
+    register_theme(name, data)
+}
+
+/// Registers the custom theme, it can be referenced by name across all chart types.
+/// The theme of the same name is replaced.
+pub fn register_theme(name: &str, data: Theme) {
     let mut m: Themes = AHashMap::new();
     for (name, data) in THEME_MAP.load().iter() {
         m.insert(name.to_string(), data.clone());
@@ -782,13 +1071,91 @@ pub fn add_theme(name: &str, data: Theme) {
     THEME_MAP.store(Arc::new(m))
 }
 
-/// Get the theme of charts
-pub fn get_theme(theme: &str) -> Arc<Theme> {
-    if let Some(theme) = THEME_MAP.load().get(theme) {
-        theme.clone()
-    } else {
-        Arc::new(LIGHT_THEME.clone())
+/// Loads the theme files(.json or .toml) of directory and registers them,
+/// the name of theme is the file stem. Returns the names of registered themes.
+pub fn load_theme_dir(dir: &str) -> canvas::Result<Vec<String>> {
+    let io_error = |file: &str| {
+        let file = file.to_string();
+        move |source| canvas::Error::Io { file, source }
+    };
+    let mut paths = vec![];
+    for entry in std::fs::read_dir(dir).map_err(io_error(dir))? {
+        paths.push(entry.map_err(io_error(dir))?.path());
+    }
+    paths.sort();
+
+    let mut names = vec![];
+    for path in paths {
+        let file = path.to_string_lossy().to_string();
+        let Some(name) = path
+            .file_stem()
+            .map(|item| item.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        let extension = path
+            .extension()
+            .map(|item| item.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if extension != "json" && extension != "toml" {
+            continue;
+        }
+        let data = std::fs::read_to_string(&path).map_err(io_error(&file))?;
+        let theme = if extension == "json" {
+            Theme::from_json(&data)?
+        } else {
+            Theme::from_toml(&data)?
+        };
+        register_theme(&name, theme);
+        names.push(name);
     }
+    Ok(names)
+}
+
+/// Process-wide default option of charts, the fields set are applied to
+/// the charts when they are created, so they don't need to be set on every chart.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DefaultOption {
+    // the theme is used if the theme of chart is not set or not found
+    pub theme: Option<String>,
+    pub font_family: Option<String>,
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+}
+
+static DEFAULT_OPTION: Lazy<ArcSwap<DefaultOption>> =
+    Lazy::new(|| ArcSwap::from_pointee(DefaultOption::default()));
+
+/// Sets the process-wide default option of charts.
+pub fn set_default_option(option: DefaultOption) {
+    DEFAULT_OPTION.store(Arc::new(option))
+}
+
+/// Gets the process-wide default option of charts.
+pub fn get_default_option() -> Arc<DefaultOption> {
+    DEFAULT_OPTION.load_full()
+}
+
+/// Finds the registered theme, the default theme of option is used if the theme is not found.
+/// The theme is returned as registered, the default option of font family and size
+/// is applied at chart construction time.
+pub fn find_theme(theme: &str) -> Option<Arc<Theme>> {
+    let themes = THEME_MAP.load();
+    themes
+        .get(theme)
+        .or_else(|| {
+            DEFAULT_OPTION
+                .load()
+                .theme
+                .as_ref()
+                .and_then(|default_theme| themes.get(default_theme))
+        })
+        .cloned()
+}
+
+/// Get the theme of charts, it is light if the theme is not found.
+pub fn get_theme(theme: &str) -> Arc<Theme> {
+    find_theme(theme).unwrap_or_else(|| Arc::new(LIGHT_THEME.clone()))
 }
 
 /// List the theme name
@@ -800,7 +1167,11 @@ pub fn list_theme_name() -> Vec<String> {
     themes
 }
 
-/// Get default theme
+/// Get default theme, it is light if the default option is not set.
 pub fn get_default_theme_name() -> String {
-    LIGHT_THEME_NAME.to_string()
+    DEFAULT_OPTION
+        .load()
+        .theme
+        .clone()
+        .unwrap_or_else(|| LIGHT_THEME_NAME.to_string())
 }
@@ -2,12 +2,21 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use super::locale::{format_date_name, localize_number};
+use crate::{Series, TrendType};
 use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use substring::Substring;
 
 pub static NIL_VALUE: f32 = f32::MIN;
 
+/// Returns true if the value is nil, it can be NIL_VALUE or NaN.
+pub fn is_nil_value(value: f32) -> bool {
+    value == NIL_VALUE || value.is_nan()
+}
+
 pub(crate) static THOUSANDS_FORMAT_LABEL: &str = "{t}";
 pub(crate) static SERIES_NAME_FORMAT_LABEL: &str = "{a}";
 pub(crate) static CATEGORY_NAME_FORMAT_LABEL: &str = "{b}";
@@ -38,6 +47,14 @@ pub struct Box {
     pub right: f32,
     pub bottom: f32,
 }
+// The float is hashed by its bits.
+impl Hash for Box {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for value in [self.left, self.top, self.right, self.bottom] {
+            value.to_bits().hash(state);
+        }
+    }
+}
 impl Box {
     pub fn width(&self) -> f32 {
         self.right - self.left
@@ -108,9 +125,9 @@ impl From<(f32, f32, f32, f32)> for Box {
 
 pub(crate) fn format_series_value(value: f32, formatter: &str) -> String {
     if formatter == THOUSANDS_FORMAT_LABEL {
-        return thousands_format_float(value);
+        return localize_number(thousands_format_float(value));
     }
-    format_float(value)
+    format_label_float(value)
 }
 
 pub(crate) fn thousands_format_float(value: f32) -> String {
@@ -135,14 +152,125 @@ pub(crate) fn thousands_format_float(value: f32) -> String {
     arr.join(",")
 }
 
+/// Formats the float of label with the separators of current locale.
+pub(crate) fn format_label_float(value: f32) -> String {
+    localize_number(format_float(value))
+}
+
 pub(crate) fn format_float(value: f32) -> String {
     let str = format!("{:.1}", value);
     if str.ends_with(".0") {
-        return str.substring(0, str.len() - 2).to_string();
+        return normalize_zero(str.substring(0, str.len() - 2).to_string());
     }
     str
 }
 
+// The negative value rounded to zero is formatted as 0,
+// so the output is the same for the tiny float errors.
+fn normalize_zero(value: String) -> String {
+    if value == "-0" {
+        return "0".to_string();
+    }
+    value
+}
+
+thread_local! {
+    static COORDINATE_PRECISION: Cell<usize> = const { Cell::new(1) };
+}
+
+// The significant digits of f32, the digits after them are float errors of arithmetic.
+const F32_SIGNIFICANT_DIGITS: i32 = 6;
+
+/// Rounds the f64 value to the significant digits, the tie is rounded away from zero.
+fn round_significant_f64(value: f64, digits: i32) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
+    }
+    let scale = 10.0_f64.powi(digits - 1 - value.abs().log10().floor() as i32);
+    (value * scale).round() / scale
+}
+
+/// Converts the coordinate of chart(f32) to the value of svg output(f64).
+/// The charts and components are computed in f32, it is the only conversion to f64,
+/// the float errors of f32 are removed, e.g. 12.249999 and 12.250001 are both 12.25.
+pub(crate) fn to_output_coordinate(value: f32) -> f64 {
+    round_significant_f64(value as f64, F32_SIGNIFICANT_DIGITS)
+}
+
+/// Formats the coordinate of svg, the precision is one decimal by default.
+/// The coordinate is rounded in f64, so the sub-pixel position is stable.
+pub(crate) fn format_coordinate(value: f32) -> String {
+    let precision = get_coordinate_precision();
+    let scale = 10.0_f64.powi(precision as i32);
+    // the float error of scaling is removed before rounding
+    let value = round_significant_f64(
+        to_output_coordinate(value) * scale,
+        F32_SIGNIFICANT_DIGITS + 3,
+    )
+    .round()
+        / scale;
+    let str = format!("{:.*}", precision, value);
+    if str.contains('.') {
+        return normalize_zero(str.trim_end_matches('0').trim_end_matches('.').to_string());
+    }
+    normalize_zero(str)
+}
+
+/// Gets the precision of coordinate of current thread.
+pub(crate) fn get_coordinate_precision() -> usize {
+    COORDINATE_PRECISION.with(|item| item.get())
+}
+
+/// Calls the function with the precision of coordinate,
+/// it is restored after the function returns.
+pub(crate) fn with_coordinate_precision<T>(precision: usize, f: impl FnOnce() -> T) -> T {
+    let prev = COORDINATE_PRECISION.with(|item| item.replace(precision));
+    let result = f();
+    COORDINATE_PRECISION.with(|item| item.set(prev));
+    result
+}
+
+/// Returns true if the text contains right-to-left characters, e.g. arabic or hebrew.
+pub fn is_rtl_text(text: &str) -> bool {
+    text.chars().any(|ch| {
+        matches!(
+            ch as u32,
+            0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF | 0x10800..=0x10FFF | 0x1E800..=0x1EFFF
+        )
+    })
+}
+
+/// Escapes the special characters of xml, e.g. & to &amp;.
+pub(crate) fn escape_xml(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    write_escaped_xml(&mut result, value);
+    result
+}
+
+/// Unescapes the special characters of xml, e.g. &amp; to &.
+pub(crate) fn unescape_xml(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Writes the escaped value to the output, it avoids the allocation of escaping.
+pub(crate) fn write_escaped_xml(out: &mut String, value: &str) {
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub(crate) struct AxisValueParams {
     pub data_list: Vec<f32>,
@@ -151,6 +279,14 @@ pub(crate) struct AxisValueParams {
     pub split_number: usize,
     pub reverse: Option<bool>,
     pub thousands_format: bool,
+    // the zero is always in the range, e.g. all values are negative
+    pub include_zero: bool,
+    // the range is symmetric around zero, it is used for diverging data
+    pub symmetric: bool,
+    // the step is rounded to 1, 2 or 5 times power of 10
+    pub nice: bool,
+    // the values of labels are rounded to the significant digits
+    pub max_significant_digits: Option<usize>,
 }
 #[derive(Clone, Debug, Default)]
 pub struct AxisValues {
@@ -167,6 +303,93 @@ impl AxisValues {
         let percent = (value - self.min) / self.get_offset();
         max_height - percent * max_height
     }
+    /// Gets the offset height of zero baseline, it is clamped to the range of values.
+    pub(crate) fn get_zero_offset_height(&self, max_height: f32) -> f32 {
+        // the range is empty if all values are nil
+        let zero = if self.min <= self.max {
+            0.0_f32.clamp(self.min, self.max)
+        } else {
+            0.0
+        };
+        self.get_offset_height(zero, max_height)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BarParams {
+    pub radius: Option<f32>,
+    pub width: Option<f32>,
+    pub max_width: Option<f32>,
+    pub gap: Option<f32>,
+    pub category_gap: Option<f32>,
+    // whether to label the total of stacked bars
+    pub stack_total: bool,
+}
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct BarSize {
+    pub width: f32,
+    pub gap: f32,
+    pub margin: f32,
+}
+
+/// Gets the size of bar, the bars of category are centered
+/// if the bar width is fixed or limited.
+pub(crate) fn get_bar_size(params: &BarParams, unit_width: f32, series_count: usize) -> BarSize {
+    let series_count = series_count.max(1) as f32;
+    let gap = params.gap.unwrap_or(3.0);
+    let mut margin = params.category_gap.unwrap_or(5.0);
+    let gap_width = gap * (series_count - 1.0);
+    let mut width = params
+        .width
+        .unwrap_or((unit_width - margin * 2.0 - gap_width) / series_count);
+    if let Some(max_width) = params.max_width {
+        width = width.min(max_width);
+    }
+    if params.width.is_some() || params.max_width.is_some() {
+        margin = (unit_width - width * series_count - gap_width) / 2.0;
+    }
+    BarSize { width, gap, margin }
+}
+
+/// Gets the positive and negative sums of stacked series at every category,
+/// the value axis takes their range as the bars are stacked.
+pub(crate) fn get_stack_sum_values(series_list: &[&Series]) -> Vec<f32> {
+    let mut stacks: Vec<(&str, Vec<(f32, f32)>)> = vec![];
+    for series in series_list {
+        let Some(stack) = series.stack.as_deref() else {
+            continue;
+        };
+        let stack_index =
+            if let Some(stack_index) = stacks.iter().position(|(name, _)| *name == stack) {
+                stack_index
+            } else {
+                stacks.push((stack, vec![]));
+                stacks.len() - 1
+            };
+        let sums = &mut stacks[stack_index].1;
+        for (index, value) in series.get_data().into_iter().enumerate() {
+            if is_nil_value(value) {
+                continue;
+            }
+            let category = index + series.start_index;
+            if sums.len() <= category {
+                sums.resize(category + 1, (0.0, 0.0));
+            }
+            if value < 0.0 {
+                sums[category].1 += value;
+            } else {
+                sums[category].0 += value;
+            }
+        }
+    }
+    stacks
+        .into_iter()
+        .flat_map(|(_, sums)| {
+            sums.into_iter()
+                .flat_map(|(positive, negative)| [positive, negative])
+        })
+        .filter(|value| *value != 0.0)
+        .collect()
 }
 
 const K_VALUE: f32 = 1000.00_f32;
@@ -174,6 +397,35 @@ const M_VALUE: f32 = K_VALUE * K_VALUE;
 const G_VALUE: f32 = M_VALUE * K_VALUE;
 const T_VALUE: f32 = G_VALUE * K_VALUE;
 
+/// Gets the nice step which is not less than the value,
+/// it is 1, 2, 5 or 10 times power of 10.
+pub(crate) fn get_nice_step(value: f32) -> f32 {
+    if !value.is_finite() || value <= 0.0 {
+        return 1.0;
+    }
+    let base = 10.0_f32.powf(value.log10().floor());
+    let fraction = value / base;
+    let nice = if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * base
+}
+
+/// Rounds the value to the significant digits, e.g. 1234 of 2 digits is 1200.
+pub(crate) fn round_significant(value: f32, digits: usize) -> f32 {
+    if value == 0.0 || digits == 0 || !value.is_finite() {
+        return value;
+    }
+    let scale = 10.0_f32.powi(digits as i32 - 1 - value.abs().log10().floor() as i32);
+    (value * scale).round() / scale
+}
+
 pub(crate) fn get_axis_values(params: AxisValueParams) -> AxisValues {
     let mut min = f32::MAX;
     let mut max = f32::MIN;
@@ -184,7 +436,7 @@ pub(crate) fn get_axis_values(params: AxisValueParams) -> AxisValues {
     }
     for item in params.data_list.iter() {
         let value = item.to_owned();
-        if value == NIL_VALUE {
+        if is_nil_value(value) {
             continue;
         }
         if value > max {
@@ -213,8 +465,31 @@ pub(crate) fn get_axis_values(params: AxisValueParams) -> AxisValues {
             is_custom_max = true
         }
     }
+    if params.include_zero && !is_custom_max && max < 0.0 {
+        max = 0.0;
+    }
+    if params.symmetric {
+        let value = min.abs().max(max.abs());
+        min = -value;
+        max = value;
+    }
     let mut unit = (max - min) / split_number as f32;
-    if !is_custom_max {
+    if params.nice && !is_custom_max {
+        unit = get_nice_step(unit);
+        loop {
+            if params.symmetric {
+                break;
+            }
+            if !is_custom_min {
+                // the tolerance avoids the float error, e.g. -0.3 / 0.1
+                min = (min / unit + 1e-4).floor() * unit;
+            }
+            if min + unit * split_number as f32 >= max {
+                break;
+            }
+            unit = get_nice_step(unit * 1.5);
+        }
+    } else if !is_custom_max {
         let ceil_value = (unit * 10.0).ceil();
         if ceil_value < 12.0 {
             unit = ceil_value / 10.0;
@@ -245,13 +520,25 @@ pub(crate) fn get_axis_values(params: AxisValueParams) -> AxisValues {
             unit = new_unit as f32;
         }
     }
+    if params.symmetric {
+        min = -unit * split_number as f32 / 2.0;
+    }
     let split_unit = unit;
+    // the decimals of small step, e.g. 0.05 has 2 decimals
+    let decimals = if params.nice && split_unit > 0.0 && split_unit < 0.1 {
+        (-split_unit.log10().floor()) as usize
+    } else {
+        1
+    };
 
     let mut data = vec![];
     for i in 0..=split_number {
         let mut value = min + (i as f32) * split_unit;
+        if let Some(digits) = params.max_significant_digits {
+            value = round_significant(value, digits);
+        }
         if params.thousands_format {
-            data.push(thousands_format_float(value));
+            data.push(localize_number(thousands_format_float(value)));
             continue;
         }
         let mut unit = "";
@@ -270,7 +557,13 @@ pub(crate) fn get_axis_values(params: AxisValueParams) -> AxisValues {
         } else {
             value
         };
-        data.push(format_float(value) + unit);
+        let text = if decimals > 1 {
+            let text = format!("{:.*}", decimals, value);
+            localize_number(text.trim_end_matches('0').trim_end_matches('.').to_string())
+        } else {
+            format_label_float(value)
+        };
+        data.push(text + unit);
     }
     if params.reverse.unwrap_or_default() {
         data.reverse();
@@ -307,31 +600,226 @@ pub(crate) struct LabelOption {
     pub value: f32,
     pub percentage: f32,
     pub formatter: String,
+    pub precision: Option<usize>,
 }
 impl LabelOption {
     pub fn format(&self) -> String {
         // {a} for series name, {b} for category name, {c} for data value, {d} for percentage
-        let value = format_float(self.value);
-        let percentage = format_float(self.percentage * 100.0) + "%";
+        let format_value = |value: f32| -> String {
+            let value = if let Some(precision) = self.precision {
+                format!("{:.*}", precision, value)
+            } else {
+                format_float(value)
+            };
+            localize_number(value)
+        };
+        let value = format_value(self.value);
+        let percentage = format_value(self.percentage * 100.0) + "%";
         if self.formatter.is_empty() {
             return value;
         }
+        // "{d}%" is the same as "{d}"
+        let percentage_label = PERCENTAGE_FORMAT_LABEL.to_string() + "%";
         self.formatter
             .replace(SERIES_NAME_FORMAT_LABEL, &self.series_name)
             .replace(CATEGORY_NAME_FORMAT_LABEL, &self.category_name)
             .replace(VALUE_FORMAT_LABEL, &value)
+            .replace(&percentage_label, &percentage)
             .replace(PERCENTAGE_FORMAT_LABEL, &percentage)
-            .replace(THOUSANDS_FORMAT_LABEL, &thousands_format_float(self.value))
+            .replace(
+                THOUSANDS_FORMAT_LABEL,
+                &localize_number(thousands_format_float(self.value)),
+            )
+    }
+}
+
+fn is_box_overlap(a: &Box, b: &Box) -> bool {
+    a.left < b.right && b.left < a.right && a.top < b.bottom && b.top < a.bottom
+}
+
+/// Resolves the overlap of label boxes, the label of higher priority is placed first.
+/// Returns the vertical offset of each label, None means the label should be hidden.
+pub(crate) fn resolve_label_overlap(boxes: &[(Box, usize)], shift: bool) -> Vec<Option<f32>> {
+    let mut indexes: Vec<usize> = (0..boxes.len()).collect();
+    indexes.sort_by(|a, b| boxes[*b].1.cmp(&boxes[*a].1));
+    let mut result = vec![None; boxes.len()];
+    let mut placed: Vec<Box> = vec![];
+    for index in indexes {
+        let mut b = boxes[index].0.clone();
+        let max_offset = b.height() * 2.0;
+        let mut offset = 0.0;
+        loop {
+            let overlap_top = placed
+                .iter()
+                .find(|item| is_box_overlap(item, &b))
+                .map(|item| item.top);
+            let Some(top) = overlap_top else {
+                placed.push(b);
+                result[index] = Some(offset);
+                break;
+            };
+            if !shift {
+                break;
+            }
+            // move the label above the overlapping one
+            let delta = b.bottom - top + 1.0;
+            offset -= delta;
+            b.top -= delta;
+            b.bottom -= delta;
+            if -offset > max_offset {
+                break;
+            }
+        }
+    }
+    result
+}
+
+/// Solves the coefficients of polynomial by least squares,
+/// the coefficient of lower degree is first.
+fn fit_polynomial(points: &[(f64, f64)], degree: usize) -> Vec<f64> {
+    let size = degree + 1;
+    // the augmented matrix of normal equations
+    let mut matrix = vec![vec![0.0_f64; size + 1]; size];
+    for (x, y) in points.iter() {
+        for (row, items) in matrix.iter_mut().enumerate() {
+            for (col, item) in items.iter_mut().take(size).enumerate() {
+                *item += x.powi((row + col) as i32);
+            }
+            items[size] += y * x.powi(row as i32);
+        }
+    }
+    for col in 0..size {
+        let pivot = (col..size)
+            .max_by(|a, b| matrix[*a][col].abs().total_cmp(&matrix[*b][col].abs()))
+            .unwrap_or(col);
+        matrix.swap(col, pivot);
+        if matrix[col][col] == 0.0 {
+            continue;
+        }
+        let pivot_row = matrix[col].clone();
+        for (row, items) in matrix.iter_mut().enumerate() {
+            if row == col {
+                continue;
+            }
+            let factor = items[col] / pivot_row[col];
+            for (item, value) in items.iter_mut().zip(pivot_row.iter()).skip(col) {
+                *item -= factor * value;
+            }
+        }
+    }
+    (0..size)
+        .map(|i| {
+            if matrix[i][i] == 0.0 {
+                0.0
+            } else {
+                matrix[i][size] / matrix[i][i]
+            }
+        })
+        .collect()
+}
+
+/// Gets the values of trend line, the value is nil if it can't be computed.
+pub(crate) fn get_trend_values(data: &[f32], trend: &TrendType) -> Vec<f32> {
+    let points: Vec<(f64, f64)> = data
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| !is_nil_value(**value))
+        .map(|(index, value)| (index as f64, *value as f64))
+        .collect();
+    if points.is_empty() {
+        return vec![NIL_VALUE; data.len()];
+    }
+    let degree = match trend {
+        TrendType::Linear => 1,
+        TrendType::Polynomial(degree) => *degree,
+        TrendType::MovingAverage(window) => {
+            let window = (*window).max(1);
+            return (0..data.len())
+                .map(|index| {
+                    if index + 1 < window {
+                        return NIL_VALUE;
+                    }
+                    let values: Vec<f32> = data[index + 1 - window..=index]
+                        .iter()
+                        .filter(|value| !is_nil_value(**value))
+                        .copied()
+                        .collect();
+                    if values.is_empty() {
+                        return NIL_VALUE;
+                    }
+                    values.iter().sum::<f32>() / values.len() as f32
+                })
+                .collect();
+        }
+    };
+    // the degree can't be greater than count of points
+    let coefficients = fit_polynomial(&points, degree.min(points.len() - 1));
+    (0..data.len())
+        .map(|index| {
+            coefficients
+                .iter()
+                .enumerate()
+                .map(|(i, c)| c * (index as f64).powi(i as i32))
+                .sum::<f64>() as f32
+        })
+        .collect()
+}
+
+/// Downsamples the data by largest-triangle-three-buckets,
+/// returns the indexes of kept values. The nil values are ignored.
+pub(crate) fn downsample_lttb(data: &[f32], threshold: usize) -> Vec<usize> {
+    let points: Vec<(f32, f32)> = data
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| !is_nil_value(**value))
+        .map(|(index, value)| (index as f32, *value))
+        .collect();
+    let count = points.len();
+    if threshold >= count || threshold < 3 {
+        return points.iter().map(|(index, _)| *index as usize).collect();
+    }
+    // the first and last point are always kept
+    let every = (count - 2) as f32 / (threshold - 2) as f32;
+    let mut indexes = vec![0];
+    let mut selected = 0;
+    for i in 0..threshold - 2 {
+        // the average point of next bucket
+        let next_start = ((i + 1) as f32 * every) as usize + 1;
+        let next_end = ((((i + 2) as f32 * every) as usize) + 1).min(count);
+        let next_points = &points[next_start..next_end.max(next_start + 1).min(count)];
+        let avg_x = next_points.iter().map(|p| p.0).sum::<f32>() / next_points.len() as f32;
+        let avg_y = next_points.iter().map(|p| p.1).sum::<f32>() / next_points.len() as f32;
+
+        let start = (i as f32 * every) as usize + 1;
+        let end = (((i + 1) as f32 * every) as usize + 1).min(count - 1);
+        let (ax, ay) = points[selected];
+        let mut max_area = -1.0;
+        for (index, (x, y)) in points.iter().enumerate().take(end).skip(start) {
+            let area = ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+            if area > max_area {
+                max_area = area;
+                selected = index;
+            }
+        }
+        indexes.push(selected);
     }
+    indexes.push(count - 1);
+    indexes
+        .into_iter()
+        .map(|index| points[index].0 as usize)
+        .collect()
 }
 
+/// Formats the value with formatter, {c} or {t} for the value,
+/// {month}, {short_month}, {weekday} and {short_weekday} for the date name of locale.
 pub fn format_string(value: &str, formatter: &str) -> String {
     if formatter.is_empty() {
         value.to_string()
     } else {
-        formatter
+        let formatter = formatter
             .replace(VALUE_FORMAT_LABEL, value)
-            .replace(THOUSANDS_FORMAT_LABEL, value)
+            .replace(THOUSANDS_FORMAT_LABEL, value);
+        format_date_name(value, &formatter)
     }
 }
 
@@ -369,9 +857,13 @@ mod tests {
     use crate::thousands_format_float;
 
     use super::{
-        convert_to_points, format_float, get_axis_values, get_box_of_points, AxisValueParams, Box,
-        Point,
+        convert_to_points, downsample_lttb, escape_xml, format_coordinate, format_float,
+        get_axis_values, get_bar_size, get_box_of_points, get_nice_step, get_trend_values,
+        is_nil_value, is_rtl_text, resolve_label_overlap, round_significant, to_output_coordinate,
+        with_coordinate_precision, AxisValueParams, BarParams, BarSize, Box, LabelOption, Point,
+        NIL_VALUE,
     };
+    use crate::TrendType;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -415,6 +907,52 @@ mod tests {
         assert_eq!("1000.1", format_float(1000.14));
     }
     #[test]
+    fn coordinate() {
+        assert_eq!("1", format_coordinate(1.0));
+        assert_eq!("1.1", format_coordinate(1.12));
+        assert_eq!("-0.5", format_coordinate(-0.54));
+        assert_eq!(
+            "1.12",
+            with_coordinate_precision(2, || format_coordinate(1.123))
+        );
+        assert_eq!(
+            "1",
+            with_coordinate_precision(0, || format_coordinate(1.23))
+        );
+        assert_eq!(
+            "1.2",
+            with_coordinate_precision(3, || format_coordinate(1.2))
+        );
+        assert_eq!("1.1", format_coordinate(1.12));
+
+        // the float errors of f32 are resolved as the same output
+        assert_eq!(12.25, to_output_coordinate(12.249_999));
+        assert_eq!(12.25, to_output_coordinate(12.250_001));
+        assert_eq!(0.3, to_output_coordinate(0.1 + 0.2));
+        assert_eq!("12.3", format_coordinate(12.249_999));
+        assert_eq!("12.3", format_coordinate(12.25));
+        assert_eq!("12.3", format_coordinate(12.250_001));
+        assert_eq!("-12.3", format_coordinate(-12.25));
+        assert_eq!(
+            "0.35",
+            with_coordinate_precision(2, || format_coordinate(0.1 + 0.25))
+        );
+    }
+    #[test]
+    fn escape() {
+        assert_eq!("Hello World", escape_xml("Hello World"));
+        assert_eq!(
+            "R&amp;D &lt;2024&gt; &quot;Q1&quot; Tom&apos;s",
+            escape_xml(r#"R&D <2024> "Q1" Tom's"#)
+        );
+    }
+    #[test]
+    fn rtl_text() {
+        assert!(!is_rtl_text("Hello World"));
+        assert!(is_rtl_text("مرحبا"));
+        assert!(is_rtl_text("Price שלום"));
+    }
+    #[test]
     fn thousands_format() {
         assert_eq!("1", thousands_format_float(1.0));
         assert_eq!("1.1", thousands_format_float(1.12));
@@ -425,6 +963,121 @@ mod tests {
         assert_eq!("1,000,000", thousands_format_float(1_000_000.1));
     }
 
+    #[test]
+    fn nil_value() {
+        assert!(is_nil_value(NIL_VALUE));
+        assert!(is_nil_value(f32::NAN));
+        assert!(!is_nil_value(0.0));
+        assert!(!is_nil_value(-1.0));
+    }
+
+    #[test]
+    fn label_option() {
+        let mut option = LabelOption {
+            series_name: "Email".to_string(),
+            category_name: "Mon".to_string(),
+            value: 1234.5678,
+            percentage: 0.12345,
+            ..Default::default()
+        };
+        assert_eq!("1234.6", option.format());
+
+        option.formatter = "{a} {b}: {c}".to_string();
+        assert_eq!("Email Mon: 1234.6", option.format());
+
+        option.formatter = "{d}%".to_string();
+        assert_eq!("12.3%", option.format());
+
+        option.precision = Some(2);
+        option.formatter = "{c}({d})".to_string();
+        assert_eq!("1234.57(12.35%)", option.format());
+    }
+
+    #[test]
+    fn label_overlap() {
+        let boxes = vec![
+            (
+                Box {
+                    left: 0.0,
+                    top: 10.0,
+                    right: 20.0,
+                    bottom: 20.0,
+                },
+                0,
+            ),
+            (
+                Box {
+                    left: 10.0,
+                    top: 15.0,
+                    right: 30.0,
+                    bottom: 25.0,
+                },
+                1,
+            ),
+            (
+                Box {
+                    left: 40.0,
+                    top: 10.0,
+                    right: 60.0,
+                    bottom: 20.0,
+                },
+                0,
+            ),
+        ];
+        assert_eq!(
+            vec![None, Some(0.0), Some(0.0)],
+            resolve_label_overlap(&boxes, false)
+        );
+        assert_eq!(
+            vec![Some(-6.0), Some(0.0), Some(0.0)],
+            resolve_label_overlap(&boxes, true)
+        );
+    }
+
+    #[test]
+    fn bar_size() {
+        let params = BarParams::default();
+        assert_eq!(
+            BarSize {
+                width: 43.5,
+                gap: 3.0,
+                margin: 5.0,
+            },
+            get_bar_size(&params, 100.0, 2)
+        );
+        assert_eq!(
+            BarSize {
+                width: 20.0,
+                gap: 10.0,
+                margin: 25.0,
+            },
+            get_bar_size(
+                &BarParams {
+                    max_width: Some(20.0),
+                    gap: Some(10.0),
+                    ..Default::default()
+                },
+                100.0,
+                2
+            )
+        );
+        assert_eq!(
+            BarSize {
+                width: 30.0,
+                gap: 3.0,
+                margin: 18.5,
+            },
+            get_bar_size(
+                &BarParams {
+                    width: Some(30.0),
+                    ..Default::default()
+                },
+                100.0,
+                2
+            )
+        );
+    }
+
     #[test]
     fn axis_values() {
         let values = get_axis_values(AxisValueParams {
@@ -437,6 +1090,61 @@ mod tests {
         assert_eq!(24.0, values.max);
         assert_eq!(24.0, values.get_offset());
         assert_eq!(50.0, values.get_offset_height(12.0, 100.0));
+
+        let values = get_axis_values(AxisValueParams {
+            data_list: vec![0.013, 0.27, 0.31],
+            split_number: 5,
+            nice: true,
+            ..Default::default()
+        });
+        assert_eq!(vec!["0", "0.1", "0.2", "0.3", "0.4", "0.5"], values.data);
+
+        let values = get_axis_values(AxisValueParams {
+            data_list: vec![0.013, 0.027, 0.041],
+            split_number: 4,
+            nice: true,
+            ..Default::default()
+        });
+        assert_eq!(vec!["0", "0.02", "0.04", "0.06", "0.08"], values.data);
+
+        let values = get_axis_values(AxisValueParams {
+            data_list: vec![-32.0, -12.0, -5.0],
+            split_number: 4,
+            nice: true,
+            include_zero: true,
+            ..Default::default()
+        });
+        assert_eq!(vec!["-40", "-30", "-20", "-10", "0"], values.data);
+
+        let values = get_axis_values(AxisValueParams {
+            data_list: vec![-12.0, 5.0, 38.0],
+            split_number: 4,
+            nice: true,
+            symmetric: true,
+            ..Default::default()
+        });
+        assert_eq!(vec!["-40", "-20", "0", "20", "40"], values.data);
+        assert_eq!(-40.0, values.min);
+        assert_eq!(40.0, values.max);
+
+        let values = get_axis_values(AxisValueParams {
+            data_list: vec![1234.0, 4321.0],
+            split_number: 3,
+            min: Some(-1234.0),
+            max_significant_digits: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(vec!["-1200", "670", "2.6k", "4.5k"], values.data);
+    }
+
+    #[test]
+    fn nice_step() {
+        assert_eq!(1.0, get_nice_step(0.8));
+        assert_eq!(2.0, get_nice_step(1.3));
+        assert_eq!(50.0, get_nice_step(31.0));
+        assert_eq!(1000.0, get_nice_step(600.0));
+        assert_eq!(1200.0, round_significant(1234.0, 2));
+        assert_eq!(-0.046, round_significant(-0.04567, 2));
     }
 
     #[test]
@@ -456,4 +1164,45 @@ mod tests {
         assert_eq!(250.0, b.right);
         assert_eq!(90.0, b.bottom);
     }
+
+    #[test]
+    fn trend_values() {
+        let data = vec![1.0, 3.0, NIL_VALUE, 7.0, 9.0];
+        assert_eq!(
+            vec!["1", "3", "5", "7", "9"],
+            get_trend_values(&data, &TrendType::Linear)
+                .iter()
+                .map(|value| format_float(*value))
+                .collect::<Vec<String>>()
+        );
+
+        assert_eq!(
+            vec!["0", "1", "4", "9", "16"],
+            get_trend_values(&[0.0, 1.0, 4.0, 9.0, 16.0], &TrendType::Polynomial(2))
+                .iter()
+                .map(|value| format_float(*value))
+                .collect::<Vec<String>>()
+        );
+
+        let values = get_trend_values(&data, &TrendType::MovingAverage(2));
+        assert!(is_nil_value(values[0]));
+        assert_eq!(vec![2.0, 3.0, 7.0, 8.0], values[1..].to_vec());
+
+        assert!(get_trend_values(&[NIL_VALUE], &TrendType::Linear)
+            .iter()
+            .all(|value| is_nil_value(*value)));
+    }
+
+    #[test]
+    fn lttb() {
+        let data: Vec<f32> = (0..100).map(|i| (i % 10) as f32).collect();
+        let indexes = downsample_lttb(&data, 20);
+        assert_eq!(20, indexes.len());
+        assert_eq!(Some(&0), indexes.first());
+        assert_eq!(Some(&99), indexes.last());
+        assert!(indexes.windows(2).all(|item| item[0] < item[1]));
+
+        assert_eq!(vec![0, 2], downsample_lttb(&[1.0, NIL_VALUE, 2.0], 2));
+        assert_eq!(vec![0, 2, 3], downsample_lttb(&[1.0, 5.0, 9.0, 2.0], 3));
+    }
 }
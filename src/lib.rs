@@ -122,6 +122,13 @@
 //! println!("{}", bar_chart.svg().unwrap());
 //! ```
 //!
+//! The font can also be registered with name after the fonts are initialized.
+//! ```rust
+//! use charts_rs::register_font;
+//! let data = include_bytes!("./Roboto.ttf") as &[u8];
+//! register_font("Corporate", data).unwrap();
+//! ```
+//!
 //! # Basic bar chart
 //! ```rust
 //! use charts_rs::{BarChart, Series, Box};
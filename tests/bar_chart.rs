@@ -1,4 +1,4 @@
-use charts_rs::BarChart;
+use charts_rs::{BarChart, ChartError};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -196,3 +196,81 @@ fn bar_chart_mixin() {
         bar_chart.svg().unwrap()
     );
 }
+
+#[test]
+fn bar_chart_shadow() {
+    let bar_chart = BarChart::from_json(
+        r###"{
+            "title_text": "Bar Chart",
+            "title_shadow": {
+                "dy": 1,
+                "blur": 1
+            },
+            "series_shadow": {
+                "color": "#999"
+            },
+            "series_list": [
+                {
+                    "name": "Email",
+                    "data": [120.0, 132.0, 101.0, 134.0, 90.0]
+                },
+                {
+                    "name": "Union Ads",
+                    "data": [220.0, 182.0, 191.0, 234.0, 290.0],
+                    "category": "line"
+                }
+            ],
+            "x_axis_data": [
+                "Mon",
+                "Tue",
+                "Wed",
+                "Thu",
+                "Fri"
+            ]
+        }"###,
+    )
+    .unwrap();
+
+    assert_eq!(
+        include_str!("../asset/bar_chart/shadow_json.svg"),
+        bar_chart.svg().unwrap()
+    );
+}
+
+#[test]
+fn bar_chart_validate() {
+    let mut bar_chart = BarChart::new(
+        vec![("Email", vec![120.0, 132.0, 101.0]).into()],
+        vec!["Mon".to_string(), "Tue".to_string()],
+    );
+    assert_eq!(
+        Err(ChartError::LengthMismatch {
+            name: "Email".to_string(),
+            length: 3,
+            expected: 2,
+        }),
+        bar_chart.validate()
+    );
+    assert_eq!(
+        "Chart is invalid: length of series Email is 3, it exceeds the expected length 2",
+        bar_chart.svg().unwrap_err().to_string()
+    );
+
+    bar_chart.series_list[0].data = vec![120.0, f32::INFINITY];
+    assert_eq!(
+        "value of series Email at index 1 is infinite",
+        bar_chart.validate().unwrap_err().to_string()
+    );
+    // NaN is nil value
+    bar_chart.series_list[0].data = vec![120.0, f32::NAN];
+    assert_eq!(Ok(()), bar_chart.validate());
+
+    bar_chart.height = 0.0;
+    assert_eq!(
+        "width and height should be greater than 0, got 600x0",
+        bar_chart.validate().unwrap_err().to_string()
+    );
+    bar_chart.height = 400.0;
+    bar_chart.series_list.clear();
+    assert_eq!(Err(ChartError::EmptySeries), bar_chart.validate());
+}
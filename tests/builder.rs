@@ -0,0 +1,85 @@
+use charts_rs::{BarChart, HeatmapChart, PieChart, THEME_GRAFANA};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn bar_chart_builder() {
+    let bar_chart = BarChart::builder()
+        .title("Water")
+        .y_axis_formatter("{c} ml")
+        .series(vec![
+            ("Evaporation", vec![2.0, 4.9, 7.0]).into(),
+            ("Precipitation", vec![2.6, 5.9, 9.0]).into(),
+        ])
+        .x_axis_data(vec![
+            "Mon".to_string(),
+            "Tue".to_string(),
+            "Wed".to_string(),
+        ])
+        .theme(THEME_GRAFANA)
+        .build()
+        .unwrap();
+
+    let mut expected = BarChart::new_with_theme(
+        vec![
+            ("Evaporation", vec![2.0, 4.9, 7.0]).into(),
+            ("Precipitation", vec![2.6, 5.9, 9.0]).into(),
+        ],
+        vec!["Mon".to_string(), "Tue".to_string(), "Wed".to_string()],
+        THEME_GRAFANA,
+    );
+    expected.title_text = "Water".to_string();
+    expected.y_axis_configs[0].axis_formatter = Some("{c} ml".to_string());
+    assert_eq!(expected.svg().unwrap(), bar_chart.svg().unwrap());
+
+    assert_eq!(
+        "Params is invalid: x axis data is required",
+        BarChart::builder()
+            .series(vec![("Email", vec![120.0]).into()])
+            .build()
+            .unwrap_err()
+            .to_string()
+    );
+}
+
+#[test]
+fn pie_and_heatmap_chart_builder() {
+    let pie_chart = PieChart::builder()
+        .series(vec![("rose 1", vec![40.0]).into()])
+        .with(|chart| chart.inner_radius = 0.0)
+        .build()
+        .unwrap();
+    // the default options of pie chart are kept
+    assert_eq!(150.0, pie_chart.radius);
+    assert_eq!(0.0, pie_chart.inner_radius);
+
+    assert_eq!(
+        "Params is invalid: series list is required",
+        PieChart::builder().build().unwrap_err().to_string()
+    );
+    assert_eq!(
+        "Params is invalid: series data is required",
+        HeatmapChart::builder().build().unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn responsive_chart_builder() {
+    let pie_chart = PieChart::builder()
+        .series(vec![("rose 1", vec![40.0]).into()])
+        .width(800.0)
+        .aspect_ratio(2.0)
+        .responsive(true)
+        .build()
+        .unwrap();
+    assert_eq!(400.0, pie_chart.height);
+    assert_eq!(
+        r#"<svg width="100%" viewBox="0 0 800 400" xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet">"#,
+        pie_chart.svg().unwrap().lines().next().unwrap()
+    );
+
+    // the width is computed by the height
+    let pie_chart =
+        PieChart::from_json(r#"{"height": 300, "aspect_ratio": 1.5, "series_list": []}"#).unwrap();
+    assert_eq!(450.0, pie_chart.width);
+    assert_eq!(300.0, pie_chart.height);
+}
@@ -0,0 +1,78 @@
+use charts_rs::{BarChart, ChartType, PieChart};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn chart_type() {
+    let mut charts = vec![
+        ChartType::from_json(
+            r###"{
+            "type": "line",
+            "series_list": [
+                {
+                    "name": "Email",
+                    "data": [120.0, 132.0, 101.0]
+                }
+            ],
+            "x_axis_data": ["Mon", "Tue", "Wed"]
+        }"###,
+        )
+        .unwrap(),
+        ChartType::from_json(
+            r###"{
+            "type": "pie",
+            "series_list": [
+                {
+                    "name": "rose 1",
+                    "data": [40.0]
+                },
+                {
+                    "name": "rose 2",
+                    "data": [38.0]
+                }
+            ]
+        }"###,
+        )
+        .unwrap(),
+    ];
+    charts.push(ChartType::Bar(BarChart::new(
+        vec![("Email", vec![120.0, 132.0, 101.0]).into()],
+        vec!["Mon".to_string(), "Tue".to_string(), "Wed".to_string()],
+    )));
+    assert_eq!(
+        vec!["line", "pie", "bar"],
+        charts.iter().map(|c| c.name()).collect::<Vec<_>>()
+    );
+    let ChartType::Pie(pie_chart) = &charts[1] else {
+        panic!("it should be pie chart");
+    };
+    assert_eq!(
+        PieChart::from_json(
+            r###"{
+            "series_list": [
+                {
+                    "name": "rose 1",
+                    "data": [40.0]
+                },
+                {
+                    "name": "rose 2",
+                    "data": [38.0]
+                }
+            ]
+        }"###
+        )
+        .unwrap()
+        .svg()
+        .unwrap(),
+        pie_chart.svg().unwrap()
+    );
+    for c in charts.iter() {
+        assert!(c.svg().unwrap().starts_with("<svg"));
+    }
+
+    assert_eq!(
+        "Params is invalid: chart type area is not supported",
+        ChartType::from_json(r###"{"type": "area"}"###)
+            .unwrap_err()
+            .to_string()
+    );
+}
@@ -0,0 +1,53 @@
+use charts_rs::{
+    get_default_option, get_theme, set_default_option, BarChart, DefaultOption, PieChart,
+    THEME_DARK,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn default_option() {
+    set_default_option(DefaultOption {
+        theme: Some(THEME_DARK.to_string()),
+        font_family: Some("Custom".to_string()),
+        width: Some(800.0),
+        height: Some(500.0),
+    });
+    assert_eq!(Some(800.0), get_default_option().width);
+    // the registered theme is not changed by default option
+    let theme = get_theme(THEME_DARK);
+    assert_eq!("Roboto", theme.font_family);
+    assert_eq!((600.0, 400.0), (theme.width, theme.height));
+
+    let bar_chart = BarChart::new(
+        vec![("Email", vec![120.0, 132.0]).into()],
+        vec!["Mon".to_string(), "Tue".to_string()],
+    );
+    assert!(!bar_chart.is_light);
+    assert_eq!("Custom", bar_chart.font_family);
+    assert_eq!((800.0, 500.0), (bar_chart.width, bar_chart.height));
+
+    // the field of chart has higher priority
+    let pie_chart = PieChart::from_json(
+        r###"{
+            "width": 400,
+            "series_list": [{"name": "Email", "data": [120.0]}]
+        }"###,
+    )
+    .unwrap();
+    assert!(!pie_chart.is_light);
+    assert_eq!((400.0, 500.0), (pie_chart.width, pie_chart.height));
+    assert_eq!("Custom", pie_chart.font_family);
+
+    // the default option has higher priority than the theme of chart
+    let pie_chart = PieChart::from_json(
+        r###"{
+            "theme": "ant",
+            "font_family": "Roboto",
+            "series_list": [{"name": "Email", "data": [120.0]}]
+        }"###,
+    )
+    .unwrap();
+    assert!(pie_chart.is_light);
+    assert_eq!("Roboto", pie_chart.font_family);
+    assert_eq!((800.0, 500.0), (pie_chart.width, pie_chart.height));
+}
@@ -0,0 +1,21 @@
+use charts_rs::{
+    get_font_families, measure_text_width_family, register_font, register_font_file,
+    DEFAULT_FONT_DATA,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn register_custom_font() {
+    register_font("Custom", DEFAULT_FONT_DATA).unwrap();
+    assert!(get_font_families().unwrap().contains(&"Custom".to_string()));
+    let b = measure_text_width_family("Custom", 14.0, "Hello World!").unwrap();
+    assert_eq!(79.0, b.width().ceil());
+
+    register_font_file("./src/Roboto.ttf").unwrap();
+    assert_eq!(
+        "Io ./not-found.ttf: No such file or directory (os error 2)",
+        register_font_file("./not-found.ttf")
+            .unwrap_err()
+            .to_string()
+    );
+}
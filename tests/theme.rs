@@ -0,0 +1,144 @@
+use charts_rs::{
+    get_theme, load_theme_dir, register_theme, BarChart, Color, LineChart, Theme, THEME_ANT,
+    THEME_DARK,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn register_custom_theme() {
+    let theme = Theme::from_base(THEME_DARK)
+        .with_font_family("Roboto")
+        .with_background_color(Color::from("#FFFFFF"))
+        .with_font_color(Color::from("#333333"))
+        .with_grid_style(Color::from("#EEEEEE"), 2.0)
+        .with_series_colors(vec![Color::from("#FF6600"), Color::from("#0066FF")]);
+    assert!(theme.is_light);
+    register_theme("corporate", theme);
+
+    let bar_chart = BarChart::new_with_theme(
+        vec![("Email", vec![120.0, 132.0]).into()],
+        vec!["Mon".to_string(), "Tue".to_string()],
+        "corporate",
+    );
+    assert_eq!("#FF6600", bar_chart.series_colors[0].hex());
+    assert_eq!("#333333", bar_chart.title_font_color.hex());
+    assert_eq!(2.0, bar_chart.grid_stroke_width);
+
+    let bar_chart = BarChart::from_json(
+        r###"{
+            "theme": "corporate",
+            "series_list": [{"name": "Email", "data": [120.0, 132.0]}],
+            "x_axis_data": ["Mon", "Tue"]
+        }"###,
+    )
+    .unwrap();
+    assert_eq!("#0066FF", bar_chart.series_colors[1].hex());
+    assert_eq!("#FFFFFF", bar_chart.background_color.hex());
+}
+
+#[test]
+fn theme_from_file() {
+    let theme = Theme::from_json(
+        r###"{
+            "base": "dark",
+            "title_font_size": 20,
+            "title_margin": {"left": 5},
+            "series_colors": ["#FF6600", "rgb(0, 102, 255)"]
+        }"###,
+    )
+    .unwrap();
+    assert!(!theme.is_light);
+    assert_eq!(20.0, theme.title_font_size);
+    assert_eq!(5.0, theme.title_margin.unwrap().left);
+    assert_eq!("#0066FF", theme.series_colors[1].hex());
+
+    let theme = Theme::from_toml(
+        r###"
+font_family = "Roboto"
+background_color = "#FAFAFA"
+y_axis_split_number = 4
+series_colors = ["#FF6600"]
+"###,
+    )
+    .unwrap();
+    assert!(theme.is_light);
+    assert_eq!("#FAFAFA", theme.background_color.hex());
+    assert_eq!(4, theme.y_axis_split_number);
+    assert!(Theme::from_toml("font=")
+        .unwrap_err()
+        .to_string()
+        .starts_with("Toml is invalid: TOML parse error at line 1"));
+
+    let dir = std::env::temp_dir().join("charts-rs-themes");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("brand.json"),
+        r##"{"series_colors": ["#123456"]}"##,
+    )
+    .unwrap();
+    std::fs::write(dir.join("brand-dark.toml"), "base = \"dark\"").unwrap();
+    std::fs::write(dir.join("readme.txt"), "").unwrap();
+    assert_eq!(
+        vec!["brand-dark".to_string(), "brand".to_string()],
+        load_theme_dir(&dir.to_string_lossy()).unwrap()
+    );
+    assert_eq!("#123456", get_theme("brand").series_colors[0].hex());
+    assert!(!get_theme("brand-dark").is_light);
+}
+
+#[test]
+fn theme_dark_light_variant() {
+    let theme = get_theme(THEME_ANT);
+    let dark = theme.to_dark();
+    assert!(!dark.is_light);
+    assert_eq!("#000000", dark.background_color.hex());
+    assert_eq!("#B9B9B9", dark.title_font_color.hex());
+    assert_eq!(theme.series_colors, dark.series_colors);
+    assert_eq!(dark.background_color, dark.to_dark().background_color);
+
+    let light = dark.to_light();
+    assert!(light.is_light);
+    assert_eq!(theme.background_color, light.background_color);
+    assert_eq!(theme.title_font_color, light.title_font_color);
+}
+
+#[test]
+fn resolve_style_cascade() {
+    let line_chart = LineChart::from_json(
+        r###"{
+            "theme": "dark",
+            "title_font_size": 0,
+            "font_family": "",
+            "legend_font_color": "transparent",
+            "series_list": [{"name": "Email", "data": [120.0]}],
+            "x_axis_data": ["Mon"]
+        }"###,
+    )
+    .unwrap();
+    let theme = get_theme(THEME_DARK);
+    // the explicit value is kept even if it is zero
+    assert_eq!(0.0, line_chart.title_font_size);
+    assert_eq!("", line_chart.font_family);
+    assert!(line_chart.legend_font_color.is_transparent());
+    // the unset value is resolved from theme
+    assert_eq!(theme.sub_title_font_size, line_chart.sub_title_font_size);
+    assert_eq!(theme.x_axis_font_color, line_chart.x_axis_font_color);
+    assert_eq!(theme.series_colors, line_chart.series_colors);
+    assert_eq!(
+        (theme.width, theme.height),
+        (line_chart.width, line_chart.height)
+    );
+    assert_eq!(12.0, line_chart.footer_font_size);
+
+    // the built-in light theme is used if the theme is not found
+    let line_chart = LineChart::from_json(
+        r###"{
+            "theme": "not-found",
+            "series_list": [{"name": "Email", "data": [120.0]}],
+            "x_axis_data": ["Mon"]
+        }"###,
+    )
+    .unwrap();
+    let theme = get_theme("light");
+    assert_eq!(theme.title_font_color, line_chart.title_font_color);
+}